@@ -14,6 +14,9 @@ rustc --edition 2021 -C panic=abort simple_ds.rs -o simple_ds
 rustc --edition 2021 -C panic=abort baremin_ls.rs -o baremin_ls
 rustc --edition 2021 -C panic=abort baremin_cat.rs -o baremin_cat
 rustc --edition 2021 -C panic=abort event_bus.rs -o event_bus
+rustc --edition 2021 -C panic=abort net_loopback.rs -o net_loopback
+rustc --edition 2021 -C panic=abort tiny_http.rs -o tiny_http
+rustc --edition 2021 -C panic=abort heap_demo.rs -o heap_demo
 
 # raylib related
 rustc --edition 2021 -C panic=abort raylib_demo.rs -o raylib_demo 
@@ -32,6 +35,9 @@ rustc --edition 2021 -C panic=abort event_bus_counter.rs -o event_bus_counter
 rustc --edition 2021 -C panic=abort earth_wireframe.rs -o earth_wireframe
 rustc --edition 2021 -C panic=abort gp2d_collision_demo.rs -o gp2d_collision_demo
 
+# golden-image tests for the games on the CPU rasterizer; needs no raylib
+rustc --edition 2021 -C opt-level=2 headless_snapshots.rs -o headless_snapshots
+
 
 
 
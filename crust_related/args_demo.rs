@@ -8,6 +8,15 @@ extern "C" {
     fn exit(status: i32) -> !;
 }
 
+#[allow(unused_attributes)]
+#[path = "lib/collections.rs"]
+mod collections;
+
+#[allow(unused_attributes)]
+#[path = "lib/string.rs"]
+mod string;
+use string::String;
+
 #[no_mangle]
 pub unsafe extern "C" fn main(argc: i32, argv: *const *const u8) -> i32 {
     let header = b"Command Line Arguments:\n";
@@ -24,9 +33,8 @@ pub unsafe extern "C" fn main(argc: i32, argv: *const *const u8) -> i32 {
             }
 
             // Write argument number
-            let mut num_buf = [0u8; 20];
-            let num_len = format_number(i, &mut num_buf);
-            let _ = unsafe { write(1, num_buf.as_ptr(), num_len) };
+            let num = String::<20>::from_i64(i as i64).unwrap_or_default();
+            let _ = unsafe { write(1, num.as_ptr(), num.len()) };
             let _ = unsafe { write(1, b": ".as_ptr(), 2) };
 
             // Write argument
@@ -54,30 +62,6 @@ pub unsafe extern "C" fn main(argc: i32, argv: *const *const u8) -> i32 {
     0
 }
 
-fn format_number(n: i32, buffer: &mut [u8]) -> usize {
-    let mut num = n;
-    let mut digits = [0u8; 20];
-    let mut digit_count = 0;
-
-    if num == 0 {
-        digits[0] = b'0';
-        digit_count = 1;
-    } else {
-        while num > 0 && digit_count < digits.len() {
-            digits[digit_count] = b'0' + (num % 10) as u8;
-            num /= 10;
-            digit_count += 1;
-        }
-    }
-
-    // Copy digits in reverse
-    for i in (0..digit_count).rev() {
-        buffer[digit_count - 1 - i] = digits[i];
-    }
-
-    digit_count
-}
-
 #[panic_handler]
 fn panic(_info: &core::panic::PanicInfo) -> ! {
     unsafe {
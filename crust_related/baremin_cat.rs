@@ -4,15 +4,14 @@
 
 #[link(name = "c")]
 extern "C" {
-    fn open(path: *const u8, flags: i32, mode: u32) -> i32;
-    fn read(fd: i32, buf: *mut u8, count: usize) -> isize;
-    fn write(fd: i32, buf: *const u8, count: usize) -> isize;
-    fn close(fd: i32) -> i32;
     fn exit(status: i32) -> !;
 }
 
-const O_RDONLY: i32 = 0;
-const BUF_SZ: usize = 4096;
+#[allow(unused_attributes)]
+#[path = "lib/io.rs"]
+mod io;
+use core::ffi::CStr;
+use io::fs::File;
 
 #[no_mangle]
 pub unsafe extern "C" fn main(argc: i32, argv: *const *const u8) -> i32 {
@@ -20,25 +19,19 @@ pub unsafe extern "C" fn main(argc: i32, argv: *const *const u8) -> i32 {
         exit(0); // no files → nothing to do
     }
 
-    let mut buf = [0u8; BUF_SZ];
+    let mut out = io::stdout();
 
     // argv[1] .. argv[argc-1] are file names
     for i in 1..argc as isize {
-        let path = *argv.offset(i);
-        let fd = open(path, O_RDONLY, 0);
-        if fd < 0 {
-            exit(1);
-        }
+        let path = CStr::from_ptr(*argv.offset(i) as *const core::ffi::c_char);
+        let mut file = match path.to_str().map(File::open) {
+            Ok(Ok(file)) => file,
+            _ => exit(1),
+        };
 
-        loop {
-            let n = read(fd, buf.as_mut_ptr(), BUF_SZ);
-            if n <= 0 {
-                break;
-            }
-            write(1, buf.as_ptr(), n as usize);
+        if io::copy(&mut file, &mut out).is_err() {
+            exit(1);
         }
-
-        close(fd);
     }
 
     0
@@ -8,6 +8,15 @@ extern "C" {
     fn exit(status: i32) -> !;
 }
 
+#[allow(unused_attributes)]
+#[path = "lib/collections.rs"]
+mod collections;
+
+#[allow(unused_attributes)]
+#[path = "lib/string.rs"]
+mod string;
+use string::String;
+
 #[no_mangle]
 pub unsafe extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
     // Test different data types
@@ -20,47 +29,13 @@ pub unsafe extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
 }
 
 fn format_and_print(n: i32) {
-    let mut buffer = [0u8; 32];
-    let len = format_signed_number(n, &mut buffer);
+    let mut text = String::<32>::new();
+    let _ = text.push_i64(n as i64);
+    let _ = text.push('\n');
     
     unsafe {
-        write(1, buffer.as_ptr(), len);
-        write(1, b"\n".as_ptr(), 1);
-    }
-}
-
-fn format_signed_number(n: i32, buffer: &mut [u8]) -> usize {
-    let mut num = n.abs() as u32;
-    let mut digits = [0u8; 20];
-    let mut digit_count = 0;
-    let mut buffer_index = 0;
-    
-    // Handle negative numbers
-    if n < 0 {
-        buffer[buffer_index] = b'-';
-        buffer_index += 1;
+        write(1, text.as_ptr(), text.len());
     }
-    
-    // Handle zero
-    if num == 0 {
-        digits[0] = b'0';
-        digit_count = 1;
-    } else {
-        // Extract digits
-        while num > 0 && digit_count < digits.len() {
-            digits[digit_count] = b'0' + (num % 10) as u8;
-            num /= 10;
-            digit_count += 1;
-        }
-    }
-    
-    // Copy digits in reverse order
-    for i in (0..digit_count).rev() {
-        buffer[buffer_index] = digits[i];
-        buffer_index += 1;
-    }
-    
-    buffer_index
 }
 
 #[panic_handler]
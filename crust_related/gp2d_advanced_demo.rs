@@ -4,12 +4,17 @@ mod math;
 mod collision;
 mod raylib;
 mod helpers;
+#[allow(unused_attributes)]
+#[path = "lib/collections.rs"]
+mod collections;
 
 use math::*;
 use collision::*;
 use raylib::*;
 use helpers::*;
 
+gp2d_conversions!(math);
+
 
 
 // ============================
@@ -380,14 +385,14 @@ impl DemoState {
                     Shape::Polygon(verts) => {
                         // Triangulate fan
                         let c = b.shape.center();
-                        let center_v = Vector2 { x: c.x, y: c.y };
+                        let center_v: Vector2 = c.into();
                         for i in 0..verts.len() {
                             let p1 = verts[i];
                             let p2 = verts[(i + 1) % verts.len()];
                             DrawTriangle(
                                 center_v,
-                                Vector2 { x: p1.x, y: p1.y },
-                                Vector2 { x: p2.x, y: p2.y },
+                                p1.into(),
+                                p2.into(),
                                 b.color
                             );
                             DrawLine(p1.x as i32, p1.y as i32, p2.x as i32, p2.y as i32, BLACK);
@@ -30,6 +30,14 @@ pub struct Segment {
     pub b: Vec2,
 }
 
+// Capsule (segment swept by a radius)
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Capsule {
+    pub a: Vec2,
+    pub b: Vec2,
+    pub radius: f32,
+}
+
 // Ray
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Ray {
@@ -60,6 +68,13 @@ impl Default for RaycastResult {
     }
 }
 
+impl RaycastResult {
+    // Discard hits farther than max_t along the ray
+    pub fn within(self, max_t: f32) -> Self {
+        if self.hit && self.distance <= max_t { self } else { Self::default() }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct CollisionResult {
     pub hit: bool,
@@ -308,6 +323,69 @@ pub fn polygon_overlap(poly_a: &[Vec2], poly_b: &[Vec2]) -> bool {
     true // All axes passed, shapes overlap
 }
 
+/* ============================
+ * Concave Polygons
+ * ============================ */
+
+// Point-in-polygon using the even-odd crossing rule; works for concave input
+pub fn point_in_polygon(point: Vec2, vertices: &[Vec2]) -> bool {
+    let n = vertices.len();
+    if n < 3 {
+        return false;
+    }
+
+    let mut inside = false;
+    let mut j = n - 1;
+    for i in 0..n {
+        let a = vertices[i];
+        let b = vertices[j];
+        if (a.y > point.y) != (b.y > point.y) {
+            let x = a.x + (point.y - a.y) * (b.x - a.x) / (b.y - a.y);
+            if point.x < x {
+                inside = !inside;
+            }
+        }
+        j = i;
+    }
+    inside
+}
+
+// Bounding box of a vertex list; an empty list gives a zero-size box at
+// the origin
+pub fn polygon_aabb(vertices: &[Vec2]) -> Aabb {
+    let Some((&first, rest)) = vertices.split_first() else {
+        return Aabb::new(Vec2::zero(), Vec2::zero());
+    };
+    let mut min = first;
+    let mut max = first;
+    for v in rest {
+        min = Vec2::new(min.x.min(v.x), min.y.min(v.y));
+        max = Vec2::new(max.x.max(v.x), max.y.max(v.y));
+    }
+    Aabb::new(min, max)
+}
+
+// Overlap test for arbitrary simple polygons: decompose both into convex
+// pieces and run SAT on each pair. Callers that test the same shape every
+// frame should cache `convex_decompose` and use `convex_pieces_overlap`.
+pub fn concave_polygon_overlap(poly_a: &[Vec2], poly_b: &[Vec2]) -> bool {
+    if !polygon_aabb(poly_a).overlaps(&polygon_aabb(poly_b)) {
+        return false;
+    }
+    convex_pieces_overlap(&convex_decompose(poly_a), &convex_decompose(poly_b))
+}
+
+pub fn convex_pieces_overlap(pieces_a: &[Vec<Vec2>], pieces_b: &[Vec<Vec2>]) -> bool {
+    for a in pieces_a {
+        for b in pieces_b {
+            if polygon_overlap(a, b) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
 /* ============================
  * Physics Helpers
  * ============================ */
@@ -374,3 +452,276 @@ pub fn raycast_segment(ray: &Ray, segment: &Segment) -> RaycastResult {
     
     result
 }
+
+
+/* ============================
+ * Ray vs Polygon / Capsule
+ * ============================ */
+
+// Ray vs convex polygon (Cyrus-Beck clipping), either winding.
+// Like raycast_aabb, a ray starting inside reports the exit point.
+pub fn raycast_polygon(ray: &Ray, vertices: &[Vec2]) -> RaycastResult {
+    let mut result = RaycastResult::default();
+    let n = vertices.len();
+    if n < 3 {
+        return result;
+    }
+
+    // Outward normal is -perp for CCW, +perp for CW
+    let outward = if polygon_signed_area(vertices) >= 0.0 { -1.0 } else { 1.0 };
+
+    let mut t_enter = f32::NEG_INFINITY;
+    let mut t_exit = f32::INFINITY;
+    let mut n_enter = Vec2::zero();
+    let mut n_exit = Vec2::zero();
+
+    for i in 0..n {
+        let a = vertices[i];
+        let b = vertices[(i + 1) % n];
+        let normal = (b - a).perp().normalize() * outward;
+
+        let denom = normal.dot(ray.direction);
+        let dist = normal.dot(a - ray.origin);
+
+        if denom.abs() < 1e-8 {
+            // Parallel to this edge: miss if outside its half-plane
+            if dist < 0.0 {
+                return result;
+            }
+            continue;
+        }
+
+        let t = dist / denom;
+        if denom < 0.0 {
+            if t > t_enter {
+                t_enter = t;
+                n_enter = normal;
+            }
+        } else if t < t_exit {
+            t_exit = t;
+            n_exit = normal;
+        }
+
+        if t_enter > t_exit {
+            return result;
+        }
+    }
+
+    if t_exit < 0.0 {
+        return result;
+    }
+
+    let (t, normal) = if t_enter >= 0.0 { (t_enter, n_enter) } else { (t_exit, n_exit) };
+    result.hit = true;
+    result.distance = t;
+    result.point = ray.origin + ray.direction * t;
+    result.normal = normal;
+    result
+}
+
+impl Capsule {
+    pub fn new(a: Vec2, b: Vec2, radius: f32) -> Self {
+        Self { a, b, radius }
+    }
+
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        closest_point_on_segment(point, self.a, self.b).dist_squared(point) <= self.radius * self.radius
+    }
+}
+
+pub fn closest_point_on_segment(point: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+    let ab = b - a;
+    let len_sq = ab.len_squared();
+    if len_sq <= 0.0 {
+        return a;
+    }
+    a + ab * clamp01((point - a).dot(ab) / len_sq)
+}
+
+// Ray vs capsule: first entry into the two end circles or the middle box.
+// A ray starting inside reports a hit at distance 0 facing back along the ray.
+pub fn raycast_capsule(ray: &Ray, capsule: &Capsule) -> RaycastResult {
+    if capsule.contains_point(ray.origin) {
+        return RaycastResult {
+            hit: true,
+            point: ray.origin,
+            normal: -ray.direction,
+            distance: 0.0,
+        };
+    }
+
+    let mut best = raycast_circle(ray, &Circle::new(capsule.a, capsule.radius));
+    let end = raycast_circle(ray, &Circle::new(capsule.b, capsule.radius));
+    if end.hit && (!best.hit || end.distance < best.distance) {
+        best = end;
+    }
+
+    let axis = capsule.b - capsule.a;
+    if axis.len_squared() > 0.0 {
+        let side = axis.perp().normalize() * capsule.radius;
+        let body = [capsule.a - side, capsule.b - side, capsule.b + side, capsule.a + side];
+        let mid = raycast_polygon(ray, &body);
+        if mid.hit && (!best.hit || mid.distance < best.distance) {
+            best = mid;
+        }
+    }
+
+    best
+}
+
+/* ============================
+ * Scene Queries
+ * ============================ */
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColliderShape {
+    Circle(Circle),
+    Aabb(Aabb),
+    Segment(Segment),
+    Capsule(Capsule),
+    Polygon(Vec<Vec2>), // Convex
+}
+
+// A shape tagged with collision layers (bit flags)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Collider {
+    pub shape: ColliderShape,
+    pub layers: u32,
+}
+
+impl Collider {
+    pub fn new(shape: ColliderShape, layers: u32) -> Self {
+        Self { shape, layers }
+    }
+
+    pub fn bounds(&self) -> Aabb {
+        match &self.shape {
+            ColliderShape::Circle(c) => Aabb::from_center(c.center, Vec2::new(c.radius, c.radius)),
+            ColliderShape::Aabb(b) => *b,
+            ColliderShape::Segment(s) => polygon_aabb(&[s.a, s.b]),
+            ColliderShape::Capsule(c) => {
+                let b = polygon_aabb(&[c.a, c.b]);
+                let r = Vec2::new(c.radius, c.radius);
+                Aabb::new(b.min - r, b.max + r)
+            }
+            ColliderShape::Polygon(verts) => polygon_aabb(verts),
+        }
+    }
+}
+
+pub fn raycast_shape(ray: &Ray, shape: &ColliderShape, max_t: f32) -> RaycastResult {
+    let result = match shape {
+        ColliderShape::Circle(c) => raycast_circle(ray, c),
+        ColliderShape::Aabb(b) => raycast_aabb(ray, b),
+        ColliderShape::Segment(s) => raycast_segment(ray, s),
+        ColliderShape::Capsule(c) => raycast_capsule(ray, c),
+        ColliderShape::Polygon(verts) => raycast_polygon(ray, verts),
+    };
+    result.within(max_t)
+}
+
+// Cheap bounds rejection before the exact test
+fn ray_may_hit(ray: &Ray, collider: &Collider, max_t: f32) -> bool {
+    let bounds = collider.bounds();
+    if bounds.contains_point(ray.origin) {
+        return true;
+    }
+    raycast_aabb(ray, &bounds).within(max_t).hit
+}
+
+// Closest hit among colliders whose layers intersect `mask`.
+// Returns the collider index alongside the hit.
+pub fn raycast_closest(ray: &Ray, colliders: &[Collider], mask: u32, max_t: f32) -> Option<(usize, RaycastResult)> {
+    let mut best: Option<(usize, RaycastResult)> = None;
+    let mut limit = max_t;
+
+    for (i, collider) in colliders.iter().enumerate() {
+        if collider.layers & mask == 0 || !ray_may_hit(ray, collider, limit) {
+            continue;
+        }
+        let hit = raycast_shape(ray, &collider.shape, limit);
+        if hit.hit {
+            limit = hit.distance;
+            best = Some((i, hit));
+        }
+    }
+
+    best
+}
+
+// Every hit among colliders whose layers intersect `mask`, nearest first
+pub fn raycast_all(ray: &Ray, colliders: &[Collider], mask: u32, max_t: f32) -> Vec<(usize, RaycastResult)> {
+    let mut hits = Vec::new();
+
+    for (i, collider) in colliders.iter().enumerate() {
+        if collider.layers & mask == 0 || !ray_may_hit(ray, collider, max_t) {
+            continue;
+        }
+        let hit = raycast_shape(ray, &collider.shape, max_t);
+        if hit.hit {
+            hits.push((i, hit));
+        }
+    }
+
+    hits.sort_by(|a, b| a.1.distance.partial_cmp(&b.1.distance).unwrap_or(std::cmp::Ordering::Equal));
+    hits
+}
+
+/* ============================
+ * Visibility (Field of View)
+ * ============================ */
+
+// Edges of a polygon as segments, for use as occluders
+pub fn polygon_segments(vertices: &[Vec2]) -> Vec<Segment> {
+    let n = vertices.len();
+    (0..n).map(|i| Segment::new(vertices[i], vertices[(i + 1) % n])).collect()
+}
+
+pub fn aabb_segments(aabb: &Aabb) -> [Segment; 4] {
+    let a = aabb.min;
+    let b = Vec2::new(aabb.max.x, aabb.min.y);
+    let c = aabb.max;
+    let d = Vec2::new(aabb.min.x, aabb.max.y);
+    [Segment::new(a, b), Segment::new(b, c), Segment::new(c, d), Segment::new(d, a)]
+}
+
+// Visible region around `origin` given occluding segments, limited to a
+// square of half-size `radius`. Casts rays at every occluder endpoint (and
+// slightly to either side, to slip past corners) and returns the hit points
+// sorted by angle; draw it as a triangle fan centered on `origin`.
+pub fn visibility_polygon(origin: Vec2, occluders: &[Segment], radius: f32) -> Vec<Vec2> {
+    const NUDGE: f32 = 0.0001;
+
+    let bounds = Aabb::from_center(origin, Vec2::new(radius, radius));
+    let mut walls: Vec<Segment> = occluders.to_vec();
+    walls.extend_from_slice(&aabb_segments(&bounds));
+
+    let mut angles = Vec::with_capacity(walls.len() * 6);
+    for wall in &walls {
+        for p in [wall.a, wall.b] {
+            let angle = (p - origin).angle();
+            angles.push(angle - NUDGE);
+            angles.push(angle);
+            angles.push(angle + NUDGE);
+        }
+    }
+
+    let mut hits: Vec<(f32, Vec2)> = Vec::with_capacity(angles.len());
+    for angle in angles {
+        let ray = Ray::new(origin, Vec2::from_angle(angle));
+        let mut closest = f32::INFINITY;
+        for wall in &walls {
+            let hit = raycast_segment(&ray, wall);
+            if hit.hit && hit.distance < closest {
+                closest = hit.distance;
+            }
+        }
+        if closest.is_finite() {
+            hits.push((angle, ray.origin + ray.direction * closest));
+        }
+    }
+
+    hits.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    hits.dedup_by(|a, b| a.1.dist_squared(b.1) < 1e-6);
+    hits.into_iter().map(|(_, p)| p).collect()
+}
@@ -4,11 +4,16 @@ mod math;
 mod collision;
 mod raylib;
 mod helpers;
+#[allow(unused_attributes)]
+#[path = "lib/collections.rs"]
+mod collections;
 
 use math::*;
 use collision::*;
 use raylib::*;
 use helpers::*;
+// raylib has a 3D `Ray` too
+use collision::Ray;
 
 
 
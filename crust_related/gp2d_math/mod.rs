@@ -81,6 +81,11 @@ impl Vec2 {
         Self::new(-self.y, self.x)
     }
 
+    // 2D cross product (z component of the 3D cross)
+    pub fn cross(self, other: Self) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
     // Interpolation
     pub fn lerp(self, other: Self, t: f32) -> Self {
         Self::new(
@@ -278,11 +283,169 @@ pub fn ease_out_expo(t: f32) -> f32 {
     if t == 1.0 { 1.0 } else { 1.0 - 2.0f32.powf(-10.0 * t) }
 }
 
+// Exponential (in-out)
+pub fn ease_in_out_expo(t: f32) -> f32 {
+    if t == 0.0 || t == 1.0 { return t; }
+    if t < 0.5 {
+        2.0f32.powf(20.0 * t - 10.0) * 0.5
+    } else {
+        (2.0 - 2.0f32.powf(-20.0 * t + 10.0)) * 0.5
+    }
+}
+
+// Sine
+pub fn ease_in_sine(t: f32) -> f32 { 1.0 - (t * PI * 0.5).cos() }
+pub fn ease_out_sine(t: f32) -> f32 { (t * PI * 0.5).sin() }
+pub fn ease_in_out_sine(t: f32) -> f32 { -((PI * t).cos() - 1.0) * 0.5 }
+
+// Quartic
+pub fn ease_in_quart(t: f32) -> f32 { t * t * t * t }
+pub fn ease_out_quart(t: f32) -> f32 { 1.0 - (1.0 - t).powi(4) }
+pub fn ease_in_out_quart(t: f32) -> f32 {
+    if t < 0.5 { 8.0 * t * t * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(4) * 0.5 }
+}
+
+// Quintic
+pub fn ease_in_quint(t: f32) -> f32 { t * t * t * t * t }
+pub fn ease_out_quint(t: f32) -> f32 { 1.0 - (1.0 - t).powi(5) }
+pub fn ease_in_out_quint(t: f32) -> f32 {
+    if t < 0.5 { 16.0 * t * t * t * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(5) * 0.5 }
+}
+
+// Circular
+pub fn ease_in_circ(t: f32) -> f32 { 1.0 - (1.0 - t * t).max(0.0).sqrt() }
+pub fn ease_out_circ(t: f32) -> f32 { (1.0 - (t - 1.0) * (t - 1.0)).max(0.0).sqrt() }
+pub fn ease_in_out_circ(t: f32) -> f32 {
+    if t < 0.5 {
+        (1.0 - (1.0 - (2.0 * t).powi(2)).max(0.0).sqrt()) * 0.5
+    } else {
+        ((1.0 - (-2.0 * t + 2.0).powi(2)).max(0.0).sqrt() + 1.0) * 0.5
+    }
+}
+
+// Back (overshoots by the standard 10%)
+const BACK_C1: f32 = 1.70158;
+const BACK_C2: f32 = BACK_C1 * 1.525;
+const BACK_C3: f32 = BACK_C1 + 1.0;
+
+pub fn ease_in_back(t: f32) -> f32 { BACK_C3 * t * t * t - BACK_C1 * t * t }
+pub fn ease_out_back(t: f32) -> f32 {
+    let s = t - 1.0;
+    1.0 + BACK_C3 * s * s * s + BACK_C1 * s * s
+}
+pub fn ease_in_out_back(t: f32) -> f32 {
+    if t < 0.5 {
+        ((2.0 * t).powi(2) * ((BACK_C2 + 1.0) * 2.0 * t - BACK_C2)) * 0.5
+    } else {
+        ((2.0 * t - 2.0).powi(2) * ((BACK_C2 + 1.0) * (t * 2.0 - 2.0) + BACK_C2) + 2.0) * 0.5
+    }
+}
+
 // Elastic
+pub fn ease_in_elastic(t: f32) -> f32 {
+    if t == 0.0 || t == 1.0 { return t; }
+    -(2.0f32.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * (2.0 * PI) / 3.0).sin()
+}
 pub fn ease_out_elastic(t: f32) -> f32 {
     if t == 0.0 || t == 1.0 { return t; }
     2.0f32.powf(-10.0 * t) * ((t - 0.075) * (2.0 * PI) / 0.3).sin() + 1.0
 }
+pub fn ease_in_out_elastic(t: f32) -> f32 {
+    if t == 0.0 || t == 1.0 { return t; }
+    let k = ((20.0 * t - 11.125) * (2.0 * PI) / 4.5).sin();
+    if t < 0.5 {
+        -(2.0f32.powf(20.0 * t - 10.0) * k) * 0.5
+    } else {
+        2.0f32.powf(-20.0 * t + 10.0) * k * 0.5 + 1.0
+    }
+}
+
+// Bounce
+pub fn ease_out_bounce(t: f32) -> f32 {
+    const N1: f32 = 7.5625;
+    const D1: f32 = 2.75;
+    if t < 1.0 / D1 {
+        N1 * t * t
+    } else if t < 2.0 / D1 {
+        let s = t - 1.5 / D1;
+        N1 * s * s + 0.75
+    } else if t < 2.5 / D1 {
+        let s = t - 2.25 / D1;
+        N1 * s * s + 0.9375
+    } else {
+        let s = t - 2.625 / D1;
+        N1 * s * s + 0.984375
+    }
+}
+pub fn ease_in_bounce(t: f32) -> f32 { 1.0 - ease_out_bounce(1.0 - t) }
+pub fn ease_in_out_bounce(t: f32) -> f32 {
+    if t < 0.5 {
+        (1.0 - ease_out_bounce(1.0 - 2.0 * t)) * 0.5
+    } else {
+        (1.0 + ease_out_bounce(2.0 * t - 1.0)) * 0.5
+    }
+}
+
+// CSS-style cubic-bezier timing curve through (0,0), (x1,y1), (x2,y2), (1,1)
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CubicBezier {
+    pub x1: f32,
+    pub y1: f32,
+    pub x2: f32,
+    pub y2: f32,
+}
+
+impl CubicBezier {
+    pub const EASE: Self = Self { x1: 0.25, y1: 0.1, x2: 0.25, y2: 1.0 };
+    pub const EASE_IN: Self = Self { x1: 0.42, y1: 0.0, x2: 1.0, y2: 1.0 };
+    pub const EASE_OUT: Self = Self { x1: 0.0, y1: 0.0, x2: 0.58, y2: 1.0 };
+    pub const EASE_IN_OUT: Self = Self { x1: 0.42, y1: 0.0, x2: 0.58, y2: 1.0 };
+
+    pub fn new(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+        Self { x1: clamp01(x1), y1, x2: clamp01(x2), y2 }
+    }
+
+    // One axis of the curve with endpoints fixed at 0 and 1
+    fn axis(a: f32, b: f32, s: f32) -> f32 {
+        let u = 1.0 - s;
+        3.0 * u * u * s * a + 3.0 * u * s * s * b + s * s * s
+    }
+
+    fn axis_slope(a: f32, b: f32, s: f32) -> f32 {
+        let u = 1.0 - s;
+        3.0 * u * u * a + 6.0 * u * s * (b - a) + 3.0 * s * s * (1.0 - b)
+    }
+
+    // Solve x(s) = t (Newton, falling back to bisection) and return y(s)
+    pub fn ease(&self, t: f32) -> f32 {
+        let t = clamp01(t);
+        let mut s = t;
+
+        for _ in 0..8 {
+            let err = Self::axis(self.x1, self.x2, s) - t;
+            if err.abs() < 1e-6 {
+                return Self::axis(self.y1, self.y2, s);
+            }
+            let slope = Self::axis_slope(self.x1, self.x2, s);
+            if slope.abs() < 1e-6 {
+                break;
+            }
+            s -= err / slope;
+        }
+
+        let (mut lo, mut hi) = (0.0, 1.0);
+        s = t;
+        for _ in 0..32 {
+            let x = Self::axis(self.x1, self.x2, s);
+            if (x - t).abs() < 1e-6 {
+                break;
+            }
+            if x < t { lo = s; } else { hi = s; }
+            s = (lo + hi) * 0.5;
+        }
+        Self::axis(self.y1, self.y2, s)
+    }
+}
 
 /* ============================
  * Timing & Animation
@@ -343,7 +506,7 @@ impl Timer {
  * Keyframe Animation
  * ============================ */
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct ColorF {
     pub r: f32,
     pub g: f32,
@@ -351,6 +514,128 @@ pub struct ColorF {
     pub a: f32,
 }
 
+impl ColorF {
+    pub fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Self::new(
+            self.r + (other.r - self.r) * t,
+            self.g + (other.g - self.g) * t,
+            self.b + (other.b - self.b) * t,
+            self.a + (other.a - self.a) * t,
+        )
+    }
+
+    // sRGB channel -> linear light
+    fn to_linear(c: f32) -> f32 {
+        if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    }
+
+    // Linear light -> sRGB channel
+    fn from_linear(c: f32) -> f32 {
+        if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.max(0.0).powf(1.0 / 2.4) - 0.055 }
+    }
+
+    // (L, a, b) in Björn Ottosson's OKLab space
+    pub fn to_oklab(self) -> (f32, f32, f32) {
+        let r = Self::to_linear(self.r);
+        let g = Self::to_linear(self.g);
+        let b = Self::to_linear(self.b);
+
+        let l = (0.41222147 * r + 0.53633254 * g + 0.051445993 * b).cbrt();
+        let m = (0.2119035 * r + 0.6806995 * g + 0.10739696 * b).cbrt();
+        let s = (0.08830246 * r + 0.28171884 * g + 0.6299787 * b).cbrt();
+
+        (
+            0.21045426 * l + 0.7936178 * m - 0.004072047 * s,
+            1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
+            0.025904037 * l + 0.78277177 * m - 0.80867577 * s,
+        )
+    }
+
+    pub fn from_oklab(lab_l: f32, lab_a: f32, lab_b: f32, alpha: f32) -> Self {
+        let l = lab_l + 0.39633778 * lab_a + 0.21580376 * lab_b;
+        let m = lab_l - 0.105561346 * lab_a - 0.06385417 * lab_b;
+        let s = lab_l - 0.08948418 * lab_a - 1.2914855 * lab_b;
+        let (l, m, s) = (l * l * l, m * m * m, s * s * s);
+
+        Self::new(
+            clamp01(Self::from_linear(4.0767417 * l - 3.3077116 * m + 0.23096993 * s)),
+            clamp01(Self::from_linear(-1.268438 * l + 2.6097574 * m - 0.34131938 * s)),
+            clamp01(Self::from_linear(-0.0041960863 * l - 0.7034186 * m + 1.7076147 * s)),
+            alpha,
+        )
+    }
+
+    // (hue in degrees 0..360, saturation, value)
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let delta = max - min;
+
+        let h = if delta <= 0.0 {
+            0.0
+        } else if max == self.r {
+            60.0 * ((self.g - self.b) / delta).rem_euclid(6.0)
+        } else if max == self.g {
+            60.0 * ((self.b - self.r) / delta + 2.0)
+        } else {
+            60.0 * ((self.r - self.g) / delta + 4.0)
+        };
+        let s = if max > 0.0 { delta / max } else { 0.0 };
+        (h, s, max)
+    }
+
+    pub fn from_hsv(h: f32, s: f32, v: f32, alpha: f32) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let c = v * s;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = v - c;
+        Self::new(r + m, g + m, b + m, alpha)
+    }
+
+    pub fn lerp_oklab(self, other: Self, t: f32) -> Self {
+        let (l1, a1, b1) = self.to_oklab();
+        let (l2, a2, b2) = other.to_oklab();
+        Self::from_oklab(
+            l1 + (l2 - l1) * t,
+            a1 + (a2 - a1) * t,
+            b1 + (b2 - b1) * t,
+            self.a + (other.a - self.a) * t,
+        )
+    }
+
+    pub fn lerp_hsv(self, other: Self, t: f32) -> Self {
+        let (h1, s1, v1) = self.to_hsv();
+        let (mut h2, s2, v2) = other.to_hsv();
+
+        // Greys have no hue; borrow the other end's so we don't sweep the wheel
+        let h1 = if s1 <= 0.0 { h2 } else { h1 };
+        if s2 <= 0.0 { h2 = h1; }
+
+        let mut dh = h2 - h1;
+        if dh > 180.0 { dh -= 360.0; }
+        if dh < -180.0 { dh += 360.0; }
+
+        Self::from_hsv(
+            h1 + dh * t,
+            s1 + (s2 - s1) * t,
+            v1 + (v2 - v1) * t,
+            self.a + (other.a - self.a) * t,
+        )
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct KeyFloat {
     pub time: f32,
@@ -369,96 +654,1311 @@ pub struct KeyColor {
     pub value: ColorF,
 }
 
-pub fn sample_float(keys: &[KeyFloat], t: f32, loop_: bool) -> f32 {
-    if keys.is_empty() {
-        return 0.0;
-    }
-    
+// Find the segment containing `t` given key times. Returns the index of
+// the segment's first key and the raw 0..1 fraction across it; times
+// outside the keys clamp to the first/last key with a fraction of 0.
+fn locate_key(len: usize, time_of: impl Fn(usize) -> f32, t: f32, loop_: bool) -> (usize, f32) {
+    let first = time_of(0);
+    let last = time_of(len - 1);
+
     let mut time = t;
-    if loop_ && keys.len() > 1 {
-        let total_duration = keys.last().unwrap().time - keys[0].time;
+    if loop_ && len > 1 {
+        let total_duration = last - first;
         if total_duration > 0.0 {
-            time = ((t - keys[0].time) % total_duration) + keys[0].time;
+            time = (t - first).rem_euclid(total_duration) + first;
         }
     }
-    
-    if keys.len() == 1 || time <= keys[0].time {
-        return keys[0].value;
+
+    if len == 1 || time <= first {
+        return (0, 0.0);
     }
-    if time >= keys.last().unwrap().time {
-        return keys.last().unwrap().value;
+    if time >= last {
+        return (len - 1, 0.0);
     }
 
-    for i in 1..keys.len() {
-        if time < keys[i].time {
-            let a = keys[i - 1].time;
-            let b = keys[i].time;
-            let p = ease_in_out_cubic((time - a) / (b - a));
-            return keys[i - 1].value + (keys[i].value - keys[i - 1].value) * p;
+    for i in 1..len {
+        if time < time_of(i) {
+            let a = time_of(i - 1);
+            let b = time_of(i);
+            return (i - 1, (time - a) / (b - a));
         }
     }
-    
-    keys.last().unwrap().value
+
+    (len - 1, 0.0)
+}
+
+pub fn sample_float(keys: &[KeyFloat], t: f32, loop_: bool) -> f32 {
+    if keys.is_empty() {
+        return 0.0;
+    }
+
+    let (i, p) = locate_key(keys.len(), |k| keys[k].time, t, loop_);
+    if i + 1 >= keys.len() {
+        return keys[i].value;
+    }
+    let p = ease_in_out_cubic(p);
+    keys[i].value + (keys[i + 1].value - keys[i].value) * p
 }
 
 pub fn sample_vec2(keys: &[KeyVec2], t: f32, loop_: bool) -> Vec2 {
     if keys.is_empty() {
         return Vec2::zero();
     }
-    
-    let mut time = t;
-    if loop_ && keys.len() > 1 {
-        let total_duration = keys.last().unwrap().time - keys[0].time;
-        if total_duration > 0.0 {
-            time = ((t - keys[0].time) % total_duration) + keys[0].time;
-        }
+
+    let (i, p) = locate_key(keys.len(), |k| keys[k].time, t, loop_);
+    if i + 1 >= keys.len() {
+        return keys[i].value;
     }
-    
-    if keys.len() == 1 || time <= keys[0].time {
-        return keys[0].value;
+    keys[i].value.lerp(keys[i + 1].value, ease_in_out_cubic(p))
+}
+
+// Which space colors are blended in
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ColorSpace {
+    Rgb,
+    Oklab, // Perceptually uniform; avoids muddy midpoints
+    Hsv,   // Hue takes the shortest way around the wheel
+}
+
+pub fn sample_color(keys: &[KeyColor], t: f32, loop_: bool, space: ColorSpace) -> ColorF {
+    if keys.is_empty() {
+        return ColorF::new(0.0, 0.0, 0.0, 0.0);
+    }
+
+    let (i, p) = locate_key(keys.len(), |k| keys[k].time, t, loop_);
+    if i + 1 >= keys.len() {
+        return keys[i].value;
+    }
+
+    let (a, b) = (keys[i].value, keys[i + 1].value);
+    let p = ease_in_out_cubic(p);
+    match space {
+        ColorSpace::Rgb => a.lerp(b, p),
+        ColorSpace::Oklab => a.lerp_oklab(b, p),
+        ColorSpace::Hsv => a.lerp_hsv(b, p),
+    }
+}
+
+/* ============================
+ * Keyframe Tracks
+ * ============================ */
+
+// How a key blends towards the next one
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Interp {
+    Step,       // Hold this key's value until the next key
+    Linear,
+    Smooth,     // ease_in_out_cubic, same as sample_float/sample_vec2
+    Hermite,    // Cubic using this key's out_tangent and the next key's in_tangent
+    CatmullRom, // Cubic with tangents derived from neighbouring keys
+}
+
+// Values that support the arithmetic cubic interpolation needs
+pub trait Animatable: Lerp {
+    fn zero() -> Self;
+    fn add(self, other: Self) -> Self;
+    fn scale(self, s: f32) -> Self;
+}
+
+impl Animatable for f32 {
+    fn zero() -> Self { 0.0 }
+    fn add(self, other: Self) -> Self { self + other }
+    fn scale(self, s: f32) -> Self { self * s }
+}
+
+impl Animatable for Vec2 {
+    fn zero() -> Self { Vec2::zero() }
+    fn add(self, other: Self) -> Self { Vec2::add(self, other) }
+    fn scale(self, s: f32) -> Self { Vec2::scale(self, s) }
+}
+
+impl Animatable for ColorF {
+    fn zero() -> Self { ColorF::new(0.0, 0.0, 0.0, 0.0) }
+    fn add(self, other: Self) -> Self {
+        ColorF::new(self.r + other.r, self.g + other.g, self.b + other.b, self.a + other.a)
+    }
+    fn scale(self, s: f32) -> Self {
+        ColorF::new(self.r * s, self.g * s, self.b * s, self.a * s)
+    }
+}
+
+// A keyframe with its own interpolation mode. Tangents are in value units
+// per second and only matter for Interp::Hermite.
+#[derive(Copy, Clone, Debug)]
+pub struct Key<T> {
+    pub time: f32,
+    pub value: T,
+    pub interp: Interp,
+    pub in_tangent: T,
+    pub out_tangent: T,
+}
+
+impl<T: Animatable> Key<T> {
+    pub fn new(time: f32, value: T, interp: Interp) -> Self {
+        Self { time, value, interp, in_tangent: T::zero(), out_tangent: T::zero() }
+    }
+
+    pub fn hermite(time: f32, value: T, in_tangent: T, out_tangent: T) -> Self {
+        Self { time, value, interp: Interp::Hermite, in_tangent, out_tangent }
+    }
+}
+
+// Cubic Hermite between p0 and p1 with tangents already scaled to the segment
+pub fn hermite<T: Animatable>(p0: T, m0: T, p1: T, m1: T, t: f32) -> T {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+    let h10 = t3 - 2.0 * t2 + t;
+    let h01 = -2.0 * t3 + 3.0 * t2;
+    let h11 = t3 - t2;
+    p0.scale(h00).add(m0.scale(h10)).add(p1.scale(h01)).add(m1.scale(h11))
+}
+
+// Finite-difference tangent at key i (per second), one-sided at the ends
+fn catmull_rom_tangent<T: Animatable>(keys: &[Key<T>], i: usize) -> T {
+    let prev = if i > 0 { i - 1 } else { i };
+    let next = if i + 1 < keys.len() { i + 1 } else { i };
+    let dt = keys[next].time - keys[prev].time;
+    if dt <= 0.0 {
+        return T::zero();
     }
-    if time >= keys.last().unwrap().time {
-        return keys.last().unwrap().value;
+    keys[next].value.add(keys[prev].value.scale(-1.0)).scale(1.0 / dt)
+}
+
+// Anything that yields a value over time
+pub trait Track<T> {
+    fn sample(&self, t: f32) -> T;
+    fn start_time(&self) -> f32;
+    fn end_time(&self) -> f32;
+
+    fn duration(&self) -> f32 {
+        self.end_time() - self.start_time()
     }
 
-    for i in 1..keys.len() {
-        if time < keys[i].time {
-            let a = keys[i - 1].time;
-            let b = keys[i].time;
-            let p = ease_in_out_cubic((time - a) / (b - a));
-            return keys[i - 1].value.lerp(keys[i].value, p);
+    fn sample_looped(&self, t: f32) -> T {
+        let d = self.duration();
+        if d <= 0.0 {
+            return self.sample(t);
         }
+        self.sample((t - self.start_time()).rem_euclid(d) + self.start_time())
+    }
+}
+
+impl<T: Animatable> Track<T> for [Key<T>] {
+    fn sample(&self, t: f32) -> T {
+        if self.is_empty() {
+            return T::zero();
+        }
+
+        let (i, p) = locate_key(self.len(), |k| self[k].time, t, false);
+        if i + 1 >= self.len() {
+            return self[i].value;
+        }
+
+        let (a, b) = (&self[i], &self[i + 1]);
+        match a.interp {
+            Interp::Step => a.value,
+            Interp::Linear => a.value.lerp(b.value, p),
+            Interp::Smooth => a.value.lerp(b.value, ease_in_out_cubic(p)),
+            Interp::Hermite => {
+                let dt = b.time - a.time;
+                hermite(a.value, a.out_tangent.scale(dt), b.value, b.in_tangent.scale(dt), p)
+            }
+            Interp::CatmullRom => {
+                let dt = b.time - a.time;
+                let m0 = catmull_rom_tangent(self, i).scale(dt);
+                let m1 = catmull_rom_tangent(self, i + 1).scale(dt);
+                hermite(a.value, m0, b.value, m1, p)
+            }
+        }
+    }
+
+    fn start_time(&self) -> f32 {
+        self.first().map_or(0.0, |k| k.time)
+    }
+
+    fn end_time(&self) -> f32 {
+        self.last().map_or(0.0, |k| k.time)
     }
-    
-    keys.last().unwrap().value
+}
+
+impl Track<f32> for [KeyFloat] {
+    fn sample(&self, t: f32) -> f32 { sample_float(self, t, false) }
+    fn start_time(&self) -> f32 { self.first().map_or(0.0, |k| k.time) }
+    fn end_time(&self) -> f32 { self.last().map_or(0.0, |k| k.time) }
+}
+
+impl Track<Vec2> for [KeyVec2] {
+    fn sample(&self, t: f32) -> Vec2 { sample_vec2(self, t, false) }
+    fn start_time(&self) -> f32 { self.first().map_or(0.0, |k| k.time) }
+    fn end_time(&self) -> f32 { self.last().map_or(0.0, |k| k.time) }
+}
+
+impl Track<ColorF> for [KeyColor] {
+    fn sample(&self, t: f32) -> ColorF { sample_color(self, t, false, ColorSpace::Rgb) }
+    fn start_time(&self) -> f32 { self.first().map_or(0.0, |k| k.time) }
+    fn end_time(&self) -> f32 { self.last().map_or(0.0, |k| k.time) }
 }
 
 /* ============================
- * Path Following (Bezier Chains)
+ * Tweens & Timelines
  * ============================ */
 
+// Anything that can be linearly interpolated
+pub trait Lerp: Copy {
+    fn lerp(self, other: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Lerp for Vec2 {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        Vec2::lerp(self, other, t)
+    }
+}
+
+impl Lerp for ColorF {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        ColorF::lerp(self, other, t)
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
-pub struct BezierSegment {
-    pub points: [Vec2; 4],
+pub enum Easing {
+    Func(fn(f32) -> f32),
+    Bezier(CubicBezier),
 }
 
-pub fn path_sample(segments: &[BezierSegment], t: f32) -> Vec2 {
-    if segments.is_empty() {
-        return Vec2::zero();
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        match self {
+            Easing::Func(f) => f(t),
+            Easing::Bezier(curve) => curve.ease(t),
+        }
     }
-    
-    let segment_t = t * segments.len() as f32;
-    let idx = (segment_t as usize).min(segments.len() - 1);
-    let local_t = segment_t - idx as f32;
+}
 
-    let segment = &segments[idx];
-    bezier_cubic(
-        segment.points[0],
-        segment.points[1],
-        segment.points[2],
-        segment.points[3],
-        local_t,
-    )
+pub const REPEAT_FOREVER: u32 = u32::MAX;
+
+// Anything a timeline can step. `advance` returns the part of `dt` left
+// over after finishing, so sequences can hand it to the next step.
+pub trait Animation {
+    fn advance(&mut self, dt: f32) -> f32;
+    fn is_finished(&self) -> bool;
+    fn reset(&mut self);
+}
+
+// Animates one value from `from` to `to`. The current value is read with
+// `value()`, and is also written to a bound `Rc<Cell<T>>` target on every
+// step so tweens inside a Sequence/Parallel can still drive game state.
+pub struct Tween<T: Lerp> {
+    pub from: T,
+    pub to: T,
+    pub duration: f32,
+    pub delay: f32,
+    pub easing: Easing,
+    pub repeat: u32, // Extra plays after the first; REPEAT_FOREVER loops
+    pub yoyo: bool,  // Alternate direction on every repeat
+    elapsed: f32,
+    value: T,
+    finished: bool,
+    target: Option<std::rc::Rc<std::cell::Cell<T>>>,
+    on_complete: Option<Box<dyn FnMut()>>,
 }
 
+impl<T: Lerp> Tween<T> {
+    pub fn new(from: T, to: T, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            delay: 0.0,
+            easing: Easing::Func(ease_linear),
+            repeat: 0,
+            yoyo: false,
+            elapsed: 0.0,
+            value: from,
+            finished: false,
+            target: None,
+            on_complete: None,
+        }
+    }
+
+    pub fn ease(mut self, f: fn(f32) -> f32) -> Self {
+        self.easing = Easing::Func(f);
+        self
+    }
+
+    pub fn bezier(mut self, curve: CubicBezier) -> Self {
+        self.easing = Easing::Bezier(curve);
+        self
+    }
+
+    pub fn delay(mut self, seconds: f32) -> Self {
+        self.delay = seconds;
+        self
+    }
+
+    pub fn repeat(mut self, count: u32) -> Self {
+        self.repeat = count;
+        self
+    }
+
+    pub fn yoyo(mut self, yoyo: bool) -> Self {
+        self.yoyo = yoyo;
+        self
+    }
+
+    pub fn bind(mut self, target: std::rc::Rc<std::cell::Cell<T>>) -> Self {
+        target.set(self.value);
+        self.target = Some(target);
+        self
+    }
+
+    pub fn on_complete(mut self, callback: impl FnMut() + 'static) -> Self {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    // Step forward and return the new value
+    pub fn update(&mut self, dt: f32) -> T {
+        self.advance(dt);
+        self.value
+    }
+
+    // Total running time including the delay; infinite when looping forever
+    pub fn total_duration(&self) -> f32 {
+        if self.repeat == REPEAT_FOREVER {
+            f32::INFINITY
+        } else {
+            self.delay + self.duration * (self.repeat as f32 + 1.0)
+        }
+    }
+
+    fn value_at(&self, local: f32) -> T {
+        if self.duration <= 0.0 {
+            return self.to;
+        }
+        let cycle = (local / self.duration).floor();
+        let mut p = (local - cycle * self.duration) / self.duration;
+        if self.yoyo && (cycle as u64) % 2 == 1 {
+            p = 1.0 - p;
+        }
+        self.from.lerp(self.to, self.easing.apply(p))
+    }
 
+    fn final_value(&self) -> T {
+        // With yoyo, an even number of plays ends back at the start
+        if self.yoyo && self.repeat % 2 == 1 { self.from } else { self.to }
+    }
+
+    fn publish(&self) {
+        if let Some(target) = &self.target {
+            target.set(self.value);
+        }
+    }
+}
+
+impl<T: Lerp> Animation for Tween<T> {
+    fn advance(&mut self, dt: f32) -> f32 {
+        if self.finished {
+            return dt;
+        }
+
+        self.elapsed += dt;
+        let total = self.total_duration();
+
+        if self.elapsed >= total {
+            let leftover = self.elapsed - total;
+            self.elapsed = total;
+            self.value = self.final_value();
+            self.finished = true;
+            self.publish();
+            if let Some(callback) = self.on_complete.as_mut() {
+                callback();
+            }
+            return leftover;
+        }
+
+        let local = self.elapsed - self.delay;
+        self.value = if local < 0.0 { self.from } else { self.value_at(local) };
+        self.publish();
+        0.0
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.finished = false;
+        self.value = self.from;
+        self.publish();
+    }
+}
+
+// Does nothing for a while; used for gaps inside a Sequence
+pub struct Wait {
+    pub duration: f32,
+    elapsed: f32,
+}
+
+impl Wait {
+    pub fn new(duration: f32) -> Self {
+        Self { duration, elapsed: 0.0 }
+    }
+}
+
+impl Animation for Wait {
+    fn advance(&mut self, dt: f32) -> f32 {
+        if self.is_finished() {
+            return dt;
+        }
+        self.elapsed += dt;
+        (self.elapsed - self.duration).max(0.0)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+}
+
+// Runs children one after another
+pub struct Sequence {
+    steps: Vec<Box<dyn Animation>>,
+    current: usize,
+    on_complete: Option<Box<dyn FnMut()>>,
+}
+
+impl Sequence {
+    pub fn new() -> Self {
+        Self { steps: Vec::new(), current: 0, on_complete: None }
+    }
+
+    pub fn then(mut self, step: impl Animation + 'static) -> Self {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    pub fn wait(self, seconds: f32) -> Self {
+        self.then(Wait::new(seconds))
+    }
+
+    pub fn on_complete(mut self, callback: impl FnMut() + 'static) -> Self {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+}
+
+impl Default for Sequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Animation for Sequence {
+    fn advance(&mut self, dt: f32) -> f32 {
+        if self.is_finished() {
+            return dt;
+        }
+
+        let mut remaining = dt;
+        while self.current < self.steps.len() {
+            remaining = self.steps[self.current].advance(remaining);
+            if !self.steps[self.current].is_finished() {
+                return 0.0;
+            }
+            self.current += 1;
+        }
+
+        if let Some(callback) = self.on_complete.as_mut() {
+            callback();
+        }
+        remaining
+    }
+
+    fn is_finished(&self) -> bool {
+        self.current >= self.steps.len()
+    }
+
+    fn reset(&mut self) {
+        for step in &mut self.steps {
+            step.reset();
+        }
+        self.current = 0;
+    }
+}
+
+// Runs children side by side; finishes when the slowest one does
+pub struct Parallel {
+    tracks: Vec<Box<dyn Animation>>,
+    finished: bool,
+    on_complete: Option<Box<dyn FnMut()>>,
+}
+
+impl Parallel {
+    pub fn new() -> Self {
+        Self { tracks: Vec::new(), finished: false, on_complete: None }
+    }
+
+    pub fn with(mut self, track: impl Animation + 'static) -> Self {
+        self.tracks.push(Box::new(track));
+        self
+    }
+
+    pub fn on_complete(mut self, callback: impl FnMut() + 'static) -> Self {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+}
+
+impl Default for Parallel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Animation for Parallel {
+    fn advance(&mut self, dt: f32) -> f32 {
+        if self.finished {
+            return dt;
+        }
+
+        let mut leftover = dt;
+        for track in &mut self.tracks {
+            leftover = leftover.min(track.advance(dt));
+        }
+
+        if self.tracks.iter().all(|t| t.is_finished()) {
+            self.finished = true;
+            if let Some(callback) = self.on_complete.as_mut() {
+                callback();
+            }
+            return leftover;
+        }
+        0.0
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn reset(&mut self) {
+        for track in &mut self.tracks {
+            track.reset();
+        }
+        self.finished = false;
+    }
+}
+
+/* ============================
+ * Path Following (Bezier Chains)
+ * ============================ */
+
+#[derive(Copy, Clone, Debug)]
+pub struct BezierSegment {
+    pub points: [Vec2; 4],
+}
+
+pub fn path_sample(segments: &[BezierSegment], t: f32) -> Vec2 {
+    if segments.is_empty() {
+        return Vec2::zero();
+    }
+    
+    let segment_t = t * segments.len() as f32;
+    let idx = (segment_t as usize).min(segments.len() - 1);
+    let local_t = segment_t - idx as f32;
+
+    let segment = &segments[idx];
+    bezier_cubic(
+        segment.points[0],
+        segment.points[1],
+        segment.points[2],
+        segment.points[3],
+        local_t,
+    )
+}
+
+impl BezierSegment {
+    pub fn new(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> Self {
+        Self { points: [p0, p1, p2, p3] }
+    }
+
+    // Exact degree elevation of a quadratic curve
+    pub fn from_quadratic(p0: Vec2, p1: Vec2, p2: Vec2) -> Self {
+        Self::new(
+            p0,
+            p0 + (p1 - p0) * (2.0 / 3.0),
+            p2 + (p1 - p2) * (2.0 / 3.0),
+            p2,
+        )
+    }
+
+    // The Catmull-Rom span from p1 to p2, with p0/p3 as neighbours
+    pub fn from_catmull_rom(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> Self {
+        Self::new(
+            p1,
+            p1 + (p2 - p0) * (1.0 / 6.0),
+            p2 - (p3 - p1) * (1.0 / 6.0),
+            p2,
+        )
+    }
+
+    // Inverse of from_catmull_rom: the four Catmull-Rom points for this span
+    pub fn to_catmull_rom(self) -> [Vec2; 4] {
+        let [b0, b1, b2, b3] = self.points;
+        [b3 + (b0 - b1) * 6.0, b0, b3, b0 + (b3 - b2) * 6.0]
+    }
+
+    // Approximate with `count` quadratics (control point from the tangent intersection midpoint)
+    pub fn to_quadratics(self, count: usize) -> Vec<[Vec2; 3]> {
+        let count = count.max(1);
+        let mut out = Vec::with_capacity(count);
+        for i in 0..count {
+            let t0 = i as f32 / count as f32;
+            let t1 = (i + 1) as f32 / count as f32;
+            let piece = self.sub_curve(t0, t1);
+            let [c0, c1, c2, c3] = piece.points;
+            let control = (c1 * 3.0 - c0 + c2 * 3.0 - c3) * 0.25;
+            out.push([c0, control, c3]);
+        }
+        out
+    }
+
+    pub fn point(&self, t: f32) -> Vec2 {
+        let [p0, p1, p2, p3] = self.points;
+        bezier_cubic(p0, p1, p2, p3, t)
+    }
+
+    // First derivative (velocity w.r.t. t)
+    pub fn derivative(&self, t: f32) -> Vec2 {
+        let [p0, p1, p2, p3] = self.points;
+        let u = 1.0 - t;
+        (p1 - p0) * (3.0 * u * u) + (p2 - p1) * (6.0 * u * t) + (p3 - p2) * (3.0 * t * t)
+    }
+
+    pub fn tangent(&self, t: f32) -> Vec2 {
+        let d = self.derivative(t);
+        if d.len_squared() > 1e-12 {
+            return d.normalize();
+        }
+        // Coincident control points: fall back to the chord
+        (self.points[3] - self.points[0]).normalize()
+    }
+
+    // Left-hand normal (tangent rotated +90°)
+    pub fn normal(&self, t: f32) -> Vec2 {
+        self.tangent(t).perp()
+    }
+
+    // de Casteljau split into [0, t] and [t, 1]
+    pub fn split(&self, t: f32) -> (Self, Self) {
+        let [p0, p1, p2, p3] = self.points;
+        let a = p0.lerp(p1, t);
+        let b = p1.lerp(p2, t);
+        let c = p2.lerp(p3, t);
+        let ab = a.lerp(b, t);
+        let bc = b.lerp(c, t);
+        let mid = ab.lerp(bc, t);
+        (Self::new(p0, a, ab, mid), Self::new(mid, bc, c, p3))
+    }
+
+    // The portion of the curve between t0 and t1; from t0 >= 1 that's the
+    // end point alone
+    pub fn sub_curve(&self, t0: f32, t1: f32) -> Self {
+        if t0 <= 0.0 {
+            return self.split(t1).0;
+        }
+        if t0 >= 1.0 {
+            let end = self.points[3];
+            return Self::new(end, end, end, end);
+        }
+        let (_, tail) = self.split(t0);
+        let local = (t1 - t0) / (1.0 - t0);
+        tail.split(local).0
+    }
+
+    // Tight bounds from the endpoints and the derivative's roots; (min, max)
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        let [p0, p1, p2, p3] = self.points;
+        let mut min = Vec2::new(p0.x.min(p3.x), p0.y.min(p3.y));
+        let mut max = Vec2::new(p0.x.max(p3.x), p0.y.max(p3.y));
+
+        let axis_roots = |a: f32, b: f32, c: f32, d: f32| -> [Option<f32>; 2] {
+            // B'(t)/3 = A t^2 + B t + C
+            let (e, f, g) = (b - a, c - b, d - c);
+            let qa = e - 2.0 * f + g;
+            let qb = 2.0 * (f - e);
+            let qc = e;
+            if qa.abs() < 1e-8 {
+                if qb.abs() < 1e-8 { return [None, None]; }
+                return [Some(-qc / qb), None];
+            }
+            let disc = qb * qb - 4.0 * qa * qc;
+            if disc < 0.0 { return [None, None]; }
+            let sq = disc.sqrt();
+            [Some((-qb + sq) / (2.0 * qa)), Some((-qb - sq) / (2.0 * qa))]
+        };
+
+        let roots_x = axis_roots(p0.x, p1.x, p2.x, p3.x);
+        let roots_y = axis_roots(p0.y, p1.y, p2.y, p3.y);
+        for t in roots_x.iter().chain(roots_y.iter()).flatten() {
+            if *t > 0.0 && *t < 1.0 {
+                let p = self.point(*t);
+                min = Vec2::new(min.x.min(p.x), min.y.min(p.y));
+                max = Vec2::new(max.x.max(p.x), max.y.max(p.y));
+            }
+        }
+        (min, max)
+    }
+
+    // Parameter of the closest point: coarse scan then Newton refinement
+    pub fn closest_t(&self, point: Vec2) -> f32 {
+        const COARSE: usize = 16;
+        let mut best_t = 0.0;
+        let mut best_d = f32::INFINITY;
+        for i in 0..=COARSE {
+            let t = i as f32 / COARSE as f32;
+            let d = self.point(t).dist_squared(point);
+            if d < best_d {
+                best_d = d;
+                best_t = t;
+            }
+        }
+
+        // Minimize |B(t) - p|^2: f(t) = (B - p)·B', f'(t) = B'·B' + (B - p)·B''
+        let mut t = best_t;
+        for _ in 0..5 {
+            let [p0, p1, p2, p3] = self.points;
+            let d1 = self.derivative(t);
+            let d2 = (p2 - p1 * 2.0 + p0) * (6.0 * (1.0 - t)) + (p3 - p2 * 2.0 + p1) * (6.0 * t);
+            let diff = self.point(t) - point;
+            let f = diff.dot(d1);
+            let df = d1.dot(d1) + diff.dot(d2);
+            if df.abs() < 1e-8 {
+                break;
+            }
+            t = clamp01(t - f / df);
+        }
+
+        if self.point(t).dist_squared(point) <= best_d { t } else { best_t }
+    }
+
+    // Arc length by fixed-step polyline approximation
+    pub fn length(&self, steps: usize) -> f32 {
+        let steps = steps.max(1);
+        let mut len = 0.0;
+        let mut prev = self.points[0];
+        for i in 1..=steps {
+            let p = self.point(i as f32 / steps as f32);
+            len += prev.dist(p);
+            prev = p;
+        }
+        len
+    }
+}
+
+// Chain of cubic segments through Catmull-Rom control points. Open
+// chains duplicate the end points so the curve passes through all of them.
+pub fn catmull_rom_chain(points: &[Vec2], closed: bool) -> Vec<BezierSegment> {
+    let n = points.len();
+    let mut segments = Vec::new();
+    if n < 2 {
+        return segments;
+    }
+
+    let at = |i: isize| -> Vec2 {
+        if closed {
+            points[i.rem_euclid(n as isize) as usize]
+        } else {
+            points[i.clamp(0, n as isize - 1) as usize]
+        }
+    };
+
+    let spans = if closed { n } else { n - 1 };
+    for i in 0..spans as isize {
+        segments.push(BezierSegment::from_catmull_rom(at(i - 1), at(i), at(i + 1), at(i + 2)));
+    }
+    segments
+}
+
+// A Bezier chain with an arc-length table, so it can be walked at
+// constant speed by distance instead of raw parameter.
+#[derive(Clone, Debug)]
+pub struct BezierPath {
+    pub segments: Vec<BezierSegment>,
+    samples_per_segment: usize,
+    lengths: Vec<f32>, // Cumulative distance at every table sample
+}
+
+impl BezierPath {
+    pub fn new(segments: Vec<BezierSegment>, samples_per_segment: usize) -> Self {
+        let mut path = Self {
+            segments,
+            samples_per_segment: samples_per_segment.max(1),
+            lengths: Vec::new(),
+        };
+        path.rebuild();
+        path
+    }
+
+    // Recompute the arc-length table after editing `segments`
+    pub fn rebuild(&mut self) {
+        let n = self.samples_per_segment;
+        self.lengths.clear();
+        self.lengths.push(0.0);
+
+        let mut total = 0.0;
+        for segment in &self.segments {
+            let mut prev = segment.points[0];
+            for i in 1..=n {
+                let p = segment.point(i as f32 / n as f32);
+                total += prev.dist(p);
+                self.lengths.push(total);
+                prev = p;
+            }
+        }
+    }
+
+    pub fn length(&self) -> f32 {
+        *self.lengths.last().unwrap_or(&0.0)
+    }
+
+    // Segment index and local t at a distance along the path
+    pub fn locate(&self, distance: f32) -> (usize, f32) {
+        if self.segments.is_empty() {
+            return (0, 0.0);
+        }
+
+        let d = clamp(distance, 0.0, self.length());
+        let upper = self.lengths.partition_point(|&l| l < d).clamp(1, self.lengths.len() - 1);
+        let lower = upper - 1;
+
+        let span = self.lengths[upper] - self.lengths[lower];
+        let frac = if span > 0.0 { (d - self.lengths[lower]) / span } else { 0.0 };
+
+        let n = self.samples_per_segment;
+        let segment = (lower / n).min(self.segments.len() - 1);
+        let local = ((lower - segment * n) as f32 + frac) / n as f32;
+        (segment, clamp01(local))
+    }
+
+    pub fn point_at(&self, distance: f32) -> Vec2 {
+        if self.segments.is_empty() {
+            return Vec2::zero();
+        }
+        let (i, t) = self.locate(distance);
+        self.segments[i].point(t)
+    }
+
+    pub fn tangent_at(&self, distance: f32) -> Vec2 {
+        if self.segments.is_empty() {
+            return Vec2::zero();
+        }
+        let (i, t) = self.locate(distance);
+        self.segments[i].tangent(t)
+    }
+
+    pub fn normal_at(&self, distance: f32) -> Vec2 {
+        self.tangent_at(distance).perp()
+    }
+
+    // Constant-speed replacement for path_sample: u in 0..1 over the whole length
+    pub fn sample_uniform(&self, u: f32) -> Vec2 {
+        self.point_at(clamp01(u) * self.length())
+    }
+
+    // Closest point on the path and its distance along the path
+    pub fn closest_point(&self, point: Vec2) -> (Vec2, f32) {
+        let mut best = (Vec2::zero(), 0.0);
+        let mut best_d = f32::INFINITY;
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            let t = segment.closest_t(point);
+            let p = segment.point(t);
+            let d = p.dist_squared(point);
+            if d < best_d {
+                best_d = d;
+                best = (p, self.distance_at(i, t));
+            }
+        }
+        best
+    }
+
+    // Distance along the path for a segment index and local t (inverse of locate)
+    pub fn distance_at(&self, segment: usize, t: f32) -> f32 {
+        if self.segments.is_empty() {
+            return 0.0;
+        }
+        let n = self.samples_per_segment;
+        let segment = segment.min(self.segments.len() - 1);
+        let pos = clamp01(t) * n as f32;
+        let step = (pos as usize).min(n - 1);
+        let base = segment * n + step;
+        let frac = pos - step as f32;
+        self.lengths[base] + (self.lengths[base + 1] - self.lengths[base]) * frac
+    }
+
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        let mut min = Vec2::new(f32::INFINITY, f32::INFINITY);
+        let mut max = Vec2::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+        for segment in &self.segments {
+            let (lo, hi) = segment.bounds();
+            min = Vec2::new(min.x.min(lo.x), min.y.min(lo.y));
+            max = Vec2::new(max.x.max(hi.x), max.y.max(hi.y));
+        }
+        (min, max)
+    }
+}
+
+/* ============================
+ * Polygon Utilities
+ * ============================ */
+
+// Signed area (shoelace). Positive for counter-clockwise winding in a
+// y-up frame, which shows up as clockwise on a y-down screen.
+pub fn polygon_signed_area(vertices: &[Vec2]) -> f32 {
+    let n = vertices.len();
+    if n < 3 {
+        return 0.0;
+    }
+    let mut sum = 0.0;
+    for i in 0..n {
+        sum += vertices[i].cross(vertices[(i + 1) % n]);
+    }
+    sum * 0.5
+}
+
+pub fn polygon_area(vertices: &[Vec2]) -> f32 {
+    polygon_signed_area(vertices).abs()
+}
+
+pub fn polygon_is_ccw(vertices: &[Vec2]) -> bool {
+    polygon_signed_area(vertices) > 0.0
+}
+
+// Reverse the vertex order in place if needed so the winding is CCW
+pub fn polygon_make_ccw(vertices: &mut [Vec2]) {
+    if polygon_signed_area(vertices) < 0.0 {
+        vertices.reverse();
+    }
+}
+
+// Area-weighted centroid; falls back to the vertex average for degenerate input
+pub fn polygon_centroid(vertices: &[Vec2]) -> Vec2 {
+    let n = vertices.len();
+    if n == 0 {
+        return Vec2::zero();
+    }
+
+    let area = polygon_signed_area(vertices);
+    if area.abs() < 1e-6 {
+        let mut sum = Vec2::zero();
+        for v in vertices { sum = sum + *v; }
+        return sum * (1.0 / n as f32);
+    }
+
+    let mut c = Vec2::zero();
+    for i in 0..n {
+        let a = vertices[i];
+        let b = vertices[(i + 1) % n];
+        c = c + (a + b) * a.cross(b);
+    }
+    c * (1.0 / (6.0 * area))
+}
+
+// Moment of inertia about the centroid for a solid polygon of uniform density
+pub fn polygon_moment_of_inertia(vertices: &[Vec2], mass: f32) -> f32 {
+    let n = vertices.len();
+    if n < 3 {
+        return 0.0;
+    }
+
+    let c = polygon_centroid(vertices);
+    let mut numerator = 0.0;
+    let mut denominator = 0.0;
+    for i in 0..n {
+        let a = vertices[i] - c;
+        let b = vertices[(i + 1) % n] - c;
+        let cross = a.cross(b);
+        numerator += cross * (a.dot(a) + a.dot(b) + b.dot(b));
+        denominator += cross;
+    }
+
+    if denominator.abs() < 1e-6 {
+        return 0.0;
+    }
+    mass * numerator / (6.0 * denominator)
+}
+
+// True if every turn has the same direction (collinear vertices allowed)
+pub fn polygon_is_convex(vertices: &[Vec2]) -> bool {
+    let n = vertices.len();
+    if n < 3 {
+        return false;
+    }
+
+    let mut sign = 0.0;
+    for i in 0..n {
+        let a = vertices[i];
+        let b = vertices[(i + 1) % n];
+        let c = vertices[(i + 2) % n];
+        let turn = (b - a).cross(c - b);
+        if turn.abs() < 1e-6 {
+            continue;
+        }
+        if sign == 0.0 {
+            sign = turn.signum();
+        } else if turn.signum() != sign {
+            return false;
+        }
+    }
+    true
+}
+
+// Convex hull via Andrew's monotone chain. Returns CCW vertices without
+// collinear points.
+pub fn convex_hull(points: &[Vec2]) -> Vec<Vec2> {
+    let mut pts: Vec<Vec2> = points.to_vec();
+    pts.sort_by(|a, b| {
+        a.x.partial_cmp(&b.x)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.y.partial_cmp(&b.y).unwrap_or(std::cmp::Ordering::Equal))
+    });
+    pts.dedup();
+
+    if pts.len() < 3 {
+        return pts;
+    }
+
+    let mut hull: Vec<Vec2> = Vec::with_capacity(pts.len() * 2);
+
+    // Lower hull
+    for &p in &pts {
+        while hull.len() >= 2 {
+            let a = hull[hull.len() - 2];
+            let b = hull[hull.len() - 1];
+            if (b - a).cross(p - b) <= 0.0 { hull.pop(); } else { break; }
+        }
+        hull.push(p);
+    }
+
+    // Upper hull
+    let lower_len = hull.len() + 1;
+    for &p in pts.iter().rev().skip(1) {
+        while hull.len() >= lower_len {
+            let a = hull[hull.len() - 2];
+            let b = hull[hull.len() - 1];
+            if (b - a).cross(p - b) <= 0.0 { hull.pop(); } else { break; }
+        }
+        hull.push(p);
+    }
+
+    hull.pop(); // Last point repeats the first
+    hull
+}
+
+// Inclusive point-in-triangle test for a CCW triangle
+fn point_in_triangle_ccw(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    (b - a).cross(p - a) >= 0.0 &&
+    (c - b).cross(p - b) >= 0.0 &&
+    (a - c).cross(p - c) >= 0.0
+}
+
+// Ear-clipping triangulation of a simple polygon (either winding).
+// Returns CCW triangles as indices into `vertices`.
+pub fn triangulate(vertices: &[Vec2]) -> Vec<[usize; 3]> {
+    let n = vertices.len();
+    let mut triangles = Vec::new();
+    if n < 3 {
+        return triangles;
+    }
+
+    let mut remaining: Vec<usize> = if polygon_signed_area(vertices) >= 0.0 {
+        (0..n).collect()
+    } else {
+        (0..n).rev().collect()
+    };
+
+    while remaining.len() > 3 {
+        let m = remaining.len();
+        let mut ear = None;
+
+        for i in 0..m {
+            let ia = remaining[(i + m - 1) % m];
+            let ib = remaining[i];
+            let ic = remaining[(i + 1) % m];
+            let (a, b, c) = (vertices[ia], vertices[ib], vertices[ic]);
+
+            // Reflex or flat corners can't be ears
+            if (b - a).cross(c - b) <= 1e-6 {
+                continue;
+            }
+
+            let blocked = remaining.iter().any(|&j| {
+                let p = vertices[j];
+                j != ia && j != ib && j != ic &&
+                p != a && p != b && p != c &&
+                point_in_triangle_ccw(p, a, b, c)
+            });
+
+            if !blocked {
+                ear = Some(i);
+                break;
+            }
+        }
+
+        // Degenerate input (self-intersection, all collinear): clip anyway so we terminate
+        let i = ear.unwrap_or(0);
+        triangles.push([
+            remaining[(i + m - 1) % m],
+            remaining[i],
+            remaining[(i + 1) % m],
+        ]);
+        remaining.remove(i);
+    }
+
+    triangles.push([remaining[0], remaining[1], remaining[2]]);
+    triangles
+}
+
+// Merge two CCW index polygons across a shared diagonal, if they share one
+fn merge_across_diagonal(p: &[usize], q: &[usize]) -> Option<Vec<usize>> {
+    for k in 0..p.len() {
+        let a = p[k];
+        let b = p[(k + 1) % p.len()];
+        for m in 0..q.len() {
+            if q[m] == b && q[(m + 1) % q.len()] == a {
+                // Walk P from b around to a, then Q's vertices strictly between a and b
+                let mut merged = Vec::with_capacity(p.len() + q.len() - 2);
+                for i in 0..p.len() {
+                    merged.push(p[(k + 1 + i) % p.len()]);
+                }
+                for i in 2..q.len() {
+                    merged.push(q[(m + i) % q.len()]);
+                }
+                return Some(merged);
+            }
+        }
+    }
+    None
+}
+
+// Convex decomposition via Hertel-Mehlhorn: triangulate, then drop every
+// diagonal whose removal keeps the merged piece convex. Produces at most
+// four times the optimal number of pieces. Output pieces are CCW.
+pub fn convex_decompose(vertices: &[Vec2]) -> Vec<Vec<Vec2>> {
+    if vertices.len() < 3 {
+        return Vec::new();
+    }
+    if polygon_is_convex(vertices) {
+        let mut piece = vertices.to_vec();
+        polygon_make_ccw(&mut piece);
+        return vec![piece];
+    }
+
+    let mut pieces: Vec<Vec<usize>> = triangulate(vertices)
+        .into_iter()
+        .map(|t| t.to_vec())
+        .collect();
+
+    let mut merged_any = true;
+    while merged_any {
+        merged_any = false;
+        'search: for i in 0..pieces.len() {
+            for j in (i + 1)..pieces.len() {
+                if let Some(merged) = merge_across_diagonal(&pieces[i], &pieces[j]) {
+                    let points: Vec<Vec2> = merged.iter().map(|&k| vertices[k]).collect();
+                    if polygon_is_convex(&points) {
+                        pieces[i] = merged;
+                        pieces.swap_remove(j);
+                        merged_any = true;
+                        break 'search;
+                    }
+                }
+            }
+        }
+    }
+
+    pieces
+        .into_iter()
+        .map(|piece| piece.iter().map(|&k| vertices[k]).collect())
+        .collect()
+}
+
+// Distance from a point to the segment ab
+fn point_segment_distance(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let len_sq = ab.len_squared();
+    if len_sq <= 0.0 {
+        return p.dist(a);
+    }
+    let t = clamp01((p - a).dot(ab) / len_sq);
+    p.dist(a + ab * t)
+}
+
+// Ramer-Douglas-Peucker over an open chain, appending kept points (excluding the last)
+fn simplify_chain(points: &[Vec2], tolerance: f32, out: &mut Vec<Vec2>) {
+    let last = points.len() - 1;
+    let mut max_dist = 0.0;
+    let mut split = 0;
+    for i in 1..last {
+        let d = point_segment_distance(points[i], points[0], points[last]);
+        if d > max_dist {
+            max_dist = d;
+            split = i;
+        }
+    }
+
+    if max_dist > tolerance {
+        simplify_chain(&points[..=split], tolerance, out);
+        simplify_chain(&points[split..], tolerance, out);
+    } else {
+        out.push(points[0]);
+    }
+}
+
+// Simplify a closed polygon, dropping vertices closer than `tolerance`
+// to the simplified outline. Splits the ring at the vertex farthest from
+// the first one and runs Ramer-Douglas-Peucker on both halves.
+pub fn simplify_polygon(vertices: &[Vec2], tolerance: f32) -> Vec<Vec2> {
+    let n = vertices.len();
+    if n <= 3 {
+        return vertices.to_vec();
+    }
+
+    let mut far = 0;
+    let mut far_dist = 0.0;
+    for i in 1..n {
+        let d = vertices[0].dist_squared(vertices[i]);
+        if d > far_dist {
+            far_dist = d;
+            far = i;
+        }
+    }
+
+    let mut ring: Vec<Vec2> = vertices.to_vec();
+    ring.push(vertices[0]);
+
+    let mut out = Vec::with_capacity(n);
+    simplify_chain(&ring[..=far], tolerance, &mut out);
+    simplify_chain(&ring[far..], tolerance, &mut out);
+
+    if out.len() < 3 {
+        return vertices.to_vec();
+    }
+    out
+}
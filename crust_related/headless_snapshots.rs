@@ -0,0 +1,256 @@
+// headless_snapshots.rs - Golden-image tests for the raylib games, no GPU needed
+//
+// rustc --edition 2021 -C opt-level=2 headless_snapshots.rs -o headless_snapshots
+//
+// Plays scripted input into snake, breakout and the wireframe earth, draws
+// the last frame of each with the CPU rasterizer (render/canvas.rs) and
+// compares its PNG with the one in golden/. Nothing here touches raylib's
+// functions, so it builds and runs without libraylib or a display.
+//
+//   ./headless_snapshots             compare; mismatches are written as
+//                                    golden/<name>.actual.png, exit 1
+//   ./headless_snapshots --bless     rewrite golden/ from this build
+//   ./headless_snapshots --dump DIR  also write every frame to DIR as
+//                                    <name>.png and <name>.ppm
+//
+// Frames are compared byte for byte. The earth goes through libm's sinf
+// and cosf, so a different libm can shift a line by a pixel; look at the
+// .actual.png and bless if that's all it is.
+
+mod linalg;
+mod raylib;
+mod render;
+
+#[allow(unused_attributes)]
+#[path = "lib/io.rs"]
+mod io;
+
+#[allow(unused_attributes)]
+#[path = "lib/collections.rs"]
+mod collections;
+
+#[allow(unused_attributes)]
+#[path = "lib/slab.rs"]
+mod slab;
+
+#[allow(unused_attributes)]
+#[path = "lib/string.rs"]
+mod string;
+
+#[path = "scenes/breakout.rs"]
+mod breakout;
+#[path = "scenes/earth_wireframe.rs"]
+mod earth;
+#[path = "scenes/snake.rs"]
+mod snake;
+
+linalg_conversions!(linalg);
+
+use raylib::Vector2;
+use render::snapshot::{write_png, write_ppm};
+use render::Canvas;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const GOLDEN_DIR: &str = "golden";
+
+// Every script steps at a steady 60 fps
+const DT: f32 = 1.0 / 60.0;
+const SNAKE_SEED: u32 = 2024;
+
+struct Case {
+    name: &'static str,
+    width: i32,
+    height: i32,
+    draw: fn(&mut Canvas),
+}
+
+const CASES: &[Case] = &[
+    Case { name: "snake_start", width: snake::WIDTH, height: snake::HEIGHT, draw: snake_start },
+    Case { name: "snake_fed", width: snake::WIDTH, height: snake::HEIGHT, draw: snake_fed },
+    Case { name: "snake_game_over", width: snake::WIDTH, height: snake::HEIGHT, draw: snake_game_over },
+    Case { name: "breakout_start", width: breakout::WIDTH, height: breakout::HEIGHT, draw: breakout_start },
+    Case { name: "breakout_rally", width: breakout::WIDTH, height: breakout::HEIGHT, draw: breakout_rally },
+    Case { name: "breakout_game_over", width: breakout::WIDTH, height: breakout::HEIGHT, draw: breakout_game_over },
+    Case { name: "earth_start", width: earth::WIDTH, height: earth::HEIGHT, draw: earth_start },
+    Case { name: "earth_dragged", width: earth::WIDTH, height: earth::HEIGHT, draw: earth_dragged },
+];
+
+// ============================
+// Scripts
+// ============================
+fn snake_start(canvas: &mut Canvas) {
+    snake::Game::new(SNAKE_SEED).draw(canvas);
+}
+
+fn snake_fed(canvas: &mut Canvas) {
+    use snake::Direction::*;
+    let mut game = snake::Game::new(SNAKE_SEED);
+    // Head for the food, across then down or up
+    for _ in 0..1200 {
+        let (head, food) = (game.head(), game.food());
+        let turn = if head.x < food.x {
+            Some(Right)
+        } else if head.x > food.x {
+            Some(Left)
+        } else if head.y < food.y {
+            Some(Down)
+        } else {
+            Some(Up)
+        };
+        game.update(&snake::Input { turn, restart: false }, DT);
+    }
+    assert!(!game.is_over());
+    game.draw(canvas);
+}
+
+fn snake_game_over(canvas: &mut Canvas) {
+    let mut game = snake::Game::new(SNAKE_SEED);
+    // Straight on into the right-hand wall
+    for _ in 0..300 {
+        game.update(&snake::Input::default(), DT);
+    }
+    assert!(game.is_over());
+    game.draw(canvas);
+}
+
+fn breakout_start(canvas: &mut Canvas) {
+    let mut game = breakout::Game::new();
+    let input = breakout::Input { mouse_x: (breakout::WIDTH / 2) as f32, ..Default::default() };
+    game.update(&input, DT);
+    game.draw(canvas);
+}
+
+fn breakout_rally(canvas: &mut Canvas) {
+    let mut game = breakout::Game::new();
+    // The paddle follows the ball, so it never drops
+    for _ in 0..600 {
+        let input = breakout::Input { mouse_x: game.ball.x, launch: true, ..Default::default() };
+        game.update(&input, DT);
+    }
+    game.draw(canvas);
+}
+
+fn breakout_game_over(canvas: &mut Canvas) {
+    let mut game = breakout::Game::new();
+    // The paddle stays in the corner and the ball is relaunched each time
+    for _ in 0..3600 {
+        if game.game_over {
+            break;
+        }
+        game.update(&breakout::Input { mouse_x: 0.0, launch: true, ..Default::default() }, DT);
+    }
+    assert!(game.game_over);
+    game.draw(canvas);
+}
+
+fn earth_start(canvas: &mut Canvas) {
+    earth::Earth::new().draw(canvas);
+}
+
+fn earth_dragged(canvas: &mut Canvas) {
+    let mut earth = earth::Earth::new();
+    let mouse = |frame: i32| Vector2 { x: 600.0 + frame as f32 * 8.0, y: 400.0 - frame as f32 * 3.0 };
+    let idle = earth::Input { position: mouse(0), pressed: false, released: false, wheel: 0.0 };
+
+    earth.update(&earth::Input { pressed: true, ..idle });
+    for frame in 1..=20 {
+        earth.update(&earth::Input { position: mouse(frame), ..idle });
+    }
+    earth.update(&earth::Input { position: mouse(20), released: true, ..idle });
+    earth.update(&earth::Input { position: mouse(20), wheel: 2.0, ..idle });
+    earth.draw(canvas);
+}
+
+// ============================
+// Runner
+// ============================
+// Writes pixels of the given width and height to a byte sink
+type Encoder = fn(&[u8], usize, usize, &mut dyn FnMut(&[u8]));
+
+fn encode(pixels: &[u8], width: i32, height: i32, write: Encoder) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    write(pixels, width as usize, height as usize, &mut |chunk: &[u8]| bytes.extend_from_slice(chunk));
+    bytes
+}
+
+fn png(pixels: &[u8], width: usize, height: usize, out: &mut dyn FnMut(&[u8])) {
+    write_png(pixels, width, height, &mut |bytes: &[u8]| out(bytes));
+}
+
+fn ppm(pixels: &[u8], width: usize, height: usize, out: &mut dyn FnMut(&[u8])) {
+    write_ppm(pixels, width, height, &mut |bytes: &[u8]| out(bytes));
+}
+
+fn main() -> ExitCode {
+    let mut bless = false;
+    let mut dump: Option<PathBuf> = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bless" => bless = true,
+            "--dump" => match args.next() {
+                Some(dir) => dump = Some(PathBuf::from(dir)),
+                None => {
+                    eprintln!("--dump needs a directory");
+                    return ExitCode::FAILURE;
+                }
+            },
+            _ => {
+                eprintln!("usage: headless_snapshots [--bless] [--dump DIR]");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let golden = Path::new(GOLDEN_DIR);
+    let mut failed = 0;
+    for case in CASES {
+        let mut pixels = vec![0u8; (case.width * case.height * 4) as usize];
+        let mut canvas = Canvas::new(&mut pixels, case.width, case.height);
+        (case.draw)(&mut canvas);
+        let actual = encode(&pixels, case.width, case.height, png);
+
+        let result = if let Some(dir) = &dump {
+            std::fs::create_dir_all(dir)
+                .and_then(|_| std::fs::write(dir.join(std::format!("{}.png", case.name)), &actual))
+                .and_then(|_| std::fs::write(dir.join(std::format!("{}.ppm", case.name)), encode(&pixels, case.width, case.height, ppm)))
+        } else {
+            Ok(())
+        };
+        if let Err(e) = result {
+            eprintln!("{}: can't dump: {}", case.name, e);
+            return ExitCode::FAILURE;
+        }
+
+        let path = golden.join(std::format!("{}.png", case.name));
+        if bless {
+            if let Err(e) = std::fs::create_dir_all(golden).and_then(|_| std::fs::write(&path, &actual)) {
+                eprintln!("{}: can't write {}: {}", case.name, path.display(), e);
+                return ExitCode::FAILURE;
+            }
+            println!("blessed  {}", case.name);
+            continue;
+        }
+
+        match std::fs::read(&path) {
+            Ok(expected) if expected == actual => println!("ok       {}", case.name),
+            Ok(_) => {
+                let actual_path = golden.join(std::format!("{}.actual.png", case.name));
+                let _ = std::fs::write(&actual_path, &actual);
+                println!("MISMATCH {} (see {})", case.name, actual_path.display());
+                failed += 1;
+            }
+            Err(e) => {
+                println!("MISSING  {} ({}: {}; run with --bless)", case.name, path.display(), e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        println!("{} of {} snapshots failed", failed, CASES.len());
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
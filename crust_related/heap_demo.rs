@@ -0,0 +1,104 @@
+// build: rustc --edition 2021 -C panic=abort heap_demo.rs -o heap_demo
+//
+// alloc's Vec, String and Box in a no_std program: the free-list allocator
+// from lib/allocators.rs is the global heap, and a bump arena and a block
+// pool are driven by hand next to it.
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+#[link(name = "c")]
+extern "C" {
+    fn exit(status: i32) -> !;
+}
+
+#[allow(unused_attributes)]
+#[path = "lib/io2.rs"]
+mod io2;
+
+#[allow(unused_attributes)]
+#[path = "lib/allocators.rs"]
+mod allocators;
+use allocators::bump::BumpAllocator;
+use allocators::free_list::FreeListAllocator;
+use allocators::pool::PoolAllocator;
+use allocators::AllocStats;
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::alloc::{GlobalAlloc, Layout};
+
+#[global_allocator]
+static HEAP: FreeListAllocator<{ 64 * 1024 }> = FreeListAllocator::new();
+
+static ARENA: BumpAllocator<1024> = BumpAllocator::new();
+static POOL: PoolAllocator<32, 8> = PoolAllocator::new();
+
+fn report(name: &str, s: AllocStats) {
+    println!(
+        "{:<10} in use {:>5} / {:>5}  peak {:>5}  allocs {:>3}  frees {:>3}  failed {}  fragmentation {:.2}",
+        name, s.in_use, s.capacity, s.peak, s.allocs, s.frees, s.failed, s.fragmentation()
+    );
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
+    // Global heap: ordinary alloc containers
+    let mut squares: Vec<u64> = Vec::new();
+    for i in 0..100 {
+        squares.push(i * i);
+    }
+    let mut words = String::new();
+    for word in ["free", "list", "heap"] {
+        if !words.is_empty() {
+            words.push('-');
+        }
+        words.push_str(word);
+    }
+    let boxed = Box::new([7u8; 256]);
+    println!("{} squares, last {}; \"{}\"; box of {}", squares.len(), squares[99], words, boxed.len());
+    report("heap", HEAP.stats());
+
+    // Freeing every other block leaves holes between the live ones
+    let mut blocks: Vec<Box<[u8; 512]>> = (0..8).map(|_| Box::new([0u8; 512])).collect();
+    let mut i = 0;
+    blocks.retain(|_| {
+        i += 1;
+        i % 2 == 0
+    });
+    report("heap", HEAP.stats());
+    drop((squares, words, boxed, blocks));
+    report("heap", HEAP.stats());
+
+    // Arena: bump until full, then drop it all at once
+    let small = Layout::from_size_align_unchecked(100, 8);
+    let mut granted = 0;
+    while !ARENA.alloc(small).is_null() {
+        granted += 1;
+    }
+    println!("arena gave {} blocks of 100 bytes", granted);
+    report("arena", ARENA.stats());
+    ARENA.reset();
+    report("arena", ARENA.stats());
+
+    // Pool: fixed 32-byte blocks, anything bigger is refused
+    let block = Layout::from_size_align_unchecked(24, 8);
+    let taken: [*mut u8; 5] = core::array::from_fn(|_| POOL.alloc(block));
+    let too_big = POOL.alloc(Layout::from_size_align_unchecked(64, 8));
+    println!("pool: {} of {} blocks free, 64-byte request {}", POOL.free_blocks(), 8, if too_big.is_null() { "refused" } else { "granted" });
+    for p in taken {
+        POOL.dealloc(p, block);
+    }
+    report("pool", POOL.stats());
+    0
+}
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    unsafe { exit(1); }
+}
+
+#[no_mangle]
+pub extern "C" fn rust_eh_personality() { loop {} }
@@ -76,65 +76,12 @@ pub unsafe fn copy_memory<T>(src: *const T, dst: *mut T, count: usize) {
 // Array Helpers
 // ============================
 
-/// Fixed-size stack-allocated vector (no heap allocation)
-pub struct StackVec<T, const N: usize> {
-    data: [core::mem::MaybeUninit<T>; N],
-    len: usize,
-}
-
-impl<T, const N: usize> StackVec<T, N> {
-    pub const fn new() -> Self {
-        Self {
-            data: unsafe { core::mem::MaybeUninit::uninit().assume_init() },
-            len: 0,
-        }
-    }
-
-    pub fn push(&mut self, value: T) -> Result<(), T> {
-        if self.len < N {
-            self.data[self.len].write(value);
-            self.len += 1;
-            Ok(())
-        } else {
-            Err(value)
-        }
-    }
-
-    pub fn pop(&mut self) -> Option<T> {
-        if self.len > 0 {
-            self.len -= 1;
-            Some(unsafe { self.data[self.len].assume_init_read() })
-        } else {
-            None
-        }
-    }
-
-    pub fn len(&self) -> usize {
-        self.len
-    }
-
-    pub fn is_empty(&self) -> bool {
-        self.len == 0
-    }
-
-    pub fn clear(&mut self) {
-        while self.pop().is_some() {}
-    }
+// The crate root mounts lib/collections.rs as `collections`, so these are
+// the same types everything else in the program uses
 
-    pub fn as_slice(&self) -> &[T] {
-        unsafe { core::slice::from_raw_parts(self.data.as_ptr() as *const T, self.len) }
-    }
-
-    pub fn as_mut_slice(&mut self) -> &mut [T] {
-        unsafe { core::slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut T, self.len) }
-    }
-}
-
-impl<T, const N: usize> Drop for StackVec<T, N> {
-    fn drop(&mut self) {
-        self.clear();
-    }
-}
+/// Fixed-size stack-allocated vector (no heap allocation)
+#[allow(unused_imports)]
+pub use crate::collections::vec::Vec as StackVec;
 
 // ============================
 // Math Utilities
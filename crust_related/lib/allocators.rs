@@ -0,0 +1,488 @@
+// crust_alloc/lib.rs
+// GlobalAlloc implementations over static buffers for no_std programs:
+//
+//   #[global_allocator]
+//   static HEAP: FreeListAllocator<{ 64 * 1024 }> = FreeListAllocator::new();
+//
+// Each allocator owns its buffer, is safe to use from a `static` (a spin
+// lock guards the state) and reports usage through `stats()`.
+#![no_std]
+#![allow(dead_code)]
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Usage counters shared by all allocators (sizes in bytes)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub capacity: usize,
+    pub in_use: usize,
+    pub peak: usize,
+    pub allocs: usize,
+    pub frees: usize,
+    pub failed: usize,
+    pub free_bytes: usize,
+    pub largest_free: usize, // Biggest single request that could succeed
+}
+
+impl AllocStats {
+    const fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            in_use: 0,
+            peak: 0,
+            allocs: 0,
+            frees: 0,
+            failed: 0,
+            free_bytes: capacity,
+            largest_free: capacity,
+        }
+    }
+
+    fn record_alloc(&mut self, size: usize) {
+        self.in_use += size;
+        self.allocs += 1;
+        if self.in_use > self.peak {
+            self.peak = self.in_use;
+        }
+    }
+
+    fn record_free(&mut self, size: usize) {
+        self.in_use -= size;
+        self.frees += 1;
+    }
+
+    /// 0.0 when all free memory is one block, approaching 1.0 as it
+    /// splinters into pieces too small to be useful
+    pub fn fragmentation(&self) -> f32 {
+        if self.free_bytes == 0 {
+            return 0.0;
+        }
+        1.0 - self.largest_free as f32 / self.free_bytes as f32
+    }
+}
+
+/// Minimal spin lock so allocators can live in a `static`
+struct SpinLock<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Sync for SpinLock<T> {}
+
+impl<T> SpinLock<T> {
+    const fn new(value: T) -> Self {
+        Self { locked: AtomicBool::new(false), value: UnsafeCell::new(value) }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        let result = f(unsafe { &mut *self.value.get() });
+        self.locked.store(false, Ordering::Release);
+        result
+    }
+}
+
+/// Backing storage; 16-byte alignment covers every primitive type
+#[repr(C, align(16))]
+struct Heap<T>(UnsafeCell<T>);
+
+unsafe impl<T> Sync for Heap<T> {}
+
+impl<T> Heap<T> {
+    const fn new(storage: T) -> Self {
+        Self(UnsafeCell::new(storage))
+    }
+
+    fn base(&self) -> usize {
+        self.0.get() as usize
+    }
+}
+
+const fn align_up(addr: usize, align: usize) -> usize {
+    (addr + align - 1) & !(align - 1)
+}
+
+pub mod bump {
+    use core::alloc::{GlobalAlloc, Layout};
+    use core::ptr;
+
+    use super::{align_up, AllocStats, Heap, SpinLock};
+
+    struct State {
+        next: usize, // Offset of the first free byte
+        live: usize, // Outstanding allocations
+        stats: AllocStats,
+    }
+
+    /// Arena allocator: allocation is a pointer bump, individual frees only
+    /// reclaim the most recent allocation, and everything is reclaimed once
+    /// the last live allocation is freed (or on `reset`).
+    pub struct BumpAllocator<const N: usize> {
+        heap: Heap<[u8; N]>,
+        state: SpinLock<State>,
+    }
+
+    impl<const N: usize> BumpAllocator<N> {
+        pub const fn new() -> Self {
+            Self {
+                heap: Heap::new([0; N]),
+                state: SpinLock::new(State { next: 0, live: 0, stats: AllocStats::new(N) }),
+            }
+        }
+
+        pub fn stats(&self) -> AllocStats {
+            self.state.with(|s| s.stats)
+        }
+
+        /// Drop everything at once, e.g. at the end of a frame.
+        ///
+        /// # Safety
+        /// No pointer previously returned by this allocator may be used afterwards.
+        pub unsafe fn reset(&self) {
+            self.state.with(|s| {
+                s.next = 0;
+                s.live = 0;
+                Self::sync_stats(s);
+            });
+        }
+
+        // Everything below the bump pointer (padding included) counts as in use
+        fn sync_stats(s: &mut State) {
+            s.stats.in_use = s.next;
+            s.stats.peak = s.stats.peak.max(s.next);
+            s.stats.free_bytes = N - s.next;
+            s.stats.largest_free = s.stats.free_bytes;
+        }
+    }
+
+    impl<const N: usize> Default for BumpAllocator<N> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    unsafe impl<const N: usize> GlobalAlloc for BumpAllocator<N> {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let base = self.heap.base();
+            self.state.with(|s| {
+                let start = align_up(base + s.next, layout.align());
+                let end = match start.checked_add(layout.size()) {
+                    Some(end) if end <= base + N => end,
+                    _ => {
+                        s.stats.failed += 1;
+                        return ptr::null_mut();
+                    }
+                };
+
+                s.next = end - base;
+                s.live += 1;
+                s.stats.allocs += 1;
+                Self::sync_stats(s);
+                start as *mut u8
+            })
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            let base = self.heap.base();
+            self.state.with(|s| {
+                s.live -= 1;
+                s.stats.frees += 1;
+
+                if s.live == 0 {
+                    s.next = 0;
+                } else if ptr as usize + layout.size() == base + s.next {
+                    // Most recent allocation: roll the bump pointer back
+                    s.next = ptr as usize - base;
+                }
+                Self::sync_stats(s);
+            });
+        }
+    }
+}
+
+pub mod free_list {
+    use core::alloc::{GlobalAlloc, Layout};
+    use core::{mem, ptr};
+
+    use super::{align_up, AllocStats, Heap, SpinLock};
+
+    /// Header written into every free block
+    struct Node {
+        size: usize,
+        next: *mut Node,
+    }
+
+    // Every block address and size is a multiple of GRAIN, and GRAIN fits a
+    // Node, so any split leftover is either empty or big enough to track.
+    const GRAIN: usize = if mem::size_of::<Node>() > 16 { mem::size_of::<Node>() } else { 16 };
+
+    struct State {
+        head: *mut Node, // Free blocks sorted by address
+        initialized: bool,
+        stats: AllocStats,
+    }
+
+    unsafe impl Send for State {}
+
+    /// General-purpose first-fit allocator. Free blocks form an
+    /// address-ordered list stored inside the free memory itself, and
+    /// adjacent blocks are merged on free to limit fragmentation.
+    pub struct FreeListAllocator<const N: usize> {
+        heap: Heap<[u8; N]>,
+        state: SpinLock<State>,
+    }
+
+    impl<const N: usize> FreeListAllocator<N> {
+        const USABLE: usize = N / GRAIN * GRAIN;
+
+        pub const fn new() -> Self {
+            Self {
+                heap: Heap::new([0; N]),
+                state: SpinLock::new(State {
+                    head: ptr::null_mut(),
+                    initialized: false,
+                    stats: AllocStats::new(N / GRAIN * GRAIN),
+                }),
+            }
+        }
+
+        pub fn stats(&self) -> AllocStats {
+            self.state.with(|s| {
+                self.init(s);
+                s.stats
+            })
+        }
+
+        fn init(&self, s: &mut State) {
+            if s.initialized {
+                return;
+            }
+            s.initialized = true;
+            if Self::USABLE >= GRAIN {
+                let node = self.heap.base() as *mut Node;
+                unsafe { node.write(Node { size: Self::USABLE, next: ptr::null_mut() }) };
+                s.head = node;
+            }
+        }
+
+        fn block_size(layout: &Layout) -> usize {
+            align_up(layout.size().max(1), GRAIN)
+        }
+
+        /// Insert a block in address order, merging with its neighbours
+        unsafe fn release(s: &mut State, addr: usize, size: usize) {
+            let mut prev: *mut Node = ptr::null_mut();
+            let mut cur = s.head;
+            while !cur.is_null() && (cur as usize) < addr {
+                prev = cur;
+                cur = (*cur).next;
+            }
+
+            let node = addr as *mut Node;
+            node.write(Node { size, next: cur });
+
+            // Merge with the following block
+            if !cur.is_null() && addr + size == cur as usize {
+                (*node).size += (*cur).size;
+                (*node).next = (*cur).next;
+            }
+
+            // Merge with the preceding block
+            if prev.is_null() {
+                s.head = node;
+            } else if prev as usize + (*prev).size == addr {
+                (*prev).size += (*node).size;
+                (*prev).next = (*node).next;
+            } else {
+                (*prev).next = node;
+            }
+        }
+
+        unsafe fn refresh_free_stats(s: &mut State) {
+            let mut total = 0;
+            let mut largest = 0;
+            let mut cur = s.head;
+            while !cur.is_null() {
+                total += (*cur).size;
+                largest = largest.max((*cur).size);
+                cur = (*cur).next;
+            }
+            s.stats.free_bytes = total;
+            s.stats.largest_free = largest;
+        }
+    }
+
+    impl<const N: usize> Default for FreeListAllocator<N> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    unsafe impl<const N: usize> GlobalAlloc for FreeListAllocator<N> {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let size = Self::block_size(&layout);
+            let align = layout.align().max(GRAIN);
+
+            self.state.with(|s| {
+                self.init(s);
+
+                let mut prev: *mut Node = ptr::null_mut();
+                let mut cur = s.head;
+                while !cur.is_null() {
+                    let region_start = cur as usize;
+                    let region_end = region_start + (*cur).size;
+                    let start = align_up(region_start, align);
+
+                    if start + size <= region_end {
+                        // Unlink, then give back whatever is left on either side
+                        let next = (*cur).next;
+                        if prev.is_null() { s.head = next; } else { (*prev).next = next; }
+
+                        if start > region_start {
+                            Self::release(s, region_start, start - region_start);
+                        }
+                        if region_end > start + size {
+                            Self::release(s, start + size, region_end - (start + size));
+                        }
+
+                        s.stats.record_alloc(size);
+                        Self::refresh_free_stats(s);
+                        return start as *mut u8;
+                    }
+
+                    prev = cur;
+                    cur = (*cur).next;
+                }
+
+                s.stats.failed += 1;
+                ptr::null_mut()
+            })
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            let size = Self::block_size(&layout);
+            self.state.with(|s| {
+                Self::release(s, ptr as usize, size);
+                s.stats.record_free(size);
+                Self::refresh_free_stats(s);
+            });
+        }
+    }
+}
+
+pub mod pool {
+    use core::alloc::{GlobalAlloc, Layout};
+    use core::{mem, ptr};
+
+    use super::{AllocStats, Heap, SpinLock};
+
+    struct State {
+        free: *mut usize, // Each free block stores the address of the next one
+        initialized: bool,
+        stats: AllocStats,
+    }
+
+    unsafe impl Send for State {}
+
+    /// `COUNT` blocks of `BLOCK` bytes with O(1) alloc and free and no
+    /// fragmentation. Requests larger than a block, or aligned more
+    /// strictly than one, fail with null.
+    pub struct PoolAllocator<const BLOCK: usize, const COUNT: usize> {
+        heap: Heap<[[u8; BLOCK]; COUNT]>,
+        state: SpinLock<State>,
+    }
+
+    impl<const BLOCK: usize, const COUNT: usize> PoolAllocator<BLOCK, COUNT> {
+        /// Block alignment: the largest power of two dividing BLOCK, up to 16
+        const ALIGN: usize = {
+            let mut a = 16;
+            while !BLOCK.is_multiple_of(a) {
+                a /= 2;
+            }
+            a
+        };
+
+        pub const fn new() -> Self {
+            assert!(BLOCK >= mem::size_of::<usize>() && BLOCK.is_multiple_of(mem::size_of::<usize>()));
+            Self {
+                heap: Heap::new([[0; BLOCK]; COUNT]),
+                state: SpinLock::new(State {
+                    free: ptr::null_mut(),
+                    initialized: false,
+                    stats: AllocStats::new(BLOCK * COUNT),
+                }),
+            }
+        }
+
+        pub fn stats(&self) -> AllocStats {
+            self.state.with(|s| s.stats)
+        }
+
+        pub fn block_size(&self) -> usize {
+            BLOCK
+        }
+
+        pub fn free_blocks(&self) -> usize {
+            self.state.with(|s| s.stats.free_bytes / BLOCK)
+        }
+
+        fn init(&self, s: &mut State) {
+            if s.initialized {
+                return;
+            }
+            s.initialized = true;
+
+            // Thread every block onto the free list, lowest address first
+            let base = self.heap.base();
+            for i in (0..COUNT).rev() {
+                let block = (base + i * BLOCK) as *mut usize;
+                unsafe { block.write(s.free as usize) };
+                s.free = block;
+            }
+        }
+    }
+
+    impl<const BLOCK: usize, const COUNT: usize> Default for PoolAllocator<BLOCK, COUNT> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    unsafe impl<const BLOCK: usize, const COUNT: usize> GlobalAlloc for PoolAllocator<BLOCK, COUNT> {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            self.state.with(|s| {
+                self.init(s);
+
+                if layout.size() > BLOCK || layout.align() > Self::ALIGN || s.free.is_null() {
+                    s.stats.failed += 1;
+                    return ptr::null_mut();
+                }
+
+                let block = s.free;
+                s.free = *block as *mut usize;
+                s.stats.record_alloc(BLOCK);
+                s.stats.free_bytes -= BLOCK;
+                s.stats.largest_free = if s.free.is_null() { 0 } else { BLOCK };
+                block as *mut u8
+            })
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, _layout: Layout) {
+            self.state.with(|s| {
+                let block = ptr as *mut usize;
+                block.write(s.free as usize);
+                s.free = block;
+                s.stats.record_free(BLOCK);
+                s.stats.free_bytes += BLOCK;
+                s.stats.largest_free = BLOCK;
+            });
+        }
+    }
+}
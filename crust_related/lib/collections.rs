@@ -1,23 +1,68 @@
 // crust_collections/lib.rs
+// Fixed-capacity, allocation-free containers sharing one error type.
 #![no_std]
+#![allow(dead_code)]
+
+use core::fmt;
+
+/// Returned when a container is full. Carries the rejected element back
+/// to the caller so nothing is silently dropped.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct CapacityError<T = ()> {
+    element: T,
+}
+
+impl<T> CapacityError<T> {
+    pub const fn new(element: T) -> Self {
+        Self { element }
+    }
+
+    pub fn element(self) -> T {
+        self.element
+    }
+
+    /// Drop the payload, e.g. to use `?` across element types
+    pub fn simplify(self) -> CapacityError {
+        CapacityError::new(())
+    }
+}
+
+impl<T> fmt::Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("CapacityError: insufficient capacity")
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("insufficient capacity")
+    }
+}
 
 pub mod vec {
+    use core::mem::MaybeUninit;
+    use core::ops::{Deref, DerefMut};
+    use core::{fmt, ptr, slice};
+
+    use super::CapacityError;
+
+    /// Stack-allocated vector holding at most `CAP` elements
     pub struct Vec<T, const CAP: usize> {
-        data: [core::mem::MaybeUninit<T>; CAP],
+        data: [MaybeUninit<T>; CAP],
         len: usize,
     }
 
     impl<T, const CAP: usize> Vec<T, CAP> {
         pub const fn new() -> Self {
             Self {
-                data: unsafe { core::mem::MaybeUninit::uninit().assume_init() },
+                data: [const { MaybeUninit::uninit() }; CAP],
                 len: 0,
             }
         }
 
-        pub fn push(&mut self, value: T) -> Result<(), &'static str> {
+        pub fn push(&mut self, value: T) -> Result<(), CapacityError<T>> {
             if self.len >= CAP {
-                return Err("Capacity exceeded");
+                return Err(CapacityError::new(value));
             }
             self.data[self.len].write(value);
             self.len += 1;
@@ -32,28 +77,99 @@ pub mod vec {
             unsafe { Some(self.data[self.len].assume_init_read()) }
         }
 
-        pub fn get(&self, index: usize) -> Option<&T> {
-            if index < self.len {
-                unsafe { Some(&*self.data[index].as_ptr()) }
-            } else {
-                None
+        /// Insert at `index`, shifting later elements right.
+        /// Panics if `index > len`.
+        pub fn insert(&mut self, index: usize, value: T) -> Result<(), CapacityError<T>> {
+            assert!(index <= self.len, "insertion index out of bounds");
+            if self.len >= CAP {
+                return Err(CapacityError::new(value));
+            }
+            unsafe {
+                let p = self.as_mut_ptr().add(index);
+                ptr::copy(p, p.add(1), self.len - index);
+                ptr::write(p, value);
             }
+            self.len += 1;
+            Ok(())
         }
 
-        pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
-            if index < self.len {
-                unsafe { Some(&mut *self.data[index].as_mut_ptr()) }
-            } else {
-                None
+        /// Remove and return the element at `index`, shifting later elements
+        /// left. Panics if `index >= len`.
+        pub fn remove(&mut self, index: usize) -> T {
+            assert!(index < self.len, "removal index out of bounds");
+            unsafe {
+                let p = self.as_mut_ptr().add(index);
+                let value = ptr::read(p);
+                ptr::copy(p.add(1), p, self.len - index - 1);
+                self.len -= 1;
+                value
+            }
+        }
+
+        /// Remove the element at `index` by moving the last one into its
+        /// place. O(1) but doesn't preserve order. Panics if `index >= len`.
+        pub fn swap_remove(&mut self, index: usize) -> T {
+            assert!(index < self.len, "removal index out of bounds");
+            let last = self.len - 1;
+            self.as_mut_slice().swap(index, last);
+            self.pop().unwrap()
+        }
+
+        /// Keep only the elements for which `f` returns true, preserving order
+        pub fn retain(&mut self, mut f: impl FnMut(&mut T) -> bool) {
+            let mut kept = 0;
+            for i in 0..self.len {
+                unsafe {
+                    let p = self.as_mut_ptr().add(i);
+                    if f(&mut *p) {
+                        if kept != i {
+                            ptr::copy_nonoverlapping(p, self.as_mut_ptr().add(kept), 1);
+                        }
+                        kept += 1;
+                    } else {
+                        ptr::drop_in_place(p);
+                    }
+                }
+            }
+            self.len = kept;
+        }
+
+        pub fn truncate(&mut self, len: usize) {
+            while self.len > len {
+                self.pop();
             }
         }
 
+        /// Push every item, stopping at the first one that doesn't fit
+        pub fn try_extend(&mut self, iter: impl IntoIterator<Item = T>) -> Result<(), CapacityError<T>> {
+            for item in iter {
+                self.push(item)?;
+            }
+            Ok(())
+        }
+
+        pub fn get(&self, index: usize) -> Option<&T> {
+            self.as_slice().get(index)
+        }
+
+        pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+            self.as_mut_slice().get_mut(index)
+        }
+
+        pub fn as_ptr(&self) -> *const T {
+            self.data.as_ptr() as *const T
+        }
+
+        pub fn as_mut_ptr(&mut self) -> *mut T {
+            self.data.as_mut_ptr() as *mut T
+        }
+
         pub fn as_slice(&self) -> &[T] {
-            unsafe { core::slice::from_raw_parts(self.data.as_ptr() as *const T, self.len) }
+            unsafe { slice::from_raw_parts(self.as_ptr(), self.len) }
         }
 
         pub fn as_mut_slice(&mut self) -> &mut [T] {
-            unsafe { core::slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut T, self.len) }
+            unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len) }
         }
 
         pub fn len(&self) -> usize {
@@ -64,12 +180,50 @@ pub mod vec {
             self.len == 0
         }
 
+        pub fn is_full(&self) -> bool {
+            self.len == CAP
+        }
+
         pub fn capacity(&self) -> usize {
             CAP
         }
 
+        pub fn remaining_capacity(&self) -> usize {
+            CAP - self.len
+        }
+
         pub fn clear(&mut self) {
-            while self.pop().is_some() {}
+            self.truncate(0);
+        }
+    }
+
+    impl<T: Clone, const CAP: usize> Vec<T, CAP> {
+        pub fn extend_from_slice(&mut self, items: &[T]) -> Result<(), CapacityError> {
+            if items.len() > self.remaining_capacity() {
+                return Err(CapacityError::new(()));
+            }
+            for item in items {
+                let _ = self.push(item.clone());
+            }
+            Ok(())
+        }
+
+        pub fn from_slice(items: &[T]) -> Result<Self, CapacityError> {
+            let mut v = Self::new();
+            v.extend_from_slice(items)?;
+            Ok(v)
+        }
+    }
+
+    impl<T, const CAP: usize> Drop for Vec<T, CAP> {
+        fn drop(&mut self) {
+            unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+        }
+    }
+
+    impl<T, const CAP: usize> Default for Vec<T, CAP> {
+        fn default() -> Self {
+            Self::new()
         }
     }
 
@@ -82,84 +236,213 @@ pub mod vec {
             new_vec
         }
     }
+
+    impl<T, const CAP: usize> Deref for Vec<T, CAP> {
+        type Target = [T];
+
+        fn deref(&self) -> &[T] {
+            self.as_slice()
+        }
+    }
+
+    impl<T, const CAP: usize> DerefMut for Vec<T, CAP> {
+        fn deref_mut(&mut self) -> &mut [T] {
+            self.as_mut_slice()
+        }
+    }
+
+    impl<T: fmt::Debug, const CAP: usize> fmt::Debug for Vec<T, CAP> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+
+    impl<T: PartialEq, const CAP: usize> PartialEq for Vec<T, CAP> {
+        fn eq(&self, other: &Self) -> bool {
+            self.as_slice() == other.as_slice()
+        }
+    }
+
+    impl<T: Eq, const CAP: usize> Eq for Vec<T, CAP> {}
+
+    /// Panics if the items don't fit; use `try_extend` to handle that instead
+    impl<T, const CAP: usize> Extend<T> for Vec<T, CAP> {
+        fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+            if self.try_extend(iter).is_err() {
+                panic!("Vec capacity exceeded");
+            }
+        }
+    }
+
+    impl<'a, T, const CAP: usize> IntoIterator for &'a Vec<T, CAP> {
+        type Item = &'a T;
+        type IntoIter = slice::Iter<'a, T>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.iter()
+        }
+    }
+
+    impl<'a, T, const CAP: usize> IntoIterator for &'a mut Vec<T, CAP> {
+        type Item = &'a mut T;
+        type IntoIter = slice::IterMut<'a, T>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.iter_mut()
+        }
+    }
+
+    impl<T, const CAP: usize> IntoIterator for Vec<T, CAP> {
+        type Item = T;
+        type IntoIter = IntoIter<T, CAP>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            IntoIter { vec: self, next: 0 }
+        }
+    }
+
+    /// Owning iterator; elements not yet yielded are dropped with it
+    pub struct IntoIter<T, const CAP: usize> {
+        vec: Vec<T, CAP>,
+        next: usize,
+    }
+
+    impl<T, const CAP: usize> Iterator for IntoIter<T, CAP> {
+        type Item = T;
+
+        fn next(&mut self) -> Option<T> {
+            if self.next >= self.vec.len {
+                return None;
+            }
+            let item = unsafe { self.vec.data[self.next].assume_init_read() };
+            self.next += 1;
+            Some(item)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let n = self.vec.len - self.next;
+            (n, Some(n))
+        }
+    }
+
+    impl<T, const CAP: usize> Drop for IntoIter<T, CAP> {
+        fn drop(&mut self) {
+            let (start, end) = (self.next, self.vec.len);
+            // Stop Vec's own Drop from touching moved-out slots
+            self.vec.len = 0;
+            for slot in &mut self.vec.data[start..end] {
+                unsafe { slot.assume_init_drop() };
+            }
+        }
+    }
 }
 
-pub mod hash_map {
-    use core::hash::{Hash, Hasher};
+pub mod deque {
+    use core::mem::MaybeUninit;
+    use core::fmt;
 
-    pub struct HashMap<K, V, const CAP: usize, H: Hasher> {
-        entries: [Option<(K, V)>; CAP],
+    use super::CapacityError;
+
+    /// Fixed-capacity double-ended ring buffer
+    pub struct Deque<T, const CAP: usize> {
+        data: [MaybeUninit<T>; CAP],
+        head: usize, // Index of the front element
         len: usize,
-        hasher: H,
     }
 
-    impl<K, V, const CAP: usize, H: Hasher + Default> HashMap<K, V, CAP, H>
-    where
-        K: Eq + Hash + Clone,
-        V: Clone,
-    {
-        pub fn new() -> Self {
+    impl<T, const CAP: usize> Deque<T, CAP> {
+        pub const fn new() -> Self {
             Self {
-                entries: [const { None }; CAP],
+                data: [const { MaybeUninit::uninit() }; CAP],
+                head: 0,
                 len: 0,
-                hasher: H::default(),
             }
         }
 
-        pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, &'static str> {
+        fn slot(&self, offset: usize) -> usize {
+            (self.head + offset) % CAP
+        }
+
+        pub fn push_back(&mut self, value: T) -> Result<(), CapacityError<T>> {
+            if self.len >= CAP {
+                return Err(CapacityError::new(value));
+            }
+            let index = self.slot(self.len);
+            self.data[index].write(value);
+            self.len += 1;
+            Ok(())
+        }
+
+        pub fn push_front(&mut self, value: T) -> Result<(), CapacityError<T>> {
             if self.len >= CAP {
-                return Err("HashMap full");
+                return Err(CapacityError::new(value));
             }
+            self.head = (self.head + CAP - 1) % CAP;
+            self.data[self.head].write(value);
+            self.len += 1;
+            Ok(())
+        }
 
-            let index = self.hash_index(&key);
-            let start_index = index;
-            
-            loop {
-                match &mut self.entries[index] {
-                    Some((k, v)) if *k == key => {
-                        // Replace existing
-                        let old_value = core::mem::replace(v, value);
-                        return Ok(Some(old_value));
-                    }
-                    None => {
-                        // Insert new
-                        self.entries[index] = Some((key, value));
-                        self.len += 1;
-                        return Ok(None);
-                    }
-                    _ => {}
-                }
-                
-                // Linear probing
-                let next_index = (index + 1) % CAP;
-                if next_index == start_index {
-                    return Err("No space available");
-                }
+        pub fn pop_front(&mut self) -> Option<T> {
+            if self.len == 0 {
+                return None;
             }
+            let value = unsafe { self.data[self.head].assume_init_read() };
+            self.head = (self.head + 1) % CAP;
+            self.len -= 1;
+            Some(value)
         }
 
-        pub fn get(&self, key: &K) -> Option<&V> {
-            let index = self.hash_index(key);
-            let start_index = index;
-            
-            loop {
-                match &self.entries[index] {
-                    Some((k, v)) if k == key => return Some(v),
-                    None => return None,
-                    _ => {}
-                }
-                
-                let next_index = (index + 1) % CAP;
-                if next_index == start_index {
-                    return None;
-                }
+        pub fn pop_back(&mut self) -> Option<T> {
+            if self.len == 0 {
+                return None;
             }
+            self.len -= 1;
+            let index = self.slot(self.len);
+            Some(unsafe { self.data[index].assume_init_read() })
         }
 
-        fn hash_index(&self, key: &K) -> usize {
-            let mut hasher = self.hasher;
-            key.hash(&mut hasher);
-            (hasher.finish() as usize) % CAP
+        pub fn get(&self, index: usize) -> Option<&T> {
+            if index < self.len {
+                Some(unsafe { self.data[self.slot(index)].assume_init_ref() })
+            } else {
+                None
+            }
+        }
+
+        pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+            if index < self.len {
+                let slot = self.slot(index);
+                Some(unsafe { self.data[slot].assume_init_mut() })
+            } else {
+                None
+            }
+        }
+
+        pub fn front(&self) -> Option<&T> {
+            self.get(0)
+        }
+
+        pub fn back(&self) -> Option<&T> {
+            if self.len == 0 { None } else { self.get(self.len - 1) }
+        }
+
+        /// The contents as two slices: from the head to the end of storage,
+        /// then whatever wrapped around to the start
+        pub fn as_slices(&self) -> (&[T], &[T]) {
+            let first_len = self.len.min(CAP - self.head);
+            unsafe {
+                let base = self.data.as_ptr() as *const T;
+                (
+                    core::slice::from_raw_parts(base.add(self.head), first_len),
+                    core::slice::from_raw_parts(base, self.len - first_len),
+                )
+            }
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = &T> {
+            let (a, b) = self.as_slices();
+            a.iter().chain(b.iter())
         }
 
         pub fn len(&self) -> usize {
@@ -169,54 +452,85 @@ pub mod hash_map {
         pub fn is_empty(&self) -> bool {
             self.len == 0
         }
+
+        pub fn is_full(&self) -> bool {
+            self.len == CAP
+        }
+
+        pub fn capacity(&self) -> usize {
+            CAP
+        }
+
+        pub fn clear(&mut self) {
+            while self.pop_front().is_some() {}
+            self.head = 0;
+        }
+    }
+
+    impl<T, const CAP: usize> Drop for Deque<T, CAP> {
+        fn drop(&mut self) {
+            self.clear();
+        }
+    }
+
+    impl<T, const CAP: usize> Default for Deque<T, CAP> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<T: Clone, const CAP: usize> Clone for Deque<T, CAP> {
+        fn clone(&self) -> Self {
+            let mut new_deque = Self::new();
+            for item in self.iter() {
+                let _ = new_deque.push_back(item.clone());
+            }
+            new_deque
+        }
+    }
+
+    impl<T: fmt::Debug, const CAP: usize> fmt::Debug for Deque<T, CAP> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.debug_list().entries(self.iter()).finish()
+        }
     }
 }
 
 pub mod binary_heap {
     use core::cmp::Ord;
 
+    use super::vec::Vec;
+    use super::CapacityError;
+
+    /// Fixed-capacity max-heap
     pub struct BinaryHeap<T: Ord, const CAP: usize> {
-        data: [Option<T>; CAP],
-        len: usize,
+        data: Vec<T, CAP>,
     }
 
     impl<T: Ord, const CAP: usize> BinaryHeap<T, CAP> {
         pub const fn new() -> Self {
-            Self {
-                data: [const { None }; CAP],
-                len: 0,
-            }
+            Self { data: Vec::new() }
         }
 
-        pub fn push(&mut self, item: T) -> Result<(), &'static str> {
-            if self.len >= CAP {
-                return Err("Heap full");
-            }
-
-            self.data[self.len] = Some(item);
-            self.sift_up(self.len);
-            self.len += 1;
+        pub fn push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+            self.data.push(item)?;
+            self.sift_up(self.data.len() - 1);
             Ok(())
         }
 
         pub fn pop(&mut self) -> Option<T> {
-            if self.len == 0 {
+            if self.data.is_empty() {
                 return None;
             }
-
-            let result = self.data[0].take();
-            self.len -= 1;
-            
-            if self.len > 0 {
-                self.data[0] = self.data[self.len].take();
+            let result = self.data.swap_remove(0);
+            if !self.data.is_empty() {
                 self.sift_down(0);
             }
-
-            result
+            Some(result)
         }
 
         pub fn peek(&self) -> Option<&T> {
-            self.data[0].as_ref()
+            self.data.first()
         }
 
         fn sift_up(&mut self, mut index: usize) {
@@ -231,16 +545,17 @@ pub mod binary_heap {
         }
 
         fn sift_down(&mut self, mut index: usize) {
+            let len = self.data.len();
             loop {
                 let left = 2 * index + 1;
                 let right = 2 * index + 2;
                 let mut largest = index;
 
-                if left < self.len && self.data[left] > self.data[largest] {
+                if left < len && self.data[left] > self.data[largest] {
                     largest = left;
                 }
 
-                if right < self.len && self.data[right] > self.data[largest] {
+                if right < len && self.data[right] > self.data[largest] {
                     largest = right;
                 }
 
@@ -253,12 +568,846 @@ pub mod binary_heap {
             }
         }
 
+        /// Elements in arbitrary (heap) order
+        pub fn iter(&self) -> core::slice::Iter<'_, T> {
+            self.data.iter()
+        }
+
         pub fn len(&self) -> usize {
-            self.len
+            self.data.len()
         }
 
         pub fn is_empty(&self) -> bool {
-            self.len == 0
+            self.data.is_empty()
+        }
+
+        pub fn capacity(&self) -> usize {
+            CAP
+        }
+
+        pub fn clear(&mut self) {
+            self.data.clear();
+        }
+    }
+
+    impl<T: Ord, const CAP: usize> Default for BinaryHeap<T, CAP> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
+
+pub mod hash_map {
+    use core::borrow::Borrow;
+    use core::hash::{Hash, Hasher};
+    use core::marker::PhantomData;
+
+    use super::CapacityError;
+
+    /// FNV-1a: tiny, allocation-free default hasher
+    pub struct FnvHasher(u64);
+
+    impl Default for FnvHasher {
+        fn default() -> Self {
+            Self(0xcbf2_9ce4_8422_2325)
+        }
+    }
+
+    impl Hasher for FnvHasher {
+        fn write(&mut self, bytes: &[u8]) {
+            for &b in bytes {
+                self.0 ^= b as u64;
+                self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        }
+
+        fn finish(&self) -> u64 {
+            self.0
+        }
+    }
+
+    struct Bucket<K, V> {
+        key: K,
+        value: V,
+        dist: usize, // Probe distance from the ideal slot
+    }
+
+    /// Fixed-capacity open-addressing map using Robin Hood probing and
+    /// backward-shift deletion (no tombstones). A fresh `H::default()` is
+    /// used for every hash, so any `Hasher + Default` works.
+    pub struct HashMap<K, V, const CAP: usize, H: Hasher + Default = FnvHasher> {
+        buckets: [Option<Bucket<K, V>>; CAP],
+        len: usize,
+        _hasher: PhantomData<H>,
+    }
+
+    impl<K, V, const CAP: usize, H: Hasher + Default> HashMap<K, V, CAP, H> {
+        pub const fn new() -> Self {
+            Self {
+                buckets: [const { None }; CAP],
+                len: 0,
+                _hasher: PhantomData,
+            }
+        }
+
+        /// Most entries the map accepts: 7/8 of the slots (all of them for
+        /// tables smaller than 8), keeping probe sequences short.
+        pub const fn capacity(&self) -> usize {
+            CAP - CAP / 8
+        }
+
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        pub fn clear(&mut self) {
+            for bucket in &mut self.buckets {
+                *bucket = None;
+            }
+            self.len = 0;
+        }
+
+        pub fn iter(&self) -> Iter<'_, K, V> {
+            Iter { inner: self.buckets.iter() }
+        }
+
+        pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+            IterMut { inner: self.buckets.iter_mut() }
+        }
+
+        pub fn keys(&self) -> impl Iterator<Item = &K> {
+            self.iter().map(|(k, _)| k)
+        }
+
+        pub fn values(&self) -> impl Iterator<Item = &V> {
+            self.iter().map(|(_, v)| v)
+        }
+
+        pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+            self.iter_mut().map(|(_, v)| v)
+        }
+    }
+
+    impl<K, V, const CAP: usize, H> HashMap<K, V, CAP, H>
+    where
+        K: Eq + Hash,
+        H: Hasher + Default,
+    {
+        fn ideal_index<Q: Hash + ?Sized>(key: &Q) -> usize {
+            let mut hasher = H::default();
+            key.hash(&mut hasher);
+            (hasher.finish() as usize) % CAP
+        }
+
+        fn find<Q>(&self, key: &Q) -> Option<usize>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            if CAP == 0 || self.len == 0 {
+                return None;
+            }
+
+            let mut index = Self::ideal_index(key);
+            for dist in 0..CAP {
+                match &self.buckets[index] {
+                    None => return None,
+                    // Robin Hood invariant: the key would have displaced this entry
+                    Some(b) if b.dist < dist => return None,
+                    Some(b) if b.key.borrow() == key => return Some(index),
+                    _ => {}
+                }
+                index = (index + 1) % CAP;
+            }
+            None
+        }
+
+        /// Place a key known to be absent; returns the slot it landed in.
+        fn insert_new(&mut self, key: K, value: V) -> Result<usize, CapacityError<(K, V)>> {
+            if self.len >= self.capacity() {
+                return Err(CapacityError::new((key, value)));
+            }
+
+            let mut index = Self::ideal_index(&key);
+            let mut carry = Bucket { key, value, dist: 0 };
+            let mut landed = None;
+
+            loop {
+                match &mut self.buckets[index] {
+                    slot @ None => {
+                        *slot = Some(carry);
+                        self.len += 1;
+                        return Ok(landed.unwrap_or(index));
+                    }
+                    Some(existing) => {
+                        // Steal from the rich: swap with entries closer to home
+                        if existing.dist < carry.dist {
+                            core::mem::swap(existing, &mut carry);
+                            if landed.is_none() {
+                                landed = Some(index);
+                            }
+                        }
+                    }
+                }
+                index = (index + 1) % CAP;
+                carry.dist += 1;
+            }
+        }
+
+        /// Insert or replace. Returns the previous value for an existing key.
+        pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, CapacityError<(K, V)>> {
+            if let Some(index) = self.find(&key) {
+                let bucket = self.buckets[index].as_mut().unwrap();
+                return Ok(Some(core::mem::replace(&mut bucket.value, value)));
+            }
+            self.insert_new(key, value).map(|_| None)
+        }
+
+        pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let index = self.find(key)?;
+            self.buckets[index].as_ref().map(|b| &b.value)
+        }
+
+        pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let index = self.find(key)?;
+            self.buckets[index].as_mut().map(|b| &mut b.value)
+        }
+
+        pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            self.find(key).is_some()
+        }
+
+        pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let index = self.find(key)?;
+            Some(self.remove_at(index).1)
+        }
+
+        pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let index = self.find(key)?;
+            Some(self.remove_at(index))
+        }
+
+        /// Remove the occupied slot at `index`, shifting its probe chain back
+        fn remove_at(&mut self, index: usize) -> (K, V) {
+            let removed = self.buckets[index].take().unwrap();
+            self.len -= 1;
+
+            let mut hole = index;
+            loop {
+                let next = (hole + 1) % CAP;
+                match self.buckets[next].take() {
+                    Some(mut b) if b.dist > 0 => {
+                        b.dist -= 1;
+                        self.buckets[hole] = Some(b);
+                        hole = next;
+                    }
+                    other => {
+                        self.buckets[next] = other;
+                        break;
+                    }
+                }
+            }
+
+            (removed.key, removed.value)
+        }
+
+        pub fn entry(&mut self, key: K) -> Entry<'_, K, V, CAP, H> {
+            match self.find(&key) {
+                Some(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+                None => Entry::Vacant(VacantEntry { map: self, key }),
+            }
+        }
+
+        /// Keep only the entries for which `f` returns true
+        pub fn retain(&mut self, mut f: impl FnMut(&K, &mut V) -> bool) {
+            // Go once round from a slot no probe chain runs through: an empty
+            // one, or one whose entry sits at its home. Backward shifts never
+            // carry an entry across it, so every entry is seen exactly once.
+            let start = self.buckets.iter().position(|b| b.as_ref().is_none_or(|b| b.dist == 0)).unwrap_or(0);
+            let mut visited = 0;
+            while visited < CAP {
+                let index = (start + visited) % CAP;
+                let keep = match &mut self.buckets[index] {
+                    Some(b) => f(&b.key, &mut b.value),
+                    None => true,
+                };
+                if keep {
+                    visited += 1;
+                } else {
+                    // The backward shift may pull an unvisited entry into this slot
+                    self.remove_at(index);
+                }
+            }
+        }
+    }
+
+    impl<K, V, const CAP: usize, H: Hasher + Default> Default for HashMap<K, V, CAP, H> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<K: Clone, V: Clone, const CAP: usize, H: Hasher + Default> Clone for HashMap<K, V, CAP, H> {
+        fn clone(&self) -> Self {
+            let mut new_map = Self::new();
+            for (slot, bucket) in new_map.buckets.iter_mut().zip(self.buckets.iter()) {
+                *slot = bucket.as_ref().map(|b| Bucket {
+                    key: b.key.clone(),
+                    value: b.value.clone(),
+                    dist: b.dist,
+                });
+            }
+            new_map.len = self.len;
+            new_map
+        }
+    }
+
+    impl<'a, K, V, const CAP: usize, H: Hasher + Default> IntoIterator for &'a HashMap<K, V, CAP, H> {
+        type Item = (&'a K, &'a V);
+        type IntoIter = Iter<'a, K, V>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.iter()
+        }
+    }
+
+    impl<'a, K, V, const CAP: usize, H: Hasher + Default> IntoIterator for &'a mut HashMap<K, V, CAP, H> {
+        type Item = (&'a K, &'a mut V);
+        type IntoIter = IterMut<'a, K, V>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.iter_mut()
+        }
+    }
+
+    pub struct Iter<'a, K, V> {
+        inner: core::slice::Iter<'a, Option<Bucket<K, V>>>,
+    }
+
+    impl<'a, K, V> Iterator for Iter<'a, K, V> {
+        type Item = (&'a K, &'a V);
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.inner.by_ref().flatten().next().map(|b| (&b.key, &b.value))
+        }
+    }
+
+    pub struct IterMut<'a, K, V> {
+        inner: core::slice::IterMut<'a, Option<Bucket<K, V>>>,
+    }
+
+    impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+        type Item = (&'a K, &'a mut V);
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.inner.by_ref().flatten().next().map(|b| (&b.key, &mut b.value))
+        }
+    }
+
+    pub enum Entry<'a, K, V, const CAP: usize, H: Hasher + Default> {
+        Occupied(OccupiedEntry<'a, K, V, CAP, H>),
+        Vacant(VacantEntry<'a, K, V, CAP, H>),
+    }
+
+    pub struct OccupiedEntry<'a, K, V, const CAP: usize, H: Hasher + Default> {
+        map: &'a mut HashMap<K, V, CAP, H>,
+        index: usize,
+    }
+
+    pub struct VacantEntry<'a, K, V, const CAP: usize, H: Hasher + Default> {
+        map: &'a mut HashMap<K, V, CAP, H>,
+        key: K,
+    }
+
+    impl<'a, K: Eq + Hash, V, const CAP: usize, H: Hasher + Default> Entry<'a, K, V, CAP, H> {
+        pub fn key(&self) -> &K {
+            match self {
+                Entry::Occupied(e) => e.key(),
+                Entry::Vacant(e) => &e.key,
+            }
+        }
+
+        pub fn or_insert(self, default: V) -> Result<&'a mut V, CapacityError<(K, V)>> {
+            match self {
+                Entry::Occupied(e) => Ok(e.into_mut()),
+                Entry::Vacant(e) => e.insert(default),
+            }
+        }
+
+        pub fn or_insert_with(self, default: impl FnOnce() -> V) -> Result<&'a mut V, CapacityError<(K, V)>> {
+            match self {
+                Entry::Occupied(e) => Ok(e.into_mut()),
+                Entry::Vacant(e) => e.insert(default()),
+            }
+        }
+
+        pub fn and_modify(mut self, f: impl FnOnce(&mut V)) -> Self {
+            if let Entry::Occupied(e) = &mut self {
+                f(e.get_mut());
+            }
+            self
+        }
+    }
+
+    impl<'a, K: Eq + Hash, V, const CAP: usize, H: Hasher + Default> OccupiedEntry<'a, K, V, CAP, H> {
+        fn bucket(&self) -> &Bucket<K, V> {
+            self.map.buckets[self.index].as_ref().unwrap()
+        }
+
+        fn bucket_mut(&mut self) -> &mut Bucket<K, V> {
+            self.map.buckets[self.index].as_mut().unwrap()
+        }
+
+        pub fn key(&self) -> &K {
+            &self.bucket().key
+        }
+
+        pub fn get(&self) -> &V {
+            &self.bucket().value
+        }
+
+        pub fn get_mut(&mut self) -> &mut V {
+            &mut self.bucket_mut().value
+        }
+
+        pub fn into_mut(self) -> &'a mut V {
+            &mut self.map.buckets[self.index].as_mut().unwrap().value
+        }
+
+        pub fn insert(&mut self, value: V) -> V {
+            core::mem::replace(self.get_mut(), value)
+        }
+
+        pub fn remove(self) -> V {
+            self.map.remove_at(self.index).1
+        }
+    }
+
+    impl<'a, K: Eq + Hash, V, const CAP: usize, H: Hasher + Default> VacantEntry<'a, K, V, CAP, H> {
+        pub fn key(&self) -> &K {
+            &self.key
+        }
+
+        pub fn insert(self, value: V) -> Result<&'a mut V, CapacityError<(K, V)>> {
+            let index = self.map.insert_new(self.key, value)?;
+            Ok(&mut self.map.buckets[index].as_mut().unwrap().value)
+        }
+    }
+}
+
+pub mod hash_set {
+    use core::borrow::Borrow;
+    use core::hash::{Hash, Hasher};
+
+    use super::hash_map::{FnvHasher, HashMap};
+    use super::CapacityError;
+
+    /// Fixed-capacity set backed by `HashMap<T, ()>`
+    pub struct HashSet<T, const CAP: usize, H: Hasher + Default = FnvHasher> {
+        map: HashMap<T, (), CAP, H>,
+    }
+
+    impl<T, const CAP: usize, H: Hasher + Default> HashSet<T, CAP, H> {
+        pub const fn new() -> Self {
+            Self { map: HashMap::new() }
+        }
+
+        pub fn len(&self) -> usize {
+            self.map.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.map.is_empty()
+        }
+
+        pub fn capacity(&self) -> usize {
+            self.map.capacity()
+        }
+
+        pub fn clear(&mut self) {
+            self.map.clear();
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = &T> {
+            self.map.keys()
+        }
+    }
+
+    impl<T: Eq + Hash, const CAP: usize, H: Hasher + Default> HashSet<T, CAP, H> {
+        /// Returns true if the value was newly added
+        pub fn insert(&mut self, value: T) -> Result<bool, CapacityError<T>> {
+            if self.map.contains_key(&value) {
+                return Ok(false);
+            }
+            match self.map.insert(value, ()) {
+                Ok(_) => Ok(true),
+                Err(e) => Err(CapacityError::new(e.element().0)),
+            }
+        }
+
+        pub fn contains<Q>(&self, value: &Q) -> bool
+        where
+            T: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            self.map.contains_key(value)
+        }
+
+        pub fn remove<Q>(&mut self, value: &Q) -> bool
+        where
+            T: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            self.map.remove(value).is_some()
+        }
+
+        pub fn retain(&mut self, mut f: impl FnMut(&T) -> bool) {
+            self.map.retain(|k, _| f(k));
+        }
+    }
+
+    impl<T, const CAP: usize, H: Hasher + Default> Default for HashSet<T, CAP, H> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
+
+pub mod index_map {
+    use core::borrow::Borrow;
+    use core::hash::{Hash, Hasher};
+
+    use super::hash_map::{FnvHasher, HashMap};
+    use super::vec::Vec;
+    use super::CapacityError;
+
+    /// Hash map that remembers insertion order. Entries live in a dense
+    /// `Vec`; a `HashMap` maps each key to its position.
+    pub struct IndexMap<K, V, const CAP: usize, H: Hasher + Default = FnvHasher> {
+        entries: Vec<(K, V), CAP>,
+        indices: HashMap<K, usize, CAP, H>,
+    }
+
+    impl<K, V, const CAP: usize, H: Hasher + Default> IndexMap<K, V, CAP, H> {
+        pub const fn new() -> Self {
+            Self { entries: Vec::new(), indices: HashMap::new() }
+        }
+
+        pub fn len(&self) -> usize {
+            self.entries.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.entries.is_empty()
+        }
+
+        pub fn capacity(&self) -> usize {
+            self.indices.capacity()
+        }
+
+        pub fn clear(&mut self) {
+            self.entries.clear();
+            self.indices.clear();
+        }
+
+        pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+            self.entries.get(index).map(|(k, v)| (k, v))
+        }
+
+        pub fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+            self.entries.get_mut(index).map(|(k, v)| (&*k, v))
+        }
+
+        /// Entries in insertion order
+        pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+            self.entries.iter().map(|(k, v)| (k, v))
+        }
+
+        pub fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> {
+            self.entries.iter_mut().map(|(k, v)| (&*k, v))
+        }
+
+        pub fn keys(&self) -> impl Iterator<Item = &K> {
+            self.entries.iter().map(|(k, _)| k)
+        }
+
+        pub fn values(&self) -> impl Iterator<Item = &V> {
+            self.entries.iter().map(|(_, v)| v)
+        }
+    }
+
+    impl<K, V, const CAP: usize, H> IndexMap<K, V, CAP, H>
+    where
+        K: Eq + Hash + Clone,
+        H: Hasher + Default,
+    {
+        /// Insert or replace; a replaced key keeps its original position
+        pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, CapacityError<(K, V)>> {
+            if let Some(&index) = self.indices.get(&key) {
+                return Ok(Some(core::mem::replace(&mut self.entries[index].1, value)));
+            }
+            if self.entries.len() >= self.indices.capacity() {
+                return Err(CapacityError::new((key, value)));
+            }
+            let index = self.entries.len();
+            if self.indices.insert(key.clone(), index).is_err() {
+                return Err(CapacityError::new((key, value)));
+            }
+            let _ = self.entries.push((key, value));
+            Ok(None)
+        }
+
+        pub fn get_index_of<Q>(&self, key: &Q) -> Option<usize>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            self.indices.get(key).copied()
+        }
+
+        pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let index = self.get_index_of(key)?;
+            Some(&self.entries[index].1)
+        }
+
+        pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let index = self.get_index_of(key)?;
+            Some(&mut self.entries[index].1)
+        }
+
+        pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            self.indices.contains_key(key)
+        }
+
+        /// O(1) removal; the last entry moves into the freed position
+        pub fn swap_remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let index = self.indices.remove(key)?;
+            let (_, value) = self.entries.swap_remove(index);
+            if let Some((moved, _)) = self.entries.get(index) {
+                if let Some(slot) = self.indices.get_mut::<K>(moved) {
+                    *slot = index;
+                }
+            }
+            Some(value)
+        }
+
+        /// O(n) removal that keeps the remaining entries in order
+        pub fn shift_remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let index = self.indices.remove(key)?;
+            let (_, value) = self.entries.remove(index);
+            for (k, _) in &self.entries[index..] {
+                if let Some(slot) = self.indices.get_mut::<K>(k) {
+                    *slot -= 1;
+                }
+            }
+            Some(value)
+        }
+
+        pub fn pop(&mut self) -> Option<(K, V)> {
+            let (key, value) = self.entries.pop()?;
+            self.indices.remove(&key);
+            Some((key, value))
+        }
+    }
+
+    impl<K, V, const CAP: usize, H: Hasher + Default> Default for IndexMap<K, V, CAP, H> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
+
+pub mod sorted_map {
+    use core::borrow::Borrow;
+    use core::ops::{Bound, RangeBounds};
+
+    use super::vec::Vec;
+    use super::CapacityError;
+
+    /// Ordered map kept as a sorted array: O(log n) lookup, O(n) insert and
+    /// remove, ordered iteration and range queries like `BTreeMap`.
+    pub struct SortedMap<K: Ord, V, const CAP: usize> {
+        entries: Vec<(K, V), CAP>,
+    }
+
+    impl<K: Ord, V, const CAP: usize> SortedMap<K, V, CAP> {
+        pub const fn new() -> Self {
+            Self { entries: Vec::new() }
+        }
+
+        fn search<Q>(&self, key: &Q) -> Result<usize, usize>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            self.entries.binary_search_by(|(k, _)| k.borrow().cmp(key))
+        }
+
+        pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, CapacityError<(K, V)>> {
+            match self.search(&key) {
+                Ok(index) => Ok(Some(core::mem::replace(&mut self.entries[index].1, value))),
+                Err(index) => self.entries.insert(index, (key, value)).map(|_| None),
+            }
+        }
+
+        pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            self.search(key).ok().map(|i| &self.entries[i].1)
+        }
+
+        pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            match self.search(key) {
+                Ok(i) => Some(&mut self.entries[i].1),
+                Err(_) => None,
+            }
+        }
+
+        pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            self.search(key).is_ok()
+        }
+
+        pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            let index = self.search(key).ok()?;
+            Some(self.entries.remove(index).1)
+        }
+
+        pub fn first(&self) -> Option<(&K, &V)> {
+            self.entries.first().map(|(k, v)| (k, v))
+        }
+
+        pub fn last(&self) -> Option<(&K, &V)> {
+            self.entries.last().map(|(k, v)| (k, v))
+        }
+
+        pub fn pop_first(&mut self) -> Option<(K, V)> {
+            if self.entries.is_empty() { None } else { Some(self.entries.remove(0)) }
+        }
+
+        pub fn pop_last(&mut self) -> Option<(K, V)> {
+            self.entries.pop()
+        }
+
+        /// Entries in ascending key order
+        pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> {
+            self.entries.iter().map(|(k, v)| (k, v))
+        }
+
+        pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = (&K, &mut V)> {
+            self.entries.iter_mut().map(|(k, v)| (&*k, v))
+        }
+
+        pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> {
+            self.entries.iter().map(|(k, _)| k)
+        }
+
+        pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> {
+            self.entries.iter().map(|(_, v)| v)
+        }
+
+        /// Entries whose keys fall within `range`, in ascending order
+        pub fn range<Q, R>(&self, range: R) -> impl DoubleEndedIterator<Item = (&K, &V)>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+            R: RangeBounds<Q>,
+        {
+            let start = match range.start_bound() {
+                Bound::Included(k) => self.search(k).unwrap_or_else(|i| i),
+                Bound::Excluded(k) => match self.search(k) { Ok(i) => i + 1, Err(i) => i },
+                Bound::Unbounded => 0,
+            };
+            let end = match range.end_bound() {
+                Bound::Included(k) => match self.search(k) { Ok(i) => i + 1, Err(i) => i },
+                Bound::Excluded(k) => self.search(k).unwrap_or_else(|i| i),
+                Bound::Unbounded => self.entries.len(),
+            };
+            let end = end.max(start);
+            self.entries[start..end].iter().map(|(k, v)| (k, v))
+        }
+
+        pub fn retain(&mut self, mut f: impl FnMut(&K, &mut V) -> bool) {
+            self.entries.retain(|(k, v)| f(k, v));
+        }
+
+        pub fn len(&self) -> usize {
+            self.entries.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.entries.is_empty()
+        }
+
+        pub fn capacity(&self) -> usize {
+            CAP
+        }
+
+        pub fn clear(&mut self) {
+            self.entries.clear();
+        }
+    }
+
+    impl<K: Ord, V, const CAP: usize> Default for SortedMap<K, V, CAP> {
+        fn default() -> Self {
+            Self::new()
         }
     }
 }
@@ -1,116 +1,3699 @@
 // crust_io/lib.rs
+// Files, directories, sockets, an epoll reactor and clocks over libc for
+// no_std programs (Linux, x86_64). Every fallible call returns `Result<T, Errno>`; descriptors
+// are closed when their owner drops.
 #![no_std]
+#![allow(dead_code)]
+
+use core::fmt;
+
+/// `errno` values this crate knows by name; anything else is `Other`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Errno {
+    NotPermitted,
+    NotFound,
+    Interrupted,
+    Io,
+    BadFd,
+    WouldBlock,
+    NoMemory,
+    AccessDenied,
+    Exists,
+    NotADirectory,
+    IsADirectory,
+    InvalidInput,
+    TooManyFiles,
+    FileTooLarge,
+    NoSpace,
+    IllegalSeek,
+    ReadOnly,
+    BrokenPipe,
+    NameTooLong,
+    NotEmpty,
+    AddrInUse,
+    AddrNotAvailable,
+    NetUnreachable,
+    ConnectionAborted,
+    ConnectionReset,
+    NoBufferSpace,
+    AlreadyConnected,
+    NotConnected,
+    TimedOut,
+    ConnectionRefused,
+    HostUnreachable,
+    InProgress,
+    Other(i32),
+}
+
+impl Errno {
+    pub fn from_raw(code: i32) -> Self {
+        match code {
+            1 => Errno::NotPermitted,
+            2 => Errno::NotFound,
+            4 => Errno::Interrupted,
+            5 => Errno::Io,
+            9 => Errno::BadFd,
+            11 => Errno::WouldBlock,
+            12 => Errno::NoMemory,
+            13 => Errno::AccessDenied,
+            17 => Errno::Exists,
+            20 => Errno::NotADirectory,
+            21 => Errno::IsADirectory,
+            22 => Errno::InvalidInput,
+            24 => Errno::TooManyFiles,
+            27 => Errno::FileTooLarge,
+            28 => Errno::NoSpace,
+            29 => Errno::IllegalSeek,
+            30 => Errno::ReadOnly,
+            32 => Errno::BrokenPipe,
+            36 => Errno::NameTooLong,
+            39 => Errno::NotEmpty,
+            98 => Errno::AddrInUse,
+            99 => Errno::AddrNotAvailable,
+            101 => Errno::NetUnreachable,
+            103 => Errno::ConnectionAborted,
+            104 => Errno::ConnectionReset,
+            105 => Errno::NoBufferSpace,
+            106 => Errno::AlreadyConnected,
+            107 => Errno::NotConnected,
+            110 => Errno::TimedOut,
+            111 => Errno::ConnectionRefused,
+            113 => Errno::HostUnreachable,
+            115 => Errno::InProgress,
+            other => Errno::Other(other),
+        }
+    }
+
+    pub fn raw(self) -> i32 {
+        match self {
+            Errno::NotPermitted => 1,
+            Errno::NotFound => 2,
+            Errno::Interrupted => 4,
+            Errno::Io => 5,
+            Errno::BadFd => 9,
+            Errno::WouldBlock => 11,
+            Errno::NoMemory => 12,
+            Errno::AccessDenied => 13,
+            Errno::Exists => 17,
+            Errno::NotADirectory => 20,
+            Errno::IsADirectory => 21,
+            Errno::InvalidInput => 22,
+            Errno::TooManyFiles => 24,
+            Errno::FileTooLarge => 27,
+            Errno::NoSpace => 28,
+            Errno::IllegalSeek => 29,
+            Errno::ReadOnly => 30,
+            Errno::BrokenPipe => 32,
+            Errno::NameTooLong => 36,
+            Errno::NotEmpty => 39,
+            Errno::AddrInUse => 98,
+            Errno::AddrNotAvailable => 99,
+            Errno::NetUnreachable => 101,
+            Errno::ConnectionAborted => 103,
+            Errno::ConnectionReset => 104,
+            Errno::NoBufferSpace => 105,
+            Errno::AlreadyConnected => 106,
+            Errno::NotConnected => 107,
+            Errno::TimedOut => 110,
+            Errno::ConnectionRefused => 111,
+            Errno::HostUnreachable => 113,
+            Errno::InProgress => 115,
+            Errno::Other(code) => code,
+        }
+    }
+
+    /// The calling thread's current `errno`
+    pub fn last() -> Self {
+        Self::from_raw(unsafe { *sys::__errno_location() })
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Errno::NotPermitted => "operation not permitted",
+            Errno::NotFound => "no such file or directory",
+            Errno::Interrupted => "interrupted system call",
+            Errno::Io => "input/output error",
+            Errno::BadFd => "bad file descriptor",
+            Errno::WouldBlock => "resource temporarily unavailable",
+            Errno::NoMemory => "out of memory",
+            Errno::AccessDenied => "permission denied",
+            Errno::Exists => "file exists",
+            Errno::NotADirectory => "not a directory",
+            Errno::IsADirectory => "is a directory",
+            Errno::InvalidInput => "invalid argument",
+            Errno::TooManyFiles => "too many open files",
+            Errno::FileTooLarge => "file too large",
+            Errno::NoSpace => "no space left on device",
+            Errno::IllegalSeek => "illegal seek",
+            Errno::ReadOnly => "read-only file system",
+            Errno::BrokenPipe => "broken pipe",
+            Errno::NameTooLong => "file name too long",
+            Errno::NotEmpty => "directory not empty",
+            Errno::AddrInUse => "address already in use",
+            Errno::AddrNotAvailable => "address not available",
+            Errno::NetUnreachable => "network unreachable",
+            Errno::ConnectionAborted => "connection aborted",
+            Errno::ConnectionReset => "connection reset by peer",
+            Errno::NoBufferSpace => "no buffer space available",
+            Errno::AlreadyConnected => "already connected",
+            Errno::NotConnected => "not connected",
+            Errno::TimedOut => "timed out",
+            Errno::ConnectionRefused => "connection refused",
+            Errno::HostUnreachable => "host unreachable",
+            Errno::InProgress => "operation in progress",
+            Errno::Other(_) => "unknown error",
+        }
+    }
+}
+
+impl fmt::Display for Errno {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (errno {})", self.description(), self.raw())
+    }
+}
+
+pub type Result<T> = core::result::Result<T, Errno>;
+
+/// Raw libc bindings plus the helpers that turn `-1`/`errno` into `Errno`
+mod sys {
+    use super::{Errno, Result};
+
+    pub const O_RDONLY: i32 = 0;
+    pub const O_WRONLY: i32 = 0o1;
+    pub const O_RDWR: i32 = 0o2;
+    pub const O_CREAT: i32 = 0o100;
+    pub const O_EXCL: i32 = 0o200;
+    pub const O_TRUNC: i32 = 0o1000;
+    pub const O_APPEND: i32 = 0o2000;
+    pub const O_DIRECTORY: i32 = 0o200000;
+    pub const O_NONBLOCK: i32 = 0o4000;
+    pub const O_CLOEXEC: i32 = 0o2000000;
+    pub const CLOCK_REALTIME: i32 = 0;
+    pub const CLOCK_MONOTONIC: i32 = 1;
+
+    #[repr(C)]
+    #[derive(Copy, Clone, Default)]
+    pub struct Timespec {
+        pub sec: i64,
+        pub nsec: i64,
+    }
+
+    impl Timespec {
+        pub fn from_duration(d: core::time::Duration) -> Self {
+            Self { sec: d.as_secs() as i64, nsec: d.subsec_nanos() as i64 }
+        }
+    }
+
+    /// x86_64 `struct stat`
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct Stat {
+        pub st_dev: u64,
+        pub st_ino: u64,
+        pub st_nlink: u64,
+        pub st_mode: u32,
+        pub st_uid: u32,
+        pub st_gid: u32,
+        pub __pad0: i32,
+        pub st_rdev: u64,
+        pub st_size: i64,
+        pub st_blksize: i64,
+        pub st_blocks: i64,
+        pub st_atime: i64,
+        pub st_atime_nsec: i64,
+        pub st_mtime: i64,
+        pub st_mtime_nsec: i64,
+        pub st_ctime: i64,
+        pub st_ctime_nsec: i64,
+        pub __unused: [i64; 3],
+    }
+
+    #[link(name = "c")]
+    extern "C" {
+        pub fn __errno_location() -> *mut i32;
+        pub fn open(path: *const u8, flags: i32, ...) -> i32;
+        pub fn read(fd: i32, buf: *mut u8, count: usize) -> isize;
+        pub fn write(fd: i32, buf: *const u8, count: usize) -> isize;
+        pub fn close(fd: i32) -> i32;
+        pub fn lseek(fd: i32, offset: i64, whence: i32) -> i64;
+        pub fn fstat(fd: i32, buf: *mut Stat) -> i32;
+        pub fn stat(path: *const u8, buf: *mut Stat) -> i32;
+        pub fn lstat(path: *const u8, buf: *mut Stat) -> i32;
+        pub fn ftruncate(fd: i32, length: i64) -> i32;
+        pub fn fsync(fd: i32) -> i32;
+        pub fn getdents64(fd: i32, dirp: *mut u8, count: usize) -> isize;
+        pub fn rename(old: *const u8, new: *const u8) -> i32;
+        pub fn unlink(path: *const u8) -> i32;
+        pub fn mkdir(path: *const u8, mode: u32) -> i32;
+        pub fn rmdir(path: *const u8) -> i32;
+        pub fn fcntl(fd: i32, cmd: i32, ...) -> i32;
+        pub fn clock_gettime(clock: i32, tp: *mut Timespec) -> i32;
+    }
+
+    /// libc convention: negative means "look at errno"
+    pub fn cvt(ret: i32) -> Result<i32> {
+        if ret < 0 {
+            Err(Errno::last())
+        } else {
+            Ok(ret)
+        }
+    }
+
+    pub fn cvt_size(ret: isize) -> Result<usize> {
+        if ret < 0 {
+            Err(Errno::last())
+        } else {
+            Ok(ret as usize)
+        }
+    }
+
+    const PATH_MAX: usize = 4096;
+
+    /// Run `f` with a nul-terminated copy of `path`
+    pub fn with_c_path<T>(path: &str, f: impl FnOnce(*const u8) -> Result<T>) -> Result<T> {
+        let mut buf = [0u8; PATH_MAX];
+        if path.len() >= PATH_MAX {
+            return Err(Errno::NameTooLong);
+        }
+        if path.as_bytes().contains(&0) {
+            return Err(Errno::InvalidInput);
+        }
+        buf[..path.len()].copy_from_slice(path.as_bytes());
+        f(buf.as_ptr())
+    }
+
+    /// Reading a clock can't fail for the ids used here
+    pub fn clock(id: i32) -> core::time::Duration {
+        let mut ts = Timespec::default();
+        unsafe { clock_gettime(id, &mut ts) };
+        core::time::Duration::new(ts.sec as u64, ts.nsec as u32)
+    }
+
+    pub fn set_nonblocking(fd: i32, on: bool) -> Result<()> {
+        const F_GETFL: i32 = 3;
+        const F_SETFL: i32 = 4;
+        let flags = cvt(unsafe { fcntl(fd, F_GETFL) })?;
+        let flags = if on { flags | O_NONBLOCK } else { flags & !O_NONBLOCK };
+        cvt(unsafe { fcntl(fd, F_SETFL, flags) }).map(|_| ())
+    }
+
+    pub fn read_fd(fd: i32, buf: &mut [u8]) -> Result<usize> {
+        cvt_size(unsafe { read(fd, buf.as_mut_ptr(), buf.len()) })
+    }
+
+    pub fn write_fd(fd: i32, buf: &[u8]) -> Result<usize> {
+        cvt_size(unsafe { write(fd, buf.as_ptr(), buf.len()) })
+    }
+}
+
+// ============================
+// Read / Write
+// ============================
+
+pub trait Read {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
+
+    fn read_exact(&mut self, mut buf: &mut [u8]) -> Result<()> {
+        while !buf.is_empty() {
+            match self.read(buf) {
+                Ok(0) => return Err(Errno::Io),
+                Ok(n) => buf = &mut buf[n..],
+                Err(Errno::Interrupted) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Read until EOF into `buf`; `NoBufferSpace` if the data doesn't fit
+    fn read_to_end(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let mut filled = 0;
+        loop {
+            if filled == buf.len() {
+                // Only an error if there really is more to come
+                let mut probe = [0u8; 1];
+                return match self.read(&mut probe)? {
+                    0 => Ok(filled),
+                    _ => Err(Errno::NoBufferSpace),
+                };
+            }
+            match self.read(&mut buf[filled..]) {
+                Ok(0) => return Ok(filled),
+                Ok(n) => filled += n,
+                Err(Errno::Interrupted) => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Like `read_to_end`, but the bytes must be valid UTF-8
+    fn read_to_str<'a>(&mut self, buf: &'a mut [u8]) -> Result<&'a str> {
+        let n = self.read_to_end(buf)?;
+        core::str::from_utf8(&buf[..n]).map_err(|_| Errno::InvalidInput)
+    }
+}
+
+pub trait Write {
+    fn write(&mut self, buf: &[u8]) -> Result<usize>;
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn write_all(&mut self, mut buf: &[u8]) -> Result<()> {
+        while !buf.is_empty() {
+            match self.write(buf) {
+                Ok(0) => return Err(Errno::Io),
+                Ok(n) => buf = &buf[n..],
+                Err(Errno::Interrupted) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Lets `write!(file, ...)` work; the first I/O error is returned
+    fn write_fmt(&mut self, args: fmt::Arguments) -> Result<()> {
+        struct Adapter<'a, W: ?Sized> {
+            inner: &'a mut W,
+            error: Result<()>,
+        }
+
+        impl<W: Write + ?Sized> fmt::Write for Adapter<'_, W> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.inner.write_all(s.as_bytes()).map_err(|e| {
+                    self.error = Err(e);
+                    fmt::Error
+                })
+            }
+        }
+
+        let mut adapter = Adapter { inner: self, error: Ok(()) };
+        match fmt::write(&mut adapter, args) {
+            Ok(()) => Ok(()),
+            Err(_) => adapter.error.and(Err(Errno::Io)),
+        }
+    }
+}
+
+/// Copy everything from `reader` to `writer`; returns the byte count
+pub fn copy<R: Read + ?Sized, W: Write + ?Sized>(reader: &mut R, writer: &mut W) -> Result<u64> {
+    let mut buf = [0u8; 4096];
+    let mut total = 0;
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => return Ok(total),
+            Ok(n) => n,
+            Err(Errno::Interrupted) => continue,
+            Err(e) => return Err(e),
+        };
+        writer.write_all(&buf[..n])?;
+        total += n as u64;
+    }
+}
+
+/// Borrowed standard streams (never closed)
+pub struct Stdin;
+pub struct Stdout;
+pub struct Stderr;
+
+pub fn stdin() -> Stdin {
+    Stdin
+}
+
+pub fn stdout() -> Stdout {
+    Stdout
+}
+
+pub fn stderr() -> Stderr {
+    Stderr
+}
+
+impl Read for Stdin {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        sys::read_fd(0, buf)
+    }
+}
+
+impl Write for Stdout {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        sys::write_fd(1, buf)
+    }
+}
+
+impl Write for Stderr {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        sys::write_fd(2, buf)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SeekFrom {
+    Start(u64),
+    End(i64),
+    Current(i64),
+}
+
+// ============================
+// Buffered I/O
+// ============================
+
+/// Reads ahead in `N`-byte chunks; adds line reading
+pub struct BufReader<R, const N: usize = 4096> {
+    inner: R,
+    buf: [u8; N],
+    pos: usize,
+    filled: usize,
+}
+
+impl<R: Read, const N: usize> BufReader<R, N> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, buf: [0; N], pos: 0, filled: 0 }
+    }
+
+    /// Buffered bytes, refilling first if everything was consumed
+    pub fn fill_buf(&mut self) -> Result<&[u8]> {
+        if self.pos >= self.filled {
+            self.filled = self.inner.read(&mut self.buf)?;
+            self.pos = 0;
+        }
+        Ok(&self.buf[self.pos..self.filled])
+    }
+
+    pub fn consume(&mut self, amount: usize) {
+        self.pos = (self.pos + amount).min(self.filled);
+    }
+
+    /// Copy the next line (without `\n`) into `out`. Returns `None` at EOF
+    /// and `NoBufferSpace` if the line is longer than `out`; the rest of
+    /// that line is skipped so the next call starts fresh.
+    pub fn read_line<'a>(&mut self, out: &'a mut [u8]) -> Result<Option<&'a str>> {
+        let mut len = 0;
+        let mut overflow = false;
+        let mut saw_any = false;
+        loop {
+            let available = self.fill_buf()?;
+            if available.is_empty() {
+                break;
+            }
+            saw_any = true;
+            let (chunk, done) = match available.iter().position(|&b| b == b'\n') {
+                Some(i) => (&available[..i], i + 1),
+                None => (available, available.len()),
+            };
+            let found_newline = done > chunk.len();
+            let take = chunk.len().min(out.len() - len);
+            out[len..len + take].copy_from_slice(&chunk[..take]);
+            len += take;
+            overflow |= take < chunk.len();
+            self.consume(done);
+            if found_newline {
+                break;
+            }
+        }
+        if !saw_any {
+            return Ok(None);
+        }
+        if overflow {
+            return Err(Errno::NoBufferSpace);
+        }
+        let line = &out[..len];
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        core::str::from_utf8(line).map(Some).map_err(|_| Errno::InvalidInput)
+    }
+
+    /// Iterate lines of at most `L` bytes each
+    pub fn lines<const L: usize>(self) -> Lines<R, N, L> {
+        Lines { reader: self }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read, const N: usize> Read for BufReader<R, N> {
+    fn read(&mut self, out: &mut [u8]) -> Result<usize> {
+        // Big reads with nothing buffered skip the copy
+        if self.pos >= self.filled && out.len() >= N {
+            return self.inner.read(out);
+        }
+        let available = self.fill_buf()?;
+        let n = available.len().min(out.len());
+        out[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+/// One line from `Lines`, stored inline
+pub struct Line<const L: usize> {
+    buf: [u8; L],
+    len: usize,
+}
+
+impl<const L: usize> Line<L> {
+    pub fn as_str(&self) -> &str {
+        unsafe { core::str::from_utf8_unchecked(&self.buf[..self.len]) }
+    }
+}
+
+impl<const L: usize> core::ops::Deref for Line<L> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+pub struct Lines<R, const N: usize, const L: usize> {
+    reader: BufReader<R, N>,
+}
+
+impl<R: Read, const N: usize, const L: usize> Iterator for Lines<R, N, L> {
+    type Item = Result<Line<L>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = Line { buf: [0; L], len: 0 };
+        match self.reader.read_line(&mut line.buf) {
+            Ok(Some(s)) => {
+                line.len = s.len();
+                Some(Ok(line))
+            }
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Batches small writes into `N`-byte chunks; flushes on drop
+pub struct BufWriter<W: Write, const N: usize = 4096> {
+    inner: W,
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<W: Write, const N: usize> BufWriter<W, N> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, buf: [0; N], len: 0 }
+    }
+
+    fn flush_buf(&mut self) -> Result<()> {
+        let pending = self.len;
+        self.len = 0;
+        self.inner.write_all(&self.buf[..pending])
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Flush and hand back the inner writer
+    pub fn into_inner(mut self) -> Result<W> {
+        self.flush_buf()?;
+        let inner = unsafe { core::ptr::read(&self.inner) };
+        core::mem::forget(self);
+        Ok(inner)
+    }
+}
+
+impl<W: Write, const N: usize> Write for BufWriter<W, N> {
+    fn write(&mut self, data: &[u8]) -> Result<usize> {
+        if self.len + data.len() > N {
+            self.flush_buf()?;
+        }
+        if data.len() >= N {
+            return self.inner.write(data);
+        }
+        self.buf[self.len..self.len + data.len()].copy_from_slice(data);
+        self.len += data.len();
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.flush_buf()?;
+        self.inner.flush()
+    }
+}
+
+impl<W: Write, const N: usize> Drop for BufWriter<W, N> {
+    fn drop(&mut self) {
+        let _ = self.flush_buf();
+    }
+}
 
 pub mod fs {
-    use crate::result::Result;
-    use crate::string::String;
-    use crate::vec::Vec;
+    use super::sys::{self, cvt, with_c_path};
+    use super::{Errno, Read, Result, SeekFrom, Write};
+
+    /// Builder for `open(2)` flags, mirroring `std::fs::OpenOptions`
+    #[derive(Copy, Clone, Debug)]
+    pub struct OpenOptions {
+        read: bool,
+        write: bool,
+        append: bool,
+        truncate: bool,
+        create: bool,
+        create_new: bool,
+        mode: u32,
+        custom_flags: i32,
+    }
+
+    impl OpenOptions {
+        pub const fn new() -> Self {
+            Self {
+                read: false,
+                write: false,
+                append: false,
+                truncate: false,
+                create: false,
+                create_new: false,
+                mode: 0o666,
+                custom_flags: 0,
+            }
+        }
+
+        pub fn read(&mut self, on: bool) -> &mut Self {
+            self.read = on;
+            self
+        }
+
+        pub fn write(&mut self, on: bool) -> &mut Self {
+            self.write = on;
+            self
+        }
+
+        pub fn append(&mut self, on: bool) -> &mut Self {
+            self.append = on;
+            self
+        }
+
+        pub fn truncate(&mut self, on: bool) -> &mut Self {
+            self.truncate = on;
+            self
+        }
+
+        pub fn create(&mut self, on: bool) -> &mut Self {
+            self.create = on;
+            self
+        }
+
+        /// Fail with `Exists` rather than open an existing file
+        pub fn create_new(&mut self, on: bool) -> &mut Self {
+            self.create_new = on;
+            self
+        }
+
+        /// Permission bits for newly created files (before umask)
+        pub fn mode(&mut self, mode: u32) -> &mut Self {
+            self.mode = mode;
+            self
+        }
+
+        /// Extra `O_*` flags OR-ed in as-is
+        pub fn custom_flags(&mut self, flags: i32) -> &mut Self {
+            self.custom_flags = flags;
+            self
+        }
+
+        fn flags(&self) -> Result<i32> {
+            let writing = self.write || self.append;
+            let mut flags = match (self.read, writing) {
+                (true, false) => sys::O_RDONLY,
+                (false, true) => sys::O_WRONLY,
+                (true, true) => sys::O_RDWR,
+                (false, false) => return Err(Errno::InvalidInput),
+            };
+            if (self.truncate || self.create || self.create_new) && !writing {
+                return Err(Errno::InvalidInput);
+            }
+            if self.append {
+                flags |= sys::O_APPEND;
+            }
+            if self.truncate {
+                flags |= sys::O_TRUNC;
+            }
+            if self.create_new {
+                flags |= sys::O_CREAT | sys::O_EXCL;
+            } else if self.create {
+                flags |= sys::O_CREAT;
+            }
+            Ok(flags | sys::O_CLOEXEC | self.custom_flags)
+        }
+
+        pub fn open(&self, path: &str) -> Result<File> {
+            let flags = self.flags()?;
+            with_c_path(path, |p| cvt(unsafe { sys::open(p, flags, self.mode) }))
+                .map(|fd| File { fd })
+        }
+    }
+
+    impl Default for OpenOptions {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// Owned file descriptor, closed on drop
+    pub struct File {
+        fd: i32,
+    }
+
+    impl File {
+        /// Open read-only
+        pub fn open(path: &str) -> Result<Self> {
+            OpenOptions::new().read(true).open(path)
+        }
+
+        /// Open write-only, creating or truncating
+        pub fn create(path: &str) -> Result<Self> {
+            OpenOptions::new().write(true).create(true).truncate(true).open(path)
+        }
+
+        pub fn options() -> OpenOptions {
+            OpenOptions::new()
+        }
+
+        /// Take ownership of an already-open descriptor
+        ///
+        /// # Safety
+        /// `fd` must be open and not owned by anything else.
+        pub unsafe fn from_raw_fd(fd: i32) -> Self {
+            Self { fd }
+        }
+
+        pub fn as_raw_fd(&self) -> i32 {
+            self.fd
+        }
+
+        /// Give up ownership without closing
+        pub fn into_raw_fd(self) -> i32 {
+            let fd = self.fd;
+            core::mem::forget(self);
+            fd
+        }
+
+        pub fn seek(&self, pos: SeekFrom) -> Result<u64> {
+            let (offset, whence) = match pos {
+                SeekFrom::Start(n) => (n as i64, 0),
+                SeekFrom::Current(n) => (n, 1),
+                SeekFrom::End(n) => (n, 2),
+            };
+            let ret = unsafe { sys::lseek(self.fd, offset, whence) };
+            if ret < 0 {
+                Err(Errno::last())
+            } else {
+                Ok(ret as u64)
+            }
+        }
+
+        pub fn rewind(&self) -> Result<()> {
+            self.seek(SeekFrom::Start(0)).map(|_| ())
+        }
+
+        pub fn metadata(&self) -> Result<Metadata> {
+            let mut st = core::mem::MaybeUninit::<sys::Stat>::uninit();
+            cvt(unsafe { sys::fstat(self.fd, st.as_mut_ptr()) })?;
+            Ok(Metadata(unsafe { st.assume_init() }))
+        }
+
+        pub fn set_len(&self, len: u64) -> Result<()> {
+            cvt(unsafe { sys::ftruncate(self.fd, len as i64) }).map(|_| ())
+        }
+
+        pub fn sync_all(&self) -> Result<()> {
+            cvt(unsafe { sys::fsync(self.fd) }).map(|_| ())
+        }
+
+        /// Close now and see the error, which `Drop` has to ignore
+        pub fn close(self) -> Result<()> {
+            let fd = self.into_raw_fd();
+            cvt(unsafe { sys::close(fd) }).map(|_| ())
+        }
+    }
+
+    impl Read for File {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            sys::read_fd(self.fd, buf)
+        }
+    }
+
+    impl Write for File {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            sys::write_fd(self.fd, buf)
+        }
+    }
+
+    impl Read for &File {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            sys::read_fd(self.fd, buf)
+        }
+    }
+
+    impl Write for &File {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            sys::write_fd(self.fd, buf)
+        }
+    }
+
+    impl Drop for File {
+        fn drop(&mut self) {
+            unsafe { sys::close(self.fd) };
+        }
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum FileType {
+        File,
+        Dir,
+        Symlink,
+        CharDevice,
+        BlockDevice,
+        Fifo,
+        Socket,
+        Unknown,
+    }
+
+    impl FileType {
+        fn from_mode(mode: u32) -> Self {
+            match mode & 0o170000 {
+                0o100000 => FileType::File,
+                0o040000 => FileType::Dir,
+                0o120000 => FileType::Symlink,
+                0o020000 => FileType::CharDevice,
+                0o060000 => FileType::BlockDevice,
+                0o010000 => FileType::Fifo,
+                0o140000 => FileType::Socket,
+                _ => FileType::Unknown,
+            }
+        }
+
+        /// `d_type` from getdents64
+        fn from_dirent(d_type: u8) -> Self {
+            match d_type {
+                8 => FileType::File,
+                4 => FileType::Dir,
+                10 => FileType::Symlink,
+                2 => FileType::CharDevice,
+                6 => FileType::BlockDevice,
+                1 => FileType::Fifo,
+                12 => FileType::Socket,
+                _ => FileType::Unknown,
+            }
+        }
+    }
+
+    /// Result of `stat`/`fstat`
+    #[derive(Copy, Clone)]
+    pub struct Metadata(sys::Stat);
+
+    impl Metadata {
+        pub fn file_type(&self) -> FileType {
+            FileType::from_mode(self.0.st_mode)
+        }
+
+        pub fn is_file(&self) -> bool {
+            self.file_type() == FileType::File
+        }
+
+        pub fn is_dir(&self) -> bool {
+            self.file_type() == FileType::Dir
+        }
+
+        pub fn is_symlink(&self) -> bool {
+            self.file_type() == FileType::Symlink
+        }
+
+        pub fn len(&self) -> u64 {
+            self.0.st_size as u64
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        /// Permission bits, e.g. `0o644`
+        pub fn permissions(&self) -> u32 {
+            self.0.st_mode & 0o7777
+        }
+
+        pub fn uid(&self) -> u32 {
+            self.0.st_uid
+        }
+
+        pub fn gid(&self) -> u32 {
+            self.0.st_gid
+        }
+
+        pub fn ino(&self) -> u64 {
+            self.0.st_ino
+        }
+
+        pub fn nlink(&self) -> u64 {
+            self.0.st_nlink
+        }
+
+        /// Modification time as (seconds, nanoseconds) since the epoch
+        pub fn modified(&self) -> (i64, i64) {
+            (self.0.st_mtime, self.0.st_mtime_nsec)
+        }
+
+        pub fn accessed(&self) -> (i64, i64) {
+            (self.0.st_atime, self.0.st_atime_nsec)
+        }
+    }
+
+    impl core::fmt::Debug for Metadata {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            f.debug_struct("Metadata")
+                .field("file_type", &self.file_type())
+                .field("len", &self.len())
+                .field("permissions", &format_args!("{:o}", self.permissions()))
+                .finish()
+        }
+    }
+
+    pub fn metadata(path: &str) -> Result<Metadata> {
+        let mut st = core::mem::MaybeUninit::<sys::Stat>::uninit();
+        with_c_path(path, |p| cvt(unsafe { sys::stat(p, st.as_mut_ptr()) }))?;
+        Ok(Metadata(unsafe { st.assume_init() }))
+    }
+
+    /// Like `metadata`, but describes a symlink itself
+    pub fn symlink_metadata(path: &str) -> Result<Metadata> {
+        let mut st = core::mem::MaybeUninit::<sys::Stat>::uninit();
+        with_c_path(path, |p| cvt(unsafe { sys::lstat(p, st.as_mut_ptr()) }))?;
+        Ok(Metadata(unsafe { st.assume_init() }))
+    }
+
+    pub fn exists(path: &str) -> bool {
+        metadata(path).is_ok()
+    }
+
+    /// Read a whole file into `buf`; returns the byte count
+    pub fn read(path: &str, buf: &mut [u8]) -> Result<usize> {
+        File::open(path)?.read_to_end(buf)
+    }
+
+    /// Create or replace a file with `data`
+    pub fn write(path: &str, data: &[u8]) -> Result<()> {
+        File::create(path)?.write_all(data)
+    }
+
+    pub fn rename(from: &str, to: &str) -> Result<()> {
+        with_c_path(from, |f| with_c_path(to, |t| cvt(unsafe { sys::rename(f, t) }))).map(|_| ())
+    }
+
+    pub fn remove_file(path: &str) -> Result<()> {
+        with_c_path(path, |p| cvt(unsafe { sys::unlink(p) })).map(|_| ())
+    }
+
+    pub fn create_dir(path: &str) -> Result<()> {
+        with_c_path(path, |p| cvt(unsafe { sys::mkdir(p, 0o777) })).map(|_| ())
+    }
+
+    pub fn remove_dir(path: &str) -> Result<()> {
+        with_c_path(path, |p| cvt(unsafe { sys::rmdir(p) })).map(|_| ())
+    }
+
+    // ============================
+    // Directory listing
+    // ============================
+
+    /// One directory entry; the name is copied out of the kernel buffer
+    pub struct DirEntry {
+        ino: u64,
+        kind: FileType,
+        name: [u8; 256],
+        name_len: usize,
+    }
+
+    impl DirEntry {
+        pub fn name_bytes(&self) -> &[u8] {
+            &self.name[..self.name_len]
+        }
+
+        /// Empty if the name isn't valid UTF-8; see `name_bytes`
+        pub fn name(&self) -> &str {
+            core::str::from_utf8(self.name_bytes()).unwrap_or("")
+        }
+
+        pub fn ino(&self) -> u64 {
+            self.ino
+        }
+
+        /// From the directory itself, so no extra `stat` needed
+        /// (`Unknown` on filesystems that don't report it)
+        pub fn file_type(&self) -> FileType {
+            self.kind
+        }
+    }
+
+    /// Iterator over a directory via getdents64; skips `.` and `..`
+    pub struct ReadDir {
+        dir: File,
+        buf: [u8; 4096],
+        pos: usize,
+        end: usize,
+        // Set once getdents64 reports the end or an error
+        done: bool,
+    }
+
+    pub fn read_dir(path: &str) -> Result<ReadDir> {
+        let dir = OpenOptions::new().read(true).custom_flags(sys::O_DIRECTORY).open(path)?;
+        Ok(ReadDir { dir, buf: [0; 4096], pos: 0, end: 0, done: false })
+    }
+
+    // struct linux_dirent64 header: ino u64, off i64, reclen u16, type u8
+    const DIRENT_NAME: usize = 19;
+
+    impl Iterator for ReadDir {
+        type Item = Result<DirEntry>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            loop {
+                if self.pos >= self.end {
+                    if self.done {
+                        return None;
+                    }
+                    let n = unsafe { sys::getdents64(self.dir.fd, self.buf.as_mut_ptr(), self.buf.len()) };
+                    match n {
+                        0 => {
+                            self.done = true;
+                            return None;
+                        }
+                        // Report the error once, then stop
+                        n if n < 0 => {
+                            self.done = true;
+                            return Some(Err(Errno::last()));
+                        }
+                        n => {
+                            self.pos = 0;
+                            self.end = n as usize;
+                        }
+                    }
+                }
+
+                let rec = &self.buf[self.pos..self.end];
+                let ino = u64::from_ne_bytes(rec[0..8].try_into().unwrap_or([0; 8]));
+                let reclen = u16::from_ne_bytes([rec[16], rec[17]]) as usize;
+                let kind = FileType::from_dirent(rec[18]);
+                let raw_name = &rec[DIRENT_NAME..reclen];
+                let name_len = raw_name.iter().position(|&b| b == 0).unwrap_or(raw_name.len());
+                self.pos += reclen;
+
+                let name = &raw_name[..name_len];
+                if name == b"." || name == b".." {
+                    continue;
+                }
+                let mut entry = DirEntry { ino, kind, name: [0; 256], name_len: name_len.min(256) };
+                entry.name[..entry.name_len].copy_from_slice(&name[..entry.name_len]);
+                return Some(Ok(entry));
+            }
+        }
+    }
+}
+
+pub mod net {
+    use core::fmt;
+    use core::str::FromStr;
+    use core::time::Duration;
+
+    use super::sys::{self, cvt, cvt_size};
+    use super::{Errno, Read, Result, Write};
+
+    const AF_INET: u16 = 2;
+    const AF_INET6: u16 = 10;
+    const SOCK_STREAM: i32 = 1;
+    const SOCK_DGRAM: i32 = 2;
+    const SOCK_CLOEXEC: i32 = 0o2000000;
+    const SOL_SOCKET: i32 = 1;
+    const SO_REUSEADDR: i32 = 2;
+    const SO_ERROR: i32 = 4;
+    const SO_BROADCAST: i32 = 6;
+    const SO_RCVTIMEO: i32 = 20;
+    const SO_SNDTIMEO: i32 = 21;
+    const IPPROTO_TCP: i32 = 6;
+    const TCP_NODELAY: i32 = 1;
+    const IPPROTO_IPV6: i32 = 41;
+    const IPV6_V6ONLY: i32 = 26;
+    const MSG_PEEK: i32 = 2;
+    const MSG_NOSIGNAL: i32 = 0x4000;
+    const FIONBIO: u64 = 0x5421;
+    const POLLIN: i16 = 0x1;
+    const POLLOUT: i16 = 0x4;
+
+    // ============================
+    // Addresses
+    // ============================
+
+    /// Returned by the `FromStr` impls for malformed addresses
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct AddrParseError;
+
+    impl fmt::Display for AddrParseError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("invalid IP address syntax")
+        }
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct Ipv4Addr([u8; 4]);
+
+    impl Ipv4Addr {
+        pub const LOCALHOST: Self = Self([127, 0, 0, 1]);
+        pub const UNSPECIFIED: Self = Self([0, 0, 0, 0]);
+        pub const BROADCAST: Self = Self([255, 255, 255, 255]);
+
+        pub const fn new(a: u8, b: u8, c: u8, d: u8) -> Self {
+            Self([a, b, c, d])
+        }
+
+        pub const fn octets(&self) -> [u8; 4] {
+            self.0
+        }
+
+        pub fn is_loopback(&self) -> bool {
+            self.0[0] == 127
+        }
+
+        pub fn is_unspecified(&self) -> bool {
+            self.0 == [0; 4]
+        }
+    }
+
+    impl FromStr for Ipv4Addr {
+        type Err = AddrParseError;
+
+        fn from_str(s: &str) -> core::result::Result<Self, AddrParseError> {
+            let mut octets = [0u8; 4];
+            let mut parts = s.split('.');
+            for octet in octets.iter_mut() {
+                let part = parts.next().ok_or(AddrParseError)?;
+                // No signs, no leading zeros ("010" is octal elsewhere)
+                if part.is_empty() || part.len() > 3 || (part.len() > 1 && part.starts_with('0')) {
+                    return Err(AddrParseError);
+                }
+                if !part.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(AddrParseError);
+                }
+                *octet = part.parse().map_err(|_| AddrParseError)?;
+            }
+            if parts.next().is_some() {
+                return Err(AddrParseError);
+            }
+            Ok(Self(octets))
+        }
+    }
+
+    impl fmt::Display for Ipv4Addr {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let [a, b, c, d] = self.0;
+            write!(f, "{}.{}.{}.{}", a, b, c, d)
+        }
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct Ipv6Addr([u8; 16]);
+
+    impl Ipv6Addr {
+        pub const LOCALHOST: Self = Self::new(0, 0, 0, 0, 0, 0, 0, 1);
+        pub const UNSPECIFIED: Self = Self([0; 16]);
+
+        #[allow(clippy::too_many_arguments)]
+        pub const fn new(a: u16, b: u16, c: u16, d: u16, e: u16, f: u16, g: u16, h: u16) -> Self {
+            let s = [a, b, c, d, e, f, g, h];
+            let mut octets = [0u8; 16];
+            let mut i = 0;
+            while i < 8 {
+                octets[2 * i] = (s[i] >> 8) as u8;
+                octets[2 * i + 1] = s[i] as u8;
+                i += 1;
+            }
+            Self(octets)
+        }
+
+        pub const fn octets(&self) -> [u8; 16] {
+            self.0
+        }
+
+        pub fn segments(&self) -> [u16; 8] {
+            let mut s = [0u16; 8];
+            for (i, seg) in s.iter_mut().enumerate() {
+                *seg = u16::from_be_bytes([self.0[2 * i], self.0[2 * i + 1]]);
+            }
+            s
+        }
+
+        pub fn is_loopback(&self) -> bool {
+            *self == Self::LOCALHOST
+        }
+
+        pub fn is_unspecified(&self) -> bool {
+            *self == Self::UNSPECIFIED
+        }
+
+        /// `::ffff:a.b.c.d` back to the IPv4 address it wraps
+        pub fn to_ipv4_mapped(self) -> Option<Ipv4Addr> {
+            match self.0 {
+                [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, a, b, c, d] => Some(Ipv4Addr([a, b, c, d])),
+                _ => None,
+            }
+        }
+    }
+
+    impl From<Ipv4Addr> for Ipv6Addr {
+        /// IPv4-mapped form, `::ffff:a.b.c.d`
+        fn from(v4: Ipv4Addr) -> Self {
+            let [a, b, c, d] = v4.0;
+            Self([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, a, b, c, d])
+        }
+    }
+
+    /// Parse colon-separated groups, allowing an IPv4 tail when they end
+    /// the address; returns how many 16-bit segments were written
+    fn parse_v6_groups(s: &str, out: &mut [u16], v4_tail: bool) -> core::result::Result<usize, AddrParseError> {
+        if s.is_empty() {
+            return Ok(0);
+        }
+        let mut n = 0;
+        let mut groups = s.split(':').peekable();
+        while let Some(group) = groups.next() {
+            if v4_tail && groups.peek().is_none() && group.contains('.') {
+                if n + 2 > out.len() {
+                    return Err(AddrParseError);
+                }
+                let [a, b, c, d] = group.parse::<Ipv4Addr>()?.0;
+                out[n] = u16::from_be_bytes([a, b]);
+                out[n + 1] = u16::from_be_bytes([c, d]);
+                return Ok(n + 2);
+            }
+            if n == out.len() || group.is_empty() || group.len() > 4 {
+                return Err(AddrParseError);
+            }
+            out[n] = u16::from_str_radix(group, 16).map_err(|_| AddrParseError)?;
+            n += 1;
+        }
+        Ok(n)
+    }
+
+    impl FromStr for Ipv6Addr {
+        type Err = AddrParseError;
+
+        fn from_str(s: &str) -> core::result::Result<Self, AddrParseError> {
+            if s.bytes().any(|b| b == b'+' || b == b'-') {
+                return Err(AddrParseError);
+            }
+            let mut segs = [0u16; 8];
+            match s.find("::") {
+                None => {
+                    if parse_v6_groups(s, &mut segs, true)? != 8 {
+                        return Err(AddrParseError);
+                    }
+                }
+                Some(gap) => {
+                    let (head, tail) = (&s[..gap], &s[gap + 2..]);
+                    if tail.contains("::") {
+                        return Err(AddrParseError);
+                    }
+                    // "::" stands for at least one zero group
+                    let mut back = [0u16; 7];
+                    // Only the last 32 bits may be written as IPv4
+                    let nh = parse_v6_groups(head, &mut segs[..7], false)?;
+                    let nt = parse_v6_groups(tail, &mut back[..7 - nh], true)?;
+                    segs[8 - nt..].copy_from_slice(&back[..nt]);
+                }
+            }
+            let [a, b, c, d, e, f, g, h] = segs;
+            Ok(Self::new(a, b, c, d, e, f, g, h))
+        }
+    }
+
+    impl fmt::Display for Ipv6Addr {
+        /// RFC 5952 form: lowercase, longest zero run collapsed to `::`
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            if let Some(v4) = self.to_ipv4_mapped() {
+                return write!(f, "::ffff:{}", v4);
+            }
+            let segs = self.segments();
+            let (mut best, mut best_len, mut run, mut run_len) = (0, 0, 0, 0);
+            for (i, &seg) in segs.iter().enumerate() {
+                if seg == 0 {
+                    if run_len == 0 {
+                        run = i;
+                    }
+                    run_len += 1;
+                    if run_len > best_len {
+                        best = run;
+                        best_len = run_len;
+                    }
+                } else {
+                    run_len = 0;
+                }
+            }
+            if best_len < 2 {
+                best_len = 0;
+            }
+            let mut i = 0;
+            while i < 8 {
+                if best_len > 0 && i == best {
+                    f.write_str("::")?;
+                    i += best_len;
+                    continue;
+                }
+                if i > 0 && !(best_len > 0 && i == best + best_len) {
+                    f.write_str(":")?;
+                }
+                write!(f, "{:x}", segs[i])?;
+                i += 1;
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub enum IpAddr {
+        V4(Ipv4Addr),
+        V6(Ipv6Addr),
+    }
+
+    impl IpAddr {
+        pub fn is_loopback(&self) -> bool {
+            match self {
+                IpAddr::V4(a) => a.is_loopback(),
+                IpAddr::V6(a) => a.is_loopback(),
+            }
+        }
+
+        pub fn is_ipv4(&self) -> bool {
+            matches!(self, IpAddr::V4(_))
+        }
+    }
+
+    impl From<Ipv4Addr> for IpAddr {
+        fn from(a: Ipv4Addr) -> Self {
+            IpAddr::V4(a)
+        }
+    }
+
+    impl From<Ipv6Addr> for IpAddr {
+        fn from(a: Ipv6Addr) -> Self {
+            IpAddr::V6(a)
+        }
+    }
+
+    impl FromStr for IpAddr {
+        type Err = AddrParseError;
+
+        fn from_str(s: &str) -> core::result::Result<Self, AddrParseError> {
+            if s.contains(':') {
+                s.parse().map(IpAddr::V6)
+            } else {
+                s.parse().map(IpAddr::V4)
+            }
+        }
+    }
+
+    impl fmt::Display for IpAddr {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                IpAddr::V4(a) => a.fmt(f),
+                IpAddr::V6(a) => a.fmt(f),
+            }
+        }
+    }
+
+    /// IP address plus port; IPv6 text form is `[addr]:port`
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct SocketAddr {
+        ip: IpAddr,
+        port: u16,
+    }
+
+    impl SocketAddr {
+        pub fn new(ip: impl Into<IpAddr>, port: u16) -> Self {
+            Self { ip: ip.into(), port }
+        }
+
+        pub fn ip(&self) -> IpAddr {
+            self.ip
+        }
+
+        pub fn port(&self) -> u16 {
+            self.port
+        }
+
+        pub fn set_port(&mut self, port: u16) {
+            self.port = port;
+        }
+
+        pub fn is_ipv4(&self) -> bool {
+            self.ip.is_ipv4()
+        }
+    }
+
+    impl FromStr for SocketAddr {
+        type Err = AddrParseError;
+
+        fn from_str(s: &str) -> core::result::Result<Self, AddrParseError> {
+            let (host, port) = s.rsplit_once(':').ok_or(AddrParseError)?;
+            if port.is_empty() || !port.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(AddrParseError);
+            }
+            let port = port.parse().map_err(|_| AddrParseError)?;
+            let ip = match host.strip_prefix('[').and_then(|h| h.strip_suffix(']')) {
+                Some(v6) => IpAddr::V6(v6.parse()?),
+                None => IpAddr::V4(host.parse()?),
+            };
+            Ok(Self { ip, port })
+        }
+    }
+
+    impl fmt::Display for SocketAddr {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.ip {
+                IpAddr::V4(a) => write!(f, "{}:{}", a, self.port),
+                IpAddr::V6(a) => write!(f, "[{}]:{}", a, self.port),
+            }
+        }
+    }
+
+    /// Anything that names one socket address
+    pub trait ToSocketAddr {
+        fn to_socket_addr(&self) -> Result<SocketAddr>;
+    }
+
+    impl ToSocketAddr for SocketAddr {
+        fn to_socket_addr(&self) -> Result<SocketAddr> {
+            Ok(*self)
+        }
+    }
+
+    impl ToSocketAddr for str {
+        fn to_socket_addr(&self) -> Result<SocketAddr> {
+            self.parse().map_err(|_| Errno::InvalidInput)
+        }
+    }
+
+    impl ToSocketAddr for &str {
+        fn to_socket_addr(&self) -> Result<SocketAddr> {
+            (*self).to_socket_addr()
+        }
+    }
+
+    impl ToSocketAddr for (&str, u16) {
+        fn to_socket_addr(&self) -> Result<SocketAddr> {
+            let ip: IpAddr = self.0.parse().map_err(|_| Errno::InvalidInput)?;
+            Ok(SocketAddr::new(ip, self.1))
+        }
+    }
+
+    impl<T: Into<IpAddr> + Copy> ToSocketAddr for (T, u16) {
+        fn to_socket_addr(&self) -> Result<SocketAddr> {
+            Ok(SocketAddr::new(self.0, self.1))
+        }
+    }
+
+    // ============================
+    // sockaddr plumbing
+    // ============================
+
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    struct SockaddrIn {
+        family: u16,
+        port: [u8; 2],
+        addr: [u8; 4],
+        zero: [u8; 8],
+    }
+
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    struct SockaddrIn6 {
+        family: u16,
+        port: [u8; 2],
+        flowinfo: u32,
+        addr: [u8; 16],
+        scope_id: u32,
+    }
+
+    /// Big enough for any address family, like `sockaddr_storage`
+    #[repr(C, align(8))]
+    struct SockaddrStorage([u8; 128]);
+
+    #[repr(C)]
+    union RawSockaddr {
+        v4: SockaddrIn,
+        v6: SockaddrIn6,
+    }
+
+    fn to_raw(addr: &SocketAddr) -> (RawSockaddr, u32) {
+        let port = addr.port.to_be_bytes();
+        match addr.ip {
+            IpAddr::V4(ip) => (
+                RawSockaddr { v4: SockaddrIn { family: AF_INET, port, addr: ip.0, zero: [0; 8] } },
+                size_of::<SockaddrIn>() as u32,
+            ),
+            IpAddr::V6(ip) => (
+                RawSockaddr { v6: SockaddrIn6 { family: AF_INET6, port, flowinfo: 0, addr: ip.0, scope_id: 0 } },
+                size_of::<SockaddrIn6>() as u32,
+            ),
+        }
+    }
+
+    fn from_raw(storage: &SockaddrStorage) -> Result<SocketAddr> {
+        let family = u16::from_ne_bytes([storage.0[0], storage.0[1]]);
+        let ptr = storage.0.as_ptr();
+        match family {
+            AF_INET => {
+                let sa = unsafe { (ptr as *const SockaddrIn).read_unaligned() };
+                Ok(SocketAddr::new(Ipv4Addr(sa.addr), u16::from_be_bytes(sa.port)))
+            }
+            AF_INET6 => {
+                let sa = unsafe { (ptr as *const SockaddrIn6).read_unaligned() };
+                Ok(SocketAddr::new(Ipv6Addr(sa.addr), u16::from_be_bytes(sa.port)))
+            }
+            _ => Err(Errno::InvalidInput),
+        }
+    }
+
+    #[repr(C)]
+    struct Timeval {
+        sec: i64,
+        usec: i64,
+    }
+
+    #[repr(C)]
+    struct PollFd {
+        fd: i32,
+        events: i16,
+        revents: i16,
+    }
+
+    #[link(name = "c")]
+    extern "C" {
+        fn socket(domain: i32, ty: i32, protocol: i32) -> i32;
+        fn connect(fd: i32, addr: *const RawSockaddr, len: u32) -> i32;
+        fn bind(fd: i32, addr: *const RawSockaddr, len: u32) -> i32;
+        fn listen(fd: i32, backlog: i32) -> i32;
+        fn accept4(fd: i32, addr: *mut SockaddrStorage, len: *mut u32, flags: i32) -> i32;
+        fn getsockname(fd: i32, addr: *mut SockaddrStorage, len: *mut u32) -> i32;
+        fn getpeername(fd: i32, addr: *mut SockaddrStorage, len: *mut u32) -> i32;
+        fn setsockopt(fd: i32, level: i32, name: i32, value: *const u8, len: u32) -> i32;
+        fn getsockopt(fd: i32, level: i32, name: i32, value: *mut u8, len: *mut u32) -> i32;
+        fn shutdown(fd: i32, how: i32) -> i32;
+        fn send(fd: i32, buf: *const u8, len: usize, flags: i32) -> isize;
+        fn recv(fd: i32, buf: *mut u8, len: usize, flags: i32) -> isize;
+        fn sendto(fd: i32, buf: *const u8, len: usize, flags: i32, addr: *const RawSockaddr, addrlen: u32) -> isize;
+        fn recvfrom(fd: i32, buf: *mut u8, len: usize, flags: i32, addr: *mut SockaddrStorage, addrlen: *mut u32) -> isize;
+        fn ioctl(fd: i32, request: u64, ...) -> i32;
+        fn poll(fds: *mut PollFd, nfds: u64, timeout: i32) -> i32;
+    }
+
+    /// Owned socket descriptor shared by the public types
+    struct Socket {
+        fd: i32,
+    }
+
+    impl Socket {
+        fn new(addr: &SocketAddr, ty: i32) -> Result<Self> {
+            let family = if addr.is_ipv4() { AF_INET } else { AF_INET6 };
+            let fd = cvt(unsafe { socket(family as i32, ty | SOCK_CLOEXEC, 0) })?;
+            Ok(Self { fd })
+        }
+
+        fn set_opt<T>(&self, level: i32, name: i32, value: T) -> Result<()> {
+            let ptr = &value as *const T as *const u8;
+            cvt(unsafe { setsockopt(self.fd, level, name, ptr, size_of::<T>() as u32) }).map(|_| ())
+        }
+
+        fn get_opt_i32(&self, level: i32, name: i32) -> Result<i32> {
+            let mut value = 0i32;
+            let mut len = size_of::<i32>() as u32;
+            cvt(unsafe { getsockopt(self.fd, level, name, &mut value as *mut i32 as *mut u8, &mut len) })?;
+            Ok(value)
+        }
+
+        fn bind(&self, addr: &SocketAddr) -> Result<()> {
+            let (raw, len) = to_raw(addr);
+            cvt(unsafe { bind(self.fd, &raw, len) }).map(|_| ())
+        }
+
+        fn connect(&self, addr: &SocketAddr) -> Result<()> {
+            let (raw, len) = to_raw(addr);
+            loop {
+                match cvt(unsafe { connect(self.fd, &raw, len) }) {
+                    Err(Errno::Interrupted) => continue,
+                    other => return other.map(|_| ()),
+                }
+            }
+        }
+
+        /// Returns `TimedOut` if the socket isn't ready in time
+        fn wait(&self, events: i16, timeout: Duration) -> Result<()> {
+            let mut pfd = PollFd { fd: self.fd, events, revents: 0 };
+            let ms = timeout.as_millis().clamp(1, i32::MAX as u128) as i32;
+            match cvt(unsafe { poll(&mut pfd, 1, ms) })? {
+                0 => Err(Errno::TimedOut),
+                _ => Ok(()),
+            }
+        }
+
+        fn set_timeout(&self, name: i32, timeout: Option<Duration>) -> Result<()> {
+            let tv = match timeout {
+                Some(d) if d.is_zero() => return Err(Errno::InvalidInput),
+                Some(d) => Timeval { sec: d.as_secs() as i64, usec: d.subsec_micros() as i64 },
+                None => Timeval { sec: 0, usec: 0 },
+            };
+            self.set_opt(SOL_SOCKET, name, tv)
+        }
+
+        fn timeout(&self, name: i32) -> Result<Option<Duration>> {
+            let mut tv = Timeval { sec: 0, usec: 0 };
+            let mut len = size_of::<Timeval>() as u32;
+            cvt(unsafe { getsockopt(self.fd, SOL_SOCKET, name, &mut tv as *mut Timeval as *mut u8, &mut len) })?;
+            if tv.sec == 0 && tv.usec == 0 {
+                Ok(None)
+            } else {
+                Ok(Some(Duration::new(tv.sec as u64, tv.usec as u32 * 1000)))
+            }
+        }
+
+        fn set_nonblocking(&self, on: bool) -> Result<()> {
+            let mut flag = on as i32;
+            cvt(unsafe { ioctl(self.fd, FIONBIO, &mut flag as *mut i32) }).map(|_| ())
+        }
+
+        fn local_addr(&self) -> Result<SocketAddr> {
+            let mut storage = SockaddrStorage([0; 128]);
+            let mut len = 128u32;
+            cvt(unsafe { getsockname(self.fd, &mut storage, &mut len) })?;
+            from_raw(&storage)
+        }
+
+        fn peer_addr(&self) -> Result<SocketAddr> {
+            let mut storage = SockaddrStorage([0; 128]);
+            let mut len = 128u32;
+            cvt(unsafe { getpeername(self.fd, &mut storage, &mut len) })?;
+            from_raw(&storage)
+        }
+
+        fn recv(&self, buf: &mut [u8], flags: i32) -> Result<usize> {
+            cvt_size(unsafe { recv(self.fd, buf.as_mut_ptr(), buf.len(), flags) })
+        }
+
+        fn send(&self, buf: &[u8]) -> Result<usize> {
+            cvt_size(unsafe { send(self.fd, buf.as_ptr(), buf.len(), MSG_NOSIGNAL) })
+        }
+
+        /// Pending error from a non-blocking connect, if any
+        fn take_error(&self) -> Result<Option<Errno>> {
+            match self.get_opt_i32(SOL_SOCKET, SO_ERROR)? {
+                0 => Ok(None),
+                code => Ok(Some(Errno::from_raw(code))),
+            }
+        }
+    }
+
+    impl Drop for Socket {
+        fn drop(&mut self) {
+            unsafe { sys::close(self.fd) };
+        }
+    }
+
+    // ============================
+    // TCP
+    // ============================
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum Shutdown {
+        Read,
+        Write,
+        Both,
+    }
+
+    pub struct TcpStream {
+        sock: Socket,
+    }
+
+    impl TcpStream {
+        pub fn connect(addr: impl ToSocketAddr) -> Result<Self> {
+            let addr = addr.to_socket_addr()?;
+            let sock = Socket::new(&addr, SOCK_STREAM)?;
+            sock.connect(&addr)?;
+            Ok(Self { sock })
+        }
+
+        /// Give up with `TimedOut` if the handshake takes too long
+        pub fn connect_timeout(addr: impl ToSocketAddr, timeout: Duration) -> Result<Self> {
+            if timeout.is_zero() {
+                return Err(Errno::InvalidInput);
+            }
+            let addr = addr.to_socket_addr()?;
+            let sock = Socket::new(&addr, SOCK_STREAM)?;
+            sock.set_nonblocking(true)?;
+            match sock.connect(&addr) {
+                Ok(()) => {}
+                Err(Errno::InProgress) => {
+                    sock.wait(POLLOUT, timeout)?;
+                    if let Some(e) = sock.take_error()? {
+                        return Err(e);
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+            sock.set_nonblocking(false)?;
+            Ok(Self { sock })
+        }
+
+        pub fn peer_addr(&self) -> Result<SocketAddr> {
+            self.sock.peer_addr()
+        }
+
+        pub fn local_addr(&self) -> Result<SocketAddr> {
+            self.sock.local_addr()
+        }
+
+        pub fn shutdown(&self, how: Shutdown) -> Result<()> {
+            let how = match how {
+                Shutdown::Read => 0,
+                Shutdown::Write => 1,
+                Shutdown::Both => 2,
+            };
+            cvt(unsafe { shutdown(self.sock.fd, how) }).map(|_| ())
+        }
+
+        /// Reads that wait longer fail with `WouldBlock`; `None` waits forever
+        pub fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+            self.sock.set_timeout(SO_RCVTIMEO, timeout)
+        }
+
+        pub fn set_write_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+            self.sock.set_timeout(SO_SNDTIMEO, timeout)
+        }
+
+        pub fn read_timeout(&self) -> Result<Option<Duration>> {
+            self.sock.timeout(SO_RCVTIMEO)
+        }
+
+        pub fn write_timeout(&self) -> Result<Option<Duration>> {
+            self.sock.timeout(SO_SNDTIMEO)
+        }
+
+        pub fn set_nonblocking(&self, on: bool) -> Result<()> {
+            self.sock.set_nonblocking(on)
+        }
+
+        pub fn set_nodelay(&self, on: bool) -> Result<()> {
+            self.sock.set_opt(IPPROTO_TCP, TCP_NODELAY, on as i32)
+        }
+
+        pub fn nodelay(&self) -> Result<bool> {
+            self.sock.get_opt_i32(IPPROTO_TCP, TCP_NODELAY).map(|v| v != 0)
+        }
+
+        /// Look at incoming data without consuming it
+        pub fn peek(&self, buf: &mut [u8]) -> Result<usize> {
+            self.sock.recv(buf, MSG_PEEK)
+        }
+
+        pub fn take_error(&self) -> Result<Option<Errno>> {
+            self.sock.take_error()
+        }
+
+        pub fn as_raw_fd(&self) -> i32 {
+            self.sock.fd
+        }
+    }
+
+    impl Read for TcpStream {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            self.sock.recv(buf, 0)
+        }
+    }
+
+    impl Write for TcpStream {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            self.sock.send(buf)
+        }
+    }
+
+    impl Read for &TcpStream {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            self.sock.recv(buf, 0)
+        }
+    }
+
+    impl Write for &TcpStream {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            self.sock.send(buf)
+        }
+    }
+
+    pub struct TcpListener {
+        sock: Socket,
+    }
+
+    impl TcpListener {
+        /// Bind and listen; port 0 picks a free port (see `local_addr`)
+        pub fn bind(addr: impl ToSocketAddr) -> Result<Self> {
+            let addr = addr.to_socket_addr()?;
+            let sock = Socket::new(&addr, SOCK_STREAM)?;
+            sock.set_opt(SOL_SOCKET, SO_REUSEADDR, 1i32)?;
+            if !addr.is_ipv4() {
+                sock.set_opt(IPPROTO_IPV6, IPV6_V6ONLY, 1i32)?;
+            }
+            sock.bind(&addr)?;
+            cvt(unsafe { listen(sock.fd, 128) })?;
+            Ok(Self { sock })
+        }
+
+        pub fn accept(&self) -> Result<(TcpStream, SocketAddr)> {
+            let mut storage = SockaddrStorage([0; 128]);
+            loop {
+                let mut len = 128u32;
+                match cvt(unsafe { accept4(self.sock.fd, &mut storage, &mut len, SOCK_CLOEXEC) }) {
+                    Ok(fd) => return Ok((TcpStream { sock: Socket { fd } }, from_raw(&storage)?)),
+                    Err(Errno::Interrupted) => continue,
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        /// Endless stream of `accept` results
+        pub fn incoming(&self) -> Incoming<'_> {
+            Incoming { listener: self }
+        }
+
+        pub fn local_addr(&self) -> Result<SocketAddr> {
+            self.sock.local_addr()
+        }
+
+        pub fn set_nonblocking(&self, on: bool) -> Result<()> {
+            self.sock.set_nonblocking(on)
+        }
+
+        pub fn as_raw_fd(&self) -> i32 {
+            self.sock.fd
+        }
+    }
+
+    pub struct Incoming<'a> {
+        listener: &'a TcpListener,
+    }
+
+    impl Iterator for Incoming<'_> {
+        type Item = Result<TcpStream>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            Some(self.listener.accept().map(|(stream, _)| stream))
+        }
+    }
+
+    // ============================
+    // UDP
+    // ============================
+
+    pub struct UdpSocket {
+        sock: Socket,
+    }
+
+    impl UdpSocket {
+        pub fn bind(addr: impl ToSocketAddr) -> Result<Self> {
+            let addr = addr.to_socket_addr()?;
+            let sock = Socket::new(&addr, SOCK_DGRAM)?;
+            sock.bind(&addr)?;
+            Ok(Self { sock })
+        }
+
+        pub fn send_to(&self, buf: &[u8], addr: impl ToSocketAddr) -> Result<usize> {
+            let (raw, len) = to_raw(&addr.to_socket_addr()?);
+            cvt_size(unsafe { sendto(self.sock.fd, buf.as_ptr(), buf.len(), MSG_NOSIGNAL, &raw, len) })
+        }
+
+        /// Datagrams longer than `buf` are truncated
+        pub fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddr)> {
+            let mut storage = SockaddrStorage([0; 128]);
+            let mut len = 128u32;
+            let n = cvt_size(unsafe { recvfrom(self.sock.fd, buf.as_mut_ptr(), buf.len(), 0, &mut storage, &mut len) })?;
+            Ok((n, from_raw(&storage)?))
+        }
+
+        /// Fix the peer so `send`/`recv` can be used
+        pub fn connect(&self, addr: impl ToSocketAddr) -> Result<()> {
+            self.sock.connect(&addr.to_socket_addr()?)
+        }
+
+        pub fn send(&self, buf: &[u8]) -> Result<usize> {
+            self.sock.send(buf)
+        }
+
+        pub fn recv(&self, buf: &mut [u8]) -> Result<usize> {
+            self.sock.recv(buf, 0)
+        }
+
+        pub fn peek(&self, buf: &mut [u8]) -> Result<usize> {
+            self.sock.recv(buf, MSG_PEEK)
+        }
+
+        pub fn local_addr(&self) -> Result<SocketAddr> {
+            self.sock.local_addr()
+        }
+
+        pub fn peer_addr(&self) -> Result<SocketAddr> {
+            self.sock.peer_addr()
+        }
+
+        pub fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+            self.sock.set_timeout(SO_RCVTIMEO, timeout)
+        }
+
+        pub fn set_write_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+            self.sock.set_timeout(SO_SNDTIMEO, timeout)
+        }
+
+        pub fn set_nonblocking(&self, on: bool) -> Result<()> {
+            self.sock.set_nonblocking(on)
+        }
+
+        pub fn set_broadcast(&self, on: bool) -> Result<()> {
+            self.sock.set_opt(SOL_SOCKET, SO_BROADCAST, on as i32)
+        }
+
+        /// Block until a datagram arrives or `timeout` passes
+        pub fn wait_readable(&self, timeout: Duration) -> Result<()> {
+            self.sock.wait(POLLIN, timeout)
+        }
+
+        pub fn as_raw_fd(&self) -> i32 {
+            self.sock.fd
+        }
+    }
+}
+
+pub mod reactor {
+    use core::cell::RefCell;
+    use core::future::Future;
+    use core::ops::BitOr;
+    use core::pin::Pin;
+    use core::sync::atomic::{AtomicU64, Ordering};
+    use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+    use core::time::Duration;
+
+    use super::fs::File;
+    use super::net::{SocketAddr, TcpListener, TcpStream, UdpSocket};
+    use super::sys::{self, cvt, Timespec};
+    use super::{Errno, Read, Result, Stdin, Write};
+
+    const EPOLLIN: u32 = 0x1;
+    const EPOLLOUT: u32 = 0x4;
+    const EPOLLERR: u32 = 0x8;
+    const EPOLLHUP: u32 = 0x10;
+    const EPOLLRDHUP: u32 = 0x2000;
+    const EPOLLET: u32 = 1 << 31;
+    const EPOLL_CTL_ADD: i32 = 1;
+    const EPOLL_CTL_DEL: i32 = 2;
+    const EPOLL_CTL_MOD: i32 = 3;
+
+    pub const SIGHUP: i32 = 1;
+    pub const SIGINT: i32 = 2;
+    pub const SIGQUIT: i32 = 3;
+    pub const SIGUSR1: i32 = 10;
+    pub const SIGUSR2: i32 = 12;
+    pub const SIGPIPE: i32 = 13;
+    pub const SIGALRM: i32 = 14;
+    pub const SIGTERM: i32 = 15;
+    pub const SIGCHLD: i32 = 17;
+
+    /// x86_64 `struct epoll_event` is packed
+    #[repr(C, packed)]
+    #[derive(Copy, Clone)]
+    struct EpollEvent {
+        events: u32,
+        data: u64,
+    }
+
+    #[repr(C)]
+    struct ITimerSpec {
+        interval: Timespec,
+        value: Timespec,
+    }
+
+    /// glibc `sigset_t`
+    #[repr(C)]
+    struct SigSet([u64; 16]);
+
+    #[link(name = "c")]
+    extern "C" {
+        fn epoll_create1(flags: i32) -> i32;
+        fn epoll_ctl(epfd: i32, op: i32, fd: i32, event: *mut EpollEvent) -> i32;
+        fn epoll_wait(epfd: i32, events: *mut EpollEvent, maxevents: i32, timeout: i32) -> i32;
+        fn timerfd_create(clock: i32, flags: i32) -> i32;
+        fn timerfd_settime(fd: i32, flags: i32, new: *const ITimerSpec, old: *mut ITimerSpec) -> i32;
+        fn eventfd(initval: u32, flags: i32) -> i32;
+        fn signalfd(fd: i32, mask: *const SigSet, flags: i32) -> i32;
+        fn sigprocmask(how: i32, set: *const SigSet, old: *mut SigSet) -> i32;
+    }
+
+    // ============================
+    // Sources and readiness
+    // ============================
+
+    /// Caller-chosen id that comes back with each readiness event
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+    pub struct Token(pub usize);
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct Interest(u32);
+
+    impl Interest {
+        pub const READABLE: Self = Self(EPOLLIN | EPOLLRDHUP);
+        pub const WRITABLE: Self = Self(EPOLLOUT);
+
+        pub fn is_readable(self) -> bool {
+            self.0 & EPOLLIN != 0
+        }
+
+        pub fn is_writable(self) -> bool {
+            self.0 & EPOLLOUT != 0
+        }
+    }
+
+    impl BitOr for Interest {
+        type Output = Self;
+
+        fn bitor(self, rhs: Self) -> Self {
+            Self(self.0 | rhs.0)
+        }
+    }
+
+    /// Anything backed by a file descriptor epoll can watch
+    pub trait Source {
+        fn raw_fd(&self) -> i32;
+    }
+
+    impl Source for i32 {
+        fn raw_fd(&self) -> i32 {
+            *self
+        }
+    }
+
+    impl Source for Stdin {
+        fn raw_fd(&self) -> i32 {
+            0
+        }
+    }
+
+    impl Source for File {
+        fn raw_fd(&self) -> i32 {
+            self.as_raw_fd()
+        }
+    }
+
+    impl Source for TcpStream {
+        fn raw_fd(&self) -> i32 {
+            self.as_raw_fd()
+        }
+    }
+
+    impl Source for TcpListener {
+        fn raw_fd(&self) -> i32 {
+            self.as_raw_fd()
+        }
+    }
+
+    impl Source for UdpSocket {
+        fn raw_fd(&self) -> i32 {
+            self.as_raw_fd()
+        }
+    }
+
+    #[derive(Copy, Clone, Debug)]
+    pub struct Event {
+        token: Token,
+        flags: u32,
+    }
+
+    impl Event {
+        pub fn token(&self) -> Token {
+            self.token
+        }
+
+        /// Also true on hangup/error so the next read reports what happened
+        pub fn is_readable(&self) -> bool {
+            self.flags & (EPOLLIN | EPOLLRDHUP | EPOLLHUP | EPOLLERR) != 0
+        }
+
+        pub fn is_writable(&self) -> bool {
+            self.flags & (EPOLLOUT | EPOLLERR) != 0
+        }
+
+        pub fn is_hangup(&self) -> bool {
+            self.flags & (EPOLLHUP | EPOLLRDHUP) != 0
+        }
+
+        pub fn is_error(&self) -> bool {
+            self.flags & EPOLLERR != 0
+        }
+    }
+
+    /// Room for up to `N` events per `Epoll::wait`
+    pub struct Events<const N: usize> {
+        buf: [EpollEvent; N],
+        len: usize,
+    }
+
+    impl<const N: usize> Events<N> {
+        pub const fn new() -> Self {
+            Self { buf: [EpollEvent { events: 0, data: 0 }; N], len: 0 }
+        }
+
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = Event> + '_ {
+            self.buf[..self.len].iter().map(|e| Event { token: Token(e.data as usize), flags: e.events })
+        }
+    }
+
+    impl<const N: usize> Default for Events<N> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    fn timeout_ms(timeout: Option<Duration>) -> i32 {
+        match timeout {
+            None => -1,
+            // Round up so a 0.5 ms wait doesn't become a busy poll
+            Some(d) => d.as_nanos().div_ceil(1_000_000).min(i32::MAX as u128) as i32,
+        }
+    }
+
+    /// Thin, level-triggered wrapper over an epoll instance
+    pub struct Epoll {
+        fd: i32,
+    }
+
+    impl Epoll {
+        pub fn new() -> Result<Self> {
+            cvt(unsafe { epoll_create1(sys::O_CLOEXEC) }).map(|fd| Self { fd })
+        }
+
+        fn ctl(&self, op: i32, fd: i32, token: Token, flags: u32) -> Result<()> {
+            let mut ev = EpollEvent { events: flags, data: token.0 as u64 };
+            cvt(unsafe { epoll_ctl(self.fd, op, fd, &mut ev) }).map(|_| ())
+        }
+
+        pub fn add(&self, source: &impl Source, token: Token, interest: Interest) -> Result<()> {
+            self.ctl(EPOLL_CTL_ADD, source.raw_fd(), token, interest.0)
+        }
+
+        pub fn modify(&self, source: &impl Source, token: Token, interest: Interest) -> Result<()> {
+            self.ctl(EPOLL_CTL_MOD, source.raw_fd(), token, interest.0)
+        }
+
+        pub fn delete(&self, source: &impl Source) -> Result<()> {
+            self.ctl(EPOLL_CTL_DEL, source.raw_fd(), Token(0), 0)
+        }
+
+        /// Block until something is ready or `timeout` passes (`None` =
+        /// forever). A signal interrupting the wait counts as zero events.
+        pub fn wait<const N: usize>(&self, events: &mut Events<N>, timeout: Option<Duration>) -> Result<usize> {
+            let n = unsafe { epoll_wait(self.fd, events.buf.as_mut_ptr(), N as i32, timeout_ms(timeout)) };
+            events.len = match cvt(n) {
+                Ok(n) => n as usize,
+                Err(Errno::Interrupted) => 0,
+                Err(e) => return Err(e),
+            };
+            Ok(events.len)
+        }
+    }
+
+    impl Drop for Epoll {
+        fn drop(&mut self) {
+            unsafe { sys::close(self.fd) };
+        }
+    }
+
+    /// Non-blocking monotonic timer; readable once it has expired
+    pub struct TimerFd {
+        fd: i32,
+    }
+
+    impl TimerFd {
+        pub fn new() -> Result<Self> {
+            let flags = sys::O_NONBLOCK | sys::O_CLOEXEC;
+            cvt(unsafe { timerfd_create(sys::CLOCK_MONOTONIC, flags) }).map(|fd| Self { fd })
+        }
+
+        /// Fire once after `after`, then every `interval` if given
+        pub fn set(&self, after: Duration, interval: Option<Duration>) -> Result<()> {
+            // An all-zero value would disarm instead
+            let after = after.max(Duration::from_nanos(1));
+            let spec = ITimerSpec {
+                interval: Timespec::from_duration(interval.unwrap_or_default()),
+                value: Timespec::from_duration(after),
+            };
+            cvt(unsafe { timerfd_settime(self.fd, 0, &spec, core::ptr::null_mut()) }).map(|_| ())
+        }
+
+        pub fn disarm(&self) -> Result<()> {
+            let spec = ITimerSpec { interval: Timespec::default(), value: Timespec::default() };
+            cvt(unsafe { timerfd_settime(self.fd, 0, &spec, core::ptr::null_mut()) }).map(|_| ())
+        }
+
+        /// Expirations since the last read; `WouldBlock` if none yet
+        pub fn read(&self) -> Result<u64> {
+            read_u64(self.fd)
+        }
+    }
+
+    /// Counter that wakes a reactor from elsewhere (another thread, a
+    /// signal handler, ...)
+    pub struct EventFd {
+        fd: i32,
+    }
+
+    impl EventFd {
+        pub fn new() -> Result<Self> {
+            let flags = sys::O_NONBLOCK | sys::O_CLOEXEC;
+            cvt(unsafe { eventfd(0, flags) }).map(|fd| Self { fd })
+        }
+
+        pub fn notify(&self, n: u64) -> Result<()> {
+            sys::write_fd(self.fd, &n.to_ne_bytes()).map(|_| ())
+        }
+
+        /// Sum of all notifies since the last take; `WouldBlock` if zero
+        pub fn take(&self) -> Result<u64> {
+            read_u64(self.fd)
+        }
+    }
+
+    /// Delivers the given signals as readable events instead of handlers.
+    /// The signals are blocked for the process while this exists.
+    pub struct SignalFd {
+        fd: i32,
+        mask: [u64; 16],
+    }
+
+    impl SignalFd {
+        pub fn new(signals: &[i32]) -> Result<Self> {
+            let mut set = SigSet([0; 16]);
+            for &sig in signals {
+                if !(1..=64).contains(&sig) {
+                    return Err(Errno::InvalidInput);
+                }
+                set.0[0] |= 1 << (sig - 1);
+            }
+            const SIG_BLOCK: i32 = 0;
+            cvt(unsafe { sigprocmask(SIG_BLOCK, &set, core::ptr::null_mut()) })?;
+            let flags = sys::O_NONBLOCK | sys::O_CLOEXEC;
+            let fd = cvt(unsafe { signalfd(-1, &set, flags) })?;
+            Ok(Self { fd, mask: set.0 })
+        }
+
+        /// Next pending signal number; `WouldBlock` if none
+        pub fn read(&self) -> Result<i32> {
+            // struct signalfd_siginfo, ssi_signo first
+            let mut info = [0u8; 128];
+            sys::read_fd(self.fd, &mut info)?;
+            Ok(u32::from_ne_bytes([info[0], info[1], info[2], info[3]]) as i32)
+        }
+    }
+
+    impl Drop for SignalFd {
+        fn drop(&mut self) {
+            const SIG_UNBLOCK: i32 = 1;
+            unsafe {
+                sys::close(self.fd);
+                sigprocmask(SIG_UNBLOCK, &SigSet(self.mask), core::ptr::null_mut());
+            }
+        }
+    }
+
+    fn read_u64(fd: i32) -> Result<u64> {
+        let mut buf = [0u8; 8];
+        sys::read_fd(fd, &mut buf)?;
+        Ok(u64::from_ne_bytes(buf))
+    }
+
+    macro_rules! fd_source {
+        ($($ty:ty),*) => {$(
+            impl Source for $ty {
+                fn raw_fd(&self) -> i32 {
+                    self.fd
+                }
+            }
+        )*};
+    }
+
+    fd_source!(Epoll, TimerFd, EventFd, SignalFd);
+
+    impl Drop for TimerFd {
+        fn drop(&mut self) {
+            unsafe { sys::close(self.fd) };
+        }
+    }
+
+    impl Drop for EventFd {
+        fn drop(&mut self) {
+            unsafe { sys::close(self.fd) };
+        }
+    }
+
+    // ============================
+    // Timer wheel
+    // ============================
+
+    /// Handle for cancelling a scheduled timer
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct TimerId {
+        index: u32,
+        generation: u32,
+    }
+
+    const NIL: u32 = u32::MAX;
+
+    struct TimerEntry<T> {
+        value: T,
+        rounds: u64,
+        slot: u32,
+        prev: u32,
+        next: u32,
+    }
+
+    enum WheelSlot<T> {
+        Vacant { next_free: u32 },
+        Occupied(TimerEntry<T>),
+    }
+
+    /// Hashed timing wheel: `SLOTS` buckets of one tick each, up to `CAP`
+    /// pending timers. Scheduling and cancelling are O(1); each tick only
+    /// visits the timers hashed into its bucket.
+    pub struct TimerWheel<T, const SLOTS: usize, const CAP: usize> {
+        heads: [u32; SLOTS],
+        entries: [WheelSlot<T>; CAP],
+        generations: [u32; CAP],
+        free_head: u32,
+        current: u64,
+        len: usize,
+    }
+
+    impl<T, const SLOTS: usize, const CAP: usize> TimerWheel<T, SLOTS, CAP> {
+        pub fn new() -> Self {
+            let mut entries = [const { WheelSlot::Vacant { next_free: NIL } }; CAP];
+            for (i, entry) in entries.iter_mut().enumerate() {
+                let next = if i + 1 < CAP { (i + 1) as u32 } else { NIL };
+                *entry = WheelSlot::Vacant { next_free: next };
+            }
+            Self {
+                heads: [NIL; SLOTS],
+                entries,
+                generations: [0; CAP],
+                free_head: if CAP > 0 { 0 } else { NIL },
+                current: 0,
+                len: 0,
+            }
+        }
+
+        /// Ticks processed so far
+        pub fn current_tick(&self) -> u64 {
+            self.current
+        }
+
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        /// Fire `value` after `ticks` more ticks (at least one); hands the
+        /// value back if the wheel is full
+        pub fn schedule(&mut self, ticks: u64, value: T) -> core::result::Result<TimerId, T> {
+            if self.free_head == NIL {
+                return Err(value);
+            }
+            let index = self.free_head;
+            if let WheelSlot::Vacant { next_free } = self.entries[index as usize] {
+                self.free_head = next_free;
+            }
+            let ticks = ticks.max(1);
+            let slot = ((self.current + ticks) % SLOTS as u64) as u32;
+            let rounds = (ticks - 1) / SLOTS as u64;
+            let next = self.heads[slot as usize];
+            if next != NIL {
+                if let WheelSlot::Occupied(e) = &mut self.entries[next as usize] {
+                    e.prev = index;
+                }
+            }
+            self.heads[slot as usize] = index;
+            self.entries[index as usize] = WheelSlot::Occupied(TimerEntry { value, rounds, slot, prev: NIL, next });
+            self.len += 1;
+            Ok(TimerId { index, generation: self.generations[index as usize] })
+        }
+
+        fn unlink(&mut self, index: u32) -> T {
+            let vacant = WheelSlot::Vacant { next_free: self.free_head };
+            let entry = match core::mem::replace(&mut self.entries[index as usize], vacant) {
+                WheelSlot::Occupied(e) => e,
+                WheelSlot::Vacant { .. } => unreachable!(),
+            };
+            if entry.prev == NIL {
+                self.heads[entry.slot as usize] = entry.next;
+            } else if let WheelSlot::Occupied(p) = &mut self.entries[entry.prev as usize] {
+                p.next = entry.next;
+            }
+            if entry.next != NIL {
+                if let WheelSlot::Occupied(n) = &mut self.entries[entry.next as usize] {
+                    n.prev = entry.prev;
+                }
+            }
+            self.generations[index as usize] = self.generations[index as usize].wrapping_add(1);
+            self.free_head = index;
+            self.len -= 1;
+            entry.value
+        }
+
+        /// Remove a pending timer; `None` if it already fired
+        pub fn cancel(&mut self, id: TimerId) -> Option<T> {
+            let index = id.index as usize;
+            if index >= CAP || self.generations[index] != id.generation {
+                return None;
+            }
+            match self.entries[index] {
+                WheelSlot::Occupied(_) => Some(self.unlink(id.index)),
+                WheelSlot::Vacant { .. } => None,
+            }
+        }
+
+        /// Advance one tick, passing every expired value to `fire`
+        pub fn tick(&mut self, mut fire: impl FnMut(T)) {
+            self.current += 1;
+            let slot = (self.current % SLOTS as u64) as usize;
+            let mut cursor = self.heads[slot];
+            while cursor != NIL {
+                let (next, due) = match &mut self.entries[cursor as usize] {
+                    WheelSlot::Occupied(e) if e.rounds == 0 => (e.next, true),
+                    WheelSlot::Occupied(e) => {
+                        e.rounds -= 1;
+                        (e.next, false)
+                    }
+                    WheelSlot::Vacant { .. } => (NIL, false),
+                };
+                if due {
+                    fire(self.unlink(cursor));
+                }
+                cursor = next;
+            }
+        }
+
+        /// Ticks until the next bucket holding a timer (it may still have
+        /// rounds to go, so treat this as a wake-up hint)
+        pub fn ticks_until_next(&self) -> Option<u64> {
+            if self.len == 0 {
+                return None;
+            }
+            (1..=SLOTS as u64).find(|i| self.heads[((self.current + i) % SLOTS as u64) as usize] != NIL)
+        }
+    }
+
+    impl<T, const SLOTS: usize, const CAP: usize> Default for TimerWheel<T, SLOTS, CAP> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    // ============================
+    // Reactor
+    // ============================
+
+    const TICK: Duration = Duration::from_millis(1);
+    const WHEEL_SLOTS: usize = 512;
+
+    struct Registration {
+        fd: i32,
+        readable: bool,
+        writable: bool,
+        read_waker: Option<Waker>,
+        write_waker: Option<Waker>,
+    }
+
+    impl Registration {
+        const EMPTY: Option<Registration> = None;
+    }
+
+    struct Inner<const N: usize> {
+        epoll: Epoll,
+        sources: [Option<Registration>; N],
+        timers: TimerWheel<Waker, WHEEL_SLOTS, N>,
+        origin: Duration,
+    }
+
+    impl<const N: usize> Inner<N> {
+        /// Run the wheel up to the current time, waking expired sleeps
+        fn advance_timers(&mut self) -> bool {
+            let elapsed = now().saturating_sub(self.origin);
+            let mut fired = false;
+            while tick_time(self.timers.current_tick() + 1) <= elapsed {
+                self.timers.tick(|waker| {
+                    fired = true;
+                    waker.wake();
+                });
+            }
+            fired
+        }
+    }
+
+    /// Edge-triggered epoll plus a timer wheel, driving futures: up to `N`
+    /// registered sources and `N` pending sleeps. Registered descriptors
+    /// are switched to non-blocking mode.
+    pub struct Reactor<const N: usize> {
+        inner: RefCell<Inner<N>>,
+    }
+
+    fn now() -> Duration {
+        sys::clock(sys::CLOCK_MONOTONIC)
+    }
+
+    fn tick_time(ticks: u64) -> Duration {
+        Duration::from_nanos(ticks.saturating_mul(TICK.as_nanos() as u64))
+    }
+
+    impl<const N: usize> Reactor<N> {
+        pub fn new() -> Result<Self> {
+            Ok(Self {
+                inner: RefCell::new(Inner {
+                    epoll: Epoll::new()?,
+                    sources: [Registration::EMPTY; N],
+                    timers: TimerWheel::new(),
+                    origin: now(),
+                }),
+            })
+        }
+
+        pub fn register(&self, source: &impl Source) -> Result<Token> {
+            let mut inner = self.inner.borrow_mut();
+            let index = inner.sources.iter().position(Option::is_none).ok_or(Errno::NoBufferSpace)?;
+            let fd = source.raw_fd();
+            sys::set_nonblocking(fd, true)?;
+            let token = Token(index);
+            let flags = Interest::READABLE | Interest::WRITABLE;
+            inner.epoll.ctl(EPOLL_CTL_ADD, fd, token, flags.0 | EPOLLET)?;
+            inner.sources[index] = Some(Registration {
+                fd,
+                readable: false,
+                writable: false,
+                read_waker: None,
+                write_waker: None,
+            });
+            Ok(token)
+        }
+
+        pub fn deregister(&self, token: Token) -> Result<()> {
+            let mut inner = self.inner.borrow_mut();
+            let reg = inner.sources.get_mut(token.0).and_then(Option::take).ok_or(Errno::NotFound)?;
+            inner.epoll.ctl(EPOLL_CTL_DEL, reg.fd, token, 0)
+        }
+
+        /// Ready now, or remember the waker for when it becomes ready
+        pub fn poll_ready(&self, token: Token, interest: Interest, cx: &mut Context) -> Poll<Result<()>> {
+            let mut inner = self.inner.borrow_mut();
+            let Some(Some(reg)) = inner.sources.get_mut(token.0) else {
+                return Poll::Ready(Err(Errno::NotFound));
+            };
+            let (ready, waker) = if interest.is_readable() {
+                (reg.readable, &mut reg.read_waker)
+            } else {
+                (reg.writable, &mut reg.write_waker)
+            };
+            if ready {
+                return Poll::Ready(Ok(()));
+            }
+            *waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+
+        /// Forget readiness after an operation hit `WouldBlock`
+        pub fn clear_ready(&self, token: Token, interest: Interest) {
+            if let Some(Some(reg)) = self.inner.borrow_mut().sources.get_mut(token.0) {
+                if interest.is_readable() {
+                    reg.readable = false;
+                }
+                if interest.is_writable() {
+                    reg.writable = false;
+                }
+            }
+        }
+
+        pub fn ready(&self, token: Token, interest: Interest) -> Ready<'_, N> {
+            Ready { reactor: self, token, interest }
+        }
+
+        /// Run a non-blocking operation until it stops saying `WouldBlock`
+        pub async fn io<T>(&self, token: Token, interest: Interest, mut op: impl FnMut() -> Result<T>) -> Result<T> {
+            loop {
+                match op() {
+                    Err(Errno::WouldBlock) => {
+                        self.clear_ready(token, interest);
+                        self.ready(token, interest).await?;
+                    }
+                    result => return result,
+                }
+            }
+        }
+
+        pub async fn read(&self, token: Token, source: &mut impl Read, buf: &mut [u8]) -> Result<usize> {
+            self.io(token, Interest::READABLE, || source.read(buf)).await
+        }
+
+        pub async fn write(&self, token: Token, source: &mut impl Write, buf: &[u8]) -> Result<usize> {
+            self.io(token, Interest::WRITABLE, || source.write(buf)).await
+        }
+
+        pub async fn write_all(&self, token: Token, source: &mut impl Write, mut buf: &[u8]) -> Result<()> {
+            while !buf.is_empty() {
+                match self.write(token, source, buf).await? {
+                    0 => return Err(Errno::Io),
+                    n => buf = &buf[n..],
+                }
+            }
+            Ok(())
+        }
+
+        /// The accepted stream is not registered yet
+        pub async fn accept(&self, token: Token, listener: &TcpListener) -> Result<(TcpStream, SocketAddr)> {
+            self.io(token, Interest::READABLE, || listener.accept()).await
+        }
+
+        pub async fn recv_from(&self, token: Token, socket: &UdpSocket, buf: &mut [u8]) -> Result<(usize, SocketAddr)> {
+            self.io(token, Interest::READABLE, || socket.recv_from(buf)).await
+        }
+
+        pub async fn send_to(&self, token: Token, socket: &UdpSocket, buf: &[u8], to: SocketAddr) -> Result<usize> {
+            self.io(token, Interest::WRITABLE, || socket.send_to(buf, to)).await
+        }
+
+        /// Wait for a registered `TimerFd`; returns the expiration count
+        pub async fn timer(&self, token: Token, timer: &TimerFd) -> Result<u64> {
+            self.io(token, Interest::READABLE, || timer.read()).await
+        }
+
+        pub async fn signal(&self, token: Token, signals: &SignalFd) -> Result<i32> {
+            self.io(token, Interest::READABLE, || signals.read()).await
+        }
+
+        pub async fn event(&self, token: Token, event: &EventFd) -> Result<u64> {
+            self.io(token, Interest::READABLE, || event.take()).await
+        }
+
+        /// Timer-wheel sleep; no descriptor needed
+        pub fn sleep(&self, duration: Duration) -> Sleep<'_, N> {
+            Sleep { reactor: self, deadline: now() + duration, timer: None }
+        }
+
+        fn schedule(&self, deadline: Duration, waker: Waker) -> core::result::Result<TimerId, Waker> {
+            let mut inner = self.inner.borrow_mut();
+            let due_tick = (deadline.saturating_sub(inner.origin).as_nanos()).div_ceil(TICK.as_nanos()) as u64;
+            let ticks = due_tick.saturating_sub(inner.timers.current_tick());
+            inner.timers.schedule(ticks, waker)
+        }
+
+        fn cancel(&self, id: TimerId) {
+            self.inner.borrow_mut().timers.cancel(id);
+        }
+
+        /// Wait for I/O or the next timer (at most `timeout`), then wake
+        /// whatever became ready. Returns the number of I/O events.
+        pub fn turn(&self, timeout: Option<Duration>) -> Result<usize> {
+            let mut events = Events::<64>::new();
+            let mut inner = self.inner.borrow_mut();
+            // Catch the wheel up first so the wait below isn't measured from
+            // a stale tick; anything that fired means nobody should block
+            let fired = inner.advance_timers();
+            let until_timer = inner.timers.ticks_until_next().map(tick_time);
+            let timeout = if fired { Some(Duration::ZERO) } else { timeout };
+            let timeout = match (timeout, until_timer) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            let n = inner.epoll.wait(&mut events, timeout)?;
+
+            for event in events.iter() {
+                let Some(Some(reg)) = inner.sources.get_mut(event.token().0) else {
+                    continue;
+                };
+                if event.is_readable() {
+                    reg.readable = true;
+                    if let Some(w) = reg.read_waker.take() {
+                        w.wake();
+                    }
+                }
+                if event.is_writable() {
+                    reg.writable = true;
+                    if let Some(w) = reg.write_waker.take() {
+                        w.wake();
+                    }
+                }
+            }
+
+            inner.advance_timers();
+            Ok(n)
+        }
+
+        /// Drop every stored waker, e.g. when an executor shuts down
+        pub fn clear_wakers(&self) {
+            let mut inner = self.inner.borrow_mut();
+            for reg in inner.sources.iter_mut().flatten() {
+                reg.read_waker = None;
+                reg.write_waker = None;
+            }
+            inner.timers = TimerWheel::new();
+        }
+    }
+
+    /// Future from `Reactor::ready`
+    pub struct Ready<'a, const N: usize> {
+        reactor: &'a Reactor<N>,
+        token: Token,
+        interest: Interest,
+    }
+
+    impl<const N: usize> Future for Ready<'_, N> {
+        type Output = Result<()>;
 
-    pub struct File {
-        fd: i32,
+        fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<()>> {
+            self.reactor.poll_ready(self.token, self.interest, cx)
+        }
     }
 
-    impl File {
-        pub unsafe fn open(path: &str, flags: i32) -> Result<Self, i32> {
-            extern "C" {
-                fn open(path: *const u8, flags: i32, ...) -> i32;
-            }
-            
-            let fd = unsafe { open(path.as_ptr(), flags) };
-            if fd < 0 {
-                Result::Err(-fd)
-            } else {
-                Result::Ok(File { fd })
+    /// Future from `Reactor::sleep`
+    pub struct Sleep<'a, const N: usize> {
+        reactor: &'a Reactor<N>,
+        deadline: Duration,
+        timer: Option<TimerId>,
+    }
+
+    impl<const N: usize> Future for Sleep<'_, N> {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+            if let Some(id) = self.timer.take() {
+                self.reactor.cancel(id);
             }
+            if now() >= self.deadline {
+                return Poll::Ready(());
+            }
+            match self.reactor.schedule(self.deadline, cx.waker().clone()) {
+                Ok(id) => self.timer = Some(id),
+                // Wheel full: keep polling rather than never waking
+                Err(waker) => waker.wake(),
+            }
+            Poll::Pending
         }
+    }
 
-        pub fn read(&self, buf: &mut [u8]) -> Result<usize, i32> {
-            extern "C" {
-                fn read(fd: i32, buf: *mut u8, count: usize) -> isize;
+    impl<const N: usize> Drop for Sleep<'_, N> {
+        fn drop(&mut self) {
+            if let Some(id) = self.timer.take() {
+                self.reactor.cancel(id);
             }
-            
-            let result = unsafe { read(self.fd, buf.as_mut_ptr(), buf.len()) };
-            if result < 0 {
-                Result::Err(-(result as i32))
-            } else {
-                Result::Ok(result as usize)
+        }
+    }
+
+    /// Which side of a `select` finished first
+    pub enum Either<A, B> {
+        Left(A),
+        Right(B),
+    }
+
+    /// First of two futures to finish; the other is dropped. Pin them
+    /// with `core::pin::pin!` first.
+    pub fn select<A: Future + Unpin, B: Future + Unpin>(a: A, b: B) -> Select<A, B> {
+        Select { a, b }
+    }
+
+    pub struct Select<A, B> {
+        a: A,
+        b: B,
+    }
+
+    impl<A: Future + Unpin, B: Future + Unpin> Future for Select<A, B> {
+        type Output = Either<A::Output, B::Output>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+            if let Poll::Ready(out) = Pin::new(&mut self.a).poll(cx) {
+                return Poll::Ready(Either::Left(out));
             }
+            if let Poll::Ready(out) = Pin::new(&mut self.b).poll(cx) {
+                return Poll::Ready(Either::Right(out));
+            }
+            Poll::Pending
+        }
+    }
+
+    // ============================
+    // Executor
+    // ============================
+
+    // One bit per task. Wakers carry only their task index, so they stay
+    // valid forever; the price is that executors must not be nested.
+    static READY: AtomicU64 = AtomicU64::new(0);
+
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |data| RawWaker::new(data, &VTABLE),
+        |data| {
+            READY.fetch_or(1 << (data as usize), Ordering::Release);
+        },
+        |data| {
+            READY.fetch_or(1 << (data as usize), Ordering::Release);
+        },
+        |_| {},
+    );
+
+    fn task_waker(index: usize) -> Waker {
+        unsafe { Waker::from_raw(RawWaker::new(index as *const (), &VTABLE)) }
+    }
+
+    /// Drive up to 64 tasks to completion, sleeping in the reactor while
+    /// none of them can make progress
+    pub fn run<const N: usize>(reactor: &Reactor<N>, tasks: &mut [Pin<&mut dyn Future<Output = ()>>]) -> Result<()> {
+        if tasks.len() > u64::BITS as usize {
+            return Err(Errno::InvalidInput);
         }
+        let all = if tasks.len() == 64 { u64::MAX } else { (1u64 << tasks.len()) - 1 };
+        let mut done = 0u64;
+        READY.store(all, Ordering::Release);
 
-        pub fn write(&self, buf: &[u8]) -> Result<usize, i32> {
-            extern "C" {
-                fn write(fd: i32, buf: *const u8, count: usize) -> isize;
+        while done != all {
+            let mut ready = READY.swap(0, Ordering::Acquire) & !done;
+            if ready == 0 {
+                reactor.turn(None)?;
+                continue;
             }
-            
-            let result = unsafe { write(self.fd, buf.as_ptr(), buf.len()) };
-            if result < 0 {
-                Result::Err(-(result as i32))
-            } else {
-                Result::Ok(result as usize)
+            while ready != 0 {
+                let index = ready.trailing_zeros() as usize;
+                ready &= ready - 1;
+                let waker = task_waker(index);
+                let mut cx = Context::from_waker(&waker);
+                if tasks[index].as_mut().poll(&mut cx).is_ready() {
+                    done |= 1 << index;
+                }
+            }
+        }
+        reactor.clear_wakers();
+        Ok(())
+    }
+
+    /// Run one future to completion on the current thread
+    pub fn block_on<F: Future, const N: usize>(reactor: &Reactor<N>, future: F) -> Result<F::Output> {
+        let mut future = core::pin::pin!(future);
+        let waker = task_waker(0);
+        let mut cx = Context::from_waker(&waker);
+        READY.store(1, Ordering::Release);
+        loop {
+            if READY.swap(0, Ordering::Acquire) & 1 != 0 {
+                if let Poll::Ready(out) = future.as_mut().poll(&mut cx) {
+                    reactor.clear_wakers();
+                    return Ok(out);
+                }
+                continue;
+            }
+            reactor.turn(None)?;
+        }
+    }
+}
+
+pub mod time {
+    use core::fmt::{self, Write};
+    use core::ops::{Add, AddAssign, Sub, SubAssign};
+
+    use super::sys::{self, Timespec};
+    use super::Errno;
+
+    pub use core::time::Duration;
+
+    const TIMER_ABSTIME: i32 = 1;
+
+    #[link(name = "c")]
+    extern "C" {
+        fn nanosleep(req: *const Timespec, rem: *mut Timespec) -> i32;
+        fn clock_nanosleep(clock: i32, flags: i32, req: *const Timespec, rem: *mut Timespec) -> i32;
+    }
+
+    // ============================
+    // Clocks
+    // ============================
+
+    /// Monotonic timestamp for measuring intervals; never goes backwards
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct Instant(Duration);
+
+    impl Instant {
+        pub fn now() -> Self {
+            Self(sys::clock(sys::CLOCK_MONOTONIC))
+        }
+
+        /// Zero if `earlier` is actually later
+        pub fn duration_since(&self, earlier: Instant) -> Duration {
+            self.0.saturating_sub(earlier.0)
+        }
+
+        pub fn checked_duration_since(&self, earlier: Instant) -> Option<Duration> {
+            self.0.checked_sub(earlier.0)
+        }
+
+        pub fn elapsed(&self) -> Duration {
+            Instant::now().duration_since(*self)
+        }
+
+        pub fn checked_add(&self, d: Duration) -> Option<Instant> {
+            self.0.checked_add(d).map(Instant)
+        }
+
+        pub fn checked_sub(&self, d: Duration) -> Option<Instant> {
+            self.0.checked_sub(d).map(Instant)
+        }
+    }
+
+    impl Add<Duration> for Instant {
+        type Output = Instant;
+
+        fn add(self, d: Duration) -> Instant {
+            Instant(self.0 + d)
+        }
+    }
+
+    impl AddAssign<Duration> for Instant {
+        fn add_assign(&mut self, d: Duration) {
+            self.0 += d;
+        }
+    }
+
+    impl Sub<Duration> for Instant {
+        type Output = Instant;
+
+        fn sub(self, d: Duration) -> Instant {
+            Instant(self.0.saturating_sub(d))
+        }
+    }
+
+    impl SubAssign<Duration> for Instant {
+        fn sub_assign(&mut self, d: Duration) {
+            *self = *self - d;
+        }
+    }
+
+    impl Sub<Instant> for Instant {
+        type Output = Duration;
+
+        fn sub(self, earlier: Instant) -> Duration {
+            self.duration_since(earlier)
+        }
+    }
+
+    /// Wall-clock time as an offset from the Unix epoch. Can jump when the
+    /// system clock is set; use `Instant` for measuring.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct SystemTime(Duration);
+
+    impl SystemTime {
+        pub const UNIX_EPOCH: SystemTime = SystemTime(Duration::ZERO);
+
+        pub fn now() -> Self {
+            Self(sys::clock(sys::CLOCK_REALTIME))
+        }
+
+        pub const fn from_unix(d: Duration) -> Self {
+            Self(d)
+        }
+
+        pub fn since_epoch(&self) -> Duration {
+            self.0
+        }
+
+        /// Whole seconds since 1970-01-01T00:00:00Z
+        pub fn unix_timestamp(&self) -> i64 {
+            self.0.as_secs() as i64
+        }
+
+        /// `None` if `earlier` is later, e.g. after the clock was set back
+        pub fn duration_since(&self, earlier: SystemTime) -> Option<Duration> {
+            self.0.checked_sub(earlier.0)
+        }
+    }
+
+    impl Add<Duration> for SystemTime {
+        type Output = SystemTime;
+
+        fn add(self, d: Duration) -> SystemTime {
+            SystemTime(self.0 + d)
+        }
+    }
+
+    impl Sub<Duration> for SystemTime {
+        type Output = SystemTime;
+
+        fn sub(self, d: Duration) -> SystemTime {
+            SystemTime(self.0.saturating_sub(d))
+        }
+    }
+
+    /// Seconds since the Unix epoch
+    pub fn current_time() -> u64 {
+        SystemTime::now().since_epoch().as_secs()
+    }
+
+    // ============================
+    // Sleeping
+    // ============================
+
+    /// Sleep for at least `d`, resuming after signal interruptions
+    pub fn sleep(d: Duration) {
+        let mut req = Timespec::from_duration(d);
+        let mut rem = Timespec::default();
+        while unsafe { nanosleep(&req, &mut rem) } != 0 && Errno::last() == Errno::Interrupted {
+            req = rem;
+        }
+    }
+
+    pub fn sleep_ms(ms: u32) {
+        sleep(Duration::from_millis(ms as u64));
+    }
+
+    /// Sleep until the monotonic clock reaches `deadline`. Absolute, so
+    /// repeated calls don't accumulate drift the way relative sleeps do.
+    pub fn sleep_until(deadline: Instant) {
+        let ts = Timespec::from_duration(deadline.0);
+        loop {
+            // Returns the error number directly instead of setting errno
+            let err = unsafe { clock_nanosleep(sys::CLOCK_MONOTONIC, TIMER_ABSTIME, &ts, core::ptr::null_mut()) };
+            if Errno::from_raw(err) != Errno::Interrupted {
+                return;
             }
         }
+    }
+
+    /// How early `sleep_until_precise` hands over from the kernel to spinning
+    const SPIN_SLACK: Duration = Duration::from_micros(500);
 
-        pub fn close(self) -> Result<(), i32> {
-            extern "C" {
-                fn close(fd: i32) -> i32;
+    /// Like `sleep_until`, but spins through the last half millisecond so
+    /// scheduler wake-up latency doesn't make it late. Costs a little CPU.
+    pub fn sleep_until_precise(deadline: Instant) {
+        if let Some(early) = deadline.checked_sub(SPIN_SLACK) {
+            if Instant::now() < early {
+                sleep_until(early);
             }
-            
-            let result = unsafe { close(self.fd) };
-            if result < 0 {
-                Result::Err(-result)
+        }
+        while Instant::now() < deadline {
+            core::hint::spin_loop();
+        }
+    }
+
+    pub fn sleep_precise(d: Duration) {
+        sleep_until_precise(Instant::now() + d);
+    }
+
+    // ============================
+    // Frame pacing
+    // ============================
+
+    /// Caps a render loop at a fixed rate: call `wait` once per frame, it
+    /// sleeps off whatever is left of the frame budget and returns the
+    /// real time since the previous call.
+    ///
+    /// ```text
+    /// let mut limiter = FrameLimiter::new(60);
+    /// loop {
+    ///     let dt = limiter.wait();
+    ///     update(dt);
+    ///     draw();
+    /// }
+    /// ```
+    pub struct FrameLimiter {
+        frame: Duration,
+        next: Instant,
+        last: Instant,
+        delta: Duration,
+        fps: f32,
+    }
+
+    impl FrameLimiter {
+        pub fn new(fps: u32) -> Self {
+            Self::with_period(Duration::from_secs(1) / fps.max(1))
+        }
+
+        pub fn with_period(frame: Duration) -> Self {
+            let now = Instant::now();
+            Self { frame, next: now + frame, last: now, delta: frame, fps: 0.0 }
+        }
+
+        pub fn set_fps(&mut self, fps: u32) {
+            self.frame = Duration::from_secs(1) / fps.max(1);
+        }
+
+        pub fn frame_time(&self) -> Duration {
+            self.frame
+        }
+
+        pub fn wait(&mut self) -> Duration {
+            let now = Instant::now();
+            if now < self.next {
+                sleep_until_precise(self.next);
+                self.next += self.frame;
+            } else if now - self.next > self.frame {
+                // More than a frame behind: start over instead of racing to catch up
+                self.next = now + self.frame;
             } else {
-                Result::Ok(())
+                self.next += self.frame;
             }
+
+            let now = Instant::now();
+            self.delta = now - self.last;
+            self.last = now;
+            let instant_fps = 1.0 / self.delta.as_secs_f32().max(1e-6);
+            self.fps = if self.fps == 0.0 { instant_fps } else { self.fps * 0.9 + instant_fps * 0.1 };
+            self.delta
+        }
+
+        /// Time between the last two `wait` calls
+        pub fn delta(&self) -> Duration {
+            self.delta
+        }
+
+        /// Smoothed frames per second
+        pub fn fps(&self) -> f32 {
+            self.fps
         }
     }
-}
 
-pub mod net {
-    use crate::result::Result;
+    /// Fixed-rate simulation on top of a variable frame rate: feed it each
+    /// frame's delta, then run one update per `step()` that returns true.
+    /// `alpha()` is how far into the next step the leftover time reaches,
+    /// for interpolating what gets drawn.
+    ///
+    /// ```text
+    /// physics.advance_secs(GetFrameTime());
+    /// while physics.step() {
+    ///     simulate(physics.step_secs());
+    /// }
+    /// ```
+    pub struct FixedTimestep {
+        step: Duration,
+        accumulator: Duration,
+        max_steps: u32,
+    }
 
-    pub struct TcpStream {
-        fd: i32,
+    impl FixedTimestep {
+        pub fn new(step: Duration) -> Self {
+            Self { step: step.max(Duration::from_nanos(1)), accumulator: Duration::ZERO, max_steps: 8 }
+        }
+
+        pub fn from_hz(hz: u32) -> Self {
+            Self::new(Duration::from_secs(1) / hz.max(1))
+        }
+
+        pub fn from_secs_f32(step: f32) -> Self {
+            Self::new(Duration::from_secs_f32(step.max(0.0)))
+        }
+
+        /// Most steps one frame may queue; a long stall (window drag,
+        /// breakpoint) drops the excess instead of fast-forwarding
+        pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+            self.max_steps = max_steps.max(1);
+            self
+        }
+
+        pub fn advance(&mut self, dt: Duration) {
+            self.accumulator = (self.accumulator + dt).min(self.step * self.max_steps);
+        }
+
+        pub fn advance_secs(&mut self, dt: f32) {
+            self.advance(Duration::from_secs_f32(dt.max(0.0)));
+        }
+
+        /// Consume one step if enough time has built up
+        pub fn step(&mut self) -> bool {
+            match self.accumulator.checked_sub(self.step) {
+                Some(rest) => {
+                    self.accumulator = rest;
+                    true
+                }
+                None => false,
+            }
+        }
+
+        pub fn step_duration(&self) -> Duration {
+            self.step
+        }
+
+        pub fn step_secs(&self) -> f32 {
+            self.step.as_secs_f32()
+        }
+
+        /// Leftover time as a fraction of one step, in `0.0..1.0`
+        pub fn alpha(&self) -> f32 {
+            self.accumulator.as_secs_f32() / self.step.as_secs_f32()
+        }
+
+        pub fn reset(&mut self) {
+            self.accumulator = Duration::ZERO;
+        }
     }
 
-    impl TcpStream {
-        pub fn connect(addr: &str, port: u16) -> Result<Self, i32> {
-            extern "C" {
-                fn socket(domain: i32, type_: i32, protocol: i32) -> i32;
-                fn connect(fd: i32, addr: *const u8, addrlen: u32) -> i32;
+    // ============================
+    // Calendar
+    // ============================
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum Weekday {
+        Sunday,
+        Monday,
+        Tuesday,
+        Wednesday,
+        Thursday,
+        Friday,
+        Saturday,
+    }
+
+    impl Weekday {
+        const ALL: [Weekday; 7] = [
+            Weekday::Sunday,
+            Weekday::Monday,
+            Weekday::Tuesday,
+            Weekday::Wednesday,
+            Weekday::Thursday,
+            Weekday::Friday,
+            Weekday::Saturday,
+        ];
+
+        pub fn name(self) -> &'static str {
+            ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"][self as usize]
+        }
+
+        pub fn short_name(self) -> &'static str {
+            &self.name()[..3]
+        }
+
+        /// 0 for Sunday through 6 for Saturday
+        pub fn number_from_sunday(self) -> u8 {
+            self as u8
+        }
+    }
+
+    const MONTH_NAMES: [&str; 12] = [
+        "January", "February", "March", "April", "May", "June", "July", "August", "September", "October",
+        "November", "December",
+    ];
+
+    pub fn month_name(month: u8) -> &'static str {
+        MONTH_NAMES[(month.clamp(1, 12) - 1) as usize]
+    }
+
+    pub fn is_leap_year(year: i32) -> bool {
+        year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+    }
+
+    pub fn days_in_month(year: i32, month: u8) -> u8 {
+        match month {
+            2 if is_leap_year(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    // Howard Hinnant's days_from_civil / civil_from_days: exact for the
+    // whole proleptic Gregorian calendar, no tables.
+    fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+        let y = year as i64 - (month <= 2) as i64;
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let m = month as i64;
+        let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146097 + doe - 719468
+    }
+
+    fn civil_from_days(days: i64) -> (i32, u8, u8) {
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + (month <= 2) as i64) as i32;
+        (year, month, day)
+    }
+
+    /// Distance from UTC in seconds, east positive
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+    pub struct UtcOffset(i32);
+
+    impl UtcOffset {
+        pub const UTC: UtcOffset = UtcOffset(0);
+
+        /// `None` outside ±24 hours
+        pub fn from_seconds(seconds: i32) -> Option<Self> {
+            (seconds.abs() < 86_400).then_some(Self(seconds))
+        }
+
+        pub fn from_hm(hours: i32, minutes: i32) -> Option<Self> {
+            let sign = if hours < 0 { -1 } else { 1 };
+            Self::from_seconds(hours * 3600 + sign * minutes.abs() * 60)
+        }
+
+        /// The machine's offset at `at`, read straight from the TZif data in
+        /// /etc/localtime, so `TZ` plays no part. After the file's last
+        /// transition the last offset holds; the POSIX rule some files end
+        /// with isn't applied. UTC if the file is missing or malformed.
+        pub fn local_at(at: SystemTime) -> Self {
+            let mut buf = [0u8; 8192];
+            let Ok(n) = super::fs::read("/etc/localtime", &mut buf) else {
+                return Self::UTC;
+            };
+            tzif_offset(&buf[..n], at.unix_timestamp()).and_then(Self::from_seconds).unwrap_or(Self::UTC)
+        }
+
+        pub fn local() -> Self {
+            Self::local_at(SystemTime::now())
+        }
+
+        pub fn seconds(self) -> i32 {
+            self.0
+        }
+
+        pub fn is_utc(self) -> bool {
+            self.0 == 0
+        }
+    }
+
+    // The UTC offset a TZif file (RFC 8536) gives for unix time `t`, from
+    // its 64-bit block when there is one. Before the first transition
+    // that's local time type 0.
+    fn tzif_offset(data: &[u8], t: i64) -> Option<i32> {
+        // isutcnt, isstdcnt, leapcnt, timecnt, typecnt, charcnt
+        let header = |at: usize| -> Option<[usize; 6]> {
+            let h = data.get(at..at + 44)?;
+            if &h[..4] != b"TZif" {
+                return None;
+            }
+            let mut counts = [0; 6];
+            for (i, count) in counts.iter_mut().enumerate() {
+                *count = u32::from_be_bytes(h[20 + 4 * i..24 + 4 * i].try_into().ok()?) as usize;
+            }
+            Some(counts)
+        };
+        let v1 = header(0)?;
+        let (start, counts, time_size) = if data[4] >= b'2' {
+            // Skip the 32-bit block to reach the second header
+            let [isut, isstd, leap, times, types, chars] = v1;
+            let second = 44 + times * 5 + types * 6 + chars + leap * 8 + isstd + isut;
+            (second + 44, header(second)?, 8)
+        } else {
+            (44, v1, 4)
+        };
+        let [_, _, _, times, types, _] = counts;
+
+        let transitions = data.get(start..start + times * time_size)?;
+        let indices = data.get(start + times * time_size..start + times * (time_size + 1))?;
+        let mut index = 0;
+        for (when, &i) in transitions.chunks_exact(time_size).zip(indices) {
+            let when = match time_size {
+                8 => i64::from_be_bytes(when.try_into().ok()?),
+                _ => i32::from_be_bytes(when.try_into().ok()?) as i64,
+            };
+            if when > t {
+                break;
+            }
+            index = i as usize;
+        }
+        if index >= types {
+            return None;
+        }
+        // ttinfo: utoff i32, isdst u8, desigidx u8
+        let info = start + times * (time_size + 1) + index * 6;
+        Some(i32::from_be_bytes(data.get(info..info + 4)?.try_into().ok()?))
+    }
+
+    /// `+01:00`, `-05:30`
+    impl fmt::Display for UtcOffset {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let sign = if self.0 < 0 { '-' } else { '+' };
+            let abs = self.0.unsigned_abs();
+            write!(f, "{}{:02}:{:02}", sign, abs / 3600, abs / 60 % 60)
+        }
+    }
+
+    /// Broken-down calendar time at a fixed UTC offset
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct DateTime {
+        unix: i64,
+        nanos: u32,
+        offset: UtcOffset,
+        year: i32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    }
+
+    impl DateTime {
+        /// `unix` seconds (negative before 1970) seen from `offset`
+        pub fn from_unix(unix: i64, nanos: u32, offset: UtcOffset) -> Self {
+            let local = unix + offset.0 as i64;
+            let days = local.div_euclid(86_400);
+            let secs = local.rem_euclid(86_400);
+            let (year, month, day) = civil_from_days(days);
+            Self {
+                unix,
+                nanos: nanos.min(999_999_999),
+                offset,
+                year,
+                month,
+                day,
+                hour: (secs / 3600) as u8,
+                minute: (secs / 60 % 60) as u8,
+                second: (secs % 60) as u8,
+            }
+        }
+
+        pub fn from_system(t: SystemTime, offset: UtcOffset) -> Self {
+            let d = t.since_epoch();
+            Self::from_unix(d.as_secs() as i64, d.subsec_nanos(), offset)
+        }
+
+        pub fn now_utc() -> Self {
+            Self::from_system(SystemTime::now(), UtcOffset::UTC)
+        }
+
+        pub fn now_local() -> Self {
+            let now = SystemTime::now();
+            Self::from_system(now, UtcOffset::local_at(now))
+        }
+
+        /// Local wall-clock fields at `offset`; `None` if any is out of range
+        pub fn from_parts(
+            year: i32,
+            month: u8,
+            day: u8,
+            hour: u8,
+            minute: u8,
+            second: u8,
+            offset: UtcOffset,
+        ) -> Option<Self> {
+            if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+                return None;
             }
-            
-            // Simplified - in real implementation you'd need proper socket address setup
-            let fd = unsafe { socket(2, 1, 0) }; // AF_INET, SOCK_STREAM
-            if fd < 0 {
-                return Result::Err(-fd);
+            if hour > 23 || minute > 59 || second > 59 {
+                return None;
             }
+            let local = days_from_civil(year, month, day) * 86_400
+                + hour as i64 * 3600
+                + minute as i64 * 60
+                + second as i64;
+            Some(Self::from_unix(local - offset.0 as i64, 0, offset))
+        }
+
+        /// Same instant seen from another offset
+        pub fn to_offset(self, offset: UtcOffset) -> Self {
+            Self::from_unix(self.unix, self.nanos, offset)
+        }
+
+        pub fn unix_timestamp(&self) -> i64 {
+            self.unix
+        }
+
+        pub fn offset(&self) -> UtcOffset {
+            self.offset
+        }
+
+        pub fn year(&self) -> i32 {
+            self.year
+        }
+
+        /// 1..=12
+        pub fn month(&self) -> u8 {
+            self.month
+        }
+
+        /// 1..=31
+        pub fn day(&self) -> u8 {
+            self.day
+        }
+
+        pub fn hour(&self) -> u8 {
+            self.hour
+        }
+
+        pub fn minute(&self) -> u8 {
+            self.minute
+        }
+
+        pub fn second(&self) -> u8 {
+            self.second
+        }
+
+        pub fn nanosecond(&self) -> u32 {
+            self.nanos
+        }
+
+        pub fn weekday(&self) -> Weekday {
+            let days = days_from_civil(self.year, self.month, self.day);
+            // 1970-01-01 was a Thursday
+            Weekday::ALL[(days + 4).rem_euclid(7) as usize]
+        }
 
-            // Connect logic would go here
-            Result::Ok(TcpStream { fd })
+        /// Day of the year, 1..=366
+        pub fn ordinal(&self) -> u16 {
+            (days_from_civil(self.year, self.month, self.day) - days_from_civil(self.year, 1, 1) + 1) as u16
         }
 
-        pub fn read(&self, buf: &mut [u8]) -> Result<usize, i32> {
-            fs::File { fd: self.fd }.read(buf)
+        /// Seconds since local midnight, with the fraction; handy for
+        /// smoothly sweeping clock hands
+        pub fn seconds_of_day(&self) -> f64 {
+            self.hour as f64 * 3600.0 + self.minute as f64 * 60.0 + self.second as f64 + self.nanos as f64 * 1e-9
         }
 
-        pub fn write(&self, buf: &[u8]) -> Result<usize, i32> {
-            fs::File { fd: self.fd }.write(buf)
+        /// strftime-style formatting:
+        ///
+        /// | spec | meaning                   | spec | meaning                |
+        /// |------|---------------------------|------|------------------------|
+        /// | `%Y` | year                      | `%j` | day of year, 001-366   |
+        /// | `%m` | month, 01-12              | `%a` | `Mon`                  |
+        /// | `%d` | day, 01-31                | `%A` | `Monday`               |
+        /// | `%e` | day, space padded         | `%b` | `Jan`                  |
+        /// | `%H` | hour, 00-23               | `%B` | `January`              |
+        /// | `%I` | hour, 01-12               | `%z` | `+0100`                |
+        /// | `%p` | `AM`/`PM`                 | `%:z`| `+01:00`               |
+        /// | `%M` | minute                    | `%s` | Unix timestamp         |
+        /// | `%S` | second                    | `%3f`| milliseconds (`%6f`, `%9f`) |
+        ///
+        /// `%%` is a literal percent; anything else is copied through.
+        pub fn format<'a>(&'a self, pattern: &'a str) -> Format<'a> {
+            Format { dt: self, pattern }
         }
     }
-}
 
-pub mod time {
-    pub fn sleep_ms(ms: u32) {
-        extern "C" {
-            fn usleep(usec: u32) -> i32;
+    /// RFC 3339: `2024-03-09T14:05:00Z`, `2024-03-09T15:05:00+01:00`
+    impl fmt::Display for DateTime {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                f,
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+                self.year, self.month, self.day, self.hour, self.minute, self.second
+            )?;
+            if self.offset.is_utc() {
+                f.write_char('Z')
+            } else {
+                write!(f, "{}", self.offset)
+            }
         }
-        unsafe { usleep(ms * 1000) };
     }
 
-    pub fn current_time() -> u64 {
-        extern "C" {
-            fn time(tloc: *mut u64) -> u64;
+    /// Returned by `DateTime::format`; does the work in `Display`
+    pub struct Format<'a> {
+        dt: &'a DateTime,
+        pattern: &'a str,
+    }
+
+    impl fmt::Display for Format<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let dt = self.dt;
+            let mut chars = self.pattern.chars();
+            while let Some(c) = chars.next() {
+                if c != '%' {
+                    f.write_char(c)?;
+                    continue;
+                }
+                match chars.next() {
+                    Some('Y') => write!(f, "{:04}", dt.year)?,
+                    Some('m') => write!(f, "{:02}", dt.month)?,
+                    Some('d') => write!(f, "{:02}", dt.day)?,
+                    Some('e') => write!(f, "{:2}", dt.day)?,
+                    Some('H') => write!(f, "{:02}", dt.hour)?,
+                    Some('I') => write!(f, "{:02}", (dt.hour + 11) % 12 + 1)?,
+                    Some('p') => f.write_str(if dt.hour < 12 { "AM" } else { "PM" })?,
+                    Some('M') => write!(f, "{:02}", dt.minute)?,
+                    Some('S') => write!(f, "{:02}", dt.second)?,
+                    Some('j') => write!(f, "{:03}", dt.ordinal())?,
+                    Some('a') => f.write_str(dt.weekday().short_name())?,
+                    Some('A') => f.write_str(dt.weekday().name())?,
+                    Some('b') => f.write_str(&month_name(dt.month)[..3])?,
+                    Some('B') => f.write_str(month_name(dt.month))?,
+                    Some('s') => write!(f, "{}", dt.unix)?,
+                    Some('z') => {
+                        let abs = dt.offset.0.unsigned_abs();
+                        let sign = if dt.offset.0 < 0 { '-' } else { '+' };
+                        write!(f, "{}{:02}{:02}", sign, abs / 3600, abs / 60 % 60)?
+                    }
+                    Some(':') => match chars.next() {
+                        Some('z') => write!(f, "{}", dt.offset)?,
+                        other => {
+                            f.write_str("%:")?;
+                            if let Some(o) = other {
+                                f.write_char(o)?;
+                            }
+                        }
+                    },
+                    Some(d @ ('3' | '6' | '9')) => match chars.next() {
+                        Some('f') => {
+                            let digits = d as u32 - '0' as u32;
+                            let value = dt.nanos / 10u32.pow(9 - digits);
+                            write!(f, "{:0width$}", value, width = digits as usize)?
+                        }
+                        other => {
+                            f.write_char('%')?;
+                            f.write_char(d)?;
+                            if let Some(o) = other {
+                                f.write_char(o)?;
+                            }
+                        }
+                    },
+                    Some('%') => f.write_char('%')?,
+                    Some(other) => {
+                        f.write_char('%')?;
+                        f.write_char(other)?;
+                    }
+                    None => f.write_char('%')?,
+                }
+            }
+            Ok(())
         }
-        unsafe { time(core::ptr::null_mut()) }
     }
 }
@@ -1,66 +1,334 @@
 // io_utils.rs
+// stdout/stderr output for no_std programs. Mount it at the crate root as
+// `mod io2;`: the print macros reach their writer through `$crate::io2`.
+//
+//   println!("{} items, {:.2}% done, flags={:#010b}", n, pct, flags);
+//   print!("{:X}", io::hexdump(&buf));
+//
+// Integers, floats (`{:.3}`), hex (`{:x}`/`{:#X}`), binary (`{:b}`) and
+// padding (`{:>8}`, `{:08}`) all come from `core::fmt`.
 #![no_std]
+#![allow(dead_code)]
 
 pub mod io {
-    use core::fmt;
-    
-    pub struct Writer;
-    
+    use core::fmt::{self, Write};
+
+    #[link(name = "c")]
+    extern "C" {
+        fn write(fd: i32, buf: *const u8, count: usize) -> isize;
+    }
+
+    pub const STDOUT: i32 = 1;
+    pub const STDERR: i32 = 2;
+
+    /// Write every byte, retrying short writes
+    fn write_all(fd: i32, mut bytes: &[u8]) -> fmt::Result {
+        while !bytes.is_empty() {
+            let n = unsafe { write(fd, bytes.as_ptr(), bytes.len()) };
+            if n <= 0 {
+                return Err(fmt::Error);
+            }
+            bytes = &bytes[n as usize..];
+        }
+        Ok(())
+    }
+
+    /// Unbuffered writer; every `write_str` is one syscall
+    pub struct Writer {
+        fd: i32,
+    }
+
     impl Writer {
         pub const fn new() -> Self {
-            Self
+            Self::stdout()
+        }
+
+        pub const fn stdout() -> Self {
+            Self { fd: STDOUT }
+        }
+
+        pub const fn stderr() -> Self {
+            Self { fd: STDERR }
         }
-        
+
+        pub const fn from_fd(fd: i32) -> Self {
+            Self { fd }
+        }
+
+        /// One raw `write`; the result may be short or negative
+        ///
+        /// # Safety
+        /// Sound for any slice, it only forwards to libc.
         pub unsafe fn write_bytes(&self, bytes: &[u8]) -> isize {
-            #[link(name = "c")]
-            extern "C" {
-                fn write(fd: i32, buf: *const u8, count: usize) -> isize;
-            }
-            
-            unsafe { write(1, bytes.as_ptr(), bytes.len()) }
+            unsafe { write(self.fd, bytes.as_ptr(), bytes.len()) }
         }
-        
+
         pub fn write_str(&self, s: &str) -> isize {
             unsafe { self.write_bytes(s.as_bytes()) }
         }
     }
-    
+
+    impl Default for Writer {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl fmt::Write for Writer {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            write_all(self.fd, s.as_bytes())
+        }
+    }
+
+    /// Collects output in an `N`-byte buffer and hands it to the fd in as
+    /// few writes as possible. Flushed when full, on `flush` and on drop;
+    /// with `line_buffered` also after every newline.
+    pub struct BufWriter<const N: usize> {
+        fd: i32,
+        buf: [u8; N],
+        len: usize,
+        line_buffered: bool,
+    }
+
+    impl<const N: usize> BufWriter<N> {
+        pub const fn new(fd: i32) -> Self {
+            Self { fd, buf: [0; N], len: 0, line_buffered: false }
+        }
+
+        pub const fn stdout() -> Self {
+            Self::new(STDOUT)
+        }
+
+        pub const fn stderr() -> Self {
+            Self::new(STDERR)
+        }
+
+        pub const fn line_buffered(mut self) -> Self {
+            self.line_buffered = true;
+            self
+        }
+
+        pub fn write_bytes(&mut self, bytes: &[u8]) -> fmt::Result {
+            if self.len + bytes.len() > N {
+                self.flush()?;
+            }
+            // Too big to ever fit: skip the copy
+            if bytes.len() > N {
+                return write_all(self.fd, bytes);
+            }
+            self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+            self.len += bytes.len();
+            if self.line_buffered && bytes.contains(&b'\n') {
+                self.flush()?;
+            }
+            Ok(())
+        }
+
+        pub fn flush(&mut self) -> fmt::Result {
+            let pending = self.len;
+            self.len = 0;
+            write_all(self.fd, &self.buf[..pending])
+        }
+
+        pub fn buffered(&self) -> &[u8] {
+            &self.buf[..self.len]
+        }
+    }
+
+    impl<const N: usize> fmt::Write for BufWriter<N> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.write_bytes(s.as_bytes())
+        }
+    }
+
+    impl<const N: usize> Drop for BufWriter<N> {
+        fn drop(&mut self) {
+            let _ = self.flush();
+        }
+    }
+
+    /// Backs the print macros: short messages go out in a single write
+    #[doc(hidden)]
+    pub fn _print(fd: i32, args: fmt::Arguments) {
+        let mut out = BufWriter::<256>::new(fd);
+        let _ = out.write_fmt(args);
+    }
+
     /// Basic formatter for no_std environment
     pub struct Formatter<'a> {
         buffer: &'a mut [u8],
         position: usize,
     }
-    
+
     impl<'a> Formatter<'a> {
         pub fn new(buffer: &'a mut [u8]) -> Self {
             Self { buffer, position: 0 }
         }
-        
-        pub fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
+
+        pub fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.buffer[..self.position]).unwrap_or("")
+        }
+
+        pub fn len(&self) -> usize {
+            self.position
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.position == 0
+        }
+
+        pub fn clear(&mut self) {
+            self.position = 0;
+        }
+    }
+
+    impl fmt::Write for Formatter<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
             let bytes = s.as_bytes();
             if self.position + bytes.len() > self.buffer.len() {
                 return Err(fmt::Error);
             }
-            
-            for (i, &byte) in bytes.iter().enumerate() {
-                self.buffer[self.position + i] = byte;
-            }
+            self.buffer[self.position..self.position + bytes.len()].copy_from_slice(bytes);
             self.position += bytes.len();
             Ok(())
         }
-        
-        pub fn write_char(&mut self, c: char) -> Result<(), fmt::Error> {
-            let mut buf = [0u8; 4];
-            let bytes = c.encode_utf8(&mut buf).as_bytes();
-            self.write_str(core::str::from_utf8(bytes).unwrap())
+    }
+
+    // ============================
+    // Byte formatting
+    // ============================
+
+    /// Bytes as space-separated hex pairs: `{}`/`{:x}` lowercase, `{:X}` upper
+    pub struct Hex<'a>(pub &'a [u8]);
+
+    impl Hex<'_> {
+        fn fmt_with(&self, f: &mut fmt::Formatter, upper: bool) -> fmt::Result {
+            for (i, byte) in self.0.iter().enumerate() {
+                if i > 0 {
+                    f.write_char(' ')?;
+                }
+                if upper {
+                    write!(f, "{:02X}", byte)?;
+                } else {
+                    write!(f, "{:02x}", byte)?;
+                }
+            }
+            Ok(())
         }
-        
-        pub fn as_str(&self) -> &str {
-            core::str::from_utf8(&self.buffer[..self.position]).unwrap_or("")
+    }
+
+    impl fmt::Display for Hex<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.fmt_with(f, false)
         }
-        
-        pub fn clear(&mut self) {
-            self.position = 0;
+    }
+
+    impl fmt::LowerHex for Hex<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.fmt_with(f, false)
+        }
+    }
+
+    impl fmt::UpperHex for Hex<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.fmt_with(f, true)
+        }
+    }
+
+    /// `hexdump -C` style listing: offset, 16 bytes, printable ASCII
+    ///
+    /// ```text
+    /// 00000000  48 65 6c 6c 6f 0a                                 |Hello.|
+    /// ```
+    pub struct HexDump<'a> {
+        data: &'a [u8],
+        base: usize,
+    }
+
+    pub fn hexdump(data: &[u8]) -> HexDump<'_> {
+        HexDump { data, base: 0 }
+    }
+
+    impl HexDump<'_> {
+        /// Start the offset column at `base` instead of 0
+        pub fn offset(mut self, base: usize) -> Self {
+            self.base = base;
+            self
+        }
+
+        fn fmt_with(&self, f: &mut fmt::Formatter, upper: bool) -> fmt::Result {
+            for (row, chunk) in self.data.chunks(16).enumerate() {
+                write!(f, "{:08x} ", self.base + row * 16)?;
+                for i in 0..16 {
+                    if i == 8 {
+                        f.write_char(' ')?;
+                    }
+                    match chunk.get(i) {
+                        Some(b) if upper => write!(f, " {:02X}", b)?,
+                        Some(b) => write!(f, " {:02x}", b)?,
+                        None => f.write_str("   ")?,
+                    }
+                }
+                f.write_str("  |")?;
+                for &b in chunk {
+                    let shown = if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' };
+                    f.write_char(shown)?;
+                }
+                f.write_str("|\n")?;
+            }
+            Ok(())
+        }
+    }
+
+    impl fmt::Display for HexDump<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.fmt_with(f, false)
         }
     }
+
+    impl fmt::Debug for HexDump<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.fmt_with(f, false)
+        }
+    }
+
+    impl fmt::UpperHex for HexDump<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.fmt_with(f, true)
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! print {
+    ($($arg:tt)*) => {
+        $crate::io2::io::_print($crate::io2::io::STDOUT, core::format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! println {
+    () => {
+        $crate::print!("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::io2::io::_print($crate::io2::io::STDOUT, core::format_args!("{}\n", core::format_args!($($arg)*)))
+    };
+}
+
+#[macro_export]
+macro_rules! eprint {
+    ($($arg:tt)*) => {
+        $crate::io2::io::_print($crate::io2::io::STDERR, core::format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! eprintln {
+    () => {
+        $crate::eprint!("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::io2::io::_print($crate::io2::io::STDERR, core::format_args!("{}\n", core::format_args!($($arg)*)))
+    };
 }
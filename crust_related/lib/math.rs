@@ -36,7 +36,7 @@ pub mod math {
     /// Least common multiple
     pub fn lcm<T>(a: T, b: T) -> T 
     where
-        T: Numeric + core::ops::Mul<Output = T> + core::ops::Div<Output = T>,
+        T: Numeric + core::ops::Mul<Output = T> + core::ops::Div<Output = T> + core::ops::Rem<Output = T>,
     {
         (a * b) / gcd(a, b)
     }
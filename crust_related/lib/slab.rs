@@ -0,0 +1,702 @@
+// crust_slab/lib.rs
+// Stable storage for game objects, no heap required:
+//
+//   Slab<T, CAP>   slots addressed by generational keys
+//   Arena<N>       bump storage reset wholesale (e.g. once per frame)
+//   ecs            entities plus one sparse set per component type
+//
+// Keys carry a generation, so a key to a removed slot never aliases
+// whatever gets stored there next.
+//
+// Expects `collections` (lib/collections.rs) at the crate root.
+#![no_std]
+#![allow(dead_code)]
+
+use core::fmt;
+
+use crate::collections::vec::Vec;
+pub use crate::collections::CapacityError;
+
+/// Handle to a slab slot; stale once the slot is removed
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Key {
+    index: u32,
+    generation: u32,
+}
+
+impl Key {
+    pub fn index(self) -> usize {
+        self.index as usize
+    }
+
+    pub fn generation(self) -> u32 {
+        self.generation
+    }
+}
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Key({}v{})", self.index, self.generation)
+    }
+}
+
+const NO_SLOT: u32 = u32::MAX;
+
+enum Entry<T> {
+    Occupied(T),
+    Vacant { next_free: u32 },
+}
+
+struct Slot<T> {
+    generation: u32,
+    entry: Entry<T>,
+}
+
+/// Fixed-capacity object pool; insert/remove are O(1) and never move
+/// other elements
+pub struct Slab<T, const CAP: usize> {
+    slots: Vec<Slot<T>, CAP>,
+    free_head: u32,
+    len: usize,
+}
+
+impl<T, const CAP: usize> Slab<T, CAP> {
+    pub const fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free_head: NO_SLOT,
+            len: 0,
+        }
+    }
+
+    pub fn insert(&mut self, value: T) -> Result<Key, CapacityError<T>> {
+        self.insert_with(|_| value).map_err(|f| CapacityError::new(f(Key { index: 0, generation: 0 })))
+    }
+
+    /// Insert a value that needs to know its own key
+    pub fn insert_with<F: FnOnce(Key) -> T>(&mut self, f: F) -> Result<Key, F> {
+        if self.free_head != NO_SLOT {
+            let index = self.free_head;
+            let slot = &mut self.slots[index as usize];
+            if let Entry::Vacant { next_free } = slot.entry {
+                self.free_head = next_free;
+            }
+            let key = Key { index, generation: slot.generation };
+            slot.entry = Entry::Occupied(f(key));
+            self.len += 1;
+            return Ok(key);
+        }
+
+        if self.slots.is_full() {
+            return Err(f);
+        }
+        let key = Key { index: self.slots.len() as u32, generation: 0 };
+        let _ = self.slots.push(Slot { generation: 0, entry: Entry::Occupied(f(key)) });
+        self.len += 1;
+        Ok(key)
+    }
+
+    pub fn remove(&mut self, key: Key) -> Option<T> {
+        if !self.contains(key) {
+            return None;
+        }
+        let slot = &mut self.slots[key.index()];
+        // Bumping the generation is what makes outstanding keys stale
+        slot.generation = slot.generation.wrapping_add(1);
+        let old = core::mem::replace(&mut slot.entry, Entry::Vacant { next_free: self.free_head });
+        self.free_head = key.index;
+        self.len -= 1;
+        match old {
+            Entry::Occupied(value) => Some(value),
+            Entry::Vacant { .. } => None,
+        }
+    }
+
+    pub fn contains(&self, key: Key) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn get(&self, key: Key) -> Option<&T> {
+        match self.slots.get(key.index()) {
+            Some(Slot { generation, entry: Entry::Occupied(value) }) if *generation == key.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, key: Key) -> Option<&mut T> {
+        match self.slots.get_mut(key.index()) {
+            Some(Slot { generation, entry: Entry::Occupied(value) }) if *generation == key.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Borrow two different slots mutably at once
+    pub fn get2_mut(&mut self, a: Key, b: Key) -> Option<(&mut T, &mut T)> {
+        if a.index == b.index || !self.contains(a) || !self.contains(b) {
+            return None;
+        }
+        let (lo, hi) = if a.index < b.index { (a, b) } else { (b, a) };
+        let (head, tail) = self.slots.split_at_mut(hi.index());
+        let (first, second) = match (&mut head[lo.index()].entry, &mut tail[0].entry) {
+            (Entry::Occupied(x), Entry::Occupied(y)) => (x, y),
+            _ => return None,
+        };
+        if a.index < b.index {
+            Some((first, second))
+        } else {
+            Some((second, first))
+        }
+    }
+
+    /// Current key for a slot index, if the slot is occupied
+    pub fn key_at(&self, index: usize) -> Option<Key> {
+        match self.slots.get(index) {
+            Some(Slot { generation, entry: Entry::Occupied(_) }) => Some(Key { index: index as u32, generation: *generation }),
+            _ => None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == CAP
+    }
+
+    pub fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Remove everything; keys handed out before stay stale
+    pub fn clear(&mut self) {
+        self.retain(|_, _| false);
+    }
+
+    pub fn retain<F: FnMut(Key, &mut T) -> bool>(&mut self, mut f: F) {
+        for index in 0..self.slots.len() {
+            if let Some(key) = self.key_at(index) {
+                let keep = match &mut self.slots[index].entry {
+                    Entry::Occupied(value) => f(key, value),
+                    Entry::Vacant { .. } => true,
+                };
+                if !keep {
+                    self.remove(key);
+                }
+            }
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { slots: self.slots.iter().enumerate() }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { slots: self.slots.iter_mut().enumerate() }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = Key> + '_ {
+        self.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
+        self.iter().map(|(_, v)| v)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        self.iter_mut().map(|(_, v)| v)
+    }
+}
+
+impl<T, const CAP: usize> Default for Slab<T, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const CAP: usize> core::ops::Index<Key> for Slab<T, CAP> {
+    type Output = T;
+
+    fn index(&self, key: Key) -> &T {
+        self.get(key).expect("stale slab key")
+    }
+}
+
+impl<T, const CAP: usize> core::ops::IndexMut<Key> for Slab<T, CAP> {
+    fn index_mut(&mut self, key: Key) -> &mut T {
+        self.get_mut(key).expect("stale slab key")
+    }
+}
+
+impl<T: fmt::Debug, const CAP: usize> fmt::Debug for Slab<T, CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T, const CAP: usize> IntoIterator for &'a Slab<T, CAP> {
+    type Item = (Key, &'a T);
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const CAP: usize> IntoIterator for &'a mut Slab<T, CAP> {
+    type Item = (Key, &'a mut T);
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+pub struct Iter<'a, T> {
+    slots: core::iter::Enumerate<core::slice::Iter<'a, Slot<T>>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (Key, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in self.slots.by_ref() {
+            if let Entry::Occupied(value) = &slot.entry {
+                return Some((Key { index: index as u32, generation: slot.generation }, value));
+            }
+        }
+        None
+    }
+}
+
+pub struct IterMut<'a, T> {
+    slots: core::iter::Enumerate<core::slice::IterMut<'a, Slot<T>>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = (Key, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in self.slots.by_ref() {
+            if let Entry::Occupied(value) = &mut slot.entry {
+                return Some((Key { index: index as u32, generation: slot.generation }, value));
+            }
+        }
+        None
+    }
+}
+
+pub mod arena {
+    use core::cell::{Cell, UnsafeCell};
+    use core::marker::PhantomData;
+    use core::mem::{align_of, size_of, MaybeUninit};
+    use core::{ptr, slice, str};
+
+    use super::CapacityError;
+
+    #[repr(C, align(16))]
+    struct Buffer<const N: usize>([MaybeUninit<u8>; N]);
+
+    /// Bump allocator for short-lived values. Allocation only needs `&self`
+    /// so many values can be live at once; `reset` (or dropping a `Scope`)
+    /// frees them all together. Destructors are never run.
+    pub struct Arena<const N: usize> {
+        buffer: UnsafeCell<Buffer<N>>,
+        used: Cell<usize>,
+        peak: Cell<usize>,
+    }
+
+    impl<const N: usize> Arena<N> {
+        pub const fn new() -> Self {
+            Self {
+                buffer: UnsafeCell::new(Buffer([MaybeUninit::uninit(); N])),
+                used: Cell::new(0),
+                peak: Cell::new(0),
+            }
+        }
+
+        /// Reserve raw space for `count` values of `T`
+        fn reserve<T>(&self, count: usize) -> Option<*mut T> {
+            let base = self.buffer.get() as *mut u8;
+            let start = base as usize + self.used.get();
+            let aligned = start.checked_add(align_of::<T>() - 1)? & !(align_of::<T>() - 1);
+            let end = aligned.checked_add(size_of::<T>().checked_mul(count)?)?;
+            if end - (base as usize) > N {
+                return None;
+            }
+            self.used.set(end - base as usize);
+            self.peak.set(self.peak.get().max(self.used.get()));
+            Some(base.wrapping_add(aligned - base as usize) as *mut T)
+        }
+
+        #[allow(clippy::mut_from_ref)]
+        pub fn alloc<T>(&self, value: T) -> Result<&mut T, CapacityError<T>> {
+            match self.reserve::<T>(1) {
+                Some(p) => unsafe {
+                    p.write(value);
+                    Ok(&mut *p)
+                },
+                None => Err(CapacityError::new(value)),
+            }
+        }
+
+        #[allow(clippy::mut_from_ref)]
+        pub fn alloc_slice_copy<T: Copy>(&self, items: &[T]) -> Result<&mut [T], CapacityError> {
+            let p = self.reserve::<T>(items.len()).ok_or(CapacityError::new(()))?;
+            unsafe {
+                ptr::copy_nonoverlapping(items.as_ptr(), p, items.len());
+                Ok(slice::from_raw_parts_mut(p, items.len()))
+            }
+        }
+
+        #[allow(clippy::mut_from_ref)]
+        pub fn alloc_slice_fill_with<T, F: FnMut(usize) -> T>(&self, len: usize, mut f: F) -> Result<&mut [T], CapacityError> {
+            let p = self.reserve::<T>(len).ok_or(CapacityError::new(()))?;
+            unsafe {
+                for i in 0..len {
+                    p.add(i).write(f(i));
+                }
+                Ok(slice::from_raw_parts_mut(p, len))
+            }
+        }
+
+        #[allow(clippy::mut_from_ref)]
+        pub fn alloc_str(&self, s: &str) -> Result<&mut str, CapacityError> {
+            let bytes = self.alloc_slice_copy(s.as_bytes())?;
+            unsafe { Ok(str::from_utf8_unchecked_mut(bytes)) }
+        }
+
+        /// Free everything; `&mut self` proves nothing still borrows it
+        pub fn reset(&mut self) {
+            self.used.set(0);
+        }
+
+        /// Allocations made through the returned scope are released when
+        /// it drops; the arena can be reused right after.
+        pub fn scope(&mut self) -> Scope<'_, N> {
+            Scope::new(self)
+        }
+
+        pub fn used(&self) -> usize {
+            self.used.get()
+        }
+
+        pub fn remaining(&self) -> usize {
+            N - self.used.get()
+        }
+
+        /// High-water mark, handy for sizing `N`
+        pub fn peak(&self) -> usize {
+            self.peak.get()
+        }
+
+        pub fn capacity(&self) -> usize {
+            N
+        }
+    }
+
+    impl<const N: usize> Default for Arena<N> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// Per-frame (or per-anything) view of an arena that rolls back on drop.
+    /// Scopes nest: `scope.scope()` keeps the outer allocations intact.
+    pub struct Scope<'a, const N: usize> {
+        arena: &'a Arena<N>,
+        mark: usize,
+        _exclusive: PhantomData<&'a mut Arena<N>>,
+    }
+
+    impl<'a, const N: usize> Scope<'a, N> {
+        fn new(arena: &'a Arena<N>) -> Self {
+            Self { arena, mark: arena.used.get(), _exclusive: PhantomData }
+        }
+
+        pub fn scope(&mut self) -> Scope<'_, N> {
+            Scope::new(self.arena)
+        }
+
+        #[allow(clippy::mut_from_ref)]
+        pub fn alloc<T>(&self, value: T) -> Result<&mut T, CapacityError<T>> {
+            self.arena.alloc(value)
+        }
+
+        #[allow(clippy::mut_from_ref)]
+        pub fn alloc_slice_copy<T: Copy>(&self, items: &[T]) -> Result<&mut [T], CapacityError> {
+            self.arena.alloc_slice_copy(items)
+        }
+
+        #[allow(clippy::mut_from_ref)]
+        pub fn alloc_slice_fill_with<T, F: FnMut(usize) -> T>(&self, len: usize, f: F) -> Result<&mut [T], CapacityError> {
+            self.arena.alloc_slice_fill_with(len, f)
+        }
+
+        #[allow(clippy::mut_from_ref)]
+        pub fn alloc_str(&self, s: &str) -> Result<&mut str, CapacityError> {
+            self.arena.alloc_str(s)
+        }
+
+        /// Bytes allocated since this scope was opened
+        pub fn used(&self) -> usize {
+            self.arena.used.get() - self.mark
+        }
+
+        pub fn remaining(&self) -> usize {
+            self.arena.remaining()
+        }
+    }
+
+    impl<const N: usize> Drop for Scope<'_, N> {
+        fn drop(&mut self) {
+            self.arena.used.set(self.mark);
+        }
+    }
+}
+
+pub mod ecs {
+    use super::{CapacityError, Key, Slab, Vec};
+
+    /// Entities are bare slab keys: despawning bumps the generation, so
+    /// stored handles to dead entities simply stop resolving.
+    pub type Entity = Key;
+
+    /// Anything holding per-entity data that must be cleared on despawn
+    pub trait ComponentStorage {
+        fn remove_entity(&mut self, entity: Entity);
+    }
+
+    /// Tracks which entities are alive; at most `CAP` at a time
+    pub struct Entities<const CAP: usize> {
+        alive: Slab<(), CAP>,
+    }
+
+    impl<const CAP: usize> Entities<CAP> {
+        pub const fn new() -> Self {
+            Self { alive: Slab::new() }
+        }
+
+        pub fn spawn(&mut self) -> Result<Entity, CapacityError> {
+            self.alive.insert(()).map_err(CapacityError::simplify)
+        }
+
+        /// Kill an entity and drop its components from every storage given
+        pub fn despawn(&mut self, entity: Entity, storages: &mut [&mut dyn ComponentStorage]) -> bool {
+            if self.alive.remove(entity).is_none() {
+                return false;
+            }
+            for storage in storages.iter_mut() {
+                storage.remove_entity(entity);
+            }
+            true
+        }
+
+        pub fn is_alive(&self, entity: Entity) -> bool {
+            self.alive.contains(entity)
+        }
+
+        pub fn len(&self) -> usize {
+            self.alive.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.alive.is_empty()
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+            self.alive.keys()
+        }
+
+        /// Despawn everything, clearing the given storages too
+        pub fn clear(&mut self, storages: &mut [&mut dyn ComponentStorage]) {
+            self.alive.retain(|entity, _| {
+                for storage in storages.iter_mut() {
+                    storage.remove_entity(entity);
+                }
+                false
+            });
+        }
+    }
+
+    impl<const CAP: usize> Default for Entities<CAP> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    const ABSENT: u32 = u32::MAX;
+
+    /// Component storage: `sparse` maps an entity index to a position in
+    /// the packed `dense`/`data` arrays, so iteration touches only entities
+    /// that actually have the component.
+    pub struct SparseSet<T, const CAP: usize> {
+        sparse: [u32; CAP],
+        dense: Vec<Entity, CAP>,
+        data: Vec<T, CAP>,
+    }
+
+    impl<T, const CAP: usize> SparseSet<T, CAP> {
+        pub const fn new() -> Self {
+            Self {
+                sparse: [ABSENT; CAP],
+                dense: Vec::new(),
+                data: Vec::new(),
+            }
+        }
+
+        fn slot(&self, entity: Entity) -> Option<usize> {
+            let pos = *self.sparse.get(entity.index())?;
+            if pos != ABSENT && self.dense[pos as usize] == entity {
+                Some(pos as usize)
+            } else {
+                None
+            }
+        }
+
+        /// Attach (or replace) the component; returns the previous value
+        pub fn insert(&mut self, entity: Entity, value: T) -> Result<Option<T>, CapacityError<T>> {
+            if entity.index() >= CAP {
+                return Err(CapacityError::new(value));
+            }
+            let pos = self.sparse[entity.index()];
+            if pos != ABSENT {
+                let pos = pos as usize;
+                if self.dense[pos] == entity {
+                    return Ok(Some(core::mem::replace(&mut self.data[pos], value)));
+                }
+                // Left behind by an earlier entity in this slot
+                self.dense[pos] = entity;
+                self.data[pos] = value;
+                return Ok(None);
+            }
+            self.data.push(value)?;
+            let _ = self.dense.push(entity);
+            self.sparse[entity.index()] = (self.dense.len() - 1) as u32;
+            Ok(None)
+        }
+
+        pub fn remove(&mut self, entity: Entity) -> Option<T> {
+            let pos = self.slot(entity)?;
+            let last = self.dense.len() - 1;
+            if pos != last {
+                let moved = self.dense[last];
+                self.sparse[moved.index()] = pos as u32;
+            }
+            self.sparse[entity.index()] = ABSENT;
+            self.dense.swap_remove(pos);
+            Some(self.data.swap_remove(pos))
+        }
+
+        pub fn contains(&self, entity: Entity) -> bool {
+            self.slot(entity).is_some()
+        }
+
+        pub fn get(&self, entity: Entity) -> Option<&T> {
+            self.slot(entity).map(|pos| &self.data[pos])
+        }
+
+        pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+            self.slot(entity).map(|pos| &mut self.data[pos])
+        }
+
+        pub fn len(&self) -> usize {
+            self.dense.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.dense.is_empty()
+        }
+
+        pub fn clear(&mut self) {
+            for entity in self.dense.iter() {
+                self.sparse[entity.index()] = ABSENT;
+            }
+            self.dense.clear();
+            self.data.clear();
+        }
+
+        /// Entities with this component, in storage order
+        pub fn entities(&self) -> &[Entity] {
+            &self.dense
+        }
+
+        pub fn values(&self) -> &[T] {
+            &self.data
+        }
+
+        pub fn values_mut(&mut self) -> &mut [T] {
+            &mut self.data
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> + '_ {
+            self.dense.iter().copied().zip(self.data.iter())
+        }
+
+        pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut T)> + '_ {
+            self.dense.iter().copied().zip(self.data.iter_mut())
+        }
+    }
+
+    impl<T, const CAP: usize> Default for SparseSet<T, CAP> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<T, const CAP: usize> ComponentStorage for SparseSet<T, CAP> {
+        fn remove_entity(&mut self, entity: Entity) {
+            self.remove(entity);
+        }
+    }
+
+    // ============================
+    // Joins
+    // ============================
+
+    /// Entities having both components; walks whichever set is smaller
+    pub fn join<'a, A, B, const CAP: usize>(
+        a: &'a SparseSet<A, CAP>,
+        b: &'a SparseSet<B, CAP>,
+    ) -> impl Iterator<Item = (Entity, &'a A, &'a B)> + 'a {
+        let drive_a = a.len() <= b.len();
+        let driver = if drive_a { a.entities() } else { b.entities() };
+        driver.iter().filter_map(move |&e| Some((e, a.get(e)?, b.get(e)?)))
+    }
+
+    pub fn join3<'a, A, B, C, const CAP: usize>(
+        a: &'a SparseSet<A, CAP>,
+        b: &'a SparseSet<B, CAP>,
+        c: &'a SparseSet<C, CAP>,
+    ) -> impl Iterator<Item = (Entity, &'a A, &'a B, &'a C)> + 'a {
+        let driver = if a.len() <= b.len() && a.len() <= c.len() {
+            a.entities()
+        } else if b.len() <= c.len() {
+            b.entities()
+        } else {
+            c.entities()
+        };
+        driver.iter().filter_map(move |&e| Some((e, a.get(e)?, b.get(e)?, c.get(e)?)))
+    }
+
+    /// Like `join`, but the first component is mutable (e.g. position
+    /// updated from velocity)
+    pub fn join_mut<'a, A, B, const CAP: usize>(
+        a: &'a mut SparseSet<A, CAP>,
+        b: &'a SparseSet<B, CAP>,
+    ) -> impl Iterator<Item = (Entity, &'a mut A, &'a B)> + 'a {
+        a.iter_mut().filter_map(move |(e, x)| Some((e, x, b.get(e)?)))
+    }
+
+    pub fn join3_mut<'a, A, B, C, const CAP: usize>(
+        a: &'a mut SparseSet<A, CAP>,
+        b: &'a SparseSet<B, CAP>,
+        c: &'a SparseSet<C, CAP>,
+    ) -> impl Iterator<Item = (Entity, &'a mut A, &'a B, &'a C)> + 'a {
+        a.iter_mut().filter_map(move |(e, x)| Some((e, x, b.get(e)?, c.get(e)?)))
+    }
+}
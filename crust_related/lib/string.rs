@@ -1,63 +1,483 @@
 // crust_string/lib.rs
+// Fixed-capacity UTF-8 string. Derefs to `str`, so `find`, `split`,
+// `trim`, `chars`, `parse` and friends all work on it directly; the
+// methods here are the ones that need to own or grow the buffer.
+//
+// Expects `collections` (lib/collections.rs) at the crate root.
 #![no_std]
+#![allow(dead_code)]
 
-use crate::vec::Vec;
+pub use crate::collections::CapacityError;
+
+use core::borrow::Borrow;
+use core::ffi::CStr;
+use core::fmt::{self, Write};
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut, Range};
+use core::{ptr, str};
+
+/// Why bytes couldn't be turned into a `String`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FromUtf8Error {
+    Capacity,
+    InvalidUtf8,
+}
+
+impl fmt::Display for FromUtf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromUtf8Error::Capacity => f.write_str("insufficient capacity"),
+            FromUtf8Error::InvalidUtf8 => f.write_str("invalid utf-8"),
+        }
+    }
+}
+
+impl From<CapacityError> for FromUtf8Error {
+    fn from(_: CapacityError) -> Self {
+        FromUtf8Error::Capacity
+    }
+}
 
 pub struct String<const CAP: usize> {
-    data: Vec<u8, CAP>,
+    buf: [u8; CAP],
+    len: usize,
 }
 
 impl<const CAP: usize> String<CAP> {
     pub const fn new() -> Self {
-        Self { data: Vec::new() }
+        Self { buf: [0; CAP], len: 0 }
     }
 
-    pub fn from_str(s: &str) -> Result<Self, &'static str> {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self, CapacityError> {
         let mut string = Self::new();
         string.push_str(s)?;
         Ok(string)
     }
 
-    pub fn push_str(&mut self, s: &str) -> Result<(), &'static str> {
-        for byte in s.bytes() {
-            self.data.push(byte)?;
+    pub fn from_utf8(bytes: &[u8]) -> Result<Self, FromUtf8Error> {
+        let s = str::from_utf8(bytes).map_err(|_| FromUtf8Error::InvalidUtf8)?;
+        Ok(Self::from_str(s)?)
+    }
+
+    pub fn from_c_str(s: &CStr) -> Result<Self, FromUtf8Error> {
+        Self::from_utf8(s.to_bytes())
+    }
+
+    /// Copy a nul-terminated C string, e.g. an `argv` entry
+    ///
+    /// # Safety
+    /// `ptr` must be non-null and point to a nul-terminated buffer.
+    pub unsafe fn from_c_ptr(ptr: *const u8) -> Result<Self, FromUtf8Error> {
+        Self::from_c_str(CStr::from_ptr(ptr as *const core::ffi::c_char))
+    }
+
+    pub fn from_i64(n: i64) -> Result<Self, CapacityError> {
+        let mut s = Self::new();
+        s.push_i64(n)?;
+        Ok(s)
+    }
+
+    pub fn from_u64(n: u64) -> Result<Self, CapacityError> {
+        let mut s = Self::new();
+        s.push_u64(n)?;
+        Ok(s)
+    }
+
+    // ============================
+    // Growing and shrinking
+    // ============================
+
+    /// Appends all of `s` or nothing
+    pub fn push_str(&mut self, s: &str) -> Result<(), CapacityError> {
+        if s.len() > self.remaining_capacity() {
+            return Err(CapacityError::new(()));
         }
+        self.buf[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+        self.len += s.len();
         Ok(())
     }
 
-    pub fn push(&mut self, c: char) -> Result<(), &'static str> {
-        let mut buf = [0; 4];
-        let bytes = c.encode_utf8(&mut buf).as_bytes();
-        for &byte in bytes {
-            self.data.push(byte)?;
+    pub fn push(&mut self, c: char) -> Result<(), CapacityError> {
+        self.push_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.chars().next_back()?;
+        self.len -= c.len_utf8();
+        Some(c)
+    }
+
+    /// Insert at byte offset `idx`; panics if it isn't a char boundary
+    pub fn insert(&mut self, idx: usize, c: char) -> Result<(), CapacityError> {
+        self.insert_str(idx, c.encode_utf8(&mut [0; 4]))
+    }
+
+    pub fn insert_str(&mut self, idx: usize, s: &str) -> Result<(), CapacityError> {
+        assert!(self.is_char_boundary(idx), "insert index not on a char boundary");
+        if s.len() > self.remaining_capacity() {
+            return Err(CapacityError::new(()));
         }
+        self.buf.copy_within(idx..self.len, idx + s.len());
+        self.buf[idx..idx + s.len()].copy_from_slice(s.as_bytes());
+        self.len += s.len();
         Ok(())
     }
 
-    pub fn as_str(&self) -> &str {
-        core::str::from_utf8(self.data.as_slice()).unwrap_or("")
+    /// Remove the char starting at byte offset `idx`
+    pub fn remove(&mut self, idx: usize) -> char {
+        let c = self[idx..].chars().next().expect("remove index out of bounds");
+        self.buf.copy_within(idx + c.len_utf8()..self.len, idx);
+        self.len -= c.len_utf8();
+        c
+    }
+
+    /// Shorten to `new_len` bytes; panics if that splits a char
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len < self.len {
+            assert!(self.is_char_boundary(new_len), "truncate index not on a char boundary");
+            self.len = new_len;
+        }
+    }
+
+    pub fn retain<F: FnMut(char) -> bool>(&mut self, mut f: F) {
+        let mut read = 0;
+        let mut write = 0;
+        while read < self.len {
+            let c = self[read..].chars().next().unwrap_or('\0');
+            let n = c.len_utf8();
+            if f(c) {
+                self.buf.copy_within(read..read + n, write);
+                write += n;
+            }
+            read += n;
+        }
+        self.len = write;
+    }
+
+    /// Move everything from byte offset `at` into a new string
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(self.is_char_boundary(at), "split index not on a char boundary");
+        let mut tail = Self::new();
+        let _ = tail.push_str(&self[at..]);
+        self.len = at;
+        tail
     }
 
     pub fn clear(&mut self) {
-        self.data.clear();
+        self.len = 0;
+    }
+
+    // ============================
+    // Owned text operations
+    // ============================
+
+    /// Copy with every `from` replaced by `to`
+    pub fn replace(&self, from: &str, to: &str) -> Result<Self, CapacityError> {
+        self.replacen(from, to, usize::MAX)
+    }
+
+    pub fn replacen(&self, from: &str, to: &str, count: usize) -> Result<Self, CapacityError> {
+        let mut out = Self::new();
+        let mut last = 0;
+        for (start, part) in self.match_indices(from).take(count) {
+            out.push_str(&self[last..start])?;
+            out.push_str(to)?;
+            last = start + part.len();
+        }
+        out.push_str(&self[last..])?;
+        Ok(out)
+    }
+
+    /// Replace in place; leaves `self` untouched if the result won't fit
+    pub fn replace_in_place(&mut self, from: &str, to: &str) -> Result<(), CapacityError> {
+        *self = self.replace(from, to)?;
+        Ok(())
+    }
+
+    pub fn trim_in_place(&mut self) {
+        self.trim_end_in_place();
+        self.trim_start_in_place();
+    }
+
+    pub fn trim_start_in_place(&mut self) {
+        let start = self.len - self.trim_start().len();
+        self.buf.copy_within(start..self.len, 0);
+        self.len -= start;
+    }
+
+    pub fn trim_end_in_place(&mut self) {
+        self.len = self.trim_end().len();
+    }
+
+    /// Unicode-aware; fails if the converted text outgrows `CAP`
+    pub fn to_uppercase(&self) -> Result<Self, CapacityError> {
+        let mut out = Self::new();
+        for c in self.chars().flat_map(char::to_uppercase) {
+            out.push(c)?;
+        }
+        Ok(out)
+    }
+
+    pub fn to_lowercase(&self) -> Result<Self, CapacityError> {
+        let mut out = Self::new();
+        for c in self.chars().flat_map(char::to_lowercase) {
+            out.push(c)?;
+        }
+        Ok(out)
+    }
+
+    // ============================
+    // Char indexing (UTF-8 safe)
+    // ============================
+
+    pub fn char_count(&self) -> usize {
+        self.chars().count()
+    }
+
+    pub fn char_at(&self, n: usize) -> Option<char> {
+        self.chars().nth(n)
+    }
+
+    /// Byte offset of the `n`th char; `n == char_count()` gives `len()`
+    pub fn char_to_byte(&self, n: usize) -> Option<usize> {
+        self.char_indices().map(|(i, _)| i).chain(Some(self.len)).nth(n)
+    }
+
+    /// Substring by char positions rather than bytes
+    pub fn char_slice(&self, range: Range<usize>) -> Option<&str> {
+        let start = self.char_to_byte(range.start)?;
+        let end = self.char_to_byte(range.end)?;
+        self.get(start..end)
+    }
+
+    // ============================
+    // Numbers
+    // ============================
+
+    pub fn push_i64(&mut self, n: i64) -> Result<(), CapacityError> {
+        let mut digits = [0u8; 20];
+        let len = format_u64(n.unsigned_abs(), 10, &mut digits);
+        if n < 0 && len + 1 > self.remaining_capacity() {
+            return Err(CapacityError::new(()));
+        }
+        if n < 0 {
+            let _ = self.push('-');
+        }
+        self.push_ascii(&digits[digits.len() - len..])
+    }
+
+    pub fn push_u64(&mut self, n: u64) -> Result<(), CapacityError> {
+        self.push_radix(n, 10)
+    }
+
+    /// Digits in any base from 2 to 36 (lowercase letters, no prefix)
+    pub fn push_radix(&mut self, n: u64, radix: u32) -> Result<(), CapacityError> {
+        let mut digits = [0u8; 64];
+        let len = format_u64(n, radix, &mut digits);
+        self.push_ascii(&digits[digits.len() - len..])
+    }
+
+    /// Fixed number of decimals, e.g. `push_f64(3.14159, 2)` → "3.14"
+    pub fn push_f64(&mut self, v: f64, decimals: usize) -> Result<(), CapacityError> {
+        let mut tmp = String::<64>::new();
+        write!(tmp, "{:.*}", decimals, v).map_err(|_| CapacityError::new(()))?;
+        self.push_str(&tmp)
+    }
+
+    fn push_ascii(&mut self, bytes: &[u8]) -> Result<(), CapacityError> {
+        self.push_str(unsafe { str::from_utf8_unchecked(bytes) })
+    }
+
+    // ============================
+    // Views
+    // ============================
+
+    pub fn as_str(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(&self.buf[..self.len]) }
+    }
+
+    pub fn as_mut_str(&mut self) -> &mut str {
+        unsafe { str::from_utf8_unchecked_mut(&mut self.buf[..self.len]) }
+    }
+
+    /// Nul-terminated view for C APIs such as raylib's `DrawText`. Needs
+    /// one spare byte; an interior nul ends the C string early.
+    pub fn as_c_str_mut(&mut self) -> Result<&CStr, CapacityError> {
+        if self.len == CAP {
+            return Err(CapacityError::new(()));
+        }
+        self.buf[self.len] = 0;
+        CStr::from_bytes_until_nul(&self.buf[..=self.len]).map_err(|_| CapacityError::new(()))
+    }
+
+    /// Like `as_c_str_mut`, but an empty string when out of room
+    pub fn c_ptr(&mut self) -> *const u8 {
+        match self.as_c_str_mut() {
+            Ok(s) => s.as_ptr() as *const u8,
+            Err(_) => c"".as_ptr() as *const u8,
+        }
     }
 
     pub fn len(&self) -> usize {
-        self.data.len()
+        self.len
     }
 
     pub fn is_empty(&self) -> bool {
-        self.data.is_empty()
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == CAP
     }
 
     pub fn capacity(&self) -> usize {
-        self.data.capacity()
+        CAP
+    }
+
+    pub fn remaining_capacity(&self) -> usize {
+        CAP - self.len
+    }
+}
+
+/// Write `n` in `radix` right-aligned into `out`; returns the digit count
+fn format_u64(mut n: u64, radix: u32, out: &mut [u8]) -> usize {
+    assert!((2..=36).contains(&radix), "radix must be in 2..=36");
+    let mut i = out.len();
+    loop {
+        i -= 1;
+        let d = (n % radix as u64) as u8;
+        out[i] = if d < 10 { b'0' + d } else { b'a' + d - 10 };
+        n /= radix as u64;
+        if n == 0 {
+            break;
+        }
+    }
+    out.len() - i
+}
+
+impl<const CAP: usize> Default for String<CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const CAP: usize> Clone for String<CAP> {
+    fn clone(&self) -> Self {
+        let mut s = Self::new();
+        unsafe { ptr::copy_nonoverlapping(self.buf.as_ptr(), s.buf.as_mut_ptr(), self.len) };
+        s.len = self.len;
+        s
+    }
+}
+
+impl<const CAP: usize> Deref for String<CAP> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const CAP: usize> DerefMut for String<CAP> {
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl<const CAP: usize> AsRef<str> for String<CAP> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<const CAP: usize> AsRef<[u8]> for String<CAP> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const CAP: usize> Borrow<str> for String<CAP> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<const CAP: usize> str::FromStr for String<CAP> {
+    type Err = CapacityError;
+
+    fn from_str(s: &str) -> Result<Self, CapacityError> {
+        String::from_str(s)
+    }
+}
+
+impl<const CAP: usize> TryFrom<&str> for String<CAP> {
+    type Error = CapacityError;
+
+    fn try_from(s: &str) -> Result<Self, CapacityError> {
+        String::from_str(s)
+    }
+}
+
+/// `write!` support; a write that doesn't fit leaves nothing behind
+impl<const CAP: usize> Write for String<CAP> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
+        let len = self.len;
+        fmt::write(self, args).inspect_err(|_| self.len = len)
+    }
+}
+
+impl<const CAP: usize> fmt::Display for String<CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl<const CAP: usize> fmt::Debug for String<CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const A: usize, const B: usize> PartialEq<String<B>> for String<A> {
+    fn eq(&self, other: &String<B>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const CAP: usize> Eq for String<CAP> {}
+
+impl<const CAP: usize> PartialEq<str> for String<CAP> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
     }
 }
 
-impl<const CAP: usize> core::fmt::Display for String<CAP> {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{}", self.as_str())
+impl<const CAP: usize> PartialEq<&str> for String<CAP> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const CAP: usize> PartialOrd for String<CAP> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const CAP: usize> Ord for String<CAP> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<const CAP: usize> Hash for String<CAP> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
     }
 }
 
@@ -72,34 +492,48 @@ impl<'a> Formatter<'a> {
         Self { buffer, pos: 0 }
     }
 
-    pub fn write_str(&mut self, s: &str) -> core::fmt::Result {
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buffer[..self.pos]).unwrap_or("")
+    }
+}
+
+impl Write for Formatter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
         let bytes = s.as_bytes();
         if self.pos + bytes.len() > self.buffer.len() {
-            return Err(core::fmt::Error);
+            return Err(fmt::Error);
         }
         self.buffer[self.pos..self.pos + bytes.len()].copy_from_slice(bytes);
         self.pos += bytes.len();
         Ok(())
     }
+}
 
-    pub fn write_char(&mut self, c: char) -> core::fmt::Result {
-        let mut buf = [0; 4];
-        let s = c.encode_utf8(&mut buf);
-        self.write_str(s)
-    }
+/// Writer that keeps as much of the output as fits, cut at a char
+/// boundary, where `String`'s own `write_fmt` keeps none of it
+pub struct Truncating<'a, const CAP: usize>(pub &'a mut String<CAP>);
 
-    pub fn as_str(&self) -> &str {
-        core::str::from_utf8(&self.buffer[..self.pos]).unwrap_or("")
+impl<const CAP: usize> Write for Truncating<'_, CAP> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut end = s.len().min(self.0.remaining_capacity());
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.0.push_str(&s[..end]).map_err(|_| fmt::Error)?;
+        if end < s.len() {
+            return Err(fmt::Error);
+        }
+        Ok(())
     }
 }
 
+/// `format!` without a heap: yields a `String<256>`, cut short if the
+/// output doesn't fit
 #[macro_export]
 macro_rules! format {
     ($($arg:tt)*) => {{
-        use $crate::string::Formatter;
-        let mut buf = [0u8; 256];
-        let mut formatter = Formatter::new(&mut buf);
-        core::write!(formatter, $($arg)*).unwrap();
-        formatter.as_str()
+        let mut s = $crate::string::String::<256>::new();
+        let _ = core::fmt::write(&mut $crate::string::Truncating(&mut s), core::format_args!($($arg)*));
+        s
     }};
 }
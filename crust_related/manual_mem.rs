@@ -5,10 +5,14 @@
 
 #[link(name = "c")]
 extern "C" {
-    fn write(fd: i32, buf: *const u8, count: usize) -> isize;
     fn exit(status: i32) -> !;
 }
 
+#[allow(unused_attributes)]
+#[path = "lib/io2.rs"]
+mod io2;
+use io2::io::hexdump;
+
 #[no_mangle]
 pub unsafe extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
     let mut array = [0u8; 64];
@@ -16,20 +20,19 @@ pub unsafe extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
     
     // Now mutable borrow is safe
     memset(&mut array, b'A', array_len);
-    print_buffer("After memset:", &array);
+    print!("After memset:\n{:X}", hexdump(&array));
 
     let mut dest = [0u8; 64];
     let copy_len = 32;
     memcpy(&array, &mut dest, copy_len);
-    print_buffer("After memcpy (first 32 bytes):", &dest);
+    print!("After memcpy (first 32 bytes):\n{:X}", hexdump(&dest));
 
     let result = memcmp(&array, &dest, copy_len);
-    let msg: &[u8] = if result == 0 {
-        b"Memory regions are equal\n"
+    if result == 0 {
+        println!("Memory regions are equal");
     } else {
-        b"Memory regions differ\n"
-    };
-    let _ = unsafe { write(1, msg.as_ptr(), msg.len()) };
+        println!("Memory regions differ");
+    }
     0
 }
 
@@ -61,29 +64,6 @@ fn memcmp(a: &[u8], b: &[u8], count: usize) -> i32 {
     0
 }
 
-fn print_buffer(prefix: &str, buffer: &[u8]) {
-    let prefix_bytes = prefix.as_bytes();
-    unsafe {
-        let _ = write(1, prefix_bytes.as_ptr(), prefix_bytes.len());
-        let _ = write(1, b" ".as_ptr(), 1);
-        for &byte in buffer.iter().take(16) {
-            let mut hex_buf = [0u8; 2];
-            let len = byte_to_hex(byte, &mut hex_buf);
-            let _ = write(1, hex_buf.as_ptr(), len);
-            let _ = write(1, b" ".as_ptr(), 1);
-        }
-        let _ = write(1, b"\n".as_ptr(), 1);
-    }
-}
-
-fn byte_to_hex(byte: u8, buffer: &mut [u8]) -> usize {
-    let high = (byte >> 4) & 0x0F;
-    let low = byte & 0x0F;
-    buffer[0] = if high < 10 { b'0' + high } else { b'A' + (high - 10) };
-    buffer[1] = if low < 10 { b'0' + low } else { b'A' + (low - 10) };
-    2
-}
-
 #[panic_handler]
 fn panic(_info: &core::panic::PanicInfo) -> ! {
     unsafe { exit(1); }
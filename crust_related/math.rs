@@ -8,6 +8,15 @@ extern "C" {
     fn exit(status: i32) -> !;
 }
 
+#[allow(unused_attributes)]
+#[path = "lib/collections.rs"]
+mod collections;
+
+#[allow(unused_attributes)]
+#[path = "lib/string.rs"]
+mod string;
+use string::String;
+
 
 #[no_mangle]
 pub unsafe extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
@@ -17,9 +26,8 @@ pub unsafe extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
     let fib_seq = generate_fibonacci();
 
     for (i, &num) in fib_seq.iter().enumerate() {
-        let mut buffer = [0u8; 20];
-        let len = format_number(num, &mut buffer);
-        let _ = write(1, buffer.as_ptr(), len);
+        let text = String::<20>::from_u64(num).unwrap_or_default();
+        let _ = write(1, text.as_ptr(), text.len());
 
         let separator: &[u8] = if i == fib_seq.len() - 1 { b"\n" } else { b", " };
         let _ = write(1, separator.as_ptr(), separator.len());
@@ -42,30 +50,6 @@ fn generate_fibonacci() -> [u64; 15] {
     fib
 }
 
-fn format_number(mut n: u64, buffer: &mut [u8]) -> usize {
-    if n == 0 {
-        buffer[0] = b'0';
-        return 1;
-    }
-
-    let mut i = 0;
-    while n > 0 {
-        if i >= buffer.len() {
-            unsafe { exit(1); }
-        }
-        buffer[i] = b'0' + (n % 10) as u8;
-        n /= 10;
-        i += 1;
-    }
-
-    let len = i;
-    for j in 0..len / 2 {
-        buffer.swap(j, len - 1 - j);
-    }
-
-    len
-}
-
 #[panic_handler]
 fn panic(_info: &core::panic::PanicInfo) -> ! {
     unsafe { exit(1); }
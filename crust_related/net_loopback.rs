@@ -0,0 +1,150 @@
+// build: rustc --edition 2021 -C panic=abort net_loopback.rs -o net_loopback
+// Loopback checks for lib/io.rs's net module: a listener and its clients
+// live in this one process. Prints each step; exits 1 on the first failure.
+#![no_std]
+#![no_main]
+
+#[link(name = "c")]
+extern "C" {
+    fn exit(status: i32) -> !;
+}
+
+#[allow(unused_attributes)]
+#[path = "lib/io.rs"]
+mod io;
+
+#[allow(unused_attributes)]
+#[path = "lib/io2.rs"]
+mod io2;
+
+use core::time::Duration;
+use io::net::{IpAddr, Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use io::{Errno, Read, Write};
+
+fn check(name: &str, ok: bool) {
+    if ok {
+        println!("ok   {}", name);
+    } else {
+        eprintln!("FAIL {}", name);
+        unsafe { exit(1) };
+    }
+}
+
+fn parsing() {
+    let v4: Ipv4Addr = "192.168.1.20".parse().unwrap_or(Ipv4Addr::UNSPECIFIED);
+    check("parse ipv4", v4 == Ipv4Addr::new(192, 168, 1, 20));
+    check("reject bad ipv4", "256.1.1.1".parse::<Ipv4Addr>().is_err() && "1.2.3".parse::<Ipv4Addr>().is_err());
+
+    let v6: Ipv6Addr = "2001:db8::ff00:42:8329".parse().unwrap_or(Ipv6Addr::UNSPECIFIED);
+    check("parse ipv6", v6 == Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0xff00, 0x42, 0x8329));
+    check("parse ::1", "::1".parse::<Ipv6Addr>() == Ok(Ipv6Addr::LOCALHOST));
+    check("reject bad ipv6", "1:::2".parse::<Ipv6Addr>().is_err() && "1:2:3:4:5:6:7:8:9".parse::<Ipv6Addr>().is_err());
+
+    let mut text = [0u8; 64];
+    let mut f = io2::io::Formatter::new(&mut text);
+    let _ = core::fmt::Write::write_fmt(&mut f, format_args!("{}", v6));
+    check("format ipv6", f.as_str() == "2001:db8::ff00:42:8329");
+
+    let sa: Result<SocketAddr, _> = "[::1]:8080".parse();
+    check("parse [v6]:port", sa == Ok(SocketAddr::new(Ipv6Addr::LOCALHOST, 8080)));
+    let sa: Result<SocketAddr, _> = "127.0.0.1:80".parse();
+    check("parse v4:port", sa.map(|a| a.ip()) == Ok(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+}
+
+fn tcp() {
+    let listener = match TcpListener::bind("127.0.0.1:0") {
+        Ok(l) => l,
+        Err(_) => return check("tcp bind", false),
+    };
+    let addr = match listener.local_addr() {
+        Ok(a) => a,
+        Err(_) => return check("tcp local_addr", false),
+    };
+    check("tcp bind to ephemeral port", addr.port() != 0);
+
+    // The kernel finishes the handshake before accept, so one thread is enough
+    let mut client = match TcpStream::connect_timeout(addr, Duration::from_secs(1)) {
+        Ok(c) => c,
+        Err(_) => return check("tcp connect", false),
+    };
+    let (mut server, peer) = match listener.accept() {
+        Ok(pair) => pair,
+        Err(_) => return check("tcp accept", false),
+    };
+    check("peer address matches client", client.local_addr() == Ok(peer));
+
+    let _ = client.set_nodelay(true);
+    check("nodelay", client.nodelay() == Ok(true));
+    check("client write", client.write_all(b"ping").is_ok());
+    let mut buf = [0u8; 16];
+    check("server read", server.read_exact(&mut buf[..4]).is_ok() && &buf[..4] == b"ping");
+    check("server write", server.write_all(b"pong").is_ok());
+    check("client read", client.read(&mut buf) == Ok(4) && &buf[..4] == b"pong");
+
+    let _ = client.set_read_timeout(Some(Duration::from_millis(50)));
+    check("read timeout", client.read(&mut buf) == Err(Errno::WouldBlock));
+    // The kernel rounds to its tick, so only check the ballpark
+    let readback = client.read_timeout().ok().flatten().unwrap_or_default();
+    check("timeout readback", readback.as_millis().abs_diff(50) < 10);
+
+    let _ = listener.set_nonblocking(true);
+    check("nonblocking accept", listener.accept().err() == Some(Errno::WouldBlock));
+
+    let _ = client.shutdown(Shutdown::Write);
+    check("shutdown gives eof", server.read(&mut buf) == Ok(0));
+
+    drop(listener);
+    check("connect to closed port refused", TcpStream::connect(addr).err() == Some(Errno::ConnectionRefused));
+}
+
+fn tcp_v6() {
+    let listener = match TcpListener::bind("[::1]:0") {
+        Ok(l) => l,
+        Err(_) => {
+            println!("skip ipv6 (no ::1 on this host)");
+            return;
+        }
+    };
+    let addr = listener.local_addr().unwrap_or(SocketAddr::new(Ipv6Addr::LOCALHOST, 0));
+    let client = TcpStream::connect(addr);
+    check("ipv6 connect + accept", client.is_ok() && listener.accept().is_ok());
+}
+
+fn udp() {
+    let (a, b) = match (UdpSocket::bind("127.0.0.1:0"), UdpSocket::bind("127.0.0.1:0")) {
+        (Ok(a), Ok(b)) => (a, b),
+        _ => return check("udp bind", false),
+    };
+    let b_addr = b.local_addr().unwrap_or(SocketAddr::new(Ipv4Addr::LOCALHOST, 0));
+    check("udp send_to", a.send_to(b"datagram", b_addr) == Ok(8));
+
+    let mut buf = [0u8; 32];
+    let got = b.recv_from(&mut buf);
+    check("udp recv_from", matches!(got, Ok((8, from)) if Ok(from) == a.local_addr()) && &buf[..8] == b"datagram");
+
+    let _ = b.set_nonblocking(true);
+    check("udp nonblocking recv", b.recv(&mut buf) == Err(Errno::WouldBlock));
+
+    let _ = a.connect(b_addr);
+    let _ = a.send(b"again");
+    check("udp wait_readable", b.wait_readable(Duration::from_millis(500)).is_ok());
+    check("udp connected send", b.recv(&mut buf) == Ok(5));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
+    parsing();
+    tcp();
+    tcp_v6();
+    udp();
+    println!("all network checks passed");
+    0
+}
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    unsafe { exit(1); }
+}
+
+#[no_mangle]
+pub extern "C" fn rust_eh_personality() { loop {} }
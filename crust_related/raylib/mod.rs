@@ -1,7 +1,30 @@
 // raylib/mod.rs - Raylib FFI bindings and constants
+//
+// The whole raylib 5.5 API a program here is likely to want: core (window,
+// drawing, timing, input, camera), shapes, textures, text, models and
+// audio. Everything is `core` only, so `no_std` programs use it as they
+// are. The raw functions are `unsafe` as raylib declares them; `window`
+// wraps the ones with a begin/end pairing in guards that end them on drop:
+//
+//     let mut window = Window::new(800, 600, c"Demo");
+//     while !window.should_close() {
+//         let mut d = window.begin_drawing();
+//         d.clear_background(RAYWHITE);
+//         {
+//             let mut scene = d.begin_mode_3d(camera);
+//             scene.draw_grid(10, 1.0);
+//         }
+//         d.draw_fps(10, 10);
+//     }
+//
+// Programs that also pull in `linalg` or `gp2d_math` get `From` between
+// those vectors and raylib's with `linalg_conversions!(linalg);` or
+// `gp2d_conversions!(math);` at the crate root.
 
 #![allow(dead_code)]
 
+use core::ffi::c_void;
+
 // ============================
 // Types
 // ============================
@@ -12,64 +35,887 @@ pub struct Color {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub struct Vector2 {
     pub x: f32,
     pub y: f32,
 }
 
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Vector3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Vector4 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+pub type Quaternion = Vector4;
+
+impl Vector2 {
+    pub const fn new(x: f32, y: f32) -> Vector2 {
+        Vector2 { x, y }
+    }
+}
+
+impl Vector3 {
+    pub const fn new(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3 { x, y, z }
+    }
+}
+
+/// 4x4 matrix; `mN` is element N of the column-major layout, so the
+/// fields are declared a row at a time
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Matrix {
+    pub m0: f32, pub m4: f32, pub m8: f32, pub m12: f32,
+    pub m1: f32, pub m5: f32, pub m9: f32, pub m13: f32,
+    pub m2: f32, pub m6: f32, pub m10: f32, pub m14: f32,
+    pub m3: f32, pub m7: f32, pub m11: f32, pub m15: f32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rectangle {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Image {
+    pub data: *mut c_void,
+    pub width: i32,
+    pub height: i32,
+    pub mipmaps: i32,
+    pub format: i32,
+}
+
+/// GPU texture; `Texture2D` in raylib
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Texture {
+    pub id: u32,
+    pub width: i32,
+    pub height: i32,
+    pub mipmaps: i32,
+    pub format: i32,
+}
+
+pub type Texture2D = Texture;
+
+/// Framebuffer to draw into with `BeginTextureMode`
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct RenderTexture {
+    pub id: u32,
+    pub texture: Texture,
+    pub depth: Texture,
+}
+
+pub type RenderTexture2D = RenderTexture;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct GlyphInfo {
+    pub value: i32,
+    pub offset_x: i32,
+    pub offset_y: i32,
+    /// 0 means the width of the glyph's rectangle
+    pub advance_x: i32,
+    pub image: Image,
+}
+
+/// Glyph atlas; `glyphs` and `recs` hold `glyph_count` entries each
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Font {
+    pub base_size: i32,
+    pub glyph_count: i32,
+    pub glyph_padding: i32,
+    pub texture: Texture2D,
+    pub recs: *mut Rectangle,
+    pub glyphs: *mut GlyphInfo,
+}
+
+/// Perspective or orthographic camera; `Camera` in raylib
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Camera3D {
+    pub position: Vector3,
+    pub target: Vector3,
+    pub up: Vector3,
+    /// Field of view in degrees, or the view's width when orthographic
+    pub fovy: f32,
+    /// `CAMERA_PERSPECTIVE` or `CAMERA_ORTHOGRAPHIC`
+    pub projection: i32,
+}
+
+pub type Camera = Camera3D;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Camera2D {
+    /// Where `target` appears on screen
+    pub offset: Vector2,
+    pub target: Vector2,
+    /// In degrees
+    pub rotation: f32,
+    pub zoom: f32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Ray {
+    pub position: Vector3,
+    pub direction: Vector3,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct RayCollision {
+    pub hit: bool,
+    pub distance: f32,
+    pub point: Vector3,
+    pub normal: Vector3,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct BoundingBox {
+    pub min: Vector3,
+    pub max: Vector3,
+}
+
+/// Vertex data, in CPU memory and once uploaded also on the GPU
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Mesh {
+    pub vertex_count: i32,
+    pub triangle_count: i32,
+    pub vertices: *mut f32,
+    pub texcoords: *mut f32,
+    pub texcoords2: *mut f32,
+    pub normals: *mut f32,
+    pub tangents: *mut f32,
+    pub colors: *mut u8,
+    pub indices: *mut u16,
+    pub anim_vertices: *mut f32,
+    pub anim_normals: *mut f32,
+    pub bone_ids: *mut u8,
+    pub bone_weights: *mut f32,
+    pub bone_matrices: *mut Matrix,
+    pub bone_count: i32,
+    pub vao_id: u32,
+    pub vbo_id: *mut u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Shader {
+    pub id: u32,
+    pub locs: *mut i32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct MaterialMap {
+    pub texture: Texture2D,
+    pub color: Color,
+    pub value: f32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Material {
+    pub shader: Shader,
+    /// Indexed by the `MATERIAL_MAP_*` constants
+    pub maps: *mut MaterialMap,
+    pub params: [f32; 4],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Transform {
+    pub translation: Vector3,
+    pub rotation: Quaternion,
+    pub scale: Vector3,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct BoneInfo {
+    pub name: [i8; 32],
+    pub parent: i32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Model {
+    pub transform: Matrix,
+    pub mesh_count: i32,
+    pub material_count: i32,
+    pub meshes: *mut Mesh,
+    pub materials: *mut Material,
+    /// Material index of each mesh
+    pub mesh_material: *mut i32,
+    pub bone_count: i32,
+    pub bones: *mut BoneInfo,
+    pub bind_pose: *mut Transform,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct ModelAnimation {
+    pub bone_count: i32,
+    pub frame_count: i32,
+    pub bones: *mut BoneInfo,
+    /// `frame_count` arrays of `bone_count` poses
+    pub frame_poses: *mut *mut Transform,
+    pub name: [i8; 32],
+}
+
+/// Samples in CPU memory
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Wave {
+    pub frame_count: u32,
+    pub sample_rate: u32,
+    /// Bits per sample: 8, 16 or 32
+    pub sample_size: u32,
+    pub channels: u32,
+    pub data: *mut c_void,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct AudioStream {
+    pub buffer: *mut c_void,
+    pub processor: *mut c_void,
+    pub sample_rate: u32,
+    pub sample_size: u32,
+    pub channels: u32,
+}
+
+/// A short sound, decoded up front
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Sound {
+    pub stream: AudioStream,
+    pub frame_count: u32,
+}
+
+/// Audio decoded as it plays; feed it with `UpdateMusicStream` every frame
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Music {
+    pub stream: AudioStream,
+    pub frame_count: u32,
+    pub looping: bool,
+    pub ctx_type: i32,
+    pub ctx_data: *mut c_void,
+}
+
+// RAII guards for the window and the begin/end pairs
+pub mod window;
+#[allow(unused_imports)]
+pub use window::{DrawingScope, Mode2D, Mode3D, Window};
+
+// Immediate-mode widgets drawn with the bindings below
+pub mod ui;
+
+// Text layout shared with the cargo demos, and drawing it with a `Font`
+#[allow(unused_attributes, unused_imports)]
+#[path = "../../packages/text-layout/src/lib.rs"]
+pub mod text_layout;
+pub mod text;
+
 // ============================
 // Color Constants
 // ============================
-pub const RAYWHITE: Color = Color { r: 245, g: 245, b: 245, a: 255 };
-pub const BLACK:    Color = Color { r:   0, g:   0, b:   0, a: 255 };
-pub const RED:      Color = Color { r: 230, g:  41, b:  55, a: 255 };
-pub const BLUE:     Color = Color { r:   0, g: 121, b: 241, a: 255 };
-pub const GREEN:    Color = Color { r:   0, g: 228, b:  48, a: 255 };
-pub const YELLOW:   Color = Color { r: 253, g: 249, b:   0, a: 255 };
-pub const PURPLE:   Color = Color { r: 163, g:  73, b: 164, a: 255 };
-pub const ORANGE:   Color = Color { r: 255, g: 161, b:   0, a: 255 };
-pub const DARKGRAY: Color = Color { r:  80, g:  80, b:  80, a: 255 };
-pub const LIGHTGRAY: Color = Color { r: 200, g: 200, b: 200, a: 255 };
+pub const LIGHTGRAY:  Color = Color { r: 200, g: 200, b: 200, a: 255 };
+pub const GRAY:       Color = Color { r: 130, g: 130, b: 130, a: 255 };
+pub const DARKGRAY:   Color = Color { r:  80, g:  80, b:  80, a: 255 };
+pub const YELLOW:     Color = Color { r: 253, g: 249, b:   0, a: 255 };
+pub const GOLD:       Color = Color { r: 255, g: 203, b:   0, a: 255 };
+pub const ORANGE:     Color = Color { r: 255, g: 161, b:   0, a: 255 };
+pub const PINK:       Color = Color { r: 255, g: 109, b: 194, a: 255 };
+pub const RED:        Color = Color { r: 230, g:  41, b:  55, a: 255 };
+pub const MAROON:     Color = Color { r: 190, g:  33, b:  55, a: 255 };
+pub const GREEN:      Color = Color { r:   0, g: 228, b:  48, a: 255 };
+pub const LIME:       Color = Color { r:   0, g: 158, b:  47, a: 255 };
+pub const DARKGREEN:  Color = Color { r:   0, g: 117, b:  44, a: 255 };
+pub const SKYBLUE:    Color = Color { r: 102, g: 191, b: 255, a: 255 };
+pub const BLUE:       Color = Color { r:   0, g: 121, b: 241, a: 255 };
+pub const DARKBLUE:   Color = Color { r:   0, g:  82, b: 172, a: 255 };
+pub const PURPLE:     Color = Color { r: 200, g: 122, b: 255, a: 255 };
+pub const VIOLET:     Color = Color { r: 135, g:  60, b: 190, a: 255 };
+pub const DARKPURPLE: Color = Color { r: 112, g:  31, b: 126, a: 255 };
+pub const BEIGE:      Color = Color { r: 211, g: 176, b: 131, a: 255 };
+pub const BROWN:      Color = Color { r: 127, g: 106, b:  79, a: 255 };
+pub const DARKBROWN:  Color = Color { r:  76, g:  63, b:  47, a: 255 };
+pub const WHITE:      Color = Color { r: 255, g: 255, b: 255, a: 255 };
+pub const BLACK:      Color = Color { r:   0, g:   0, b:   0, a: 255 };
+pub const BLANK:      Color = Color { r:   0, g:   0, b:   0, a:   0 };
+pub const MAGENTA:    Color = Color { r: 255, g:   0, b: 255, a: 255 };
+pub const RAYWHITE:   Color = Color { r: 245, g: 245, b: 245, a: 255 };
+
+// ============================
+// Enum Constants
+// ============================
+
+// Window flags for `SetConfigFlags`, before `InitWindow`
+pub const FLAG_VSYNC_HINT: u32 = 0x0000_0040;
+pub const FLAG_FULLSCREEN_MODE: u32 = 0x0000_0002;
+pub const FLAG_WINDOW_RESIZABLE: u32 = 0x0000_0004;
+pub const FLAG_WINDOW_UNDECORATED: u32 = 0x0000_0008;
+pub const FLAG_WINDOW_HIDDEN: u32 = 0x0000_0080;
+pub const FLAG_MSAA_4X_HINT: u32 = 0x0000_0020;
+pub const FLAG_WINDOW_HIGHDPI: u32 = 0x0000_2000;
+
+// Trace log levels
+pub const LOG_ALL: i32 = 0;
+pub const LOG_INFO: i32 = 3;
+pub const LOG_WARNING: i32 = 4;
+pub const LOG_ERROR: i32 = 5;
+pub const LOG_NONE: i32 = 7;
+
+// Camera modes for `UpdateCamera`, and projections
+pub const CAMERA_CUSTOM: i32 = 0;
+pub const CAMERA_FREE: i32 = 1;
+pub const CAMERA_ORBITAL: i32 = 2;
+pub const CAMERA_FIRST_PERSON: i32 = 3;
+pub const CAMERA_THIRD_PERSON: i32 = 4;
+pub const CAMERA_PERSPECTIVE: i32 = 0;
+pub const CAMERA_ORTHOGRAPHIC: i32 = 1;
+
+// Texture filters, for fonts drawn at other than their loaded size
+pub const TEXTURE_FILTER_POINT: i32 = 0;
+pub const TEXTURE_FILTER_BILINEAR: i32 = 1;
+pub const TEXTURE_FILTER_TRILINEAR: i32 = 2;
+
+pub const TEXTURE_WRAP_REPEAT: i32 = 0;
+pub const TEXTURE_WRAP_CLAMP: i32 = 1;
+
+pub const PIXELFORMAT_UNCOMPRESSED_GRAYSCALE: i32 = 1;
+pub const PIXELFORMAT_UNCOMPRESSED_R8G8B8: i32 = 4;
+pub const PIXELFORMAT_UNCOMPRESSED_R8G8B8A8: i32 = 7;
+
+pub const BLEND_ALPHA: i32 = 0;
+pub const BLEND_ADDITIVE: i32 = 1;
+pub const BLEND_MULTIPLIED: i32 = 2;
+
+pub const MATERIAL_MAP_ALBEDO: i32 = 0;
+pub const MATERIAL_MAP_METALNESS: i32 = 1;
+pub const MATERIAL_MAP_NORMAL: i32 = 2;
+
+pub const MOUSE_CURSOR_DEFAULT: i32 = 0;
+pub const MOUSE_CURSOR_ARROW: i32 = 1;
+pub const MOUSE_CURSOR_IBEAM: i32 = 2;
+pub const MOUSE_CURSOR_POINTING_HAND: i32 = 4;
 
 // ============================
 // Input Constants
 // ============================
 pub const MOUSE_LEFT: i32 = 0;
 pub const MOUSE_RIGHT: i32 = 1;
+pub const MOUSE_MIDDLE: i32 = 2;
+
+pub const KEY_NULL: i32 = 0;
+pub const KEY_APOSTROPHE: i32 = 39;
+pub const KEY_COMMA: i32 = 44;
+pub const KEY_MINUS: i32 = 45;
+pub const KEY_PERIOD: i32 = 46;
+pub const KEY_SLASH: i32 = 47;
+pub const KEY_ZERO: i32 = 48;
+pub const KEY_ONE: i32 = 49;
+pub const KEY_TWO: i32 = 50;
+pub const KEY_THREE: i32 = 51;
+pub const KEY_FOUR: i32 = 52;
+pub const KEY_FIVE: i32 = 53;
+pub const KEY_SIX: i32 = 54;
+pub const KEY_SEVEN: i32 = 55;
+pub const KEY_EIGHT: i32 = 56;
+pub const KEY_NINE: i32 = 57;
+pub const KEY_SEMICOLON: i32 = 59;
+pub const KEY_EQUAL: i32 = 61;
+pub const KEY_A: i32 = 65;
+pub const KEY_B: i32 = 66;
+pub const KEY_C: i32 = 67;
+pub const KEY_D: i32 = 68;
+pub const KEY_E: i32 = 69;
+pub const KEY_F: i32 = 70;
+pub const KEY_G: i32 = 71;
+pub const KEY_H: i32 = 72;
+pub const KEY_I: i32 = 73;
+pub const KEY_J: i32 = 74;
+pub const KEY_K: i32 = 75;
+pub const KEY_L: i32 = 76;
+pub const KEY_M: i32 = 77;
+pub const KEY_N: i32 = 78;
+pub const KEY_O: i32 = 79;
+pub const KEY_P: i32 = 80;
+pub const KEY_Q: i32 = 81;
 pub const KEY_R: i32 = 82;
+pub const KEY_S: i32 = 83;
+pub const KEY_T: i32 = 84;
+pub const KEY_U: i32 = 85;
+pub const KEY_V: i32 = 86;
+pub const KEY_W: i32 = 87;
+pub const KEY_X: i32 = 88;
+pub const KEY_Y: i32 = 89;
+pub const KEY_Z: i32 = 90;
 pub const KEY_SPACE: i32 = 32;
-pub const KEY_C: i32 = 67;
-pub const KEY_UP: i32 = 265;
-pub const KEY_DOWN: i32 = 264;
-pub const KEY_LEFT: i32 = 263;
+pub const KEY_ESCAPE: i32 = 256;
+pub const KEY_ENTER: i32 = 257;
+pub const KEY_TAB: i32 = 258;
+pub const KEY_BACKSPACE: i32 = 259;
+pub const KEY_INSERT: i32 = 260;
+pub const KEY_DELETE: i32 = 261;
 pub const KEY_RIGHT: i32 = 262;
+pub const KEY_LEFT: i32 = 263;
+pub const KEY_DOWN: i32 = 264;
+pub const KEY_UP: i32 = 265;
+pub const KEY_PAGE_UP: i32 = 266;
+pub const KEY_PAGE_DOWN: i32 = 267;
+pub const KEY_HOME: i32 = 268;
+pub const KEY_END: i32 = 269;
+pub const KEY_F1: i32 = 290;
+pub const KEY_F2: i32 = 291;
+pub const KEY_F3: i32 = 292;
+pub const KEY_F4: i32 = 293;
+pub const KEY_F5: i32 = 294;
+pub const KEY_F6: i32 = 295;
+pub const KEY_F7: i32 = 296;
+pub const KEY_F8: i32 = 297;
+pub const KEY_F9: i32 = 298;
+pub const KEY_F10: i32 = 299;
+pub const KEY_F11: i32 = 300;
+pub const KEY_F12: i32 = 301;
+pub const KEY_LEFT_SHIFT: i32 = 340;
+pub const KEY_LEFT_CONTROL: i32 = 341;
+pub const KEY_LEFT_ALT: i32 = 342;
+pub const KEY_RIGHT_SHIFT: i32 = 344;
+pub const KEY_RIGHT_CONTROL: i32 = 345;
+pub const KEY_RIGHT_ALT: i32 = 346;
 
 // ============================
 // FFI Bindings
 // ============================
+// Each program links raylib itself, so one that only borrows the types
+// (the headless snapshot runner) builds on a machine without it.
 extern "C" {
+    // --- Core: window ---
     pub fn InitWindow(width: i32, height: i32, title: *const i8);
     pub fn CloseWindow();
     pub fn WindowShouldClose() -> bool;
-    pub fn SetTargetFPS(fps: i32);
+    pub fn IsWindowReady() -> bool;
+    pub fn IsWindowResized() -> bool;
+    pub fn SetConfigFlags(flags: u32);
+    pub fn SetWindowTitle(title: *const i8);
+    pub fn SetWindowSize(width: i32, height: i32);
+    pub fn ToggleFullscreen();
+    pub fn GetScreenWidth() -> i32;
+    pub fn GetScreenHeight() -> i32;
+    pub fn GetRenderWidth() -> i32;
+    pub fn GetRenderHeight() -> i32;
+    pub fn ShowCursor();
+    pub fn HideCursor();
+    pub fn IsCursorHidden() -> bool;
+    pub fn EnableCursor();
+    pub fn DisableCursor();
 
+    // --- Core: drawing ---
+    pub fn ClearBackground(color: Color);
     pub fn BeginDrawing();
     pub fn EndDrawing();
-    pub fn ClearBackground(color: Color);
+    pub fn BeginMode2D(camera: Camera2D);
+    pub fn EndMode2D();
+    pub fn BeginMode3D(camera: Camera3D);
+    pub fn EndMode3D();
+    pub fn BeginTextureMode(target: RenderTexture2D);
+    pub fn EndTextureMode();
+    pub fn BeginBlendMode(mode: i32);
+    pub fn EndBlendMode();
+    pub fn BeginScissorMode(x: i32, y: i32, width: i32, height: i32);
+    pub fn EndScissorMode();
 
-    pub fn DrawText(text: *const i8, posX: i32, posY: i32, fontSize: i32, color: Color);
+    // --- Core: screen and world space ---
+    pub fn GetScreenToWorldRay(position: Vector2, camera: Camera) -> Ray;
+    pub fn GetWorldToScreen(position: Vector3, camera: Camera) -> Vector2;
+    pub fn GetWorldToScreen2D(position: Vector2, camera: Camera2D) -> Vector2;
+    pub fn GetScreenToWorld2D(position: Vector2, camera: Camera2D) -> Vector2;
+    pub fn GetCameraMatrix(camera: Camera) -> Matrix;
+    pub fn GetCameraMatrix2D(camera: Camera2D) -> Matrix;
+
+    // --- Core: timing and misc ---
+    pub fn SetTargetFPS(fps: i32);
+    pub fn GetFrameTime() -> f32;
+    pub fn GetTime() -> f64;
+    pub fn GetFPS() -> i32;
+    pub fn SetRandomSeed(seed: u32);
+    pub fn GetRandomValue(min: i32, max: i32) -> i32;
+    pub fn TakeScreenshot(fileName: *const i8);
+    pub fn SetTraceLogLevel(logLevel: i32);
+
+    // --- Core: input ---
+    pub fn IsKeyPressed(key: i32) -> bool;
+    pub fn IsKeyPressedRepeat(key: i32) -> bool;
+    pub fn IsKeyDown(key: i32) -> bool;
+    pub fn IsKeyReleased(key: i32) -> bool;
+    pub fn IsKeyUp(key: i32) -> bool;
+    pub fn GetKeyPressed() -> i32;
+    pub fn GetCharPressed() -> i32;
+    pub fn SetExitKey(key: i32);
+    pub fn IsMouseButtonPressed(button: i32) -> bool;
+    pub fn IsMouseButtonDown(button: i32) -> bool;
+    pub fn IsMouseButtonReleased(button: i32) -> bool;
+    pub fn IsMouseButtonUp(button: i32) -> bool;
+    pub fn GetMouseX() -> i32;
+    pub fn GetMouseY() -> i32;
+    pub fn GetMousePosition() -> Vector2;
+    pub fn GetMouseDelta() -> Vector2;
+    pub fn SetMousePosition(x: i32, y: i32);
+    pub fn GetMouseWheelMove() -> f32;
+    pub fn SetMouseCursor(cursor: i32);
+    pub fn GetClipboardText() -> *const i8;
+    pub fn SetClipboardText(text: *const i8);
+
+    // --- Camera ---
+    pub fn UpdateCamera(camera: *mut Camera, mode: i32);
+    pub fn UpdateCameraPro(camera: *mut Camera, movement: Vector3, rotation: Vector3, zoom: f32);
+
+    // --- Shapes ---
+    pub fn DrawPixel(posX: i32, posY: i32, color: Color);
+    pub fn DrawPixelV(position: Vector2, color: Color);
+    pub fn DrawLine(x1: i32, y1: i32, x2: i32, y2: i32, color: Color);
+    pub fn DrawLineV(startPos: Vector2, endPos: Vector2, color: Color);
+    pub fn DrawLineEx(startPos: Vector2, endPos: Vector2, thick: f32, color: Color);
+    pub fn DrawLineStrip(points: *const Vector2, pointCount: i32, color: Color);
     pub fn DrawCircle(posX: i32, posY: i32, radius: f32, color: Color);
+    pub fn DrawCircleV(center: Vector2, radius: f32, color: Color);
+    pub fn DrawCircleSector(center: Vector2, radius: f32, startAngle: f32, endAngle: f32, segments: i32, color: Color);
+    pub fn DrawCircleGradient(centerX: i32, centerY: i32, radius: f32, inner: Color, outer: Color);
+    pub fn DrawCircleLines(centerX: i32, centerY: i32, radius: f32, color: Color);
+    pub fn DrawCircleLinesV(center: Vector2, radius: f32, color: Color);
+    pub fn DrawEllipse(centerX: i32, centerY: i32, radiusH: f32, radiusV: f32, color: Color);
+    pub fn DrawEllipseLines(centerX: i32, centerY: i32, radiusH: f32, radiusV: f32, color: Color);
+    pub fn DrawRing(center: Vector2, innerRadius: f32, outerRadius: f32, startAngle: f32, endAngle: f32, segments: i32, color: Color);
     pub fn DrawRectangle(posX: i32, posY: i32, width: i32, height: i32, color: Color);
+    pub fn DrawRectangleV(position: Vector2, size: Vector2, color: Color);
+    pub fn DrawRectangleRec(rec: Rectangle, color: Color);
+    pub fn DrawRectanglePro(rec: Rectangle, origin: Vector2, rotation: f32, color: Color);
+    pub fn DrawRectangleGradientV(posX: i32, posY: i32, width: i32, height: i32, top: Color, bottom: Color);
+    pub fn DrawRectangleGradientH(posX: i32, posY: i32, width: i32, height: i32, left: Color, right: Color);
     pub fn DrawRectangleLines(posX: i32, posY: i32, width: i32, height: i32, color: Color);
-    pub fn DrawLine(x1: i32, y1: i32, x2: i32, y2: i32, color: Color);
+    pub fn DrawRectangleLinesEx(rec: Rectangle, lineThick: f32, color: Color);
+    pub fn DrawRectangleRounded(rec: Rectangle, roundness: f32, segments: i32, color: Color);
+    pub fn DrawRectangleRoundedLines(rec: Rectangle, roundness: f32, segments: i32, color: Color);
+    pub fn DrawRectangleRoundedLinesEx(rec: Rectangle, roundness: f32, segments: i32, lineThick: f32, color: Color);
     pub fn DrawTriangle(v1: Vector2, v2: Vector2, v3: Vector2, color: Color);
+    pub fn DrawTriangleLines(v1: Vector2, v2: Vector2, v3: Vector2, color: Color);
+    pub fn DrawTriangleFan(points: *const Vector2, pointCount: i32, color: Color);
     pub fn DrawPoly(center: Vector2, sides: i32, radius: f32, rotation: f32, color: Color);
+    pub fn DrawPolyLines(center: Vector2, sides: i32, radius: f32, rotation: f32, color: Color);
+    pub fn DrawPolyLinesEx(center: Vector2, sides: i32, radius: f32, rotation: f32, lineThick: f32, color: Color);
 
-    pub fn IsMouseButtonPressed(button: i32) -> bool;
-    pub fn IsMouseButtonDown(button: i32) -> bool;
-    pub fn IsMouseButtonReleased(button: i32) -> bool;
-    pub fn GetMousePosition() -> Vector2;
-    pub fn IsKeyPressed(key: i32) -> bool;
-    pub fn GetFrameTime() -> f32;
+    pub fn CheckCollisionRecs(rec1: Rectangle, rec2: Rectangle) -> bool;
+    pub fn CheckCollisionCircles(center1: Vector2, radius1: f32, center2: Vector2, radius2: f32) -> bool;
+    pub fn CheckCollisionCircleRec(center: Vector2, radius: f32, rec: Rectangle) -> bool;
+    pub fn CheckCollisionPointRec(point: Vector2, rec: Rectangle) -> bool;
+    pub fn CheckCollisionPointCircle(point: Vector2, center: Vector2, radius: f32) -> bool;
+    pub fn GetCollisionRec(rec1: Rectangle, rec2: Rectangle) -> Rectangle;
+
+    // --- Textures: images (CPU) ---
+    pub fn LoadImage(fileName: *const i8) -> Image;
+    pub fn LoadImageFromTexture(texture: Texture2D) -> Image;
+    pub fn LoadImageFromScreen() -> Image;
+    pub fn IsImageValid(image: Image) -> bool;
+    pub fn UnloadImage(image: Image);
+    pub fn ExportImage(image: Image, fileName: *const i8) -> bool;
+    pub fn GenImageColor(width: i32, height: i32, color: Color) -> Image;
+    pub fn GenImageChecked(width: i32, height: i32, checksX: i32, checksY: i32, col1: Color, col2: Color) -> Image;
+    pub fn ImageResize(image: *mut Image, newWidth: i32, newHeight: i32);
+    pub fn ImageFlipVertical(image: *mut Image);
+
+    // --- Textures: textures (GPU) ---
+    pub fn LoadTexture(fileName: *const i8) -> Texture2D;
+    pub fn LoadTextureFromImage(image: Image) -> Texture2D;
+    pub fn LoadRenderTexture(width: i32, height: i32) -> RenderTexture2D;
+    pub fn IsTextureValid(texture: Texture2D) -> bool;
+    pub fn UnloadTexture(texture: Texture2D);
+    pub fn IsRenderTextureValid(target: RenderTexture2D) -> bool;
+    pub fn UnloadRenderTexture(target: RenderTexture2D);
+    pub fn UpdateTexture(texture: Texture2D, pixels: *const c_void);
+    pub fn GenTextureMipmaps(texture: *mut Texture2D);
+    pub fn SetTextureFilter(texture: Texture2D, filter: i32);
+    pub fn SetTextureWrap(texture: Texture2D, wrap: i32);
+    pub fn DrawTexture(texture: Texture2D, posX: i32, posY: i32, tint: Color);
+    pub fn DrawTextureV(texture: Texture2D, position: Vector2, tint: Color);
+    pub fn DrawTextureEx(texture: Texture2D, position: Vector2, rotation: f32, scale: f32, tint: Color);
+    pub fn DrawTextureRec(texture: Texture2D, source: Rectangle, position: Vector2, tint: Color);
+    pub fn DrawTexturePro(texture: Texture2D, source: Rectangle, dest: Rectangle, origin: Vector2, rotation: f32, tint: Color);
+
+    // --- Textures: colors ---
+    pub fn Fade(color: Color, alpha: f32) -> Color;
+    pub fn ColorAlpha(color: Color, alpha: f32) -> Color;
+    pub fn ColorLerp(color1: Color, color2: Color, factor: f32) -> Color;
+    pub fn ColorToInt(color: Color) -> i32;
+    pub fn GetColor(hexValue: u32) -> Color;
+
+    // --- Text ---
+    pub fn DrawFPS(posX: i32, posY: i32);
+    pub fn DrawText(text: *const i8, posX: i32, posY: i32, fontSize: i32, color: Color);
+    pub fn MeasureText(text: *const i8, fontSize: i32) -> i32;
+    pub fn GetFontDefault() -> Font;
+    pub fn LoadFont(fileName: *const i8) -> Font;
+    pub fn LoadFontEx(fileName: *const i8, fontSize: i32, codepoints: *const i32, codepointCount: i32) -> Font;
+    pub fn IsFontValid(font: Font) -> bool;
+    pub fn UnloadFont(font: Font);
+    pub fn MeasureTextEx(font: Font, text: *const i8, fontSize: f32, spacing: f32) -> Vector2;
+    pub fn DrawTextEx(font: Font, text: *const i8, position: Vector2, fontSize: f32, spacing: f32, tint: Color);
+    pub fn DrawTextPro(font: Font, text: *const i8, position: Vector2, origin: Vector2, rotation: f32, fontSize: f32, spacing: f32, tint: Color);
+    pub fn DrawTextCodepoint(font: Font, codepoint: i32, position: Vector2, fontSize: f32, tint: Color);
+    pub fn DrawTextCodepoints(font: Font, codepoints: *const i32, codepointCount: i32, position: Vector2, fontSize: f32, spacing: f32, tint: Color);
+    pub fn GetGlyphIndex(font: Font, codepoint: i32) -> i32;
+
+    // --- Models: 3D shapes ---
+    pub fn DrawLine3D(startPos: Vector3, endPos: Vector3, color: Color);
+    pub fn DrawPoint3D(position: Vector3, color: Color);
+    pub fn DrawCircle3D(center: Vector3, radius: f32, rotationAxis: Vector3, rotationAngle: f32, color: Color);
+    pub fn DrawTriangle3D(v1: Vector3, v2: Vector3, v3: Vector3, color: Color);
+    pub fn DrawCube(position: Vector3, width: f32, height: f32, length: f32, color: Color);
+    pub fn DrawCubeV(position: Vector3, size: Vector3, color: Color);
+    pub fn DrawCubeWires(position: Vector3, width: f32, height: f32, length: f32, color: Color);
+    pub fn DrawCubeWiresV(position: Vector3, size: Vector3, color: Color);
+    pub fn DrawSphere(centerPos: Vector3, radius: f32, color: Color);
+    pub fn DrawSphereEx(centerPos: Vector3, radius: f32, rings: i32, slices: i32, color: Color);
+    pub fn DrawSphereWires(centerPos: Vector3, radius: f32, rings: i32, slices: i32, color: Color);
+    pub fn DrawCylinder(position: Vector3, radiusTop: f32, radiusBottom: f32, height: f32, slices: i32, color: Color);
+    pub fn DrawCylinderEx(startPos: Vector3, endPos: Vector3, startRadius: f32, endRadius: f32, sides: i32, color: Color);
+    pub fn DrawCylinderWires(position: Vector3, radiusTop: f32, radiusBottom: f32, height: f32, slices: i32, color: Color);
+    pub fn DrawCylinderWiresEx(startPos: Vector3, endPos: Vector3, startRadius: f32, endRadius: f32, sides: i32, color: Color);
+    pub fn DrawCapsule(startPos: Vector3, endPos: Vector3, radius: f32, slices: i32, rings: i32, color: Color);
+    pub fn DrawCapsuleWires(startPos: Vector3, endPos: Vector3, radius: f32, slices: i32, rings: i32, color: Color);
+    pub fn DrawPlane(centerPos: Vector3, size: Vector2, color: Color);
+    pub fn DrawRay(ray: Ray, color: Color);
+    pub fn DrawGrid(slices: i32, spacing: f32);
+
+    // --- Models: models ---
+    pub fn LoadModel(fileName: *const i8) -> Model;
+    pub fn LoadModelFromMesh(mesh: Mesh) -> Model;
+    pub fn IsModelValid(model: Model) -> bool;
+    pub fn UnloadModel(model: Model);
+    pub fn GetModelBoundingBox(model: Model) -> BoundingBox;
+    pub fn DrawModel(model: Model, position: Vector3, scale: f32, tint: Color);
+    pub fn DrawModelEx(model: Model, position: Vector3, rotationAxis: Vector3, rotationAngle: f32, scale: Vector3, tint: Color);
+    pub fn DrawModelWires(model: Model, position: Vector3, scale: f32, tint: Color);
+    pub fn DrawModelWiresEx(model: Model, position: Vector3, rotationAxis: Vector3, rotationAngle: f32, scale: Vector3, tint: Color);
+    pub fn DrawBoundingBox(bbox: BoundingBox, color: Color);
+    pub fn DrawBillboard(camera: Camera, texture: Texture2D, position: Vector3, scale: f32, tint: Color);
+
+    // --- Models: meshes and materials ---
+    pub fn UploadMesh(mesh: *mut Mesh, dynamic: bool);
+    pub fn UnloadMesh(mesh: Mesh);
+    pub fn DrawMesh(mesh: Mesh, material: Material, transform: Matrix);
+    pub fn GetMeshBoundingBox(mesh: Mesh) -> BoundingBox;
+    pub fn ExportMesh(mesh: Mesh, fileName: *const i8) -> bool;
+    pub fn GenMeshPoly(sides: i32, radius: f32) -> Mesh;
+    pub fn GenMeshPlane(width: f32, length: f32, resX: i32, resZ: i32) -> Mesh;
+    pub fn GenMeshCube(width: f32, height: f32, length: f32) -> Mesh;
+    pub fn GenMeshSphere(radius: f32, rings: i32, slices: i32) -> Mesh;
+    pub fn GenMeshHemiSphere(radius: f32, rings: i32, slices: i32) -> Mesh;
+    pub fn GenMeshCylinder(radius: f32, height: f32, slices: i32) -> Mesh;
+    pub fn GenMeshCone(radius: f32, height: f32, slices: i32) -> Mesh;
+    pub fn GenMeshTorus(radius: f32, size: f32, radSeg: i32, sides: i32) -> Mesh;
+    pub fn GenMeshKnot(radius: f32, size: f32, radSeg: i32, sides: i32) -> Mesh;
+    pub fn LoadMaterialDefault() -> Material;
+    pub fn UnloadMaterial(material: Material);
+    pub fn SetMaterialTexture(material: *mut Material, mapType: i32, texture: Texture2D);
+    pub fn SetModelMeshMaterial(model: *mut Model, meshId: i32, materialId: i32);
+
+    // --- Models: animations ---
+    pub fn LoadModelAnimations(fileName: *const i8, animCount: *mut i32) -> *mut ModelAnimation;
+    pub fn UpdateModelAnimation(model: Model, anim: ModelAnimation, frame: i32);
+    pub fn UnloadModelAnimations(animations: *mut ModelAnimation, animCount: i32);
+
+    // --- Models: collision ---
+    pub fn CheckCollisionSpheres(center1: Vector3, radius1: f32, center2: Vector3, radius2: f32) -> bool;
+    pub fn CheckCollisionBoxes(box1: BoundingBox, box2: BoundingBox) -> bool;
+    pub fn CheckCollisionBoxSphere(bbox: BoundingBox, center: Vector3, radius: f32) -> bool;
+    pub fn GetRayCollisionSphere(ray: Ray, center: Vector3, radius: f32) -> RayCollision;
+    pub fn GetRayCollisionBox(ray: Ray, bbox: BoundingBox) -> RayCollision;
+    pub fn GetRayCollisionMesh(ray: Ray, mesh: Mesh, transform: Matrix) -> RayCollision;
+    pub fn GetRayCollisionTriangle(ray: Ray, p1: Vector3, p2: Vector3, p3: Vector3) -> RayCollision;
+
+    // --- Audio: device ---
+    pub fn InitAudioDevice();
+    pub fn CloseAudioDevice();
+    pub fn IsAudioDeviceReady() -> bool;
+    pub fn SetMasterVolume(volume: f32);
+    pub fn GetMasterVolume() -> f32;
+
+    // --- Audio: waves and sounds ---
+    pub fn LoadWave(fileName: *const i8) -> Wave;
+    pub fn IsWaveValid(wave: Wave) -> bool;
+    pub fn UnloadWave(wave: Wave);
+    pub fn LoadSound(fileName: *const i8) -> Sound;
+    pub fn LoadSoundFromWave(wave: Wave) -> Sound;
+    pub fn IsSoundValid(sound: Sound) -> bool;
+    pub fn UnloadSound(sound: Sound);
+    pub fn PlaySound(sound: Sound);
+    pub fn StopSound(sound: Sound);
+    pub fn PauseSound(sound: Sound);
+    pub fn ResumeSound(sound: Sound);
+    pub fn IsSoundPlaying(sound: Sound) -> bool;
+    pub fn SetSoundVolume(sound: Sound, volume: f32);
+    pub fn SetSoundPitch(sound: Sound, pitch: f32);
+    pub fn SetSoundPan(sound: Sound, pan: f32);
+
+    // --- Audio: music ---
+    pub fn LoadMusicStream(fileName: *const i8) -> Music;
+    pub fn IsMusicValid(music: Music) -> bool;
+    pub fn UnloadMusicStream(music: Music);
+    pub fn PlayMusicStream(music: Music);
+    pub fn IsMusicStreamPlaying(music: Music) -> bool;
+    pub fn UpdateMusicStream(music: Music);
+    pub fn StopMusicStream(music: Music);
+    pub fn PauseMusicStream(music: Music);
+    pub fn ResumeMusicStream(music: Music);
+    pub fn SeekMusicStream(music: Music, position: f32);
+    pub fn SetMusicVolume(music: Music, volume: f32);
+    pub fn SetMusicPitch(music: Music, pitch: f32);
+    pub fn SetMusicPan(music: Music, pan: f32);
+    pub fn GetMusicTimeLength(music: Music) -> f32;
+    pub fn GetMusicTimePlayed(music: Music) -> f32;
+
+    // --- Audio: raw streams ---
+    pub fn LoadAudioStream(sampleRate: u32, sampleSize: u32, channels: u32) -> AudioStream;
+    pub fn IsAudioStreamValid(stream: AudioStream) -> bool;
+    pub fn UnloadAudioStream(stream: AudioStream);
+    pub fn UpdateAudioStream(stream: AudioStream, data: *const c_void, frameCount: i32);
+    pub fn IsAudioStreamProcessed(stream: AudioStream) -> bool;
+    pub fn PlayAudioStream(stream: AudioStream);
+    pub fn StopAudioStream(stream: AudioStream);
+    pub fn SetAudioStreamVolume(stream: AudioStream, volume: f32);
+}
+
+// ============================
+// Math Library Conversions
+// ============================
+
+/// `From` both ways between raylib's `Vector3`, `Vector4` and `Matrix` and
+/// `linalg`'s `Vec3`, `Vec4`/`Quat` and `Mat4`. Invoked at the crate root
+/// with the name `linalg` was declared under.
+#[macro_export]
+macro_rules! linalg_conversions {
+    ($linalg:ident) => {
+        impl From<$crate::$linalg::Vec3> for $crate::raylib::Vector3 {
+            fn from(v: $crate::$linalg::Vec3) -> Self {
+                $crate::raylib::Vector3 { x: v.x, y: v.y, z: v.z }
+            }
+        }
+
+        impl From<$crate::raylib::Vector3> for $crate::$linalg::Vec3 {
+            fn from(v: $crate::raylib::Vector3) -> Self {
+                $crate::$linalg::Vec3 { x: v.x, y: v.y, z: v.z }
+            }
+        }
+
+        impl From<$crate::$linalg::Vec4> for $crate::raylib::Vector4 {
+            fn from(v: $crate::$linalg::Vec4) -> Self {
+                $crate::raylib::Vector4 { x: v.x, y: v.y, z: v.z, w: v.w }
+            }
+        }
+
+        impl From<$crate::raylib::Vector4> for $crate::$linalg::Vec4 {
+            fn from(v: $crate::raylib::Vector4) -> Self {
+                $crate::$linalg::Vec4 { x: v.x, y: v.y, z: v.z, w: v.w }
+            }
+        }
+
+        impl From<$crate::$linalg::Quat> for $crate::raylib::Vector4 {
+            fn from(q: $crate::$linalg::Quat) -> Self {
+                $crate::raylib::Vector4 { x: q.x, y: q.y, z: q.z, w: q.w }
+            }
+        }
+
+        impl From<$crate::raylib::Vector4> for $crate::$linalg::Quat {
+            fn from(q: $crate::raylib::Vector4) -> Self {
+                $crate::$linalg::Quat { x: q.x, y: q.y, z: q.z, w: q.w }
+            }
+        }
+
+        // Both are column-major, so element N maps to `mN`
+        impl From<$crate::$linalg::Mat4> for $crate::raylib::Matrix {
+            fn from(m: $crate::$linalg::Mat4) -> Self {
+                let m = m.m;
+                $crate::raylib::Matrix {
+                    m0: m[0], m4: m[4], m8: m[8], m12: m[12],
+                    m1: m[1], m5: m[5], m9: m[9], m13: m[13],
+                    m2: m[2], m6: m[6], m10: m[10], m14: m[14],
+                    m3: m[3], m7: m[7], m11: m[11], m15: m[15],
+                }
+            }
+        }
+
+        impl From<$crate::raylib::Matrix> for $crate::$linalg::Mat4 {
+            fn from(m: $crate::raylib::Matrix) -> Self {
+                $crate::$linalg::Mat4 {
+                    m: [
+                        m.m0, m.m1, m.m2, m.m3,
+                        m.m4, m.m5, m.m6, m.m7,
+                        m.m8, m.m9, m.m10, m.m11,
+                        m.m12, m.m13, m.m14, m.m15,
+                    ],
+                }
+            }
+        }
+    };
+}
+
+/// `From` both ways between raylib's `Vector2` and `Color` and
+/// `gp2d_math`'s `Vec2` and `ColorF` (0 to 1 per channel, clamped on the
+/// way to `Color`). Invoked at the crate root with the name `gp2d_math`
+/// was declared under.
+#[macro_export]
+macro_rules! gp2d_conversions {
+    ($gp2d:ident) => {
+        impl From<$crate::$gp2d::Vec2> for $crate::raylib::Vector2 {
+            fn from(v: $crate::$gp2d::Vec2) -> Self {
+                $crate::raylib::Vector2 { x: v.x, y: v.y }
+            }
+        }
+
+        impl From<$crate::raylib::Vector2> for $crate::$gp2d::Vec2 {
+            fn from(v: $crate::raylib::Vector2) -> Self {
+                $crate::$gp2d::Vec2 { x: v.x, y: v.y }
+            }
+        }
+
+        impl From<$crate::$gp2d::ColorF> for $crate::raylib::Color {
+            fn from(c: $crate::$gp2d::ColorF) -> Self {
+                let channel = |v: f32| (v.max(0.0).min(1.0) * 255.0 + 0.5) as u8;
+                $crate::raylib::Color { r: channel(c.r), g: channel(c.g), b: channel(c.b), a: channel(c.a) }
+            }
+        }
+
+        impl From<$crate::raylib::Color> for $crate::$gp2d::ColorF {
+            fn from(c: $crate::raylib::Color) -> Self {
+                let channel = |v: u8| v as f32 / 255.0;
+                $crate::$gp2d::ColorF { r: channel(c.r), g: channel(c.g), b: channel(c.b), a: channel(c.a) }
+            }
+        }
+    };
 }
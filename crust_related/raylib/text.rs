@@ -0,0 +1,193 @@
+// raylib/text.rs - Text in a TTF or the built-in font, measured and wrapped
+// by `text_layout`.
+//
+// Lines are drawn with `DrawTextCodepoints` from the `&str` itself, so
+// nothing needs copying into a NUL-terminated buffer first and UTF-8 works
+// wherever the font has the glyphs.
+
+use super::text_layout::{self, Align, Metrics, Span, ELLIPSIS};
+use super::*;
+
+/// Codepoints `load_font` rasterizes: printable ASCII, Latin-1, Latin
+/// Extended-A, and the dashes, quotes and ellipsis of General Punctuation
+const CODEPOINTS: [i32; 95 + 224 + 24] = {
+    let mut codepoints = [0; 95 + 224 + 24];
+    let mut i = 0;
+    while i < codepoints.len() {
+        codepoints[i] = match i {
+            0..=94 => 32 + i as i32,
+            95..=318 => 160 + (i - 95) as i32,
+            _ => 0x2010 + (i - 319) as i32,
+        };
+        i += 1;
+    }
+    codepoints
+};
+
+/// Codepoints drawn per `DrawTextCodepoints` call
+const CHUNK: usize = 64;
+
+/// Load the TTF or OTF file at `path` (NUL-terminated) rasterized at
+/// `size` pixels, or `None` if it can't be read
+pub fn load_font(path: &[u8], size: i32) -> Option<Font> {
+    if path.last() != Some(&0) {
+        return None;
+    }
+    unsafe {
+        let font = LoadFontEx(path.as_ptr() as *const i8, size, CODEPOINTS.as_ptr(), CODEPOINTS.len() as i32);
+        if !IsFontValid(font) {
+            return None;
+        }
+        SetTextureFilter(font.texture, TEXTURE_FILTER_BILINEAR);
+        Some(font)
+    }
+}
+
+/// How to draw some text: font, size and colour
+#[derive(Copy, Clone, Debug)]
+pub struct TextStyle {
+    pub font: Font,
+    pub size: f32,
+    /// Between glyphs
+    pub spacing: f32,
+    pub line_height: f32,
+    pub color: Color,
+    /// Drawn twice, a pixel apart
+    pub bold: bool,
+}
+
+impl TextStyle {
+    pub fn new(font: Font, size: f32, color: Color) -> TextStyle {
+        TextStyle { font, size, spacing: 0.0, line_height: size * 1.25, color, bold: false }
+    }
+
+    /// The built-in font, spaced like `DrawText` spaces it
+    pub fn default_font(size: i32, color: Color) -> TextStyle {
+        let font = unsafe { GetFontDefault() };
+        TextStyle { spacing: (size / 10) as f32, ..TextStyle::new(font, size as f32, color) }
+    }
+
+    pub fn with_color(self, color: Color) -> TextStyle {
+        TextStyle { color, ..self }
+    }
+
+    pub fn bold(self) -> TextStyle {
+        TextStyle { bold: true, ..self }
+    }
+}
+
+impl Metrics for TextStyle {
+    fn advance(&self, c: char) -> f32 {
+        let font = &self.font;
+        if font.glyphs.is_null() || font.base_size == 0 {
+            return 0.0;
+        }
+        unsafe {
+            // Falls back to '?' for glyphs the font lacks
+            let index = GetGlyphIndex(*font, c as i32) as usize;
+            let glyph = *font.glyphs.add(index);
+            let width = if glyph.advance_x != 0 { glyph.advance_x as f32 } else { (*font.recs.add(index)).width };
+            width * self.size / font.base_size as f32
+        }
+    }
+
+    fn spacing(&self) -> f32 {
+        // Room for the second, offset copy
+        self.spacing + if self.bold { 1.0 } else { 0.0 }
+    }
+
+    fn line_height(&self) -> f32 {
+        self.line_height
+    }
+}
+
+/// Width of `text` on one line
+pub fn measure(style: &TextStyle, text: &str) -> f32 {
+    text_layout::measure(style, text)
+}
+
+/// Size `text` takes wrapped at `width`
+pub fn measure_wrapped(style: &TextStyle, text: &str, width: f32) -> Vector2 {
+    let mut size = Vector2 { x: 0.0, y: 0.0 };
+    for line in text_layout::wrap(style, text, width) {
+        size.x = size.x.max(line.width);
+        size.y += style.line_height;
+    }
+    size
+}
+
+/// One line of `text` with its top left at `x`, `y`
+pub fn draw(style: &TextStyle, text: &str, x: f32, y: f32) {
+    let mut codepoints = [0i32; CHUNK];
+    let mut x = x;
+    let mut count = 0;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        codepoints[count] = c as i32;
+        count += 1;
+        if count == CHUNK || chars.peek().is_none() {
+            draw_codepoints(style, &codepoints[..count], x, y);
+            for &c in &codepoints[..count] {
+                x += style.advance(char::from_u32(c as u32).unwrap_or('?')) + style.spacing();
+            }
+            count = 0;
+        }
+    }
+}
+
+fn draw_codepoints(style: &TextStyle, codepoints: &[i32], x: f32, y: f32) {
+    let spacing = style.spacing();
+    let count = codepoints.len() as i32;
+    unsafe {
+        DrawTextCodepoints(style.font, codepoints.as_ptr(), count, Vector2 { x, y }, style.size, spacing, style.color);
+        if style.bold {
+            let x = x + 1.0;
+            DrawTextCodepoints(style.font, codepoints.as_ptr(), count, Vector2 { x, y }, style.size, spacing, style.color);
+        }
+    }
+}
+
+/// `text` wrapped to `rect`'s width and placed in it; lines past the
+/// bottom are still drawn. Returns the height the lines took.
+pub fn draw_wrapped(style: &TextStyle, text: &str, rect: Rectangle, align: Align, valign: Align) -> f32 {
+    let height = text_layout::wrap(style, text, rect.width).count() as f32 * style.line_height;
+    let mut y = rect.y + valign.offset(rect.height, height);
+    for line in text_layout::wrap(style, text, rect.width) {
+        draw(style, line.text, rect.x + align.offset(rect.width, line.width), y);
+        y += style.line_height;
+    }
+    height
+}
+
+/// `text` on one line in `rect`, cut short with `...` if it's too wide
+pub fn draw_ellipsized(style: &TextStyle, text: &str, rect: Rectangle, align: Align, valign: Align) {
+    let (shown, cut) = text_layout::ellipsize(style, text, rect.width);
+    let mut width = measure(style, shown);
+    if cut {
+        width += style.spacing() + measure(style, ELLIPSIS);
+    }
+    let x = rect.x + align.offset(rect.width, width);
+    let y = rect.y + valign.offset(rect.height, style.size);
+    draw(style, shown, x, y);
+    if cut {
+        let x = x + width - measure(style, ELLIPSIS);
+        draw(style, ELLIPSIS, x, y);
+    }
+}
+
+/// Differently styled `spans` wrapped together into `rect`, each line as
+/// tall as the first span's `line_height`. Returns the height taken.
+pub fn draw_rich(spans: &[Span<TextStyle>], rect: Rectangle, align: Align, valign: Align) -> f32 {
+    let line_height = spans.first().map_or(0.0, |span| span.style.line_height);
+    let metrics = |style: &TextStyle| *style;
+    let height = text_layout::wrap_spans(spans, metrics, rect.width).count() as f32 * line_height;
+    let mut y = rect.y + valign.offset(rect.height, height);
+    for line in text_layout::wrap_spans(spans, metrics, rect.width) {
+        let x = rect.x + align.offset(rect.width, line.width);
+        for run in line.runs(spans, &metrics) {
+            draw(run.style, run.text, x + run.x, y);
+        }
+        y += line_height;
+    }
+    height
+}
@@ -0,0 +1,1158 @@
+// raylib/ui.rs - Immediate-mode widgets over the raylib bindings
+//
+// Widgets are function calls made every frame between `Ui::begin` and
+// `Ui::end`; the caller owns every value they show or edit. A widget is
+// identified by its label hashed with the ID stack, which is how the
+// toolkit remembers what is hot (under the mouse), active (pressed or
+// being dragged) and focused (taking keyboard input) from one frame to
+// the next. Text after `##` in a label is part of the ID but not shown.
+//
+//     let mut ui = Ui::new();
+//     while !WindowShouldClose() {
+//         BeginDrawing();
+//         ClearBackground(RAYWHITE);
+//         ui.begin(Input::poll());
+//         ui.row(2, |ui| {
+//             if ui.button("Reset") { volume = 0.5; }
+//             ui.checkbox("Mute", &mut mute);
+//         });
+//         ui.slider("Volume", &mut volume, 0.0, 1.0);
+//         ui.end();
+//         EndDrawing();
+//     }
+//
+// Only `core` is used, so `no_std` programs can draw with it too.
+
+use super::*;
+use core::fmt::{self, Write};
+use core::hash::{Hash, Hasher};
+
+// ============================
+// Limits
+// ============================
+/// Longest text a widget draws in one piece; the rest is cut off
+pub const TEXT_CAP: usize = 128;
+const ID_DEPTH: usize = 16;
+const LAYOUT_DEPTH: usize = 16;
+/// Rows an open dropdown shows before it scrolls
+const POPUP_ROWS: usize = 8;
+const TYPED_CAP: usize = 16;
+const SCROLLBAR: f32 = 8.0;
+
+/// Clip rectangle meaning "the whole window"
+const UNCLIPPED: Rectangle = Rectangle { x: -1.0e6, y: -1.0e6, width: 2.0e6, height: 2.0e6 };
+
+// ============================
+// Geometry
+// ============================
+impl Rectangle {
+    pub const fn new(x: f32, y: f32, width: f32, height: f32) -> Rectangle {
+        Rectangle { x, y, width, height }
+    }
+
+    pub fn contains(&self, point: Vector2) -> bool {
+        point.x >= self.x && point.x < self.x + self.width && point.y >= self.y && point.y < self.y + self.height
+    }
+
+    /// Shrunk by `amount` on every side
+    pub fn inset(&self, amount: f32) -> Rectangle {
+        Rectangle::new(
+            self.x + amount,
+            self.y + amount,
+            (self.width - 2.0 * amount).max(0.0),
+            (self.height - 2.0 * amount).max(0.0),
+        )
+    }
+
+    /// Overlap of two rectangles, empty if they don't touch
+    pub fn intersect(&self, other: &Rectangle) -> Rectangle {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = (self.x + self.width).min(other.x + other.width);
+        let bottom = (self.y + self.height).min(other.y + other.height);
+        Rectangle::new(x, y, (right - x).max(0.0), (bottom - y).max(0.0))
+    }
+}
+
+// ============================
+// Style
+// ============================
+/// Sizes and colours every widget draws with. `Ui::style` can be changed
+/// between widgets, like pushing a style in other toolkits.
+#[derive(Copy, Clone, Debug)]
+pub struct Style {
+    pub font_size: i32,
+    /// Height of buttons, fields and the other one-line widgets
+    pub item_height: f32,
+    /// Gap between neighbouring widgets
+    pub spacing: f32,
+    /// Gap between a panel's edge and its contents
+    pub padding: f32,
+    pub background: Color,
+    /// Fill of buttons, fields and tracks
+    pub surface: Color,
+    /// Checked boxes, slider fill, selection, focus
+    pub accent: Color,
+    pub text: Color,
+    /// Placeholders and the unselected half of things
+    pub muted: Color,
+    pub border: Color,
+}
+
+impl Style {
+    pub const LIGHT: Style = Style {
+        font_size: 20,
+        item_height: 32.0,
+        spacing: 8.0,
+        padding: 8.0,
+        background: RAYWHITE,
+        surface: LIGHTGRAY,
+        accent: BLUE,
+        text: BLACK,
+        muted: GRAY,
+        border: DARKGRAY,
+    };
+}
+
+/// `color` moved toward white for positive `amount`, black for negative
+pub fn shade(color: Color, amount: f32) -> Color {
+    let (target, t) = if amount >= 0.0 { (255.0, amount) } else { (0.0, -amount) };
+    let mix = |c: u8| (c as f32 + (target - c as f32) * t.min(1.0)) as u8;
+    Color { r: mix(color.r), g: mix(color.g), b: mix(color.b), a: color.a }
+}
+
+// ============================
+// Input
+// ============================
+/// Editing keys seen this frame, already combined with Shift and Ctrl
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Keys {
+    pub backspace: bool,
+    pub delete: bool,
+    pub left: bool,
+    pub right: bool,
+    pub home: bool,
+    pub end: bool,
+    pub enter: bool,
+    pub escape: bool,
+    /// Held; arrows extend the selection
+    pub shift: bool,
+    pub select_all: bool,
+    pub copy: bool,
+    pub cut: bool,
+    pub paste: bool,
+}
+
+/// Everything the widgets read from the mouse and keyboard in one frame.
+/// `Input::poll` asks raylib; tests and replays can fill one in by hand.
+#[derive(Copy, Clone, Debug)]
+pub struct Input {
+    pub mouse: Vector2,
+    /// Left button went down this frame
+    pub pressed: bool,
+    pub down: bool,
+    /// Left button came up this frame
+    pub released: bool,
+    /// Wheel notches, positive away from the user
+    pub wheel: f32,
+    /// Printable ASCII typed this frame, as raylib's font has no more
+    pub typed: [u8; TYPED_CAP],
+    pub typed_len: usize,
+    pub keys: Keys,
+}
+
+impl Input {
+    pub const NONE: Input = Input {
+        mouse: Vector2 { x: -1.0, y: -1.0 },
+        pressed: false,
+        down: false,
+        released: false,
+        wheel: 0.0,
+        typed: [0; TYPED_CAP],
+        typed_len: 0,
+        keys: Keys {
+            backspace: false,
+            delete: false,
+            left: false,
+            right: false,
+            home: false,
+            end: false,
+            enter: false,
+            escape: false,
+            shift: false,
+            select_all: false,
+            copy: false,
+            cut: false,
+            paste: false,
+        },
+    };
+
+    /// Read this frame's input from raylib; needs an open window
+    pub fn poll() -> Input {
+        unsafe {
+            let mut input = Input {
+                mouse: GetMousePosition(),
+                pressed: IsMouseButtonPressed(MOUSE_LEFT),
+                down: IsMouseButtonDown(MOUSE_LEFT),
+                released: IsMouseButtonReleased(MOUSE_LEFT),
+                wheel: GetMouseWheelMove(),
+                ..Input::NONE
+            };
+            loop {
+                let c = GetCharPressed();
+                if c == 0 {
+                    break;
+                }
+                if (32..127).contains(&c) && input.typed_len < TYPED_CAP {
+                    input.typed[input.typed_len] = c as u8;
+                    input.typed_len += 1;
+                }
+            }
+            let repeat = |key: i32| IsKeyPressed(key) || IsKeyPressedRepeat(key);
+            let ctrl = IsKeyDown(KEY_LEFT_CONTROL) || IsKeyDown(KEY_RIGHT_CONTROL);
+            input.keys = Keys {
+                backspace: repeat(KEY_BACKSPACE),
+                delete: repeat(KEY_DELETE),
+                left: repeat(KEY_LEFT),
+                right: repeat(KEY_RIGHT),
+                home: IsKeyPressed(KEY_HOME),
+                end: IsKeyPressed(KEY_END),
+                enter: IsKeyPressed(KEY_ENTER),
+                escape: IsKeyPressed(KEY_ESCAPE),
+                shift: IsKeyDown(KEY_LEFT_SHIFT) || IsKeyDown(KEY_RIGHT_SHIFT),
+                select_all: ctrl && IsKeyPressed(KEY_A),
+                copy: ctrl && IsKeyPressed(KEY_C),
+                cut: ctrl && IsKeyPressed(KEY_X),
+                paste: ctrl && IsKeyPressed(KEY_V),
+            };
+            input
+        }
+    }
+
+    pub fn typed(&self) -> &[u8] {
+        &self.typed[..self.typed_len]
+    }
+}
+
+// ============================
+// Text
+// ============================
+/// NUL-terminated copy of some text for raylib, cut off at `TEXT_CAP`
+#[derive(Copy, Clone)]
+pub struct CText {
+    buf: [u8; TEXT_CAP],
+    len: usize,
+}
+
+impl CText {
+    pub const fn new() -> CText {
+        CText { buf: [0; TEXT_CAP], len: 0 }
+    }
+
+    pub fn from_str(s: &str) -> CText {
+        let mut text = CText::new();
+        let _ = text.write_str(s);
+        text
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only whole chars are ever copied in
+        unsafe { core::str::from_utf8_unchecked(&self.buf[..self.len]) }
+    }
+
+    pub fn as_ptr(&self) -> *const i8 {
+        self.buf.as_ptr() as *const i8
+    }
+}
+
+impl fmt::Write for CText {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            let mut utf8 = [0u8; 4];
+            let bytes = c.encode_utf8(&mut utf8).as_bytes();
+            if self.len + bytes.len() >= TEXT_CAP {
+                return Err(fmt::Error);
+            }
+            self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+            self.len += bytes.len();
+        }
+        self.buf[self.len] = 0;
+        Ok(())
+    }
+}
+
+/// Editable line of ASCII text with a cursor and a selection, the state
+/// behind `Ui::text_input`. Holds up to `N - 1` characters.
+#[derive(Copy, Clone)]
+pub struct TextInput<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+    cursor: usize,
+    /// Other end of the selection; equal to `cursor` when nothing is
+    /// selected
+    anchor: usize,
+}
+
+impl<const N: usize> TextInput<N> {
+    pub const fn new() -> Self {
+        Self { buf: [0; N], len: 0, cursor: 0, anchor: 0 }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only printable ASCII gets in
+        unsafe { core::str::from_utf8_unchecked(&self.buf[..self.len]) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Start and end of the selection, in order
+    pub fn selection(&self) -> (usize, usize) {
+        (self.cursor.min(self.anchor), self.cursor.max(self.anchor))
+    }
+
+    pub fn selected(&self) -> &str {
+        let (start, end) = self.selection();
+        &self.as_str()[start..end]
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.cursor = 0;
+        self.anchor = 0;
+        self.terminate();
+    }
+
+    /// Replace everything, leaving the cursor at the end
+    pub fn set(&mut self, text: &str) {
+        self.clear();
+        self.replace_selection(text);
+    }
+
+    /// Put the cursor at `to`; with `extend` the selection grows to it
+    pub fn move_cursor(&mut self, to: usize, extend: bool) {
+        self.cursor = to.min(self.len);
+        if !extend {
+            self.anchor = self.cursor;
+        }
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.cursor = self.len;
+    }
+
+    /// Type `text` over the selection. Anything but printable ASCII is
+    /// skipped and whatever doesn't fit is dropped. True if the text
+    /// changed.
+    pub fn replace_selection(&mut self, text: &str) -> bool {
+        let mut changed = self.delete_selection();
+        for &b in text.as_bytes() {
+            if !(32..127).contains(&b) || self.len + 1 >= N {
+                continue;
+            }
+            self.buf.copy_within(self.cursor..self.len, self.cursor + 1);
+            self.buf[self.cursor] = b;
+            self.len += 1;
+            self.cursor += 1;
+            changed = true;
+        }
+        self.anchor = self.cursor;
+        self.terminate();
+        changed
+    }
+
+    /// Backspace (`forward` false) or Delete: the selection if there is
+    /// one, otherwise the character beside the cursor
+    pub fn erase(&mut self, forward: bool) -> bool {
+        if self.cursor == self.anchor {
+            match forward {
+                true if self.cursor < self.len => self.anchor = self.cursor + 1,
+                false if self.cursor > 0 => self.anchor = self.cursor - 1,
+                _ => return false,
+            }
+        }
+        self.delete_selection()
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        let (start, end) = self.selection();
+        if start == end {
+            return false;
+        }
+        self.buf.copy_within(end..self.len, start);
+        self.len -= end - start;
+        self.cursor = start;
+        self.anchor = start;
+        self.terminate();
+        true
+    }
+
+    fn terminate(&mut self) {
+        if self.len < N {
+            self.buf[self.len] = 0;
+        }
+    }
+}
+
+impl<const N: usize> Default for TextInput<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================
+// Identity
+// ============================
+/// FNV-1a, enough to tell widget labels apart
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Text before `##`
+fn shown(label: &str) -> &str {
+    match label.find("##") {
+        Some(at) => &label[..at],
+        None => label,
+    }
+}
+
+// ============================
+// Layout
+// ============================
+/// Widgets fill cells left to right, `columns` to a line, then wrap; one
+/// column stacks them top to bottom. A line is as tall as its tallest
+/// cell.
+#[derive(Copy, Clone)]
+struct Layout {
+    area: Rectangle,
+    columns: u32,
+    count: u32,
+    /// Top of the current line
+    top: f32,
+    /// Height of the current line so far
+    line: f32,
+    clip: Rectangle,
+}
+
+impl Layout {
+    fn new(area: Rectangle, columns: u32, clip: Rectangle) -> Layout {
+        Layout { area, columns: columns.max(1), count: 0, top: area.y, line: 0.0, clip }
+    }
+
+    /// Next cell, `height` tall
+    fn place(&mut self, height: f32, spacing: f32) -> Rectangle {
+        let index = self.count % self.columns;
+        if index == 0 && self.count > 0 {
+            self.top += self.line + spacing;
+            self.line = 0.0;
+        }
+        let n = self.columns as f32;
+        let width = ((self.area.width - spacing * (n - 1.0)) / n).max(0.0);
+        self.count += 1;
+        self.line = self.line.max(height);
+        Rectangle::new(self.area.x + index as f32 * (width + spacing), self.top, width, height)
+    }
+
+    /// The cell just placed turned out `height` tall
+    fn grow(&mut self, height: f32) {
+        self.line = self.line.max(height);
+    }
+
+    fn used(&self) -> f32 {
+        self.top + self.line - self.area.y
+    }
+}
+
+/// What the mouse did to a widget this frame
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Response {
+    /// Under the mouse, and nothing else is being pressed
+    pub hovered: bool,
+    /// The button went down on it this frame
+    pub pressed: bool,
+    /// Pressed on it earlier and still down: a drag
+    pub held: bool,
+    /// Pressed and released on it
+    pub clicked: bool,
+}
+
+/// List of an open dropdown, copied so it can be drawn over everything
+/// at `Ui::end`
+#[derive(Copy, Clone)]
+struct Popup {
+    visible: bool,
+    rect: Rectangle,
+    labels: [CText; POPUP_ROWS],
+    count: usize,
+    hovered: Option<usize>,
+    current: Option<usize>,
+}
+
+// ============================
+// Ui
+// ============================
+pub struct Ui {
+    pub style: Style,
+    input: Input,
+    hot: u64,
+    active: u64,
+    focus: u64,
+    /// The focused widget was drawn this frame
+    focus_seen: bool,
+    /// A text input took this frame's click
+    focus_clicked: bool,
+    /// Dropdown whose list is open
+    open: u64,
+    open_seen: bool,
+    /// First item shown by the open dropdown
+    popup_first: usize,
+    popup: Popup,
+    /// Last frame's popup; widgets under it don't get the mouse
+    blocked: Option<Rectangle>,
+    ids: [u64; ID_DEPTH],
+    id_depth: usize,
+    layouts: [Layout; LAYOUT_DEPTH],
+    depth: usize,
+}
+
+impl Ui {
+    pub fn new() -> Ui {
+        let root = Layout::new(Rectangle::new(0.0, 0.0, 0.0, 0.0), 1, UNCLIPPED);
+        Ui {
+            style: Style::LIGHT,
+            input: Input::NONE,
+            hot: 0,
+            active: 0,
+            focus: 0,
+            focus_seen: false,
+            focus_clicked: false,
+            open: 0,
+            open_seen: false,
+            popup_first: 0,
+            popup: Popup {
+                visible: false,
+                rect: Rectangle::new(0.0, 0.0, 0.0, 0.0),
+                labels: [CText::new(); POPUP_ROWS],
+                count: 0,
+                hovered: None,
+                current: None,
+            },
+            blocked: None,
+            ids: [FNV_OFFSET; ID_DEPTH],
+            id_depth: 1,
+            layouts: [root; LAYOUT_DEPTH],
+            depth: 1,
+        }
+    }
+
+    /// Start a frame. Widgets go in a column filling the window, inside
+    /// `style.padding`, until a layout call says otherwise.
+    pub fn begin(&mut self, input: Input) {
+        self.input = input;
+        self.hot = 0;
+        self.id_depth = 1;
+        let screen = unsafe { Rectangle::new(0.0, 0.0, GetScreenWidth() as f32, GetScreenHeight() as f32) };
+        self.layouts[0] = Layout::new(screen.inset(self.style.padding), 1, UNCLIPPED);
+        self.depth = 1;
+    }
+
+    /// Finish a frame: draw the open dropdown on top and settle which
+    /// widget keeps focus
+    pub fn end(&mut self) {
+        self.blocked = None;
+        if self.popup.visible {
+            self.draw_popup();
+            self.blocked = Some(self.popup.rect);
+            self.popup.visible = false;
+        }
+        if !self.open_seen {
+            self.open = 0;
+        }
+        self.open_seen = false;
+        if self.input.released {
+            self.active = 0;
+        }
+        if !self.focus_seen || self.input.pressed && !self.focus_clicked {
+            self.focus = 0;
+        }
+        self.focus_seen = false;
+        self.focus_clicked = false;
+    }
+
+    pub fn input(&self) -> &Input {
+        &self.input
+    }
+
+    // ----------------------------
+    // IDs
+    // ----------------------------
+    /// Scope the IDs of the widgets that follow, for labels that repeat,
+    /// such as a button per list row
+    pub fn push_id(&mut self, scope: impl Hash) {
+        // Past ID_DEPTH scopes still count, so pops pair up, but share the
+        // deepest stored one
+        let mut hasher = Fnv(self.ids[self.id_depth.min(ID_DEPTH) - 1]);
+        scope.hash(&mut hasher);
+        if self.id_depth < ID_DEPTH {
+            self.ids[self.id_depth] = hasher.finish();
+        }
+        self.id_depth += 1;
+    }
+
+    pub fn pop_id(&mut self) {
+        if self.id_depth > 1 {
+            self.id_depth -= 1;
+        }
+    }
+
+    /// ID of `label` in the current scope; never 0
+    pub fn id(&self, label: &str) -> u64 {
+        let mut hasher = Fnv(self.ids[self.id_depth.min(ID_DEPTH) - 1]);
+        hasher.write(label.as_bytes());
+        hasher.finish().max(1)
+    }
+
+    // ----------------------------
+    // Layout
+    // ----------------------------
+    fn layout(&mut self) -> &mut Layout {
+        &mut self.layouts[self.depth - 1]
+    }
+
+    /// Claim the next cell of the current layout for a custom widget
+    pub fn allocate(&mut self, height: f32) -> Rectangle {
+        let spacing = self.style.spacing;
+        self.layout().place(height, spacing)
+    }
+
+    /// Empty cell `height` tall
+    pub fn space(&mut self, height: f32) {
+        self.allocate(height);
+    }
+
+    /// Run `contents` in `layout`, then return the height it used
+    fn nest(&mut self, layout: Layout, contents: impl FnOnce(&mut Ui)) -> f32 {
+        if self.depth == LAYOUT_DEPTH {
+            // Too deep: keep placing in the current layout
+            contents(self);
+            return 0.0;
+        }
+        self.layouts[self.depth] = layout;
+        self.depth += 1;
+        contents(self);
+        self.depth -= 1;
+        self.layouts[self.depth].used()
+    }
+
+    /// Lay `contents` out in `columns` equal columns, wrapping onto more
+    /// lines as needed, so a row is also a grid
+    pub fn row(&mut self, columns: u32, contents: impl FnOnce(&mut Ui)) {
+        self.padding(0.0, columns, contents);
+    }
+
+    /// Stack `contents` top to bottom in one cell of the current layout
+    pub fn column(&mut self, contents: impl FnOnce(&mut Ui)) {
+        self.padding(0.0, 1, contents);
+    }
+
+    /// `columns` wide layout inset by `amount` on every side
+    pub fn padding(&mut self, amount: f32, columns: u32, contents: impl FnOnce(&mut Ui)) {
+        let cell = self.allocate(0.0);
+        let clip = self.layout().clip;
+        let area = Rectangle::new(cell.x + amount, cell.y + amount, (cell.width - 2.0 * amount).max(0.0), 0.0);
+        let used = self.nest(Layout::new(area, columns, clip), contents);
+        self.layout().grow(used + 2.0 * amount);
+    }
+
+    /// Column at a fixed place in the window, outside the current layout
+    pub fn area(&mut self, rect: Rectangle, contents: impl FnOnce(&mut Ui)) {
+        let clip = self.layout().clip;
+        self.nest(Layout::new(rect, 1, clip), contents);
+    }
+
+    /// Column `height` tall on the panel background, clipped to its edge
+    pub fn panel(&mut self, height: f32, contents: impl FnOnce(&mut Ui)) {
+        let rect = self.allocate(height);
+        self.fill(rect, self.style.background);
+        self.outline(rect, self.style.border);
+        let parent = self.layout().clip;
+        let inner = rect.inset(self.style.padding);
+        let clip = rect.intersect(&parent);
+        set_clip(clip);
+        self.nest(Layout::new(inner, 1, clip), contents);
+        set_clip(parent);
+    }
+
+    /// Panel whose contents scroll with the wheel or the bar on the right.
+    /// `scroll` is how far down the contents are shown.
+    pub fn scroll_panel(&mut self, label: &str, height: f32, scroll: &mut f32, contents: impl FnOnce(&mut Ui)) {
+        let id = self.id(label);
+        let rect = self.allocate(height);
+        self.fill(rect, self.style.background);
+        self.outline(rect, self.style.border);
+
+        let padding = self.style.padding;
+        let bar = Rectangle::new(rect.x + rect.width - SCROLLBAR - 2.0, rect.y + 2.0, SCROLLBAR, rect.height - 4.0);
+        let wheel = self.input.wheel;
+        if wheel != 0.0 && self.mouse_over(rect) {
+            *scroll -= wheel * self.style.item_height * 2.0;
+        }
+
+        let parent = self.layout().clip;
+        let clip = rect.intersect(&parent);
+        let inner = Rectangle::new(
+            rect.x + padding,
+            rect.y + padding - scroll.max(0.0),
+            (rect.width - 2.0 * padding - SCROLLBAR).max(0.0),
+            0.0,
+        );
+        set_clip(clip);
+        let used = self.nest(Layout::new(inner, 1, clip), contents);
+        set_clip(parent);
+
+        let max = (used + 2.0 * padding - rect.height).max(0.0);
+        if max > 0.0 {
+            let thumb = (bar.height * rect.height / (used + 2.0 * padding)).max(SCROLLBAR * 2.0);
+            let response = self.interact(id, bar);
+            if response.held {
+                let t = (self.input.mouse.y - bar.y - thumb / 2.0) / (bar.height - thumb);
+                *scroll = t * max;
+            }
+            *scroll = scroll.clamp(0.0, max);
+            let y = bar.y + (bar.height - thumb) * *scroll / max;
+            self.fill(bar, shade(self.style.surface, 0.4));
+            let color = if response.hovered || response.held { self.style.accent } else { self.style.muted };
+            self.fill(Rectangle::new(bar.x, y, bar.width, thumb), color);
+        } else {
+            *scroll = 0.0;
+        }
+    }
+
+    // ----------------------------
+    // Interaction
+    // ----------------------------
+    /// Whether the mouse is over `rect`, inside the current clip and not
+    /// under an open dropdown
+    pub fn mouse_over(&mut self, rect: Rectangle) -> bool {
+        let mouse = self.input.mouse;
+        let blocked = self.blocked.is_some_and(|b| b.contains(mouse));
+        rect.contains(mouse) && self.layout().clip.contains(mouse) && !blocked
+    }
+
+    /// Hit-test widget `id` occupying `rect` and track it as hot or
+    /// active; the building block of every widget here
+    pub fn interact(&mut self, id: u64, rect: Rectangle) -> Response {
+        let over = self.mouse_over(rect);
+        if over && (self.active == 0 || self.active == id) {
+            self.hot = id;
+        }
+        let mut response = Response { hovered: self.hot == id, ..Response::default() };
+        if response.hovered && self.input.pressed {
+            self.active = id;
+            response.pressed = true;
+        }
+        if self.active == id {
+            response.held = self.input.down || response.pressed;
+            response.clicked = self.input.released && over;
+        }
+        response
+    }
+
+    // ----------------------------
+    // Widgets
+    // ----------------------------
+    /// One line of text
+    pub fn label(&mut self, text: impl fmt::Display) {
+        let rect = self.allocate(self.style.item_height);
+        let mut line = CText::new();
+        let _ = write!(line, "{}", text);
+        self.text(&line, rect.x, self.text_top(rect), self.style.text);
+    }
+
+    pub fn button(&mut self, label: &str) -> bool {
+        self.tinted_button(label, self.style.surface)
+    }
+
+    /// Button filled with `fill` instead of `style.surface`
+    pub fn tinted_button(&mut self, label: &str, fill: Color) -> bool {
+        let id = self.id(label);
+        let rect = self.allocate(self.style.item_height);
+        let response = self.interact(id, rect);
+        let color = match (response.hovered, response.held) {
+            (true, true) => shade(fill, -0.2),
+            (true, false) => shade(fill, 0.25),
+            _ => fill,
+        };
+        self.fill(rect, color);
+        self.outline(rect, self.style.border);
+        let text = CText::from_str(shown(label));
+        let x = rect.x + (rect.width - self.measure(&text)) / 2.0;
+        self.text(&text, x, self.text_top(rect), self.style.text);
+        response.clicked
+    }
+
+    /// Box that toggles `checked`; true when clicked
+    pub fn checkbox(&mut self, label: &str, checked: &mut bool) -> bool {
+        let id = self.id(label);
+        let rect = self.allocate(self.style.item_height);
+        let response = self.interact(id, rect);
+        if response.clicked {
+            *checked = !*checked;
+        }
+        let size = self.style.font_size as f32;
+        let square = Rectangle::new(rect.x, rect.y + (rect.height - size) / 2.0, size, size);
+        self.fill(square, if response.hovered { shade(self.style.surface, 0.25) } else { self.style.surface });
+        self.outline(square, self.style.border);
+        if *checked {
+            self.fill(square.inset(4.0), self.style.accent);
+        }
+        let text = CText::from_str(shown(label));
+        self.text(&text, square.x + size + self.style.spacing, self.text_top(rect), self.style.text);
+        response.clicked
+    }
+
+    /// Drag along a track to pick `value` in `min..=max`; true when it
+    /// changed
+    pub fn slider(&mut self, label: &str, value: &mut f32, min: f32, max: f32) -> bool {
+        let id = self.id(label);
+        let rect = self.allocate(self.style.item_height);
+        let response = self.interact(id, rect);
+        let before = *value;
+        if response.held && rect.width > 0.0 {
+            let t = ((self.input.mouse.x - rect.x) / rect.width).clamp(0.0, 1.0);
+            *value = min + t * (max - min);
+        }
+        let t = if max > min { ((*value - min) / (max - min)).clamp(0.0, 1.0) } else { 0.0 };
+        self.fill(rect, self.style.surface);
+        let accent = if response.hovered || response.held { shade(self.style.accent, 0.2) } else { self.style.accent };
+        self.fill(Rectangle::new(rect.x, rect.y, rect.width * t, rect.height), accent);
+        self.outline(rect, self.style.border);
+        let mut text = CText::new();
+        let _ = write!(text, "{}: {:.2}", shown(label), *value);
+        let x = rect.x + (rect.width - self.measure(&text)) / 2.0;
+        self.text(&text, x, self.text_top(rect), self.style.text);
+        *value != before
+    }
+
+    /// One-line text field: click to focus, type, Backspace/Delete,
+    /// arrows and Home/End (with Shift to select), drag to select,
+    /// Ctrl+A/C/X/V. Enter or Escape lets go of the keyboard. The label is
+    /// shown as a placeholder while empty. True when the text changed.
+    pub fn text_input<const N: usize>(&mut self, label: &str, input: &mut TextInput<N>) -> bool {
+        let id = self.id(label);
+        let rect = self.allocate(self.style.item_height);
+        let response = self.interact(id, rect);
+        let padding = self.style.padding;
+        let focused_before = self.focus == id;
+
+        // Scroll the text so the cursor stays inside the box
+        let room = (rect.width - 2.0 * padding).max(0.0);
+        let cursor_x = self.measure_prefix(input.as_str(), input.cursor());
+        let offset = (cursor_x - room).max(0.0);
+        let origin = rect.x + padding - offset;
+
+        let mut changed = false;
+        if response.pressed {
+            self.focus = id;
+            self.focus_clicked = true;
+            let at = self.hit_char(input.as_str(), self.input.mouse.x - origin);
+            input.move_cursor(at, focused_before && self.input.keys.shift);
+        } else if response.held {
+            let at = self.hit_char(input.as_str(), self.input.mouse.x - origin);
+            input.move_cursor(at, true);
+        }
+
+        if self.focus == id {
+            self.focus_seen = true;
+            let keys = self.input.keys;
+            let typed = self.input.typed;
+            let typed = unsafe { core::str::from_utf8_unchecked(&typed[..self.input.typed_len]) };
+            if !typed.is_empty() {
+                changed |= input.replace_selection(typed);
+            }
+            if keys.backspace {
+                changed |= input.erase(false);
+            }
+            if keys.delete {
+                changed |= input.erase(true);
+            }
+            let (start, end) = input.selection();
+            if keys.left {
+                let to = if start != end && !keys.shift { start } else { input.cursor().saturating_sub(1) };
+                input.move_cursor(to, keys.shift);
+            }
+            if keys.right {
+                let to = if start != end && !keys.shift { end } else { input.cursor() + 1 };
+                input.move_cursor(to, keys.shift);
+            }
+            if keys.home {
+                input.move_cursor(0, keys.shift);
+            }
+            if keys.end {
+                input.move_cursor(input.len(), keys.shift);
+            }
+            if keys.select_all {
+                input.select_all();
+            }
+            if (keys.copy || keys.cut) && !input.selected().is_empty() {
+                let selected = CText::from_str(input.selected());
+                unsafe { SetClipboardText(selected.as_ptr()) };
+                if keys.cut {
+                    changed |= input.erase(false);
+                }
+            }
+            if keys.paste {
+                changed |= input.replace_selection(clipboard());
+            }
+            if keys.enter || keys.escape {
+                self.focus = 0;
+            }
+        }
+
+        let focused = self.focus == id;
+        self.fill(rect, shade(self.style.surface, 0.5));
+        self.outline(rect, if focused { self.style.accent } else { self.style.border });
+
+        let parent = self.layout().clip;
+        let clip = rect.inset(2.0).intersect(&parent);
+        set_clip(clip);
+        let top = self.text_top(rect);
+        let height = self.style.font_size as f32;
+        if input.is_empty() && !focused {
+            let text = CText::from_str(shown(label));
+            self.text(&text, rect.x + padding, top, self.style.muted);
+        } else {
+            let (start, end) = input.selection();
+            if focused && start != end {
+                let x0 = self.measure_prefix(input.as_str(), start);
+                let x1 = self.measure_prefix(input.as_str(), end);
+                self.fill(Rectangle::new(origin + x0, top, x1 - x0, height), shade(self.style.accent, 0.6));
+            }
+            let text = CText::from_str(input.as_str());
+            self.text(&text, origin, top, self.style.text);
+            if focused {
+                let x = origin + self.measure_prefix(input.as_str(), input.cursor());
+                self.fill(Rectangle::new(x, top, 2.0, height), self.style.text);
+            }
+        }
+        set_clip(parent);
+        changed
+    }
+
+    /// Closed, shows `items[*selected]`; click to open the list, click an
+    /// item to pick it. The list is drawn over everything at `Ui::end`.
+    /// True when the selection changed.
+    pub fn dropdown(&mut self, label: &str, items: &[&str], selected: &mut usize) -> bool {
+        let id = self.id(label);
+        let rect = self.allocate(self.style.item_height);
+        let response = self.interact(id, rect);
+        let rows = items.len().min(POPUP_ROWS);
+        let list = Rectangle::new(rect.x, rect.y + rect.height, rect.width, rows as f32 * rect.height);
+        let was_open = self.open == id;
+
+        let mut changed = false;
+        let mut hovered = None;
+        if was_open {
+            let mouse = self.input.mouse;
+            if list.contains(mouse) {
+                if self.input.wheel < 0.0 {
+                    self.popup_first += 1;
+                } else if self.input.wheel > 0.0 {
+                    self.popup_first = self.popup_first.saturating_sub(1);
+                }
+                let row = ((mouse.y - list.y) / rect.height) as usize;
+                hovered = Some(row.min(rows.saturating_sub(1)));
+                if self.input.pressed {
+                    let index = self.popup_first.min(items.len() - rows) + row;
+                    if index < items.len() && index != *selected {
+                        *selected = index;
+                        changed = true;
+                    }
+                    self.open = 0;
+                }
+            } else if self.input.pressed && !rect.contains(mouse) {
+                self.open = 0;
+            }
+        }
+        if response.clicked {
+            if was_open {
+                self.open = 0;
+            } else {
+                self.open = id;
+                // Open with the selection in view
+                self.popup_first = selected.saturating_sub(POPUP_ROWS / 2);
+            }
+        }
+
+        let fill = if response.hovered { shade(self.style.surface, 0.25) } else { self.style.surface };
+        self.fill(rect, fill);
+        self.outline(rect, if self.open == id { self.style.accent } else { self.style.border });
+        let current = CText::from_str(items.get(*selected).copied().unwrap_or(shown(label)));
+        let top = self.text_top(rect);
+        self.text(&current, rect.x + self.style.padding, top, self.style.text);
+        let arrow = CText::from_str(if self.open == id { "^" } else { "v" });
+        let x = rect.x + rect.width - self.style.padding - self.measure(&arrow);
+        self.text(&arrow, x, top, self.style.muted);
+
+        if self.open == id && rows > 0 {
+            self.open_seen = true;
+            let first = self.popup_first.min(items.len() - rows);
+            self.popup_first = first;
+            let popup = &mut self.popup;
+            popup.visible = true;
+            popup.rect = list;
+            popup.count = rows;
+            popup.hovered = hovered;
+            popup.current = selected.checked_sub(first).filter(|&row| row < rows);
+            for (row, item) in items[first..first + rows].iter().enumerate() {
+                popup.labels[row] = CText::from_str(item);
+            }
+        }
+        changed
+    }
+
+    /// Strip of tabs across the layout; true when another one was picked
+    pub fn tabs(&mut self, labels: &[&str], selected: &mut usize) -> bool {
+        let rect = self.allocate(self.style.item_height);
+        if labels.is_empty() {
+            return false;
+        }
+        let width = rect.width / labels.len() as f32;
+        let mut changed = false;
+        for (i, label) in labels.iter().enumerate() {
+            let tab = Rectangle::new(rect.x + i as f32 * width, rect.y, width, rect.height);
+            let response = self.interact(self.id(label), tab);
+            if response.clicked && *selected != i {
+                *selected = i;
+                changed = true;
+            }
+            let current = *selected == i;
+            let fill = match (current, response.hovered) {
+                (true, _) => self.style.background,
+                (false, true) => shade(self.style.surface, 0.25),
+                (false, false) => self.style.surface,
+            };
+            self.fill(tab, fill);
+            self.outline(tab, self.style.border);
+            if current {
+                self.fill(Rectangle::new(tab.x, tab.y + tab.height - 3.0, tab.width, 3.0), self.style.accent);
+            }
+            let text = CText::from_str(shown(label));
+            let x = tab.x + (tab.width - self.measure(&text)) / 2.0;
+            let color = if current { self.style.text } else { self.style.muted };
+            self.text(&text, x, self.text_top(tab), color);
+        }
+        changed
+    }
+
+    // ----------------------------
+    // Drawing
+    // ----------------------------
+    fn fill(&self, rect: Rectangle, color: Color) {
+        unsafe { DrawRectangle(rect.x as i32, rect.y as i32, rect.width as i32, rect.height as i32, color) };
+    }
+
+    fn outline(&self, rect: Rectangle, color: Color) {
+        unsafe { DrawRectangleLines(rect.x as i32, rect.y as i32, rect.width as i32, rect.height as i32, color) };
+    }
+
+    fn text(&self, text: &CText, x: f32, y: f32, color: Color) {
+        unsafe { DrawText(text.as_ptr(), x as i32, y as i32, self.style.font_size, color) };
+    }
+
+    fn measure(&self, text: &CText) -> f32 {
+        unsafe { MeasureText(text.as_ptr(), self.style.font_size) as f32 }
+    }
+
+    /// Width of the first `chars` characters of `s`
+    fn measure_prefix(&self, s: &str, chars: usize) -> f32 {
+        self.measure(&CText::from_str(&s[..chars.min(s.len())]))
+    }
+
+    /// Character boundary of `s` nearest `x` pixels from its start
+    fn hit_char(&self, s: &str, x: f32) -> usize {
+        let mut best = 0;
+        let mut best_distance = f32::MAX;
+        for i in 0..=s.len() {
+            let distance = (self.measure_prefix(s, i) - x).abs();
+            if distance < best_distance {
+                best = i;
+                best_distance = distance;
+            }
+        }
+        best
+    }
+
+    /// Baseline-free top for text centred in `rect`
+    fn text_top(&self, rect: Rectangle) -> f32 {
+        rect.y + (rect.height - self.style.font_size as f32) / 2.0
+    }
+
+    fn draw_popup(&self) {
+        let popup = &self.popup;
+        let row_height = popup.rect.height / popup.count.max(1) as f32;
+        self.fill(popup.rect, self.style.background);
+        for (row, label) in popup.labels[..popup.count].iter().enumerate() {
+            let rect = Rectangle::new(popup.rect.x, popup.rect.y + row as f32 * row_height, popup.rect.width, row_height);
+            if popup.current == Some(row) {
+                self.fill(rect, shade(self.style.accent, 0.6));
+            } else if popup.hovered == Some(row) {
+                self.fill(rect, self.style.surface);
+            }
+            self.text(label, rect.x + self.style.padding, self.text_top(rect), self.style.text);
+        }
+        self.outline(popup.rect, self.style.accent);
+    }
+}
+
+impl Default for Ui {
+    fn default() -> Self {
+        Ui::new()
+    }
+}
+
+/// Scissor to `clip`, or none for `UNCLIPPED`
+fn set_clip(clip: Rectangle) {
+    unsafe {
+        if clip == UNCLIPPED {
+            EndScissorMode();
+        } else {
+            BeginScissorMode(clip.x as i32, clip.y as i32, clip.width as i32, clip.height as i32);
+        }
+    }
+}
+
+/// System clipboard as text, empty if it holds none
+fn clipboard() -> &'static str {
+    unsafe {
+        let text = GetClipboardText();
+        if text.is_null() {
+            return "";
+        }
+        let bytes = core::ffi::CStr::from_ptr(text).to_bytes();
+        core::str::from_utf8(bytes).unwrap_or("")
+    }
+}
@@ -0,0 +1,231 @@
+// raylib/window.rs - The window and raylib's begin/end pairs as guards
+//
+// `Window` opens on `new` and closes on drop. Drawing happens through the
+// `DrawingScope` that `begin_drawing` returns, which ends the frame when
+// it goes out of scope; `Mode2D` and `Mode3D` do the same for a camera.
+// Each guard borrows the one it came from mutably, so a frame can't be
+// begun twice or a 3D pass outlive its frame, and holding one is what
+// makes its drawing methods safe to call.
+
+use super::*;
+use core::ffi::CStr;
+use core::marker::PhantomData;
+
+/// The raylib window; only one can be open at a time
+pub struct Window {
+    // raylib's state is global and tied to the thread that opened it
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Window {
+    pub fn new(width: i32, height: i32, title: &CStr) -> Window {
+        unsafe { InitWindow(width, height, title.as_ptr()) };
+        Window { _not_send: PhantomData }
+    }
+
+    /// `new` after `SetConfigFlags(flags)`, e.g. `FLAG_MSAA_4X_HINT`
+    pub fn with_flags(width: i32, height: i32, title: &CStr, flags: u32) -> Window {
+        unsafe { SetConfigFlags(flags) };
+        Window::new(width, height, title)
+    }
+
+    pub fn should_close(&self) -> bool {
+        unsafe { WindowShouldClose() }
+    }
+
+    pub fn set_target_fps(&mut self, fps: i32) {
+        unsafe { SetTargetFPS(fps) };
+    }
+
+    pub fn width(&self) -> i32 {
+        unsafe { GetScreenWidth() }
+    }
+
+    pub fn height(&self) -> i32 {
+        unsafe { GetScreenHeight() }
+    }
+
+    /// Seconds the last frame took
+    pub fn frame_time(&self) -> f32 {
+        unsafe { GetFrameTime() }
+    }
+
+    /// Seconds since the window opened
+    pub fn time(&self) -> f64 {
+        unsafe { GetTime() }
+    }
+
+    pub fn begin_drawing(&mut self) -> DrawingScope<'_> {
+        unsafe { BeginDrawing() };
+        DrawingScope { _window: PhantomData }
+    }
+}
+
+impl Drop for Window {
+    fn drop(&mut self) {
+        unsafe { CloseWindow() };
+    }
+}
+
+/// One frame, between `BeginDrawing` and `EndDrawing`
+pub struct DrawingScope<'w> {
+    _window: PhantomData<&'w mut Window>,
+}
+
+impl DrawingScope<'_> {
+    pub fn begin_mode_2d(&mut self, camera: Camera2D) -> Mode2D<'_> {
+        unsafe { BeginMode2D(camera) };
+        Mode2D { _scope: PhantomData }
+    }
+
+    pub fn begin_mode_3d(&mut self, camera: Camera3D) -> Mode3D<'_> {
+        unsafe { BeginMode3D(camera) };
+        Mode3D { _scope: PhantomData }
+    }
+
+    pub fn clear_background(&mut self, color: Color) {
+        unsafe { ClearBackground(color) };
+    }
+
+    pub fn draw_fps(&mut self, x: i32, y: i32) {
+        unsafe { DrawFPS(x, y) };
+    }
+
+    /// `text` in the built-in font, `size` pixels tall
+    pub fn draw_text(&mut self, text: &CStr, x: i32, y: i32, size: i32, color: Color) {
+        unsafe { DrawText(text.as_ptr(), x, y, size, color) };
+    }
+
+    pub fn draw_pixel(&mut self, x: i32, y: i32, color: Color) {
+        unsafe { DrawPixel(x, y, color) };
+    }
+
+    pub fn draw_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: Color) {
+        unsafe { DrawLine(x1, y1, x2, y2, color) };
+    }
+
+    pub fn draw_line_ex(&mut self, start: Vector2, end: Vector2, thick: f32, color: Color) {
+        unsafe { DrawLineEx(start, end, thick, color) };
+    }
+
+    pub fn draw_circle(&mut self, x: i32, y: i32, radius: f32, color: Color) {
+        unsafe { DrawCircle(x, y, radius, color) };
+    }
+
+    pub fn draw_circle_lines(&mut self, x: i32, y: i32, radius: f32, color: Color) {
+        unsafe { DrawCircleLines(x, y, radius, color) };
+    }
+
+    pub fn draw_rectangle(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        unsafe { DrawRectangle(x, y, width, height, color) };
+    }
+
+    pub fn draw_rectangle_rec(&mut self, rect: Rectangle, color: Color) {
+        unsafe { DrawRectangleRec(rect, color) };
+    }
+
+    pub fn draw_rectangle_lines(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        unsafe { DrawRectangleLines(x, y, width, height, color) };
+    }
+
+    pub fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        unsafe { DrawTriangle(v1, v2, v3, color) };
+    }
+
+    pub fn draw_texture(&mut self, texture: Texture2D, x: i32, y: i32, tint: Color) {
+        unsafe { DrawTexture(texture, x, y, tint) };
+    }
+}
+
+impl Drop for DrawingScope<'_> {
+    fn drop(&mut self) {
+        unsafe { EndDrawing() };
+    }
+}
+
+/// Drawing through a 2D camera, until `EndMode2D`
+pub struct Mode2D<'s> {
+    _scope: PhantomData<&'s mut ()>,
+}
+
+impl Mode2D<'_> {
+    pub fn draw_line_ex(&mut self, start: Vector2, end: Vector2, thick: f32, color: Color) {
+        unsafe { DrawLineEx(start, end, thick, color) };
+    }
+
+    pub fn draw_circle_v(&mut self, center: Vector2, radius: f32, color: Color) {
+        unsafe { DrawCircleV(center, radius, color) };
+    }
+
+    pub fn draw_rectangle_rec(&mut self, rect: Rectangle, color: Color) {
+        unsafe { DrawRectangleRec(rect, color) };
+    }
+
+    pub fn draw_texture_v(&mut self, texture: Texture2D, position: Vector2, tint: Color) {
+        unsafe { DrawTextureV(texture, position, tint) };
+    }
+}
+
+impl Drop for Mode2D<'_> {
+    fn drop(&mut self) {
+        unsafe { EndMode2D() };
+    }
+}
+
+/// Drawing through a 3D camera, until `EndMode3D`
+pub struct Mode3D<'s> {
+    _scope: PhantomData<&'s mut ()>,
+}
+
+impl Mode3D<'_> {
+    pub fn draw_line_3d(&mut self, start: Vector3, end: Vector3, color: Color) {
+        unsafe { DrawLine3D(start, end, color) };
+    }
+
+    pub fn draw_triangle_3d(&mut self, v1: Vector3, v2: Vector3, v3: Vector3, color: Color) {
+        unsafe { DrawTriangle3D(v1, v2, v3, color) };
+    }
+
+    /// `slices` lines each way, `spacing` apart, centered on the origin
+    pub fn draw_grid(&mut self, slices: i32, spacing: f32) {
+        unsafe { DrawGrid(slices, spacing) };
+    }
+
+    pub fn draw_cube(&mut self, position: Vector3, width: f32, height: f32, length: f32, color: Color) {
+        unsafe { DrawCube(position, width, height, length, color) };
+    }
+
+    pub fn draw_cube_wires(&mut self, position: Vector3, width: f32, height: f32, length: f32, color: Color) {
+        unsafe { DrawCubeWires(position, width, height, length, color) };
+    }
+
+    pub fn draw_sphere(&mut self, center: Vector3, radius: f32, color: Color) {
+        unsafe { DrawSphere(center, radius, color) };
+    }
+
+    pub fn draw_sphere_wires(&mut self, center: Vector3, radius: f32, rings: i32, slices: i32, color: Color) {
+        unsafe { DrawSphereWires(center, radius, rings, slices, color) };
+    }
+
+    pub fn draw_cylinder(&mut self, position: Vector3, radius_top: f32, radius_bottom: f32, height: f32, slices: i32, color: Color) {
+        unsafe { DrawCylinder(position, radius_top, radius_bottom, height, slices, color) };
+    }
+
+    pub fn draw_cylinder_wires(&mut self, position: Vector3, radius_top: f32, radius_bottom: f32, height: f32, slices: i32, color: Color) {
+        unsafe { DrawCylinderWires(position, radius_top, radius_bottom, height, slices, color) };
+    }
+
+    pub fn draw_plane(&mut self, center: Vector3, size: Vector2, color: Color) {
+        unsafe { DrawPlane(center, size, color) };
+    }
+
+    pub fn draw_model(&mut self, model: Model, position: Vector3, scale: f32, tint: Color) {
+        unsafe { DrawModel(model, position, scale, tint) };
+    }
+}
+
+impl Drop for Mode3D<'_> {
+    fn drop(&mut self) {
+        unsafe { EndMode3D() };
+    }
+}
@@ -9,6 +9,10 @@ use reactive::*;
 
 use core::str;
 
+mod raylib;
+use raylib::*;
+
+// Link against raylib and its system dependencies
 #[link(name = "raylib")]
 #[link(name = "m")]
 #[link(name = "pthread")]
@@ -16,46 +20,11 @@ use core::str;
 #[link(name = "rt")]
 #[link(name = "GL")]
 #[link(name = "c")]
-extern "C" {
-    fn InitWindow(width: i32, height: i32, title: *const u8);
-    fn CloseWindow();
-    fn WindowShouldClose() -> bool;
-    fn SetTargetFPS(fps: i32);
-
-    fn BeginDrawing();
-    fn EndDrawing();
-    fn ClearBackground(color: Color);
-
-    fn DrawText(text: *const u8, x: i32, y: i32, size: i32, color: Color);
-    fn DrawRectangle(x: i32, y: i32, w: i32, h: i32, color: Color);
-
-    fn GetFrameTime() -> f32;
-    fn IsMouseButtonPressed(button: i32) -> bool;
-    fn GetMouseX() -> i32;
-    fn GetMouseY() -> i32;
-}
-
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub struct Color { 
-    pub r: u8, 
-    pub g: u8, 
-    pub b: u8, 
-    pub a: u8 
-}
-
-const WHITE: Color = Color{r:255, g:255, b:255, a:255};
-const BLACK: Color = Color{r:0, g:0, b:0, a:255};
-// const RED: Color = Color{r:230, g:41, b:55, a:255};
-const GREEN: Color = Color{r:0, g:228, b:48, a:255};
-const BLUE: Color = Color{r:0, g:121, b:241, a:255};
-const GRAY: Color = Color{r:130, g:130, b:130, a:255};
-const LIGHTGRAY: Color = Color{r:200, g:200, b:200, a:255};
-const DARKGRAY: Color = Color{r:80, g:80, b:80, a:255};
+extern "C" {}
 
 macro_rules! cstr {
     ($s:expr) => {
-        concat!($s, "\0").as_ptr() as *const u8
+        concat!($s, "\0").as_ptr() as *const i8
     };
 }
 
@@ -157,7 +126,7 @@ pub unsafe extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
         let _dt = GetFrameTime();
 
         // ----- Input: button click -----------------------------------------
-        if IsMouseButtonPressed(0) { // 0 = MOUSE_BUTTON_LEFT
+        if IsMouseButtonPressed(MOUSE_LEFT) {
             let mx = GetMouseX();
             let my = GetMouseY();
 
@@ -198,9 +167,9 @@ pub unsafe extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
         get_string(log, &mut log_buf);
 
         // Draw text
-        DrawText(counter_buf.as_ptr(), 20, 120, 32, BLACK);
-        DrawText(doubled_buf.as_ptr(), 20, 170, 32, BLACK);
-        DrawText(log_buf.as_ptr(), 20, 250, 24, GRAY);
+        DrawText(counter_buf.as_ptr() as *const i8, 20, 120, 32, BLACK);
+        DrawText(doubled_buf.as_ptr() as *const i8, 20, 170, 32, BLACK);
+        DrawText(log_buf.as_ptr() as *const i8, 20, 250, 24, GRAY);
 
         // Button with color based on counter parity
         let btn_color = if counter_val % 2 == 0 { GREEN } else { BLUE };
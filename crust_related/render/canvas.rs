@@ -0,0 +1,316 @@
+// render/canvas.rs - `Render` on the CPU, into an RGBA buffer
+//
+// Shapes cover the pixels whose centers fall inside them, blended over
+// what's there by the color's alpha the way raylib's default blend mode
+// does. Thin lines are Bresenham's; thick ones are two triangles. 3D lines
+// go through the same view and projection raylib builds for a camera
+// (near plane at 0.01, far at 1000), clipped against the near plane.
+// There's no depth buffer, so a line drawn later covers an earlier one
+// where they cross, even if it's further away.
+// Text uses the 5x7 font in font.rs.
+//
+// Nothing is allocated: the caller owns the pixels.
+//
+//     let mut pixels = [0u8; 64 * 48 * 4];
+//     let mut canvas = Canvas::new(&mut pixels, 64, 48);
+//     canvas.clear(BLACK);
+//     canvas.circle(32, 24, 10.0, RED);
+
+use super::font::{self, CELL_HEIGHT, GLYPH_HEIGHT, GLYPH_WIDTH};
+use super::{Render, Render3D};
+use crate::linalg::*;
+use crate::raylib::{Camera3D, Color, Vector2, Vector3, CAMERA_ORTHOGRAPHIC};
+
+const NEAR: f32 = 0.01;
+const FAR: f32 = 1000.0;
+
+/// Pixels drawn by `Render` calls, four bytes each, rows top to bottom
+pub struct Canvas<'a> {
+    pixels: &'a mut [u8],
+    width: i32,
+    height: i32,
+}
+
+impl<'a> Canvas<'a> {
+    /// Panics if `pixels` is shorter than `width * height * 4`
+    pub fn new(pixels: &'a mut [u8], width: i32, height: i32) -> Canvas<'a> {
+        assert!(width >= 0 && height >= 0 && pixels.len() >= (width * height * 4) as usize);
+        Canvas { pixels, width, height }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// RGBA, row after row
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels[..(self.width * self.height * 4) as usize]
+    }
+
+    /// `None` off the canvas
+    pub fn pixel(&self, x: i32, y: i32) -> Option<Color> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = ((y * self.width + x) * 4) as usize;
+        let p = &self.pixels[i..i + 4];
+        Some(Color { r: p[0], g: p[1], b: p[2], a: p[3] })
+    }
+
+    /// Blend `color` over one pixel; off the canvas is ignored
+    pub fn blend(&mut self, x: i32, y: i32, color: Color) {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height || color.a == 0 {
+            return;
+        }
+        let i = ((y * self.width + x) * 4) as usize;
+        let p = &mut self.pixels[i..i + 4];
+        if color.a == 255 {
+            p.copy_from_slice(&[color.r, color.g, color.b, 255]);
+            return;
+        }
+        let a = color.a as u32;
+        let mix = |src: u8, dst: u8| ((src as u32 * a + dst as u32 * (255 - a) + 127) / 255) as u8;
+        p[0] = mix(color.r, p[0]);
+        p[1] = mix(color.g, p[1]);
+        p[2] = mix(color.b, p[2]);
+        p[3] = mix(255, p[3]);
+    }
+
+    // Pixels `x0..x1` of row `y`
+    fn span(&mut self, y: i32, x0: i32, x1: i32, color: Color) {
+        if y < 0 || y >= self.height {
+            return;
+        }
+        for x in x0.max(0)..x1.min(self.width) {
+            self.blend(x, y, color);
+        }
+    }
+
+    // Bresenham between pixel centers, after clipping to the canvas so a
+    // line from far off screen doesn't walk every pixel on the way
+    fn thin_line(&mut self, start: Vector2, end: Vector2, color: Color) {
+        let Some((start, end)) = clip_to(start, end, self.width as f32, self.height as f32) else {
+            return;
+        };
+        let (mut x, mut y) = (floor_f32(start.x) as i32, floor_f32(start.y) as i32);
+        let (x1, y1) = (floor_f32(end.x) as i32, floor_f32(end.y) as i32);
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let sx = if x < x1 { 1 } else { -1 };
+        let sy = if y < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        loop {
+            self.blend(x, y, color);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+}
+
+impl Render for Canvas<'_> {
+    fn size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    fn clear(&mut self, color: Color) {
+        for p in self.pixels.chunks_exact_mut(4) {
+            p.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }
+
+    fn rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        for row in y.max(0)..(y + height).min(self.height) {
+            self.span(row, x, x + width, color);
+        }
+    }
+
+    fn circle(&mut self, x: i32, y: i32, radius: f32, color: Color) {
+        let (cx, cy) = (x as f32, y as f32);
+        let reach = radius as i32 + 1;
+        for row in (y - reach).max(0)..(y + reach + 1).min(self.height) {
+            let dy = row as f32 + 0.5 - cy;
+            let left = radius * radius - dy * dy;
+            if left < 0.0 {
+                continue;
+            }
+            let half = sqrt_f32(left);
+            // Columns whose centers are within `half` of the middle
+            let x0 = -floor_f32(-(cx - half - 0.5)) as i32;
+            let x1 = floor_f32(cx + half - 0.5) as i32;
+            self.span(row, x0, x1 + 1, color);
+        }
+    }
+
+    fn line(&mut self, start: Vector2, end: Vector2, thick: f32, color: Color) {
+        if thick <= 1.0 {
+            self.thin_line(start, end, color);
+            return;
+        }
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        let len = sqrt_f32(dx * dx + dy * dy);
+        if len == 0.0 {
+            return;
+        }
+        let (nx, ny) = (-dy / len * thick / 2.0, dx / len * thick / 2.0);
+        let a = Vector2 { x: start.x + nx, y: start.y + ny };
+        let b = Vector2 { x: start.x - nx, y: start.y - ny };
+        let c = Vector2 { x: end.x - nx, y: end.y - ny };
+        let d = Vector2 { x: end.x + nx, y: end.y + ny };
+        self.triangle(a, b, c, color);
+        self.triangle(a, c, d, color);
+    }
+
+    fn triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        let area = edge(v1, v2, v3);
+        if area == 0.0 {
+            return;
+        }
+        let x0 = (floor_f32(fmin_f32(v1.x, fmin_f32(v2.x, v3.x))) as i32).max(0);
+        let x1 = (floor_f32(fmax_f32(v1.x, fmax_f32(v2.x, v3.x))) as i32 + 1).min(self.width);
+        let y0 = (floor_f32(fmin_f32(v1.y, fmin_f32(v2.y, v3.y))) as i32).max(0);
+        let y1 = (floor_f32(fmax_f32(v1.y, fmax_f32(v2.y, v3.y))) as i32 + 1).min(self.height);
+        // Either winding: flip the edge tests to match
+        let sign = if area > 0.0 { 1.0 } else { -1.0 };
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let p = Vector2 { x: x as f32 + 0.5, y: y as f32 + 0.5 };
+                if edge(v1, v2, p) * sign >= 0.0 && edge(v2, v3, p) * sign >= 0.0 && edge(v3, v1, p) * sign >= 0.0 {
+                    self.blend(x, y, color);
+                }
+            }
+        }
+    }
+
+    fn text(&mut self, text: &str, x: i32, y: i32, size: i32, color: Color) {
+        // Font units to pixels, never below one
+        let scale = ((size + CELL_HEIGHT / 2) / CELL_HEIGHT).max(1);
+        let top = y + (CELL_HEIGHT - GLYPH_HEIGHT) / 2 * scale;
+        let mut left = x;
+        for c in text.chars() {
+            for (col, bits) in font::glyph(c).iter().enumerate() {
+                for row in 0..GLYPH_HEIGHT {
+                    if bits & (1 << row) != 0 {
+                        self.rect(left + col as i32 * scale, top + row * scale, scale, scale, color);
+                    }
+                }
+            }
+            left += (GLYPH_WIDTH + 1) * scale;
+        }
+    }
+
+    fn scene_3d(&mut self, camera: Camera3D, draw: &mut dyn FnMut(&mut dyn Render3D)) {
+        let view_projection = mat4_mul(projection(&camera, self.width, self.height), view(&camera));
+        draw(&mut Scene { canvas: self, view_projection });
+    }
+}
+
+/// A `Canvas` seen through a camera
+struct Scene<'c, 'a> {
+    canvas: &'c mut Canvas<'a>,
+    view_projection: Mat4,
+}
+
+impl Render3D for Scene<'_, '_> {
+    fn line_3d(&mut self, start: Vector3, end: Vector3, color: Color) {
+        let mut a = mat4_mul_vec4(self.view_projection, Vec4 { x: start.x, y: start.y, z: start.z, w: 1.0 });
+        let mut b = mat4_mul_vec4(self.view_projection, Vec4 { x: end.x, y: end.y, z: end.z, w: 1.0 });
+
+        // In front of the near plane is z > -w
+        let (da, db) = (a.z + a.w, b.z + b.w);
+        if da < 0.0 && db < 0.0 {
+            return;
+        }
+        if da < 0.0 {
+            a = vec4_lerp(a, b, da / (da - db));
+        } else if db < 0.0 {
+            b = vec4_lerp(b, a, db / (db - da));
+        }
+
+        let (width, height) = (self.canvas.width as f32, self.canvas.height as f32);
+        let to_screen = |v: Vec4| Vector2 {
+            x: (v.x / v.w * 0.5 + 0.5) * width,
+            y: (0.5 - v.y / v.w * 0.5) * height,
+        };
+        self.canvas.thin_line(to_screen(a), to_screen(b), color);
+    }
+}
+
+fn view(camera: &Camera3D) -> Mat4 {
+    let v = |v: Vector3| vec3(v.x, v.y, v.z);
+    mat4_look_at(v(camera.position), v(camera.target), v(camera.up))
+}
+
+fn projection(camera: &Camera3D, width: i32, height: i32) -> Mat4 {
+    let aspect = width as f32 / height.max(1) as f32;
+    if camera.projection != CAMERA_ORTHOGRAPHIC {
+        return mat4_perspective(camera.fovy * core::f32::consts::PI / 180.0, aspect, NEAR, FAR);
+    }
+    // `fovy` is the view's height
+    let top = camera.fovy / 2.0;
+    let right = top * aspect;
+    let mut m = mat4_identity();
+    m.m[0] = 1.0 / right;
+    m.m[5] = 1.0 / top;
+    m.m[10] = -2.0 / (FAR - NEAR);
+    m.m[14] = -(FAR + NEAR) / (FAR - NEAR);
+    m
+}
+
+fn vec4_lerp(a: Vec4, b: Vec4, t: f32) -> Vec4 {
+    Vec4 {
+        x: a.x + (b.x - a.x) * t,
+        y: a.y + (b.y - a.y) * t,
+        z: a.z + (b.z - a.z) * t,
+        w: a.w + (b.w - a.w) * t,
+    }
+}
+
+// Twice the signed area of `a`, `b`, `p`; which side of `a`-`b` `p` is on
+fn edge(a: Vector2, b: Vector2, p: Vector2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+// Liang-Barsky: the part of `start`-`end` within a pixel of the canvas
+fn clip_to(start: Vector2, end: Vector2, width: f32, height: f32) -> Option<(Vector2, Vector2)> {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    let (mut t0, mut t1) = (0.0f32, 1.0f32);
+    let edges = [
+        (-dx, start.x + 1.0),
+        (dx, width - start.x),
+        (-dy, start.y + 1.0),
+        (dy, height - start.y),
+    ];
+    for (p, q) in edges {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let t = q / p;
+            if p < 0.0 {
+                t0 = fmax_f32(t0, t);
+            } else {
+                t1 = fmin_f32(t1, t);
+            }
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+    let at = |t: f32| Vector2 { x: start.x + dx * t, y: start.y + dy * t };
+    Some((at(t0), at(t1)))
+}
@@ -0,0 +1,65 @@
+// render/mod.rs - Drawing that doesn't care where the pixels end up
+//
+// The games and demos draw through `Render` instead of calling raylib
+// directly, so the same frame can go to a window or into memory:
+//
+//   * `DrawingScope` (window.rs) forwards every call to raylib, and
+//   * `Canvas` (canvas.rs) rasterizes on the CPU into an RGBA buffer, which
+//     `snapshot` turns into PNG or PPM files.
+//
+// The second needs no GPU, display or libraylib, which is what lets
+// headless_snapshots.rs compare frames against golden images.
+//
+// The calls follow raylib's: integer pixel coordinates with y down,
+// `size` for the height of text, and 3D through a `Camera3D`.
+//
+// Expects `raylib` and `linalg` at the crate root; the rasterizer takes
+// its perspective and look-at matrices from the latter.
+
+#![allow(dead_code)]
+
+use crate::raylib::{Camera3D, Color, Vector2, Vector3};
+
+// The raylib window as a `Render`
+pub mod window;
+
+// CPU rasterizer and its bitmap font
+pub mod canvas;
+pub mod font;
+#[allow(unused_imports)]
+pub use canvas::Canvas;
+
+// PNG and PPM encoders for a canvas' pixels
+pub mod snapshot;
+
+/// Somewhere to draw a frame
+pub trait Render {
+    /// Width and height in pixels
+    fn size(&self) -> (i32, i32);
+
+    /// Fill everything with `color`, replacing what was there
+    fn clear(&mut self, color: Color);
+
+    /// Filled, top left at `x`, `y`
+    fn rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color);
+
+    /// Filled, centered on `x`, `y`
+    fn circle(&mut self, x: i32, y: i32, radius: f32, color: Color);
+
+    /// `thick` pixels wide; 1 or less draws single pixels
+    fn line(&mut self, start: Vector2, end: Vector2, thick: f32, color: Color);
+
+    /// Filled; the vertices can come in either order
+    fn triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color);
+
+    /// One line of `text` in the built-in font, top left at `x`, `y`
+    fn text(&mut self, text: &str, x: i32, y: i32, size: i32, color: Color);
+
+    /// Run `draw` with what it draws seen through `camera`
+    fn scene_3d(&mut self, camera: Camera3D, draw: &mut dyn FnMut(&mut dyn Render3D));
+}
+
+/// What can be drawn inside `Render::scene_3d`
+pub trait Render3D {
+    fn line_3d(&mut self, start: Vector3, end: Vector3, color: Color);
+}
@@ -0,0 +1,335 @@
+// render/snapshot.rs - RGBA pixels as PNG or PPM bytes
+//
+// Both writers hand their output to a callback a few bytes or one chunk at
+// a time, so they need no allocator; a std program collects it into a
+// `Vec` or writes it to a file as it comes.
+//
+// PNG: 8-bit RGBA, every row "Up" filtered (the difference from the row
+// above, which is mostly zeros for flat game graphics), deflated as a
+// single fixed-Huffman block with a hash-chain match finder. Cheap to
+// write, and the same pixels always give the same bytes, so comparing
+// files is comparing frames.
+//
+// PPM: binary P6, which drops alpha; anything can read it.
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+/// IDAT data is written out whenever this much has built up
+const IDAT_CHUNK: usize = 16 * 1024;
+
+// Deflate's window, longest match and how hard to look for one
+const WINDOW: usize = 32 * 1024;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+const HASH_BITS: u32 = 15;
+const MAX_CHAIN: usize = 32;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31,
+    35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2,
+    3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193,
+    257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6,
+    7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+}
+
+fn crc_update(mut crc: u32, bytes: &[u8]) -> u32 {
+    for &b in bytes {
+        crc = CRC_TABLE[((crc ^ b as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    crc
+}
+
+/// `pixels` (RGBA, `width * height * 4` bytes) as a binary PPM
+pub fn write_ppm<W: FnMut(&[u8])>(pixels: &[u8], width: usize, height: usize, out: &mut W) {
+    assert!(pixels.len() >= width * height * 4);
+    out(b"P6\n");
+    write_decimal(width, out);
+    out(b" ");
+    write_decimal(height, out);
+    out(b"\n255\n");
+    for row in pixels[..width * height * 4].chunks_exact(width * 4) {
+        // Drop alpha a row at a time; rows up to 1024 pixels go out whole
+        let mut rgb = [0u8; 1024 * 3];
+        for part in row.chunks(1024 * 4) {
+            let n = part.len() / 4;
+            for (dst, src) in rgb.chunks_exact_mut(3).zip(part.chunks_exact(4)) {
+                dst.copy_from_slice(&src[..3]);
+            }
+            out(&rgb[..n * 3]);
+        }
+    }
+}
+
+fn write_decimal<W: FnMut(&[u8])>(mut n: usize, out: &mut W) {
+    let mut digits = [0u8; 20];
+    let mut i = digits.len();
+    loop {
+        i -= 1;
+        digits[i] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    out(&digits[i..]);
+}
+
+/// `pixels` (RGBA, `width * height * 4` bytes) as a PNG
+pub fn write_png<W: FnMut(&[u8])>(pixels: &[u8], width: usize, height: usize, out: &mut W) {
+    assert!(pixels.len() >= width * height * 4);
+    out(&SIGNATURE);
+
+    let mut header = [0u8; 13];
+    header[0..4].copy_from_slice(&(width as u32).to_be_bytes());
+    header[4..8].copy_from_slice(&(height as u32).to_be_bytes());
+    // 8 bits a channel, RGBA, deflate, adaptive filters, not interlaced
+    header[8..13].copy_from_slice(&[8, 6, 0, 0, 0]);
+    write_chunk(out, b"IHDR", &header);
+
+    let rows = Rows { pixels, stride: width * 4, height };
+    let mut idat = Idat { out, buf: [0; IDAT_CHUNK], len: 0 };
+    zlib(&rows, &mut idat);
+    idat.flush();
+
+    write_chunk(out, b"IEND", &[]);
+}
+
+fn write_chunk<W: FnMut(&[u8])>(out: &mut W, kind: &[u8; 4], data: &[u8]) {
+    out(&(data.len() as u32).to_be_bytes());
+    out(kind);
+    out(data);
+    let crc = crc_update(crc_update(0xFFFF_FFFF, kind), data) ^ 0xFFFF_FFFF;
+    out(&crc.to_be_bytes());
+}
+
+/// The filtered image deflate compresses: each row is a filter byte then
+/// the row minus the one above. Read a byte at a time rather than built,
+/// so it takes no memory.
+struct Rows<'p> {
+    pixels: &'p [u8],
+    stride: usize,
+    height: usize,
+}
+
+impl Rows<'_> {
+    fn len(&self) -> usize {
+        (self.stride + 1) * self.height
+    }
+
+    fn at(&self, i: usize) -> u8 {
+        let (row, col) = (i / (self.stride + 1), i % (self.stride + 1));
+        if col == 0 {
+            return 2; // Up
+        }
+        let here = self.pixels[row * self.stride + col - 1];
+        if row == 0 {
+            here
+        } else {
+            here.wrapping_sub(self.pixels[(row - 1) * self.stride + col - 1])
+        }
+    }
+}
+
+/// IDAT chunks, cut from the zlib stream as it's written
+struct Idat<'o, W: FnMut(&[u8])> {
+    out: &'o mut W,
+    buf: [u8; IDAT_CHUNK],
+    len: usize,
+}
+
+impl<W: FnMut(&[u8])> Idat<'_, W> {
+    fn push(&mut self, byte: u8) {
+        self.buf[self.len] = byte;
+        self.len += 1;
+        if self.len == IDAT_CHUNK {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.len > 0 {
+            write_chunk(self.out, b"IDAT", &self.buf[..self.len]);
+            self.len = 0;
+        }
+    }
+}
+
+/// Deflate's bit order: values least significant bit first, Huffman codes
+/// most significant first
+struct Bits<'i, 'o, W: FnMut(&[u8])> {
+    idat: &'i mut Idat<'o, W>,
+    acc: u32,
+    count: u32,
+}
+
+impl<W: FnMut(&[u8])> Bits<'_, '_, W> {
+    fn put(&mut self, value: u32, bits: u32) {
+        self.acc |= value << self.count;
+        self.count += bits;
+        while self.count >= 8 {
+            self.idat.push(self.acc as u8);
+            self.acc >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    fn code(&mut self, code: u32, bits: u32) {
+        self.put(code.reverse_bits() >> (32 - bits), bits);
+    }
+
+    /// Pad to a byte boundary
+    fn finish(&mut self) {
+        if self.count > 0 {
+            self.idat.push(self.acc as u8);
+        }
+        self.acc = 0;
+        self.count = 0;
+    }
+
+    // The fixed literal/length code for `symbol` (0..=287)
+    fn symbol(&mut self, symbol: u32) {
+        match symbol {
+            0..=143 => self.code(0x30 + symbol, 8),
+            144..=255 => self.code(0x190 + symbol - 144, 9),
+            256..=279 => self.code(symbol - 256, 7),
+            _ => self.code(0xC0 + symbol - 280, 8),
+        }
+    }
+
+    fn copy(&mut self, len: usize, dist: usize) {
+        let i = LENGTH_BASE.iter().rposition(|&base| base as usize <= len).unwrap_or(0);
+        self.symbol(257 + i as u32);
+        self.put((len - LENGTH_BASE[i] as usize) as u32, LENGTH_EXTRA[i] as u32);
+
+        let i = DIST_BASE.iter().rposition(|&base| base as usize <= dist).unwrap_or(0);
+        self.code(i as u32, 5);
+        self.put((dist - DIST_BASE[i] as usize) as u32, DIST_EXTRA[i] as u32);
+    }
+}
+
+fn zlib<W: FnMut(&[u8])>(rows: &Rows, idat: &mut Idat<'_, W>) {
+    // Deflate with a 32K window, no preset dictionary, fastest level
+    idat.push(0x78);
+    idat.push(0x01);
+
+    let mut bits = Bits { idat, acc: 0, count: 0 };
+    deflate(rows, &mut bits);
+    bits.finish();
+
+    for b in adler32(rows).to_be_bytes() {
+        bits.idat.push(b);
+    }
+}
+
+fn adler32(rows: &Rows) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for i in 0..rows.len() {
+        a = (a + rows.at(i) as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+fn deflate<W: FnMut(&[u8])>(rows: &Rows, bits: &mut Bits<'_, '_, W>) {
+    // Last block, fixed Huffman codes
+    bits.put(1, 1);
+    bits.put(1, 2);
+
+    let mut matches = Matches { head: [u32::MAX; 1 << HASH_BITS], prev: [u32::MAX; WINDOW] };
+    let mut i = 0;
+    while i < rows.len() {
+        let (len, dist) = matches.longest(rows, i);
+        if len >= MIN_MATCH {
+            bits.copy(len, dist);
+            for j in i..i + len {
+                matches.insert(rows, j);
+            }
+            i += len;
+        } else {
+            bits.symbol(rows.at(i) as u32);
+            matches.insert(rows, i);
+            i += 1;
+        }
+    }
+    bits.symbol(256);
+}
+
+/// Earlier positions that start with the same three bytes
+struct Matches {
+    // Latest position for each hash
+    head: [u32; 1 << HASH_BITS],
+    // The position before it with the same hash, for the last `WINDOW`
+    prev: [u32; WINDOW],
+}
+
+impl Matches {
+    fn hash(rows: &Rows, i: usize) -> usize {
+        let h = (rows.at(i) as u32) << 16 | (rows.at(i + 1) as u32) << 8 | rows.at(i + 2) as u32;
+        (h.wrapping_mul(0x9E37_79B1) >> (32 - HASH_BITS)) as usize
+    }
+
+    fn insert(&mut self, rows: &Rows, i: usize) {
+        if i + MIN_MATCH <= rows.len() {
+            let h = Matches::hash(rows, i);
+            self.prev[i % WINDOW] = self.head[h];
+            self.head[h] = i as u32;
+        }
+    }
+
+    /// Length and distance of the longest match for what starts at `i`
+    fn longest(&self, rows: &Rows, i: usize) -> (usize, usize) {
+        let (mut best_len, mut best_dist) = (0, 0);
+        if i + MIN_MATCH > rows.len() {
+            return (0, 0);
+        }
+        let limit = MAX_MATCH.min(rows.len() - i);
+        let mut candidate = self.head[Matches::hash(rows, i)];
+        for _ in 0..MAX_CHAIN {
+            let c = candidate as usize;
+            // Slots past the window have been reused by newer positions
+            if candidate == u32::MAX || i - c > WINDOW {
+                break;
+            }
+            let mut len = 0;
+            while len < limit && rows.at(c + len) == rows.at(i + len) {
+                len += 1;
+            }
+            if len > best_len {
+                best_len = len;
+                best_dist = i - c;
+                if len == limit {
+                    break;
+                }
+            }
+            candidate = self.prev[c % WINDOW];
+        }
+        (best_len, best_dist)
+    }
+}
@@ -0,0 +1,59 @@
+// render/window.rs - `Render` over the raylib window
+//
+// A frame's `DrawingScope` draws straight through raylib, and `scene_3d`
+// hands out the `Mode3D` guard for the camera.
+
+use super::{Render, Render3D};
+use crate::raylib::text::{self, TextStyle};
+use crate::raylib::*;
+
+impl Render for DrawingScope<'_> {
+    fn size(&self) -> (i32, i32) {
+        unsafe { (GetScreenWidth(), GetScreenHeight()) }
+    }
+
+    fn clear(&mut self, color: Color) {
+        self.clear_background(color);
+    }
+
+    fn rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        self.draw_rectangle(x, y, width, height, color);
+    }
+
+    fn circle(&mut self, x: i32, y: i32, radius: f32, color: Color) {
+        self.draw_circle(x, y, radius, color);
+    }
+
+    fn line(&mut self, start: Vector2, end: Vector2, thick: f32, color: Color) {
+        if thick <= 1.0 {
+            self.draw_line(start.x as i32, start.y as i32, end.x as i32, end.y as i32, color);
+        } else {
+            self.draw_line_ex(start, end, thick, color);
+        }
+    }
+
+    fn triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        // raylib only fills counter-clockwise triangles, as seen on screen
+        let cross = (v2.x - v1.x) * (v3.y - v1.y) - (v2.y - v1.y) * (v3.x - v1.x);
+        if cross > 0.0 {
+            self.draw_triangle(v1, v3, v2, color);
+        } else {
+            self.draw_triangle(v1, v2, v3, color);
+        }
+    }
+
+    fn text(&mut self, s: &str, x: i32, y: i32, size: i32, color: Color) {
+        text::draw(&TextStyle::default_font(size, color), s, x as f32, y as f32);
+    }
+
+    fn scene_3d(&mut self, camera: Camera3D, draw: &mut dyn FnMut(&mut dyn Render3D)) {
+        let mut scene = self.begin_mode_3d(camera);
+        draw(&mut scene);
+    }
+}
+
+impl Render3D for Mode3D<'_> {
+    fn line_3d(&mut self, start: Vector3, end: Vector3, color: Color) {
+        self.draw_line_3d(start, end, color);
+    }
+}
@@ -0,0 +1,335 @@
+// scenes/breakout.rs - Breakout, with its input and drawing kept apart
+//
+// game_breakout.rs fills an `Input` from the keyboard and mouse each frame
+// and draws to the raylib window; headless_snapshots.rs scripts the input
+// and draws to a `Canvas`.
+//
+// Expects `raylib`, `render`, `slab` (lib/slab.rs) and `string`
+// (lib/string.rs) at the crate root.
+
+#![allow(dead_code)]
+
+use crate::raylib::*;
+use crate::render::Render;
+use crate::slab::Slab;
+use crate::string::String;
+
+pub const WIDTH: i32 = 800;
+pub const HEIGHT: i32 = 600;
+
+// Game structs
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Paddle {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub speed: f32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Ball {
+    pub x: f32,
+    pub y: f32,
+    pub radius: f32,
+    pub velocity_x: f32,
+    pub velocity_y: f32,
+    pub active: bool,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Brick {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub color: Color,
+}
+
+/// The keys held and where the mouse is this frame
+#[derive(Copy, Clone, Default)]
+pub struct Input {
+    pub left: bool,
+    pub right: bool,
+    /// The paddle centers on it
+    pub mouse_x: f32,
+    pub launch: bool,
+    pub restart: bool,
+}
+
+pub struct Game {
+    pub game_over: bool,
+    pub game_won: bool,
+    pub score: i32,
+    pub lives: i32,
+    pub paddle: Paddle,
+    pub ball: Ball,
+    pub bricks: Slab<Brick, 50>,
+}
+
+// Collision detection
+pub unsafe fn check_ball_paddle_collision(ball: *mut Ball, paddle: *const Paddle) -> bool {
+    let b = *ball;
+    let p = *paddle;
+
+    if b.y + b.radius >= p.y &&
+       b.y - b.radius <= p.y + p.height &&
+       b.x >= p.x &&
+       b.x <= p.x + p.width {
+        (*ball).velocity_y = -(*ball).velocity_y;
+        (*ball).y = p.y - b.radius;
+
+        // Add angle based on hit position
+        let hit_pos = (b.x - p.x) / p.width;
+        (*ball).velocity_x = (hit_pos - 0.5) * 400.0;
+        true
+    } else {
+        false
+    }
+}
+
+pub unsafe fn check_ball_brick_collision(ball: *mut Ball, brick: *const Brick) -> bool {
+    let b = *ball;
+    let br = *brick;
+
+    if b.x + b.radius >= br.x &&
+       b.x - b.radius <= br.x + br.width &&
+       b.y + b.radius >= br.y &&
+       b.y - b.radius <= br.y + br.height {
+
+        // Determine collision side
+        let overlap_left = (b.x + b.radius) - br.x;
+        let overlap_right = (br.x + br.width) - (b.x - b.radius);
+        let overlap_top = (b.y + b.radius) - br.y;
+        let overlap_bottom = (br.y + br.height) - (b.y - b.radius);
+
+        // Find minimum overlap to determine collision side
+        let mut min_overlap = overlap_left;
+        let mut side = 0; // 0=left, 1=right, 2=top, 3=bottom
+
+        if overlap_right < min_overlap {
+            min_overlap = overlap_right;
+            side = 1;
+        }
+        if overlap_top < min_overlap {
+            min_overlap = overlap_top;
+            side = 2;
+        }
+        if overlap_bottom < min_overlap {
+            side = 3;
+        }
+
+        // Bounce based on collision side
+        if side == 0 || side == 1 {
+            (*ball).velocity_x = -(*ball).velocity_x;
+        } else {
+            (*ball).velocity_y = -(*ball).velocity_y;
+        }
+
+        true
+    } else {
+        false
+    }
+}
+
+// Lay out a fresh wall of bricks; destroyed ones are removed from the slab
+pub fn spawn_bricks(bricks: &mut Slab<Brick, 50>) {
+    let brick_cols = 10;
+    let brick_width = 70.0;
+    let brick_height = 25.0;
+    let brick_padding = 5.0;
+    let brick_offset_x = 35.0;
+    let brick_offset_y = 50.0;
+
+    // One row per color
+    let colors = [RED, ORANGE, YELLOW, GREEN, BLUE];
+
+    bricks.clear();
+    for (row, &color) in colors.iter().enumerate() {
+        for col in 0..brick_cols {
+            let _ = bricks.insert(Brick {
+                x: brick_offset_x + col as f32 * (brick_width + brick_padding),
+                y: brick_offset_y + row as f32 * (brick_height + brick_padding),
+                width: brick_width,
+                height: brick_height,
+                color,
+            });
+        }
+    }
+}
+
+impl Game {
+    pub fn new() -> Game {
+        let mut game = Game {
+            game_over: false,
+            game_won: false,
+            score: 0,
+            lives: 3,
+            paddle: Paddle {
+                x: (WIDTH / 2 - 60) as f32,
+                y: (HEIGHT - 50) as f32,
+                width: 120.0,
+                height: 20.0,
+                speed: 500.0,
+            },
+            ball: Ball {
+                x: (WIDTH / 2) as f32,
+                y: (HEIGHT - 80) as f32,
+                radius: 8.0,
+                velocity_x: 0.0,
+                velocity_y: 0.0,
+                active: false,
+            },
+            bricks: Slab::new(),
+        };
+        spawn_bricks(&mut game.bricks);
+        game
+    }
+
+    pub fn update(&mut self, input: &Input, delta: f32) {
+        let width = WIDTH as f32;
+        let height = HEIGHT as f32;
+        let paddle = &mut self.paddle;
+        let ball = &mut self.ball;
+
+        if !self.game_over && !self.game_won {
+            // Paddle movement
+            if input.left {
+                paddle.x -= paddle.speed * delta;
+                if paddle.x < 0.0 {
+                    paddle.x = 0.0;
+                }
+            }
+            if input.right {
+                paddle.x += paddle.speed * delta;
+                if paddle.x + paddle.width > width {
+                    paddle.x = width - paddle.width;
+                }
+            }
+
+            // Mouse control (alternative)
+            paddle.x = input.mouse_x - paddle.width / 2.0;
+            if paddle.x < 0.0 {
+                paddle.x = 0.0;
+            }
+            if paddle.x + paddle.width > width {
+                paddle.x = width - paddle.width;
+            }
+
+            // Launch ball
+            if !ball.active && input.launch {
+                ball.active = true;
+                ball.velocity_x = 200.0;
+                ball.velocity_y = -300.0;
+            }
+
+            // Update ball if active
+            if ball.active {
+                ball.x += ball.velocity_x * delta;
+                ball.y += ball.velocity_y * delta;
+
+                // Wall collisions
+                if ball.x - ball.radius <= 0.0 || ball.x + ball.radius >= width {
+                    ball.velocity_x = -ball.velocity_x;
+                }
+                if ball.y - ball.radius <= 0.0 {
+                    ball.velocity_y = -ball.velocity_y;
+                }
+
+                // Bottom boundary (lose life)
+                if ball.y - ball.radius >= height {
+                    self.lives -= 1;
+                    if self.lives <= 0 {
+                        self.game_over = true;
+                    } else {
+                        ball.x = paddle.x + paddle.width / 2.0;
+                        ball.y = paddle.y - 30.0;
+                        ball.velocity_x = 0.0;
+                        ball.velocity_y = 0.0;
+                        ball.active = false;
+                    }
+                }
+
+                // Paddle collision
+                unsafe { check_ball_paddle_collision(&mut *ball as *mut Ball, &*paddle as *const Paddle) };
+
+                // Brick collisions
+                let score = &mut self.score;
+                self.bricks.retain(|_, brick| {
+                    if unsafe { check_ball_brick_collision(&mut *ball as *mut Ball, brick as *const Brick) } {
+                        *score += 10;
+                        false
+                    } else {
+                        true
+                    }
+                });
+
+                // Check win condition
+                if self.bricks.is_empty() {
+                    self.game_won = true;
+                }
+            } else {
+                // Ball follows paddle when not active
+                ball.x = paddle.x + paddle.width / 2.0;
+                ball.y = paddle.y - 30.0;
+            }
+        }
+
+        // Restart game
+        if (self.game_over || self.game_won) && input.restart {
+            *self = Game::new();
+        }
+    }
+
+    pub fn draw(&self, r: &mut impl Render) {
+        let (paddle, ball) = (&self.paddle, &self.ball);
+
+        r.clear(RAYWHITE);
+
+        // Draw title
+        r.text("CRUST BREAKOUT", 20, 10, 20, DARKGRAY);
+
+        // Draw score
+        let score_text = String::<16>::from_i64(self.score as i64).unwrap_or_default();
+        r.text("Score: ", WIDTH - 200, 10, 20, DARKGRAY);
+        r.text(&score_text, WIDTH - 100, 10, 20, DARKGRAY);
+
+        // Draw lives
+        let lives_text = String::<16>::from_i64(self.lives as i64).unwrap_or_default();
+        r.text("Lives: ", WIDTH - 200, 35, 20, DARKGRAY);
+        r.text(&lives_text, WIDTH - 100, 35, 20, DARKGRAY);
+
+        if !self.game_over && !self.game_won {
+            // Draw paddle
+            r.rect(paddle.x as i32, paddle.y as i32, paddle.width as i32, paddle.height as i32, DARKGRAY);
+
+            // Draw ball
+            r.circle(ball.x as i32, ball.y as i32, ball.radius, BLACK);
+
+            // Draw bricks
+            for (_, brick) in self.bricks.iter() {
+                r.rect(
+                    brick.x as i32,
+                    brick.y as i32,
+                    brick.width as i32,
+                    brick.height as i32,
+                    brick.color
+                );
+            }
+
+            // Draw instructions if ball not active
+            if !ball.active {
+                r.text("Press SPACE to launch!", WIDTH / 2 - 150, HEIGHT / 2, 20, DARKGRAY);
+            }
+        } else if self.game_over {
+            r.text("GAME OVER!", WIDTH / 2 - 120, HEIGHT / 2 - 40, 40, RED);
+            r.text("Press R to restart", WIDTH / 2 - 120, HEIGHT / 2 + 20, 20, DARKGRAY);
+        } else if self.game_won {
+            r.text("YOU WIN!", WIDTH / 2 - 100, HEIGHT / 2 - 40, 40, GREEN);
+            r.text("Press R to restart", WIDTH / 2 - 120, HEIGHT / 2 + 20, 20, DARKGRAY);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+// scenes/earth_wireframe.rs - The wireframe globe, orbited with the mouse
+//
+// `Earth::update` takes the frame's mouse state as an `Input`, and
+// `Earth::draw` traces the globe's lines through `Render::scene_3d`, so
+// earth_wireframe.rs shows it in a raylib window and headless_snapshots.rs
+// renders it into a `Canvas`.
+//
+// Expects `raylib`, `render` and `linalg` at the crate root, with
+// `linalg_conversions!(linalg);` invoked there.
+
+#![allow(dead_code)]
+
+use crate::linalg::*;
+use crate::raylib::*;
+use crate::render::{Render, Render3D};
+
+pub const WIDTH: i32 = 1200;
+pub const HEIGHT: i32 = 800;
+
+// Lighter than raylib's own, to read against black
+const YELLOW:  Color = Color { r: 255, g: 249, b:   0, a: 255 };
+const SKYBLUE: Color = Color { r: 100, g: 180, b: 255, a: 255 };
+
+const RADIUS: f32 = 2.0;
+const SEG_LAT: i32 = 36;
+const SEG_LON: i32 = 72;
+
+// Radians per pixel dragged, and distance per wheel notch
+const SENSITIVITY: f32 = 0.005;
+const ZOOM_SPEED: f32 = 1.5;
+
+/// The mouse this frame
+#[derive(Copy, Clone)]
+pub struct Input {
+    pub position: Vector2,
+    /// The left button went down or up
+    pub pressed: bool,
+    pub released: bool,
+    pub wheel: f32,
+}
+
+pub struct Earth {
+    pub camera: Camera3D,
+    orientation: Quat,
+    distance: f32,
+    dragging: bool,
+    last_mouse: Vector2,
+}
+
+#[inline]
+fn clamp(v: f32, lo: f32, hi: f32) -> f32 {
+    if v < lo { lo } else if v > hi { hi } else { v }
+}
+
+impl Earth {
+    pub fn new() -> Earth {
+        let mut earth = Earth {
+            camera: Camera3D {
+                position: Vector3 { x: 0.0, y: 0.0, z: 10.0 },
+                target:   Vector3 { x: 0.0, y: 0.0, z: 0.0 },
+                up:       Vector3 { x: 0.0, y: 1.0, z: 0.0 },
+                fovy: 45.0,
+                projection: CAMERA_PERSPECTIVE,
+            },
+            orientation: quat_identity(),
+            distance: 10.0,
+            dragging: false,
+            last_mouse: Vector2 { x: 0.0, y: 0.0 },
+        };
+        earth.place_camera();
+        earth
+    }
+
+    pub fn update(&mut self, input: &Input) {
+        // Mouse orbit
+        if input.pressed {
+            self.last_mouse = input.position;
+            self.dragging = true;
+        }
+        if input.released {
+            self.dragging = false;
+        }
+
+        if self.dragging {
+            let dx = input.position.x - self.last_mouse.x;
+            let dy = input.position.y - self.last_mouse.y;
+            self.last_mouse = input.position;
+
+            if dx != 0.0 || dy != 0.0 {
+                // Horizontal orbit (around Y axis)
+                let yaw_quat = quat_angle_axis(-dx * SENSITIVITY, vec3(0.0, 1.0, 0.0));
+
+                // Vertical orbit (around camera's right axis)
+                let camera_right = vec3_normalize(vec3_cross(
+                    vec3_sub(self.camera.position.into(), self.camera.target.into()),
+                    vec3(0.0, 1.0, 0.0),
+                ));
+                let pitch_quat = quat_angle_axis(-dy * SENSITIVITY, camera_right);
+
+                // Combine rotations
+                self.orientation = quat_mul(pitch_quat, quat_mul(yaw_quat, self.orientation));
+            }
+        }
+
+        // Zoom
+        if input.wheel != 0.0 {
+            self.distance = clamp(self.distance - input.wheel * ZOOM_SPEED, 3.0, 30.0);
+        }
+
+        self.place_camera();
+    }
+
+    // The camera rides along with the globe's rotation
+    fn place_camera(&mut self) {
+        let model = quat_to_mat4(self.orientation);
+        self.camera.position = mat4_mul_vec3(model, vec3(0.0, 0.0, self.distance)).into();
+        self.camera.target = mat4_mul_vec3(model, vec3_zero()).into();
+    }
+
+    pub fn draw(&self, r: &mut impl Render) {
+        r.clear(BLACK);
+        let model = quat_to_mat4(self.orientation);
+        r.scene_3d(self.camera, &mut |scene| draw_globe(scene, model));
+
+        r.text("Left Drag = Rotate | Wheel = Zoom", 10, 10, 20, WHITE);
+        r.text("100% linalg.rs + Rust", 10, 40, 20, YELLOW);
+    }
+}
+
+fn draw_globe(scene: &mut dyn Render3D, model: Mat4) {
+    let pi = core::f32::consts::PI;
+
+    // Longitude lines
+    for i in 0..SEG_LON {
+        let lon = 2.0 * pi * i as f32 / SEG_LON as f32;
+        let col = if i % (SEG_LON / 12) == 0 { YELLOW } else { DARKGRAY };
+        trace(scene, model, col, SEG_LAT, |t| {
+            let phi = pi * t;
+            vec3(
+                RADIUS * sin_f32(phi) * cos_f32(lon),
+                RADIUS * cos_f32(phi),
+                RADIUS * sin_f32(phi) * sin_f32(lon),
+            )
+        });
+    }
+
+    // Latitude lines
+    for j in 1..SEG_LAT {
+        let phi = pi * j as f32 / SEG_LAT as f32;
+        let y = RADIUS * cos_f32(phi);
+        let r = RADIUS * sin_f32(phi);
+        let col = if j == SEG_LAT / 2 { GREEN }
+                  else if j == SEG_LAT / 4 || j == 3 * SEG_LAT / 4 { ORANGE }
+                  else { SKYBLUE };
+        trace(scene, model, col, SEG_LON, |t| {
+            let theta = 2.0 * pi * t;
+            vec3(r * cos_f32(theta), y, r * sin_f32(theta))
+        });
+    }
+
+    // Equator (red, extra smooth)
+    trace(scene, model, RED, 128, |t| {
+        let a = 2.0 * pi * t;
+        vec3(RADIUS * cos_f32(a), 0.0, RADIUS * sin_f32(a))
+    });
+}
+
+// `segments` lines joining `point(0.0)` to `point(1.0)`, turned by `model`
+fn trace(scene: &mut dyn Render3D, model: Mat4, color: Color, segments: i32, point: impl Fn(f32) -> Vec3) {
+    let mut prev: Vector3 = mat4_mul_vec3(model, point(0.0)).into();
+    for k in 1..=segments {
+        let pos = mat4_mul_vec3(model, point(k as f32 / segments as f32)).into();
+        scene.line_3d(prev, pos, color);
+        prev = pos;
+    }
+}
@@ -0,0 +1,219 @@
+// scenes/snake.rs - Snake, with its input and drawing kept apart
+//
+// `Game::update` takes the frame's key presses as an `Input` and
+// `Game::draw` goes through `Render`, so game_snake.rs plays it in a
+// raylib window and headless_snapshots.rs plays a script into a `Canvas`.
+// Food comes from a small seeded generator rather than raylib's, so the
+// same seed and input give the same game.
+//
+// Expects `raylib`, `render` and `io` (lib/io.rs) at the crate root.
+
+#![allow(dead_code)]
+
+use crate::io::time::FixedTimestep;
+use crate::raylib::{Color, BLACK, GOLD, GRAY, RED};
+use crate::render::Render;
+
+pub const MAX_SNAKE_LENGTH: usize = 400;
+pub const CELL_SIZE: i32 = 20;
+pub const GRID_WIDTH: i32 = 40;
+pub const GRID_HEIGHT: i32 = 30;
+pub const WIDTH: i32 = GRID_WIDTH * CELL_SIZE;
+pub const HEIGHT: i32 = GRID_HEIGHT * CELL_SIZE;
+const MOVE_INTERVAL: f32 = 0.12;
+
+// Darker than raylib's own, so the snake stands out on gray
+const DARKGREEN: Color = Color { r: 0, g: 100, b: 0, a: 255 };
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum Direction {
+    Up, Down, Left, Right,
+}
+
+impl Direction {
+    fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// The keys pressed this frame
+#[derive(Copy, Clone, Default)]
+pub struct Input {
+    pub turn: Option<Direction>,
+    pub restart: bool,
+}
+
+pub struct Game {
+    snake: [Position; MAX_SNAKE_LENGTH],
+    length: usize,
+    direction: Direction,
+    next_direction: Direction,
+    food: Position,
+    game_over: bool,
+    // One snake move per step
+    mover: FixedTimestep,
+    // xorshift32 state for placing food; never zero
+    rng: u32,
+}
+
+impl Game {
+    /// A new game; `seed` decides where the food turns up
+    pub fn new(seed: u32) -> Game {
+        let mut snake = [Position { x: 0, y: 0 }; MAX_SNAKE_LENGTH];
+        snake[0] = Position { x: 5, y: 15 };
+        snake[1] = Position { x: 4, y: 15 };
+        snake[2] = Position { x: 3, y: 15 };
+
+        let mut game = Game {
+            snake,
+            length: 3,
+            direction: Direction::Right,
+            next_direction: Direction::Right,
+            food: Position { x: 0, y: 0 },
+            game_over: false,
+            mover: FixedTimestep::from_secs_f32(MOVE_INTERVAL),
+            rng: if seed == 0 { 1 } else { seed },
+        };
+        game.food = game.generate_food_position();
+        game
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.game_over
+    }
+
+    pub fn head(&self) -> Position {
+        self.snake[0]
+    }
+
+    pub fn food(&self) -> Position {
+        self.food
+    }
+
+    /// Segments eaten so far
+    pub fn score(&self) -> i32 {
+        self.length as i32 - 3
+    }
+
+    pub fn update(&mut self, input: &Input, delta: f32) {
+        match input.turn {
+            Some(turn) if turn != self.direction.opposite() => self.next_direction = turn,
+            Some(_) => {}
+            None if input.restart && self.game_over => *self = Game::new(self.rng),
+            None => {}
+        }
+
+        if self.game_over {
+            return;
+        }
+        self.mover.advance_secs(delta);
+        while !self.game_over && self.mover.step() {
+            self.step();
+        }
+    }
+
+    fn step(&mut self) {
+        self.direction = self.next_direction;
+
+        // 1. Calculate new head position
+        let mut head = self.snake[0];
+        match self.direction {
+            Direction::Up => head.y -= 1,
+            Direction::Down => head.y += 1,
+            Direction::Left => head.x -= 1,
+            Direction::Right => head.x += 1,
+        }
+
+        // 2. Collision Checks
+        if head.x < 0 || head.x >= GRID_WIDTH || head.y < 0 || head.y >= GRID_HEIGHT
+            || is_occupied(head, &self.snake[1..self.length])
+        {
+            self.game_over = true;
+            return;
+        }
+
+        // 3. Move snake body (Shift all segments back one position)
+        let old_tail_pos = self.snake[self.length - 1];
+        for i in (1..self.length).rev() {
+            self.snake[i] = self.snake[i - 1];
+        }
+        self.snake[0] = head;
+
+        // 4. Food consumption
+        if head == self.food {
+            if self.length < MAX_SNAKE_LENGTH {
+                self.snake[self.length] = old_tail_pos;
+                self.length += 1;
+            }
+            self.food = self.generate_food_position();
+        }
+    }
+
+    pub fn draw(&self, r: &mut impl Render) {
+        r.clear(GRAY);
+
+        if self.game_over {
+            r.text("GAME OVER! Press SPACE to restart.", WIDTH / 2 - 300, HEIGHT / 2 - 50, 40, RED);
+        } else {
+            r.rect(self.food.x * CELL_SIZE, self.food.y * CELL_SIZE, CELL_SIZE, CELL_SIZE, GOLD);
+
+            for (i, segment) in self.snake[..self.length].iter().enumerate() {
+                let color = if i == 0 { BLACK } else { DARKGREEN };
+                r.rect(segment.x * CELL_SIZE, segment.y * CELL_SIZE, CELL_SIZE, CELL_SIZE, color);
+            }
+        }
+
+        r.text("SCORE: ", 20, 20, 20, BLACK);
+        let mut score_buffer = [0u8; 12];
+        r.text(itoa(self.score(), &mut score_buffer), 100, 20, 20, BLACK);
+    }
+
+    // A free cell for the food
+    fn generate_food_position(&mut self) -> Position {
+        loop {
+            let x = self.random(GRID_WIDTH);
+            let y = self.random(GRID_HEIGHT);
+            let food_pos = Position { x, y };
+            if !is_occupied(food_pos, &self.snake[..self.length]) {
+                return food_pos;
+            }
+        }
+    }
+
+    // 0..below
+    fn random(&mut self, below: i32) -> i32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        (self.rng % below as u32) as i32
+    }
+}
+
+fn is_occupied(pos: Position, snake: &[Position]) -> bool {
+    snake.contains(&pos)
+}
+
+// Digits of a non-negative `n`, written to the end of `buffer`
+fn itoa(mut n: i32, buffer: &mut [u8; 12]) -> &str {
+    let mut i = buffer.len();
+    loop {
+        i -= 1;
+        buffer[i] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n <= 0 {
+            break;
+        }
+    }
+    core::str::from_utf8(&buffer[i..]).unwrap_or("")
+}
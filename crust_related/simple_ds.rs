@@ -8,42 +8,20 @@ extern "C" {
     fn exit(status: i32) -> !;
 }
 
-// A simple fixed-size stack
-struct Stack<T, const N: usize> {
-    data: [Option<T>; N],
-    top: usize,
-}
+// Fixed-size stack backed by the shared no_std Vec
+#[allow(unused_attributes)]
+#[path = "lib/collections.rs"]
+mod collections;
+use collections::vec::Vec;
 
-impl<T: Copy, const N: usize> Stack<T, N> {
-    const fn new() -> Self {
-        Self {
-            data: [None; N],
-            top: 0,
-        }
-    }
-    
-    fn push(&mut self, value: T) -> Result<(), &'static str> {
-        if self.top >= N {
-            return Err("Stack overflow");
-        }
-        self.data[self.top] = Some(value);
-        self.top += 1;
-        Ok(())
-    }
-    
-    fn pop(&mut self) -> Option<T> {
-        if self.top == 0 {
-            return None;
-        }
-        self.top -= 1;
-        self.data[self.top]
-    }
-    
-}
+#[allow(unused_attributes)]
+#[path = "lib/string.rs"]
+mod string;
+use string::String;
 
 #[no_mangle]
 pub unsafe extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
-    let mut stack = Stack::<i32, 5>::new();
+    let mut stack = Vec::<i32, 5>::new();
     
     // Push values
     for i in 0..5 {
@@ -61,47 +39,16 @@ pub unsafe extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
     
     // Pop and print values
     while let Some(value) = stack.pop() {
-        let mut buffer = [0u8; 20];
-        let len = format_signed_number(value, &mut buffer);
+        let mut text = String::<20>::from_i64(value as i64).unwrap_or_default();
+        let _ = text.push('\n');
         unsafe {
-            write(1, buffer.as_ptr(), len);
-            write(1, b"\n".as_ptr(), 1);
+            write(1, text.as_ptr(), text.len());
         }
     }
     
     0
 }
 
-fn format_signed_number(n: i32, buffer: &mut [u8]) -> usize {
-    let mut num = n.abs() as u32;
-    let mut digits = [0u8; 20];
-    let mut digit_count = 0;
-    let mut buffer_index = 0;
-    
-    if n < 0 {
-        buffer[buffer_index] = b'-';
-        buffer_index += 1;
-    }
-    
-    if num == 0 {
-        digits[0] = b'0';
-        digit_count = 1;
-    } else {
-        while num > 0 && digit_count < digits.len() {
-            digits[digit_count] = b'0' + (num % 10) as u8;
-            num /= 10;
-            digit_count += 1;
-        }
-    }
-    
-    for i in (0..digit_count).rev() {
-        buffer[buffer_index] = digits[i];
-        buffer_index += 1;
-    }
-    
-    buffer_index
-}
-
 #[panic_handler]
 fn panic(_info: &core::panic::PanicInfo) -> ! {
     unsafe { exit(1); }
@@ -0,0 +1,158 @@
+// build: rustc --edition 2021 -C panic=abort tiny_http.rs -o tiny_http
+// Single-threaded HTTP server on lib/io.rs's reactor: an accept loop, a
+// heartbeat task on the timer wheel and a SIGINT/SIGTERM watcher that shuts
+// everything down cleanly. Try `curl localhost:8080/hello`, then Ctrl-C.
+#![no_std]
+#![no_main]
+
+#[link(name = "c")]
+extern "C" {
+    fn exit(status: i32) -> !;
+}
+
+#[allow(unused_attributes)]
+#[path = "lib/io.rs"]
+mod io;
+
+#[allow(unused_attributes)]
+#[path = "lib/io2.rs"]
+mod io2;
+
+use core::cell::Cell;
+use core::fmt::Write as _;
+use core::future::Future;
+use core::pin::{pin, Pin};
+use core::time::Duration;
+use io::net::TcpListener;
+use io::reactor::{self, Either, Reactor, SignalFd, Token, SIGINT, SIGTERM};
+use io2::io::Formatter;
+
+type Rt = Reactor<16>;
+
+// Pause after a failed accept
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Path from "GET /path HTTP/1.1", if the request line is complete
+fn request_path(head: &[u8]) -> Option<&str> {
+    let line = head.split(|&b| b == b'\r' || b == b'\n').next()?;
+    let line = core::str::from_utf8(line).ok()?;
+    let mut parts = line.split(' ');
+    let _method = parts.next()?;
+    parts.next()
+}
+
+/// One request per connection, then close
+async fn serve(rt: &Rt, listener: &TcpListener, token: Token, served: &Cell<u32>) {
+    loop {
+        let (mut stream, peer) = match rt.accept(token, listener).await {
+            Ok(pair) => pair,
+            Err(e) => {
+                // Running out of descriptors doesn't clear by retrying at
+                // once, so wait a little rather than spin on it
+                eprintln!("accept: {}", e);
+                rt.sleep(ACCEPT_BACKOFF).await;
+                continue;
+            }
+        };
+        let Ok(conn) = rt.register(&stream) else {
+            continue;
+        };
+
+        let mut head = [0u8; 1024];
+        let mut len = 0;
+        while len < head.len() && !head[..len].windows(4).any(|w| w == b"\r\n\r\n") {
+            match rt.read(conn, &mut stream, &mut head[len..]).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => len += n,
+            }
+        }
+
+        let path = request_path(&head[..len]).unwrap_or("/");
+        let (status, body_text) = match path {
+            "/" | "/hello" => ("200 OK", "Hello from a no_std reactor\n"),
+            _ => ("404 Not Found", "not found\n"),
+        };
+        served.set(served.get() + 1);
+
+        let mut out = [0u8; 512];
+        let mut f = Formatter::new(&mut out);
+        let _ = write!(
+            f,
+            "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body_text.len(),
+            body_text
+        );
+        if rt.write_all(conn, &mut stream, f.as_str().as_bytes()).await.is_err() {
+            eprintln!("{}: write failed", peer);
+        }
+        println!("{} {} -> {}", peer, path, status);
+        let _ = rt.deregister(conn);
+    }
+}
+
+async fn heartbeat(rt: &Rt, served: &Cell<u32>) {
+    loop {
+        rt.sleep(Duration::from_secs(5)).await;
+        println!("[heartbeat] {} requests served", served.get());
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
+    let rt = match Rt::new() {
+        Ok(rt) => rt,
+        Err(e) => {
+            eprintln!("reactor: {}", e);
+            return 1;
+        }
+    };
+    let listener = match TcpListener::bind("127.0.0.1:8080") {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("bind 127.0.0.1:8080: {}", e);
+            return 1;
+        }
+    };
+    let signals = match SignalFd::new(&[SIGINT, SIGTERM]) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("signalfd: {}", e);
+            return 1;
+        }
+    };
+    let (Ok(listen_token), Ok(signal_token)) = (rt.register(&listener), rt.register(&signals)) else {
+        eprintln!("register failed");
+        return 1;
+    };
+    println!("listening on http://127.0.0.1:8080 (Ctrl-C to stop)");
+
+    let served = Cell::new(0);
+    let server = async {
+        let work = pin!(async {
+            let serving = pin!(serve(&rt, &listener, listen_token, &served));
+            let beating = pin!(heartbeat(&rt, &served));
+            let _ = reactor::select(serving, beating).await;
+        });
+        let stop = pin!(rt.signal(signal_token, &signals));
+        if let Either::Right(Ok(sig)) = reactor::select(work, stop).await {
+            println!("\nsignal {}, shutting down", sig);
+        }
+    };
+    let mut server = pin!(server);
+    let mut tasks: [Pin<&mut dyn Future<Output = ()>>; 1] = [server.as_mut()];
+    if let Err(e) = reactor::run(&rt, &mut tasks) {
+        eprintln!("reactor: {}", e);
+        return 1;
+    }
+    println!("served {} requests", served.get());
+    0
+}
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    unsafe { exit(1); }
+}
+
+#[no_mangle]
+pub extern "C" fn rust_eh_personality() { loop {} }
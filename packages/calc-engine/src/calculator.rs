@@ -0,0 +1,1019 @@
+use super::decimal::{Decimal, Rounding, DIVISION_DIGITS};
+use super::functions::{Angle, Constant, Function};
+use super::history::{History, EXPR_CAP, SHOWN_CAP};
+use super::locale::NumberFormat;
+use super::parser::{eval_decimal, Context};
+use super::programmer::{eval_int, format_int, radix_prefix, IntContext, WordSize};
+use super::text::Text;
+use super::{CalcError, Result, ERROR_TEXT};
+
+/// Past evaluations kept by `Calculator`
+pub const HISTORY_CAP: usize = 32;
+
+/// Longest number that can be typed, enough for a 64-bit word in binary
+const ENTRY_CAP: usize = SHOWN_CAP;
+
+/// Which keys a `Calculator` takes and how it evaluates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Standard,
+    /// Adds functions, constants, powers and factorials
+    Scientific,
+    /// Whole numbers only, in any radix, wrapped to a word size
+    Programmer,
+}
+
+impl Mode {
+    pub const ALL: [Mode; 3] = [Mode::Standard, Mode::Scientific, Mode::Programmer];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Mode::Standard => "Standard",
+            Mode::Scientific => "Scientific",
+            Mode::Programmer => "Programmer",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+    Modulo,
+    And,
+    Or,
+    Xor,
+    ShiftLeft,
+    ShiftRight,
+}
+
+impl Operator {
+    /// How the operator is written in expressions, spaces included
+    fn spaced(self) -> &'static str {
+        match self {
+            Operator::Add => " + ",
+            Operator::Subtract => " − ",
+            Operator::Multiply => " × ",
+            Operator::Divide => " ÷ ",
+            Operator::Power => " ^ ",
+            Operator::Modulo => " mod ",
+            Operator::And => " & ",
+            Operator::Or => " | ",
+            Operator::Xor => " xor ",
+            Operator::ShiftLeft => " << ",
+            Operator::ShiftRight => " >> ",
+        }
+    }
+
+    /// Button text
+    pub fn label(self) -> &'static str {
+        self.spaced().trim()
+    }
+
+    /// Whether `mode` has this key
+    pub fn available(self, mode: Mode) -> bool {
+        match self {
+            Operator::Add | Operator::Subtract | Operator::Multiply | Operator::Divide => true,
+            Operator::Power => mode == Mode::Scientific,
+            Operator::Modulo => mode != Mode::Standard,
+            Operator::And | Operator::Or | Operator::Xor | Operator::ShiftLeft | Operator::ShiftRight => {
+                mode == Mode::Programmer
+            }
+        }
+    }
+}
+
+/// Everything a calculator keypad can send
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// 0-9, and 10-15 for A-F in hex
+    Digit(u8),
+    Decimal,
+    Operator(Operator),
+    LeftParen,
+    RightParen,
+    /// ±
+    Negate,
+    Percent,
+    Equals,
+    /// AC: everything but memory and history
+    Clear,
+    /// CE: just the number being typed
+    ClearEntry,
+    Backspace,
+    MemoryAdd,
+    MemorySubtract,
+    MemoryRecall,
+    MemoryClear,
+    /// Opens `sin(` and the like
+    Function(Function),
+    Constant(Constant),
+    Factorial,
+    /// Bitwise NOT of the number being typed
+    BitNot,
+}
+
+impl Key {
+    /// Keyboard character to key, for frontends that read typed text
+    pub fn from_char(c: char) -> Option<Key> {
+        Some(match c {
+            '0'..='9' => Key::Digit(c as u8 - b'0'),
+            'a'..='f' => Key::Digit(c as u8 - b'a' + 10),
+            'A'..='F' => Key::Digit(c as u8 - b'A' + 10),
+            '.' | ',' => Key::Decimal,
+            '+' => Key::Operator(Operator::Add),
+            '-' | '−' => Key::Operator(Operator::Subtract),
+            '*' | '×' | 'x' => Key::Operator(Operator::Multiply),
+            '/' | '÷' => Key::Operator(Operator::Divide),
+            '^' => Key::Operator(Operator::Power),
+            '&' => Key::Operator(Operator::And),
+            '|' => Key::Operator(Operator::Or),
+            '~' => Key::BitNot,
+            '!' => Key::Factorial,
+            '(' => Key::LeftParen,
+            ')' => Key::RightParen,
+            '%' => Key::Percent,
+            '=' | '\n' | '\r' => Key::Equals,
+            '\u{8}' => Key::Backspace,
+            '\u{1b}' => Key::Clear,
+            _ => return None,
+        })
+    }
+}
+
+/// A result: decimal, or a machine word in programmer mode
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Value {
+    Decimal(Decimal),
+    Int(i128),
+}
+
+impl Value {
+    fn as_decimal(self) -> Decimal {
+        match self {
+            Value::Decimal(v) => v,
+            Value::Int(n) => Decimal::from_i128(n),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum State {
+    Entering,
+    Done(Value),
+    Failed(CalcError),
+}
+
+/// Keypad-driven calculator: builds an infix expression from key presses
+/// and evaluates it with full precedence on `Equals`.
+///
+/// ```text
+/// 2 + 3 × 4 =       -> 14
+/// 200 + 10 % =      -> 220
+/// 5 ÷ 0 =           -> Error
+/// sin( 30 ) =       -> 0.5    (scientific, degrees)
+/// FF & 0F =         -> F      (programmer, hex)
+/// ```
+///
+/// Keys the current mode doesn't have are ignored.
+pub struct Calculator {
+    /// Committed part of the expression
+    expr: Text<EXPR_CAP>,
+    /// Number being typed, may carry a leading '-' or '~' and a trailing
+    /// '%' or '!'. In programmer mode it's in `radix`, without a prefix.
+    entry: Text<ENTRY_CAP>,
+    open_parens: u8,
+    /// Entry shows a recalled or stored value; typing replaces it
+    entry_shown: bool,
+    state: State,
+    mode: Mode,
+    angle: Angle,
+    radix: u32,
+    word: WordSize,
+    signed: bool,
+    precision: u32,
+    rounding: Rounding,
+    format: NumberFormat,
+    ans: Decimal,
+    ans_int: i128,
+    memory: Option<Decimal>,
+    history: History<HISTORY_CAP>,
+    display: Text<SHOWN_CAP>,
+    line: Text<{ EXPR_CAP + ENTRY_CAP }>,
+}
+
+impl Calculator {
+    pub fn new() -> Self {
+        let mut calc = Self {
+            expr: Text::new(),
+            entry: Text::new(),
+            open_parens: 0,
+            entry_shown: false,
+            state: State::Entering,
+            mode: Mode::Standard,
+            angle: Angle::Degrees,
+            radix: 10,
+            word: WordSize::QWord,
+            signed: true,
+            precision: DIVISION_DIGITS,
+            rounding: Rounding::HalfEven,
+            format: NumberFormat::PLAIN,
+            ans: Decimal::ZERO,
+            ans_int: 0,
+            memory: None,
+            history: History::new(),
+            display: Text::new(),
+            line: Text::new(),
+        };
+        calc.refresh(None);
+        calc
+    }
+
+    /// Big number on the display: what's being typed, the latest result,
+    /// or "Error"
+    pub fn display(&self) -> &str {
+        self.display.as_str()
+    }
+
+    /// Whole expression so far, for a secondary display line
+    pub fn expression(&self) -> &str {
+        self.line.as_str()
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self.state, State::Failed(_))
+    }
+
+    pub fn error(&self) -> Option<CalcError> {
+        match self.state {
+            State::Failed(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Set right after `Equals` until the next edit
+    pub fn result(&self) -> Option<f64> {
+        match self.state {
+            State::Done(v) => Some(v.as_decimal().to_f64()),
+            _ => None,
+        }
+    }
+
+    /// The number on the display at full precision, for copying out
+    pub fn value(&self) -> Option<Decimal> {
+        self.current_value().map(Value::as_decimal)
+    }
+
+    /// Last successful result
+    pub fn ans(&self) -> f64 {
+        self.ans.to_f64()
+    }
+
+    pub fn memory(&self) -> Option<f64> {
+        self.memory.map(Decimal::to_f64)
+    }
+
+    pub fn number_format(&self) -> NumberFormat {
+        self.format
+    }
+
+    /// Decimal point, grouping and digits of the display
+    pub fn set_number_format(&mut self, format: NumberFormat) {
+        self.format = format;
+        self.refresh(None);
+    }
+
+    /// Significant digits kept by division, and how it rounds to them
+    pub fn set_precision(&mut self, digits: u32, rounding: Rounding) {
+        self.precision = digits;
+        self.rounding = rounding;
+    }
+
+    pub fn history(&self) -> &History<HISTORY_CAP> {
+        &self.history
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Put back an entry saved by an earlier run, oldest first, so the
+    /// last one restored is `history().get(0)`
+    pub fn restore_history(&mut self, expr: &str, result: Result<f64>, integer: Option<i128>, shown: &str) {
+        match integer {
+            Some(n) => self.history.push_integer(expr, Ok(n), shown),
+            None => self.history.push(expr, result, shown),
+        }
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Switch modes, carrying the number on the display over; programmer
+    /// mode truncates it to a whole number
+    pub fn set_mode(&mut self, mode: Mode) {
+        if mode == self.mode {
+            return;
+        }
+        let value = self.current_value();
+        self.clear();
+        self.mode = mode;
+        if let Some(value) = value {
+            self.insert_value(value);
+            self.entry_shown = true;
+        }
+        self.refresh(None);
+    }
+
+    pub fn angle(&self) -> Angle {
+        self.angle
+    }
+
+    pub fn set_angle(&mut self, angle: Angle) {
+        self.angle = angle;
+    }
+
+    /// Radix of programmer mode: 16, 10, 8 or 2
+    pub fn radix(&self) -> u32 {
+        self.radix
+    }
+
+    /// Other radixes are ignored. The number being typed is converted.
+    pub fn set_radix(&mut self, radix: u32) {
+        if matches!(radix, 16 | 10 | 8 | 2) {
+            self.reinterpret(|calc| calc.radix = radix);
+        }
+    }
+
+    pub fn word_size(&self) -> WordSize {
+        self.word
+    }
+
+    /// Narrowing wraps the number on the display
+    pub fn set_word_size(&mut self, word: WordSize) {
+        self.reinterpret(|calc| calc.word = word);
+    }
+
+    pub fn is_signed(&self) -> bool {
+        self.signed
+    }
+
+    pub fn set_signed(&mut self, signed: bool) {
+        self.reinterpret(|calc| calc.signed = signed);
+    }
+
+    /// In programmer mode, the number on the display; for showing it in
+    /// every radix with `format_int`
+    pub fn integer(&self) -> Option<i128> {
+        match (self.mode, self.current_value()) {
+            (Mode::Programmer, Some(Value::Int(n))) => Some(n),
+            _ => None,
+        }
+    }
+
+    pub fn press(&mut self, key: Key) {
+        if self.entry_shown && matches!(key, Key::Digit(_) | Key::Decimal) {
+            self.entry.clear();
+        }
+        self.entry_shown =
+            matches!(key, Key::MemoryAdd | Key::MemorySubtract | Key::MemoryRecall | Key::Constant(_));
+        let scientific = self.mode == Mode::Scientific;
+        let programmer = self.mode == Mode::Programmer;
+        let mut preview = None;
+        match key {
+            Key::Digit(d) if (d as u32) < self.input_radix() => self.digit(d),
+            Key::Decimal if !programmer => self.decimal(),
+            Key::Operator(op) if op.available(self.mode) => {
+                self.operator(op);
+                preview = self.preview();
+            }
+            Key::LeftParen => self.open_group(""),
+            Key::RightParen => {
+                self.right_paren();
+                preview = self.preview();
+            }
+            Key::Negate => self.negate(),
+            Key::Percent if !programmer => self.percent(),
+            Key::Equals => self.equals(),
+            Key::Clear => self.clear(),
+            Key::ClearEntry => match self.state {
+                State::Entering => self.entry.clear(),
+                _ => self.clear(),
+            },
+            Key::Backspace => self.backspace(),
+            Key::MemoryAdd => self.memory_add(false),
+            Key::MemorySubtract => self.memory_add(true),
+            Key::MemoryRecall => {
+                if let Some(m) = self.memory {
+                    self.insert_value(Value::Decimal(m));
+                }
+            }
+            Key::MemoryClear => self.memory = None,
+            Key::Function(f) if scientific => self.open_group(f.name()),
+            Key::Constant(c) if scientific => self.constant(c),
+            Key::Factorial if scientific => self.factorial(),
+            Key::BitNot if programmer => self.bit_not(),
+            _ => {}
+        }
+        self.refresh(preview);
+    }
+
+    /// Evaluate a typed or pasted expression as if it had been keyed in
+    pub fn evaluate(&mut self, expr: &str) {
+        self.clear();
+        if !self.expr.push_str(expr.trim()) {
+            self.state = State::Failed(CalcError::Overflow);
+        } else {
+            self.finish();
+        }
+        self.refresh(None);
+    }
+
+    /// Put a past result back on the display as the current number
+    pub fn recall(&mut self, index: usize) {
+        let Some(entry) = self.history.get(index) else {
+            return;
+        };
+        let value = match (entry.integer(), entry.result()) {
+            (Some(n), _) => Value::Int(n),
+            (None, Ok(v)) => match Decimal::from_f64(v) {
+                Ok(v) => Value::Decimal(v),
+                Err(_) => return,
+            },
+            (None, Err(_)) => return,
+        };
+        self.insert_value(value);
+        self.entry_shown = true;
+        self.refresh(None);
+    }
+
+    fn clear(&mut self) {
+        self.expr.clear();
+        self.entry.clear();
+        self.open_parens = 0;
+        self.entry_shown = false;
+        self.state = State::Entering;
+    }
+
+    /// Digits are typed in this radix
+    fn input_radix(&self) -> u32 {
+        match self.mode {
+            Mode::Programmer => self.radix,
+            _ => 10,
+        }
+    }
+
+    /// After a result or an error, typing starts a new expression
+    fn start_fresh(&mut self) {
+        if self.state != State::Entering {
+            self.clear();
+        }
+    }
+
+    /// Continue from a result: it becomes the number being typed
+    fn continue_from_result(&mut self) -> bool {
+        match self.state {
+            State::Done(v) => {
+                self.clear();
+                self.set_entry(v);
+                true
+            }
+            State::Failed(_) => false,
+            State::Entering => true,
+        }
+    }
+
+    fn expr_ends_with_operator(&self) -> bool {
+        self.expr.as_str().ends_with(' ')
+    }
+
+    fn expr_ends_with(&self, c: char) -> bool {
+        self.expr.as_str().ends_with(c)
+    }
+
+    /// Committed expression ends in something that can take an operator:
+    /// `(2 + 3)` or `(2 + 3)!`
+    fn expr_ends_with_operand(&self) -> bool {
+        self.expr_ends_with(')') || self.expr_ends_with('!')
+    }
+
+    /// Entry holds something that can stand as an operand
+    fn entry_is_operand(&self) -> bool {
+        !self.entry.as_str().trim_start_matches(['-', '~']).is_empty()
+    }
+
+    /// More digits can go on the end of the entry: not after `%`, `!` or
+    /// a constant
+    fn entry_takes_digits(&self) -> bool {
+        match self.entry.as_str().chars().last() {
+            Some(c) => c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '~',
+            None => true,
+        }
+    }
+
+    /// Move the entry into the expression; programmer-mode numbers get a
+    /// radix prefix so they keep their value if the radix changes
+    fn commit_entry(&mut self) {
+        if self.entry.is_empty() {
+            return;
+        }
+        let entry = self.entry.as_str();
+        let digits = entry.trim_start_matches(['-', '~']);
+        let mut number = Text::<{ ENTRY_CAP + 2 }>::new();
+        number.push_str(&entry[..entry.len() - digits.len()]);
+        if self.mode == Mode::Programmer && !digits.is_empty() {
+            number.push_str(radix_prefix(self.radix));
+        }
+        number.push_str(digits);
+        if self.expr.push_str(number.as_str()) {
+            self.entry.clear();
+        }
+    }
+
+    /// "(2 + 3)4" means "(2 + 3) × 4"
+    fn implicit_multiply(&mut self) {
+        if self.entry.is_empty() && self.expr_ends_with_operand() {
+            self.expr.push_str(Operator::Multiply.spaced());
+        }
+    }
+
+    fn digit(&mut self, d: u8) {
+        self.start_fresh();
+        if !self.entry_takes_digits() {
+            return;
+        }
+        self.implicit_multiply();
+        match self.entry.as_str() {
+            "0" => self.entry.clear(),
+            "-0" | "~0" => {
+                self.entry.pop();
+            }
+            _ => {}
+        }
+        let c = char::from_digit(d as u32, 16).unwrap_or('0').to_ascii_uppercase();
+        if self.entry.push(c) && !self.entry_fits_word() {
+            self.entry.pop();
+        }
+    }
+
+    /// In programmer mode, the typed digits fit the word size
+    fn entry_fits_word(&self) -> bool {
+        if self.mode != Mode::Programmer {
+            return true;
+        }
+        let digits = self.entry.as_str().trim_start_matches(['-', '~']);
+        match u128::from_str_radix(digits, self.radix) {
+            Ok(n) => n >> self.word.bits() == 0,
+            Err(_) => digits.is_empty(),
+        }
+    }
+
+    fn decimal(&mut self) {
+        self.start_fresh();
+        let entry = self.entry.as_str();
+        if entry.contains('.') || entry.contains('e') || !self.entry_takes_digits() {
+            return;
+        }
+        self.implicit_multiply();
+        if !self.entry_is_operand() {
+            self.entry.push('0');
+        }
+        self.entry.push('.');
+    }
+
+    fn operator(&mut self, op: Operator) {
+        if !self.continue_from_result() {
+            return;
+        }
+        if !self.entry.is_empty() && !self.entry_is_operand() {
+            // "-" then another operator: drop the sign
+            self.entry.clear();
+        }
+        if self.entry.is_empty() {
+            if self.expr.is_empty() {
+                self.expr.push('0');
+            } else if self.expr_ends_with_operator() {
+                // Changed their mind: swap the operator
+                pop_operator(&mut self.expr);
+            } else if self.expr_ends_with('(') {
+                if op == Operator::Subtract {
+                    self.entry.push('-');
+                }
+                return;
+            }
+        }
+        self.commit_entry();
+        self.expr.push_str(op.spaced());
+    }
+
+    /// `(`, or a function call like `sin(` when `name` is given. A number
+    /// right before it multiplies: "2(" is "2 × (".
+    fn open_group(&mut self, name: &str) {
+        self.start_fresh();
+        if self.entry_is_operand() {
+            self.commit_entry();
+            self.expr.push_str(Operator::Multiply.spaced());
+        } else {
+            self.commit_entry();
+            self.implicit_multiply();
+        }
+        let mut group = Text::<8>::new();
+        if group.push_str(name) && group.push('(') && self.expr.push_str(group.as_str()) {
+            self.open_parens += 1;
+        }
+    }
+
+    fn right_paren(&mut self) {
+        if self.state != State::Entering || self.open_parens == 0 {
+            return;
+        }
+        if !(self.entry_is_operand() || self.entry.is_empty() && self.expr_ends_with_operand()) {
+            return;
+        }
+        self.commit_entry();
+        if self.expr.push(')') {
+            self.open_parens -= 1;
+        }
+    }
+
+    /// Put a sign like '-' or '~' in front of the entry, or take it off
+    fn toggle_prefix(&mut self, sign: char) {
+        if !self.continue_from_result() || (self.entry.is_empty() && self.expr_ends_with_operand()) {
+            return;
+        }
+        if self.entry.as_str().starts_with(sign) {
+            self.entry.remove_first();
+        } else {
+            let mut buf = [0; 4];
+            self.entry.prepend(sign.encode_utf8(&mut buf));
+        }
+    }
+
+    fn negate(&mut self) {
+        self.toggle_prefix('-');
+    }
+
+    fn bit_not(&mut self) {
+        self.toggle_prefix('~');
+    }
+
+    /// Append a postfix operator like '%' or '!' to the entry
+    fn postfix(&mut self, op: char) -> bool {
+        if !self.continue_from_result() {
+            return false;
+        }
+        let entry = self.entry.as_str();
+        if self.entry_is_operand() && !entry.ends_with(['%', '!']) {
+            return self.entry.push(op);
+        }
+        false
+    }
+
+    fn percent(&mut self) {
+        self.postfix('%');
+    }
+
+    fn factorial(&mut self) {
+        if self.postfix('!') {
+            return;
+        }
+        // "(2 + 3)!"
+        if self.state == State::Entering && self.entry.is_empty() && self.expr_ends_with(')') {
+            self.expr.push('!');
+        }
+    }
+
+    fn constant(&mut self, c: Constant) {
+        self.start_fresh();
+        if self.entry_is_operand() {
+            self.commit_entry();
+            self.expr.push_str(Operator::Multiply.spaced());
+        } else {
+            self.implicit_multiply();
+        }
+        self.entry.set(c.name());
+    }
+
+    fn backspace(&mut self) {
+        if self.state != State::Entering {
+            return;
+        }
+        if self.entry.is_empty() {
+            self.reopen_entry();
+            if !self.entry.is_empty() {
+                return self.backspace();
+            }
+            if self.expr_ends_with_operator() {
+                pop_operator(&mut self.expr);
+            } else if let Some(c) = self.expr.pop() {
+                match c {
+                    '(' => {
+                        self.open_parens -= 1;
+                        self.pop_function_name();
+                    }
+                    ')' => self.open_parens += 1,
+                    _ => {}
+                }
+            }
+            return;
+        }
+        self.entry.pop();
+    }
+
+    /// "sin(" goes in one press, so it comes out in one too
+    fn pop_function_name(&mut self) {
+        let expr = self.expr.as_str();
+        let name = Function::ALL.into_iter().map(Function::name).find(|name| {
+            expr.strip_suffix(name)
+                .is_some_and(|before| !before.ends_with(|c: char| c.is_ascii_alphanumeric()))
+        });
+        if let Some(name) = name {
+            for _ in 0..name.len() {
+                self.expr.pop();
+            }
+        }
+    }
+
+    /// Move a number at the end of the committed expression back into the
+    /// entry so it can be edited
+    fn reopen_entry(&mut self) {
+        let expr = self.expr.as_str();
+        let start = expr
+            .char_indices()
+            .rev()
+            .take_while(|&(_, c)| c.is_ascii_alphanumeric() || matches!(c, '.' | '%' | '!' | '-' | '~'))
+            .last()
+            .map(|(i, _)| i);
+        let Some(start) = start else {
+            return;
+        };
+        let mut number = Text::<ENTRY_CAP>::new();
+        let reopened = match self.mode {
+            Mode::Programmer => self.unprefixed(&expr[start..], &mut number),
+            _ => number.push_str(&expr[start..]),
+        };
+        if reopened {
+            while self.expr.len() > start {
+                self.expr.pop();
+            }
+            self.entry = number;
+        }
+    }
+
+    /// "-0xFF" back to "-FF", or to whatever it is in the current radix
+    fn unprefixed(&self, literal: &str, out: &mut Text<ENTRY_CAP>) -> bool {
+        let digits = literal.trim_start_matches(['-', '~']);
+        let (radix, body) = match digits.get(..2) {
+            Some("0x") => (16, &digits[2..]),
+            Some("0o") => (8, &digits[2..]),
+            Some("0b") => (2, &digits[2..]),
+            _ => (self.radix, digits),
+        };
+        let Ok(n) = u128::from_str_radix(body, radix) else {
+            return false;
+        };
+        out.push_str(&literal[..literal.len() - digits.len()]);
+        let ctx = self.int_context();
+        format_int(ctx.wrap(n as i128), self.radix, self.word, false, out).is_ok()
+    }
+
+    fn memory_add(&mut self, subtract: bool) {
+        let Some(v) = self.current_value().map(Value::as_decimal) else {
+            return;
+        };
+        let memory = self.memory.unwrap_or(Decimal::ZERO);
+        if let Ok(m) = if subtract { memory.checked_sub(v) } else { memory.checked_add(v) } {
+            self.memory = Some(m);
+        }
+    }
+
+    /// Replace the number being typed with `value` (memory or history)
+    fn insert_value(&mut self, value: Value) {
+        self.start_fresh();
+        self.implicit_multiply();
+        self.set_entry(self.convert(value));
+    }
+
+    /// The entry is typed text, so it takes the number without grouping
+    /// and with a '.' point
+    fn set_entry(&mut self, value: Value) {
+        let plain = NumberFormat { decimal_point: '.', group_separator: None, ..self.format };
+        let mut entry = Text::new();
+        let _ = self.format_value(value, &plain, &mut entry);
+        self.entry = entry;
+    }
+
+    /// Apply a radix, word size or signedness change, keeping the number
+    /// on the display and rewrapping it
+    fn reinterpret(&mut self, change: impl FnOnce(&mut Self)) {
+        let typed = match self.mode {
+            Mode::Programmer if self.entry_is_operand() => eval_int(self.entry.as_str(), &self.int_context()).ok(),
+            _ => None,
+        };
+        change(self);
+        let ctx = self.int_context();
+        if let Some(n) = typed {
+            self.entry.clear();
+            let _ = format_int(ctx.wrap(n), self.radix, self.word, self.signed, &mut self.entry);
+            self.entry_shown = true;
+        }
+        if let State::Done(Value::Int(n)) = self.state {
+            self.state = State::Done(Value::Int(ctx.wrap(n)));
+        }
+        self.ans_int = ctx.wrap(self.ans_int);
+        self.refresh(None);
+    }
+
+    /// `value` in the current mode's number type
+    fn convert(&self, value: Value) -> Value {
+        match (self.mode, value) {
+            (Mode::Programmer, Value::Decimal(v)) => {
+                let whole = v.to_i128().unwrap_or(if v.is_negative() { i128::MIN } else { i128::MAX });
+                Value::Int(self.int_context().wrap(whole))
+            }
+            (Mode::Programmer, Value::Int(n)) => Value::Int(self.int_context().wrap(n)),
+            (_, Value::Int(n)) => Value::Decimal(Decimal::from_i128(n)),
+            (_, v) => v,
+        }
+    }
+
+    fn format_value(&self, value: Value, format: &NumberFormat, out: &mut impl core::fmt::Write) -> core::fmt::Result {
+        match value {
+            Value::Decimal(v) => v.format(format, out),
+            Value::Int(n) => format_int(n, self.radix, self.word, self.signed, out),
+        }
+    }
+
+    /// The number the display stands for right now
+    fn current_value(&self) -> Option<Value> {
+        match self.state {
+            State::Done(v) => Some(v),
+            State::Failed(_) => None,
+            State::Entering if self.entry_is_operand() => self.eval(self.entry.as_str()).ok(),
+            State::Entering => self.preview(),
+        }
+    }
+
+    /// Value of what's been typed so far, trailing operator dropped and
+    /// open parentheses closed
+    fn preview(&self) -> Option<Value> {
+        let mut expr = Text::<{ EXPR_CAP + ENTRY_CAP + 32 }>::new();
+        expr.push_str(self.expr.as_str());
+        expr.push_str(self.entry.as_str());
+        if expr.as_str().ends_with(' ') {
+            pop_operator(&mut expr);
+        }
+        for _ in 0..self.open_parens {
+            expr.push(')');
+        }
+        self.eval(expr.as_str()).ok()
+    }
+
+    fn context(&self) -> Context {
+        Context {
+            ans: self.ans,
+            memory: self.memory.unwrap_or(Decimal::ZERO),
+            angle: self.angle,
+            precision: self.precision,
+            rounding: self.rounding,
+        }
+    }
+
+    fn int_context(&self) -> IntContext {
+        IntContext {
+            radix: self.radix,
+            word: self.word,
+            signed: self.signed,
+            ans: self.ans_int,
+            memory: self.memory.and_then(Decimal::to_i128).unwrap_or(0),
+        }
+    }
+
+    fn eval(&self, expr: &str) -> Result<Value> {
+        match self.mode {
+            Mode::Programmer => eval_int(expr, &self.int_context()).map(Value::Int),
+            _ => eval_decimal(expr, &self.context()).map(Value::Decimal),
+        }
+    }
+
+    fn equals(&mut self) {
+        if self.state != State::Entering {
+            return;
+        }
+        if !self.entry_is_operand() {
+            self.entry.clear();
+        }
+        self.commit_entry();
+        loop {
+            if self.expr_ends_with_operator() {
+                pop_operator(&mut self.expr);
+            } else if self.expr_ends_with('(') {
+                self.expr.pop();
+                self.open_parens -= 1;
+                self.pop_function_name();
+            } else {
+                break;
+            }
+        }
+        if self.expr.is_empty() {
+            return;
+        }
+        while self.open_parens > 0 && self.expr.push(')') {
+            self.open_parens -= 1;
+        }
+        self.finish();
+    }
+
+    /// Evaluate `expr` and record it
+    fn finish(&mut self) {
+        let result = self.eval(self.expr.as_str());
+        let mut shown = Text::<SHOWN_CAP>::new();
+        match result {
+            Ok(v) => {
+                let _ = self.format_value(v, &self.format, &mut shown);
+            }
+            Err(_) => {
+                shown.set(ERROR_TEXT);
+            }
+        }
+        let expr = self.expr.as_str();
+        match result {
+            Ok(Value::Int(n)) => self.history.push_integer(expr, Ok(n), shown.as_str()),
+            Ok(Value::Decimal(v)) => self.history.push(expr, Ok(v.to_f64()), shown.as_str()),
+            Err(e) => self.history.push(expr, Err(e), shown.as_str()),
+        }
+        self.state = match result {
+            Ok(v) => {
+                match v {
+                    Value::Decimal(v) => self.ans = v,
+                    Value::Int(n) => self.ans_int = n,
+                }
+                State::Done(v)
+            }
+            Err(e) => State::Failed(e),
+        };
+    }
+
+    fn refresh(&mut self, preview: Option<Value>) {
+        self.line.clear();
+        self.line.push_str(self.expr.as_str());
+        self.line.push_str(self.entry.as_str());
+        if self.state != State::Entering {
+            self.line.push_str(" =");
+        }
+
+        let mut display = Text::<SHOWN_CAP>::new();
+        match self.state {
+            State::Failed(_) => {
+                display.set(ERROR_TEXT);
+            }
+            State::Done(v) => {
+                let _ = self.format_value(v, &self.format, &mut display);
+            }
+            State::Entering if !self.entry.is_empty() => {
+                if self.mode == Mode::Programmer {
+                    display.set(self.entry.as_str());
+                } else {
+                    let _ = self.format.localize(self.entry.as_str(), &mut display);
+                }
+            }
+            State::Entering => {
+                if let Some(v) = preview {
+                    let _ = self.format_value(v, &self.format, &mut display);
+                } else if self.expr.is_empty() {
+                    display.set("0");
+                } else {
+                    return;
+                }
+            }
+        }
+        self.display = display;
+    }
+}
+
+/// Drop a trailing " op " from an expression
+fn pop_operator<const N: usize>(expr: &mut Text<N>) {
+    expr.pop();
+    while let Some(c) = expr.pop() {
+        if c == ' ' {
+            break;
+        }
+    }
+}
+
+impl Default for Calculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for Calculator {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Calculator({:?} | {:?})", self.expression(), self.display())
+    }
+}
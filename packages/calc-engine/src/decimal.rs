@@ -0,0 +1,693 @@
+use super::locale::NumberFormat;
+use super::text::Text;
+use super::{CalcError, Result};
+use core::cmp::Ordering;
+use core::fmt;
+use core::num::IntErrorKind;
+
+/// Significant digits a `Decimal` keeps; longer results are rounded half
+/// to even
+pub const MAX_DIGITS: u32 = 50;
+
+/// Default significant digits of a quotient, as in IEEE decimal128
+pub const DIVISION_DIGITS: u32 = 34;
+
+/// Largest power of ten a `Decimal` reaches; smaller than the negative of
+/// it underflows to zero
+const MAX_EXPONENT: i64 = 9999;
+
+/// Room for every intermediate mantissa: a product of two `MAX_DIGITS`
+/// numbers, or a dividend scaled up for `DIVISION_DIGITS` more
+const DIGITS_CAP: usize = 160;
+
+const LIMBS: usize = 16;
+
+/// Fixed-width unsigned integer, little-endian 32-bit limbs; 512 bits is
+/// 154 decimal digits
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Uint([u32; LIMBS]);
+
+impl Uint {
+    const ZERO: Uint = Uint([0; LIMBS]);
+    const ONE: Uint = {
+        let mut limbs = [0; LIMBS];
+        limbs[0] = 1;
+        Uint(limbs)
+    };
+
+    fn from_u128(mut v: u128) -> Uint {
+        let mut limbs = [0; LIMBS];
+        for limb in limbs.iter_mut().take(4) {
+            *limb = v as u32;
+            v >>= 32;
+        }
+        Uint(limbs)
+    }
+
+    fn to_u128(self) -> Option<u128> {
+        if self.0[4..].iter().any(|&l| l != 0) {
+            return None;
+        }
+        Some(self.0[..4].iter().rev().fold(0, |acc, &l| acc << 32 | l as u128))
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(|&l| l == 0)
+    }
+
+    fn is_odd(&self) -> bool {
+        self.0[0] & 1 == 1
+    }
+
+    fn add(&self, other: &Uint) -> Option<Uint> {
+        let mut out = [0; LIMBS];
+        let mut carry = 0u64;
+        for (i, limb) in out.iter_mut().enumerate() {
+            let sum = self.0[i] as u64 + other.0[i] as u64 + carry;
+            *limb = sum as u32;
+            carry = sum >> 32;
+        }
+        if carry == 0 { Some(Uint(out)) } else { None }
+    }
+
+    /// `self - other`; `self` must be the larger
+    fn sub(&self, other: &Uint) -> Uint {
+        let mut out = [0; LIMBS];
+        let mut borrow = 0i64;
+        for (i, limb) in out.iter_mut().enumerate() {
+            let mut diff = self.0[i] as i64 - other.0[i] as i64 - borrow;
+            borrow = 0;
+            if diff < 0 {
+                diff += 1 << 32;
+                borrow = 1;
+            }
+            *limb = diff as u32;
+        }
+        Uint(out)
+    }
+
+    fn mul_small(&self, m: u32) -> Option<Uint> {
+        let mut out = [0; LIMBS];
+        let mut carry = 0u64;
+        for (i, limb) in out.iter_mut().enumerate() {
+            let product = self.0[i] as u64 * m as u64 + carry;
+            *limb = product as u32;
+            carry = product >> 32;
+        }
+        if carry == 0 { Some(Uint(out)) } else { None }
+    }
+
+    fn mul(&self, other: &Uint) -> Option<Uint> {
+        let mut wide = [0u64; 2 * LIMBS];
+        for i in 0..LIMBS {
+            if self.0[i] == 0 {
+                continue;
+            }
+            let mut carry = 0u64;
+            for j in 0..LIMBS {
+                let t = wide[i + j] + self.0[i] as u64 * other.0[j] as u64 + carry;
+                wide[i + j] = t & 0xFFFF_FFFF;
+                carry = t >> 32;
+            }
+            wide[i + LIMBS] = carry;
+        }
+        if wide[LIMBS..].iter().any(|&l| l != 0) {
+            return None;
+        }
+        let mut out = [0; LIMBS];
+        for (limb, &w) in out.iter_mut().zip(wide.iter()) {
+            *limb = w as u32;
+        }
+        Some(Uint(out))
+    }
+
+    fn div_small(&self, d: u32) -> (Uint, u32) {
+        let mut out = [0; LIMBS];
+        let mut rem = 0u64;
+        for i in (0..LIMBS).rev() {
+            let cur = rem << 32 | self.0[i] as u64;
+            out[i] = (cur / d as u64) as u32;
+            rem = cur % d as u64;
+        }
+        (Uint(out), rem as u32)
+    }
+
+    fn bits(&self) -> u32 {
+        match self.0.iter().rposition(|&l| l != 0) {
+            Some(i) => i as u32 * 32 + 32 - self.0[i].leading_zeros(),
+            None => 0,
+        }
+    }
+
+    /// Quotient and remainder, by binary long division
+    fn div_rem(&self, d: &Uint) -> (Uint, Uint) {
+        if d.0[1..].iter().all(|&l| l == 0) {
+            let (q, r) = self.div_small(d.0[0]);
+            return (q, Uint::from_u128(r as u128));
+        }
+        let mut q = Uint::ZERO;
+        let mut r = Uint::ZERO;
+        for i in (0..self.bits()).rev() {
+            // r < d, so shifting left can't overflow for our sizes
+            for j in (1..LIMBS).rev() {
+                r.0[j] = r.0[j] << 1 | r.0[j - 1] >> 31;
+            }
+            r.0[0] = r.0[0] << 1 | (self.0[i as usize / 32] >> (i % 32)) & 1;
+            if r >= *d {
+                r = r.sub(d);
+                q.0[i as usize / 32] |= 1 << (i % 32);
+            }
+        }
+        (q, r)
+    }
+
+    fn mul_pow10(&self, mut n: u32) -> Option<Uint> {
+        let mut v = *self;
+        while n >= 9 {
+            v = v.mul_small(1_000_000_000)?;
+            n -= 9;
+        }
+        v.mul_small(10u32.pow(n))
+    }
+
+    fn pow10(n: u32) -> Option<Uint> {
+        Uint::ONE.mul_pow10(n)
+    }
+
+    /// Number of decimal digits; 0 has none
+    fn digits(&self) -> u32 {
+        let mut v = *self;
+        let mut count = 0;
+        while v.0[1..].iter().any(|&l| l != 0) || v.0[0] >= 1_000_000_000 {
+            v = v.div_small(1_000_000_000).0;
+            count += 9;
+        }
+        match v.0[0] {
+            0 => count,
+            low => count + low.ilog10() + 1,
+        }
+    }
+
+    fn write_digits(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        // Nine digits at a time, least significant chunk first
+        let mut chunks = [0u32; DIGITS_CAP / 9 + 1];
+        let mut len = 0;
+        let mut v = *self;
+        loop {
+            let (q, r) = v.div_small(1_000_000_000);
+            chunks[len] = r;
+            len += 1;
+            v = q;
+            if v.is_zero() {
+                break;
+            }
+        }
+        write!(out, "{}", chunks[len - 1])?;
+        for chunk in chunks[..len - 1].iter().rev() {
+            write!(out, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for Uint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl PartialOrd for Uint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// How to round away digits that don't fit
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Rounding {
+    /// Nearest, ties to the even digit (banker's rounding)
+    #[default]
+    HalfEven,
+    /// Nearest, ties away from zero (school rounding)
+    HalfUp,
+    /// Nearest, ties toward zero
+    HalfDown,
+    /// Away from zero
+    Up,
+    /// Toward zero: truncation
+    Down,
+    /// Toward +∞
+    Ceiling,
+    /// Toward -∞
+    Floor,
+}
+
+/// Drop the last `drop` digits of `m`, rounding per `mode`. `sticky` says
+/// nonzero digits were already lost beyond the ones being dropped.
+fn round_mantissa(m: &Uint, drop: u32, mode: Rounding, negative: bool, sticky: bool) -> Uint {
+    if drop == 0 {
+        return *m;
+    }
+    let (q, r, vs_half) = match Uint::pow10(drop) {
+        Some(d) if drop <= m.digits() => {
+            let (q, r) = m.div_rem(&d);
+            (q, r, r.cmp(&d.div_small(2).0))
+        }
+        // Everything goes, and it's less than half of the last place kept
+        _ => (Uint::ZERO, *m, Ordering::Less),
+    };
+    let inexact = sticky || !r.is_zero();
+    let vs_half = if vs_half == Ordering::Equal && sticky { Ordering::Greater } else { vs_half };
+    let up = match mode {
+        Rounding::HalfEven => vs_half == Ordering::Greater || vs_half == Ordering::Equal && q.is_odd(),
+        Rounding::HalfUp => vs_half != Ordering::Less,
+        Rounding::HalfDown => vs_half == Ordering::Greater,
+        Rounding::Up => inexact,
+        Rounding::Down => false,
+        Rounding::Ceiling => inexact && !negative,
+        Rounding::Floor => inexact && negative,
+    };
+    if up { q.add(&Uint::ONE).unwrap_or(q) } else { q }
+}
+
+/// Exact decimal number: an integer mantissa of up to `MAX_DIGITS` digits
+/// times a power of ten. `0.1 + 0.2` is exactly `0.3`, unlike with `f64`.
+///
+/// Addition, subtraction and multiplication are exact as long as the
+/// result fits in `MAX_DIGITS`; division rounds to a chosen number of
+/// significant digits. Values are kept without trailing zeros, so `2.50`
+/// and `2.5` compare equal.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Decimal {
+    negative: bool,
+    mantissa: Uint,
+    exponent: i32,
+}
+
+impl Decimal {
+    pub const ZERO: Decimal = Decimal { negative: false, mantissa: Uint::ZERO, exponent: 0 };
+    pub const ONE: Decimal = Decimal { negative: false, mantissa: Uint::ONE, exponent: 0 };
+
+    /// Normalise `(-1)^negative × mantissa × 10^exponent`: round to
+    /// `MAX_DIGITS`, drop trailing zeros and check the range
+    fn finish(negative: bool, mantissa: Uint, exponent: i64) -> Result<Decimal> {
+        let mut m = mantissa;
+        let mut e = exponent;
+        let digits = m.digits();
+        if digits == 0 {
+            return Ok(Decimal::ZERO);
+        }
+        if digits > MAX_DIGITS {
+            let drop = digits - MAX_DIGITS;
+            m = round_mantissa(&m, drop, Rounding::HalfEven, negative, false);
+            e = e.saturating_add(drop as i64);
+        }
+        loop {
+            let (q, r) = m.div_small(10);
+            if r != 0 {
+                break;
+            }
+            m = q;
+            e = e.saturating_add(1);
+        }
+        let adjusted = e.saturating_add(m.digits() as i64 - 1);
+        if adjusted > MAX_EXPONENT {
+            return Err(CalcError::Overflow);
+        }
+        if adjusted < -MAX_EXPONENT {
+            return Ok(Decimal::ZERO);
+        }
+        Ok(Decimal { negative, mantissa: m, exponent: e as i32 })
+    }
+
+    pub fn from_i128(v: i128) -> Decimal {
+        Decimal::finish(v < 0, Uint::from_u128(v.unsigned_abs()), 0).unwrap_or(Decimal::ZERO)
+    }
+
+    /// The shortest decimal that reads back as `v`, so `0.1` becomes
+    /// exactly 0.1 rather than 0.1000000000000000055511151231257827
+    pub fn from_f64(v: f64) -> Result<Decimal> {
+        if v.is_nan() {
+            return Err(CalcError::Domain);
+        }
+        if v.is_infinite() {
+            return Err(CalcError::Overflow);
+        }
+        let mut buf = Text::<40>::new();
+        let _ = fmt::Write::write_fmt(&mut buf, format_args!("{:e}", v));
+        Decimal::parse(buf.as_str())
+    }
+
+    /// Nearest `f64`; infinite past its range
+    pub fn to_f64(self) -> f64 {
+        let mut buf = Text::<{ DIGITS_CAP + 16 }>::new();
+        if self.negative {
+            buf.push('-');
+        }
+        let _ = self.mantissa.write_digits(&mut buf);
+        let _ = fmt::Write::write_fmt(&mut buf, format_args!("e{}", self.exponent));
+        buf.as_str().parse().unwrap_or(f64::NAN)
+    }
+
+    /// `123`, `-0.05`, `1.5e-7`, `2E10`; digits past `MAX_DIGITS` are
+    /// rounded
+    pub fn parse(s: &str) -> Result<Decimal> {
+        let s = s.trim();
+        let (negative, body) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (digits, exponent) = match body.find(['e', 'E']) {
+            // An exponent too long for an i64 is still just out of range
+            Some(i) => match body[i + 1..].parse::<i64>() {
+                Ok(e) => (&body[..i], e),
+                Err(err) => match err.kind() {
+                    IntErrorKind::PosOverflow => (&body[..i], i64::MAX),
+                    IntErrorKind::NegOverflow => (&body[..i], i64::MIN),
+                    _ => return Err(CalcError::Syntax(0)),
+                },
+            },
+            None => (body, 0),
+        };
+        // Digits move the exponent by at most one each, so past this bound
+        // the result overflows or underflows whatever they are; clamping
+        // keeps the counting below from overflowing an i64
+        let bound = MAX_EXPONENT + DIGITS_CAP as i64 + digits.len() as i64;
+        let mut m = Uint::ZERO;
+        let mut e = exponent.clamp(-bound - 1, bound + 1);
+        let mut kept = 0;
+        let mut seen_digit = false;
+        let mut seen_point = false;
+        for c in digits.chars() {
+            match c {
+                '0'..='9' => {
+                    seen_digit = true;
+                    let d = c as u32 - '0' as u32;
+                    if m.is_zero() && d == 0 || kept < DIGITS_CAP - 10 {
+                        m = m.mul_small(10).and_then(|m| m.add(&Uint::from_u128(d as u128))).unwrap_or(m);
+                        kept += !m.is_zero() as usize;
+                        if seen_point {
+                            e -= 1;
+                        }
+                    } else if !seen_point {
+                        // Too many digits to keep; they only affect rounding
+                        e += 1;
+                    }
+                }
+                '.' if !seen_point => seen_point = true,
+                _ => return Err(CalcError::Syntax(0)),
+            }
+        }
+        if !seen_digit {
+            return Err(CalcError::Syntax(0));
+        }
+        Decimal::finish(negative, m, e)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Whole number, no fraction
+    pub fn is_integer(&self) -> bool {
+        self.exponent >= 0
+    }
+
+    pub fn abs(self) -> Decimal {
+        Decimal { negative: false, ..self }
+    }
+
+    /// Power of ten of the leading digit: 1234.5 -> 3, 0.05 -> -2
+    fn adjusted(&self) -> i64 {
+        self.exponent as i64 + self.mantissa.digits() as i64 - 1
+    }
+
+    /// Whole part, truncated toward zero; `None` if it won't fit
+    pub fn to_i128(self) -> Option<i128> {
+        let magnitude = if self.exponent >= 0 {
+            self.mantissa.mul_pow10(self.exponent as u32)?.to_u128()?
+        } else {
+            let drop = self.exponent.unsigned_abs();
+            match Uint::pow10(drop) {
+                Some(d) if drop <= self.mantissa.digits() => self.mantissa.div_rem(&d).0.to_u128()?,
+                _ => 0,
+            }
+        };
+        if self.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
+    /// `self × 10^n`, exactly
+    pub fn scale(self, n: i32) -> Result<Decimal> {
+        Decimal::finish(self.negative, self.mantissa, self.exponent as i64 + n as i64)
+    }
+
+    pub fn checked_add(self, other: Decimal) -> Result<Decimal> {
+        if other.is_zero() {
+            return Ok(self);
+        }
+        if self.is_zero() {
+            return Ok(other);
+        }
+        // Something more than MAX_DIGITS places below the other operand's
+        // leading digit can't change the rounded sum
+        let gap = self.adjusted() - other.adjusted();
+        if gap > MAX_DIGITS as i64 + 1 {
+            return Ok(self);
+        }
+        if gap < -(MAX_DIGITS as i64 + 1) {
+            return Ok(other);
+        }
+        let e = self.exponent.min(other.exponent);
+        let a = self.mantissa.mul_pow10((self.exponent - e) as u32).ok_or(CalcError::Overflow)?;
+        let b = other.mantissa.mul_pow10((other.exponent - e) as u32).ok_or(CalcError::Overflow)?;
+        if self.negative == other.negative {
+            let sum = a.add(&b).ok_or(CalcError::Overflow)?;
+            return Decimal::finish(self.negative, sum, e as i64);
+        }
+        match a.cmp(&b) {
+            Ordering::Equal => Ok(Decimal::ZERO),
+            Ordering::Greater => Decimal::finish(self.negative, a.sub(&b), e as i64),
+            Ordering::Less => Decimal::finish(other.negative, b.sub(&a), e as i64),
+        }
+    }
+
+    pub fn checked_sub(self, other: Decimal) -> Result<Decimal> {
+        self.checked_add(-other)
+    }
+
+    pub fn checked_mul(self, other: Decimal) -> Result<Decimal> {
+        let product = self.mantissa.mul(&other.mantissa).ok_or(CalcError::Overflow)?;
+        Decimal::finish(self.negative != other.negative, product, self.exponent as i64 + other.exponent as i64)
+    }
+
+    /// Quotient rounded to `digits` significant digits (at most
+    /// `MAX_DIGITS`)
+    pub fn div(self, other: Decimal, digits: u32, rounding: Rounding) -> Result<Decimal> {
+        if other.is_zero() {
+            return Err(CalcError::DivideByZero);
+        }
+        if self.is_zero() {
+            return Ok(Decimal::ZERO);
+        }
+        let digits = digits.clamp(1, MAX_DIGITS);
+        // Scale the dividend so the integer quotient has at least one
+        // digit more than we keep
+        let shift = (digits as i64 + other.mantissa.digits() as i64 - self.mantissa.digits() as i64 + 1).max(0) as u32;
+        let dividend = self.mantissa.mul_pow10(shift).ok_or(CalcError::Overflow)?;
+        let (q, r) = dividend.div_rem(&other.mantissa);
+        let negative = self.negative != other.negative;
+        let drop = q.digits().saturating_sub(digits);
+        let q = round_mantissa(&q, drop, rounding, negative, !r.is_zero());
+        let exponent = self.exponent as i64 - other.exponent as i64 - shift as i64 + drop as i64;
+        Decimal::finish(negative, q, exponent)
+    }
+
+    /// Remainder of truncating division, with the sign of `self`
+    pub fn checked_rem(self, other: Decimal) -> Result<Decimal> {
+        if other.is_zero() {
+            return Err(CalcError::DivideByZero);
+        }
+        let e = self.exponent.min(other.exponent);
+        let a = self.mantissa.mul_pow10((self.exponent - e) as u32).ok_or(CalcError::Overflow)?;
+        let b = other.mantissa.mul_pow10((other.exponent - e) as u32).ok_or(CalcError::Overflow)?;
+        Decimal::finish(self.negative, a.div_rem(&b).1, e as i64)
+    }
+
+    /// `self^n` by repeated squaring; negative powers divide like `div`.
+    /// Results too small to represent are zero, either sign of `n`
+    pub fn powi(self, n: i64, digits: u32, rounding: Rounding) -> Result<Decimal> {
+        if n < 0 && self.is_zero() {
+            return Err(CalcError::DivideByZero);
+        }
+        let result = self.powu(n.unsigned_abs());
+        if n >= 0 {
+            return result;
+        }
+        // Out of range one way means the reciprocal is out of range the other
+        match result {
+            Err(CalcError::Overflow) => Ok(Decimal::ZERO),
+            Ok(r) if r.is_zero() => Err(CalcError::Overflow),
+            Ok(r) => Decimal::ONE.div(r, digits, rounding),
+            Err(e) => Err(e),
+        }
+    }
+
+    // self^k by squaring; too small a result comes out as zero
+    fn powu(self, mut k: u64) -> Result<Decimal> {
+        let mut result = Decimal::ONE;
+        let mut base = self;
+        while k > 0 {
+            if k & 1 == 1 {
+                result = result.checked_mul(base)?;
+            }
+            k >>= 1;
+            if k > 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Round to `places` digits after the point; negative rounds to tens,
+    /// hundreds...
+    pub fn round(self, places: i32, rounding: Rounding) -> Decimal {
+        let drop = -(self.exponent as i64) - places as i64;
+        if drop <= 0 {
+            return self;
+        }
+        let drop = drop.min(DIGITS_CAP as i64) as u32;
+        let m = round_mantissa(&self.mantissa, drop, rounding, self.negative, false);
+        Decimal::finish(self.negative, m, self.exponent as i64 + drop as i64).unwrap_or(self)
+    }
+
+    /// Round to `digits` significant digits
+    pub fn round_significant(self, digits: u32, rounding: Rounding) -> Decimal {
+        let drop = self.mantissa.digits().saturating_sub(digits.max(1));
+        let m = round_mantissa(&self.mantissa, drop, rounding, self.negative, false);
+        Decimal::finish(self.negative, m, self.exponent as i64 + drop as i64).unwrap_or(self)
+    }
+
+    /// `-0.0012`, `1500`
+    fn write_plain(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let mut digits = Text::<DIGITS_CAP>::new();
+        self.mantissa.write_digits(&mut digits)?;
+        let digits = digits.as_str();
+        if self.negative {
+            out.write_char('-')?;
+        }
+        if self.exponent >= 0 {
+            out.write_str(digits)?;
+            for _ in 0..self.exponent {
+                out.write_char('0')?;
+            }
+            return Ok(());
+        }
+        let point = digits.len() as i64 + self.exponent as i64;
+        if point > 0 {
+            let (int, frac) = digits.split_at(point as usize);
+            return write!(out, "{}.{}", int, frac);
+        }
+        out.write_str("0.")?;
+        for _ in 0..-point {
+            out.write_char('0')?;
+        }
+        out.write_str(digits)
+    }
+
+    /// `-1.2e-12`, `5e20`
+    fn write_scientific(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let mut digits = Text::<DIGITS_CAP>::new();
+        self.mantissa.write_digits(&mut digits)?;
+        let (lead, rest) = digits.as_str().split_at(1);
+        if self.negative {
+            out.write_char('-')?;
+        }
+        out.write_str(lead)?;
+        if !rest.is_empty() {
+            write!(out, ".{}", rest)?;
+        }
+        write!(out, "e{}", self.adjusted())
+    }
+
+    /// Display form: rounded to `format.significant_digits`, grouped and
+    /// with the locale's decimal point. Magnitudes of 1e15 and up or below
+    /// 1e-9 switch to `1.5e20` notation.
+    pub fn format(&self, format: &NumberFormat, out: &mut impl fmt::Write) -> fmt::Result {
+        let rounded = self.round_significant(format.significant_digits, format.rounding);
+        if rounded.is_zero() {
+            return out.write_char('0');
+        }
+        let mut plain = Text::<{ DIGITS_CAP + 16 }>::new();
+        let adjusted = rounded.adjusted();
+        if !(-9..15).contains(&adjusted) {
+            rounded.write_scientific(&mut plain)?;
+        } else {
+            rounded.write_plain(&mut plain)?;
+        }
+        format.localize(plain.as_str(), out)
+    }
+}
+
+impl core::ops::Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        if self.is_zero() {
+            return self;
+        }
+        Decimal { negative: !self.negative, ..self }
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let diff = self.checked_sub(*other).ok()?;
+        Some(if diff.is_zero() {
+            Ordering::Equal
+        } else if diff.negative {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        })
+    }
+}
+
+impl Default for Decimal {
+    fn default() -> Self {
+        Decimal::ZERO
+    }
+}
+
+impl core::str::FromStr for Decimal {
+    type Err = CalcError;
+
+    fn from_str(s: &str) -> Result<Decimal> {
+        Decimal::parse(s)
+    }
+}
+
+/// Every digit: plain notation unless the exponent is far out
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if (-30..=40).contains(&self.adjusted()) {
+            self.write_plain(f)
+        } else {
+            self.write_scientific(f)
+        }
+    }
+}
+
+impl fmt::Debug for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
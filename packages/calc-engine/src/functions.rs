@@ -0,0 +1,288 @@
+use super::decimal::{Decimal, Rounding};
+use super::{CalcError, Result};
+
+#[cfg_attr(unix, link(name = "m"))]
+unsafe extern "C" {
+    fn sin(x: f64) -> f64;
+    fn cos(x: f64) -> f64;
+    fn tan(x: f64) -> f64;
+    fn asin(x: f64) -> f64;
+    fn acos(x: f64) -> f64;
+    fn atan(x: f64) -> f64;
+    fn sinh(x: f64) -> f64;
+    fn cosh(x: f64) -> f64;
+    fn tanh(x: f64) -> f64;
+    fn log(x: f64) -> f64;
+    fn log10(x: f64) -> f64;
+    fn log2(x: f64) -> f64;
+    fn exp(x: f64) -> f64;
+    fn sqrt(x: f64) -> f64;
+    fn cbrt(x: f64) -> f64;
+    fn pow(x: f64, y: f64) -> f64;
+    fn fmod(x: f64, y: f64) -> f64;
+}
+
+/// How trig functions read and return angles
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Angle {
+    #[default]
+    Degrees,
+    Radians,
+}
+
+impl Angle {
+    fn to_radians(self, x: f64) -> f64 {
+        match self {
+            Angle::Degrees => x * (core::f64::consts::PI / 180.0),
+            Angle::Radians => x,
+        }
+    }
+
+    fn convert_radians(self, x: f64) -> f64 {
+        match self {
+            Angle::Degrees => x * (180.0 / core::f64::consts::PI),
+            Angle::Radians => x,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Angle::Degrees => "DEG",
+            Angle::Radians => "RAD",
+        }
+    }
+}
+
+/// One-argument functions of scientific mode
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Function {
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    Sinh,
+    Cosh,
+    Tanh,
+    /// Natural log
+    Ln,
+    Log10,
+    Log2,
+    /// e^x
+    Exp,
+    Sqrt,
+    Cbrt,
+    Abs,
+}
+
+impl Function {
+    pub const ALL: [Function; 16] = [
+        Function::Sin,
+        Function::Cos,
+        Function::Tan,
+        Function::Asin,
+        Function::Acos,
+        Function::Atan,
+        Function::Sinh,
+        Function::Cosh,
+        Function::Tanh,
+        Function::Ln,
+        Function::Log10,
+        Function::Log2,
+        Function::Exp,
+        Function::Sqrt,
+        Function::Cbrt,
+        Function::Abs,
+    ];
+
+    /// Name as written in expressions
+    pub fn name(self) -> &'static str {
+        match self {
+            Function::Sin => "sin",
+            Function::Cos => "cos",
+            Function::Tan => "tan",
+            Function::Asin => "asin",
+            Function::Acos => "acos",
+            Function::Atan => "atan",
+            Function::Sinh => "sinh",
+            Function::Cosh => "cosh",
+            Function::Tanh => "tanh",
+            Function::Ln => "ln",
+            Function::Log10 => "log",
+            Function::Log2 => "log2",
+            Function::Exp => "exp",
+            Function::Sqrt => "sqrt",
+            Function::Cbrt => "cbrt",
+            Function::Abs => "abs",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Function> {
+        Self::ALL.into_iter().find(|f| f.name() == name)
+    }
+
+    /// Computed in `f64`, then rounded to the 15 digits it's good for,
+    /// so `sin 30` comes out as exactly 0.5
+    pub fn apply(self, x: Decimal, angle: Angle) -> Result<Decimal> {
+        if self == Function::Abs {
+            return Ok(x.abs());
+        }
+        let x = x.to_f64();
+        let domain = |ok: bool| if ok { Ok(()) } else { Err(CalcError::Domain) };
+        let y = unsafe {
+            match self {
+                Function::Sin => sin(angle.to_radians(x)),
+                Function::Cos => cos(angle.to_radians(x)),
+                Function::Tan => {
+                    // tan(90°) would otherwise come out as 1.6e16
+                    if angle == Angle::Degrees && fmod(x - 90.0, 180.0) == 0.0 {
+                        return Err(CalcError::Domain);
+                    }
+                    tan(angle.to_radians(x))
+                }
+                Function::Asin => {
+                    domain((-1.0..=1.0).contains(&x))?;
+                    angle.convert_radians(asin(x))
+                }
+                Function::Acos => {
+                    domain((-1.0..=1.0).contains(&x))?;
+                    angle.convert_radians(acos(x))
+                }
+                Function::Atan => angle.convert_radians(atan(x)),
+                Function::Sinh => sinh(x),
+                Function::Cosh => cosh(x),
+                Function::Tanh => tanh(x),
+                Function::Ln => {
+                    domain(x > 0.0)?;
+                    log(x)
+                }
+                Function::Log10 => {
+                    domain(x > 0.0)?;
+                    log10(x)
+                }
+                Function::Log2 => {
+                    domain(x > 0.0)?;
+                    log2(x)
+                }
+                Function::Exp => exp(x),
+                Function::Sqrt => {
+                    domain(x >= 0.0)?;
+                    sqrt(x)
+                }
+                Function::Cbrt => cbrt(x),
+                Function::Abs => x.abs(),
+            }
+        };
+        let y = Decimal::from_f64(finite(snap(y))?)?;
+        Ok(y.round_significant(F64_DIGITS, Rounding::HalfEven))
+    }
+}
+
+/// Named values of scientific mode
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Constant {
+    Pi,
+    E,
+    Tau,
+    /// Golden ratio
+    Phi,
+}
+
+impl Constant {
+    pub const ALL: [Constant; 4] = [Constant::Pi, Constant::E, Constant::Tau, Constant::Phi];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Constant::Pi => "π",
+            Constant::E => "e",
+            Constant::Tau => "τ",
+            Constant::Phi => "φ",
+        }
+    }
+
+    pub fn value(self) -> f64 {
+        match self {
+            Constant::Pi => core::f64::consts::PI,
+            Constant::E => core::f64::consts::E,
+            Constant::Tau => core::f64::consts::TAU,
+            Constant::Phi => 1.618_033_988_749_895,
+        }
+    }
+
+    /// To the 50 digits a `Decimal` holds
+    pub fn decimal(self) -> Decimal {
+        let digits = match self {
+            Constant::Pi => "3.1415926535897932384626433832795028841971693993751",
+            Constant::E => "2.7182818284590452353602874713526624977572470936999",
+            Constant::Tau => "6.2831853071795864769252867665590057683943387987502",
+            Constant::Phi => "1.6180339887498948482045868343656381177203091798057",
+        };
+        Decimal::parse(digits).unwrap_or(Decimal::ZERO)
+    }
+
+    /// Also takes the ASCII spellings `pi`, `tau` and `phi`
+    pub fn from_name(name: &str) -> Option<Constant> {
+        match name {
+            "pi" => Some(Constant::Pi),
+            "tau" => Some(Constant::Tau),
+            "phi" => Some(Constant::Phi),
+            _ => Self::ALL.into_iter().find(|c| c.name() == name),
+        }
+    }
+}
+
+/// Significant digits an `f64` result is trusted to
+const F64_DIGITS: u32 = 15;
+
+/// Largest whole exponent worked out exactly by `power`
+const EXACT_POWER_MAX: i128 = 100_000;
+
+/// Largest n for `factorial`; 3249! is already past `Decimal`'s range
+const FACTORIAL_MAX: i128 = 3249;
+
+fn finite(y: f64) -> Result<f64> {
+    if y.is_nan() {
+        Err(CalcError::Domain)
+    } else if y.is_infinite() {
+        Err(CalcError::Overflow)
+    } else {
+        Ok(y)
+    }
+}
+
+/// Round away float noise so sin(180°) is 0 rather than 1.2e-16
+fn snap(y: f64) -> f64 {
+    if y.abs() < 1e-15 { 0.0 } else { y }
+}
+
+/// Whole exponents are exact (`0.1^3` is 0.001); anything else goes
+/// through `f64`
+pub fn power(base: Decimal, exponent: Decimal, digits: u32, rounding: Rounding) -> Result<Decimal> {
+    match exponent.to_i128() {
+        Some(n) if exponent.is_integer() && n.abs() <= EXACT_POWER_MAX => base.powi(n as i64, digits, rounding),
+        _ if base.is_zero() && exponent.is_negative() => Err(CalcError::DivideByZero),
+        _ => {
+            let y = finite(unsafe { pow(base.to_f64(), exponent.to_f64()) })?;
+            Ok(Decimal::from_f64(y)?.round_significant(F64_DIGITS, Rounding::HalfEven))
+        }
+    }
+}
+
+/// n! for whole n, exactly while it fits in `MAX_DIGITS`
+pub fn factorial(n: Decimal) -> Result<Decimal> {
+    if n.is_negative() || !n.is_integer() {
+        return Err(CalcError::Domain);
+    }
+    let n = match n.to_i128() {
+        Some(n) if n <= FACTORIAL_MAX => n as u32,
+        _ => return Err(CalcError::Overflow),
+    };
+    // 20! is the largest that fits a u64, so take that much in one go
+    let small = n.min(20);
+    let mut acc = Decimal::from_i128((1..=small as u64).product::<u64>() as i128);
+    for i in small + 1..=n {
+        acc = acc.checked_mul(Decimal::from_i128(i as i128))?;
+    }
+    Ok(acc)
+}
@@ -0,0 +1,112 @@
+use super::text::Text;
+use super::Result;
+
+/// Longest expression the calculator builds or history keeps
+pub const EXPR_CAP: usize = 128;
+
+/// Longest result text: a 64-bit word in binary, with a sign to spare
+pub const SHOWN_CAP: usize = 72;
+
+/// One evaluated expression
+#[derive(Copy, Clone, Debug)]
+pub struct Entry {
+    expr: Text<EXPR_CAP>,
+    result: Result<f64>,
+    /// Exact value of a programmer-mode result
+    integer: Option<i128>,
+    shown: Text<SHOWN_CAP>,
+}
+
+impl Entry {
+    pub fn expression(&self) -> &str {
+        self.expr.as_str()
+    }
+
+    pub fn result(&self) -> Result<f64> {
+        self.result
+    }
+
+    pub fn integer(&self) -> Option<i128> {
+        self.integer
+    }
+
+    /// Result as the display showed it, in the radix of the time
+    pub fn display(&self) -> &str {
+        self.shown.as_str()
+    }
+}
+
+/// Ring of the last `N` evaluations; the oldest falls off
+pub struct History<const N: usize> {
+    entries: [Option<Entry>; N],
+    next: usize,
+    len: usize,
+}
+
+impl<const N: usize> History<N> {
+    pub const fn new() -> Self {
+        Self { entries: [None; N], next: 0, len: 0 }
+    }
+
+    /// Expressions longer than `EXPR_CAP` are cut off
+    pub fn push(&mut self, expr: &str, result: Result<f64>, shown: &str) {
+        self.insert(expr, result, None, shown);
+    }
+
+    /// Programmer-mode result, kept exact
+    pub fn push_integer(&mut self, expr: &str, result: Result<i128>, shown: &str) {
+        self.insert(expr, result.map(|v| v as f64), result.ok(), shown);
+    }
+
+    fn insert(&mut self, expr: &str, result: Result<f64>, integer: Option<i128>, shown: &str) {
+        if N == 0 {
+            return;
+        }
+        self.entries[self.next] = Some(Entry { expr: truncated(expr), result, integer, shown: truncated(shown) });
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.entries = [None; N];
+        self.next = 0;
+        self.len = 0;
+    }
+
+    /// 0 is the most recent
+    pub fn get(&self, index: usize) -> Option<&Entry> {
+        if index >= self.len {
+            return None;
+        }
+        self.entries[(self.next + N - 1 - index) % N].as_ref()
+    }
+
+    /// Newest first
+    pub fn iter(&self) -> impl Iterator<Item = &Entry> + '_ {
+        (0..self.len).filter_map(move |i| self.get(i))
+    }
+}
+
+fn truncated<const N: usize>(s: &str) -> Text<N> {
+    let mut text = Text::new();
+    for c in s.chars() {
+        if !text.push(c) {
+            break;
+        }
+    }
+    text
+}
+
+impl<const N: usize> Default for History<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,180 @@
+/// One lexical unit of an expression
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Token<'a> {
+    /// Decimal literal as written: `12`, `0.5`, `1e-3`
+    Number(&'a str),
+    /// Whole number from a radix lexer or a `0x`/`0o`/`0b` literal
+    Integer(u128),
+    Name(&'a str),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    Caret,
+    Bang,
+    Amp,
+    Pipe,
+    Tilde,
+    Shl,
+    Shr,
+    LParen,
+    RParen,
+    /// Anything the lexer doesn't understand
+    Invalid,
+}
+
+/// Splits an expression into tokens with their byte offsets. Accepts the
+/// typographic operators the button labels use (`×`, `÷`, `−`).
+///
+/// A radix lexer (programmer mode) reads whole numbers in that base, so
+/// `FF` is a number in hex; `0x1F`, `0o17` and `0b101` work in any base.
+pub struct Lexer<'a> {
+    src: &'a str,
+    pos: usize,
+    radix: Option<u32>,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(src: &'a str) -> Self {
+        Self { src, pos: 0, radix: None }
+    }
+
+    pub fn with_radix(src: &'a str, radix: u32) -> Self {
+        Self { src, pos: 0, radix: Some(radix) }
+    }
+
+    /// Byte offset of the next unread character
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    fn number(&mut self) -> Token<'a> {
+        let bytes = self.src.as_bytes();
+        let start = self.pos;
+        let mut end = start;
+        while end < bytes.len() && (bytes[end].is_ascii_digit() || bytes[end] == b'.') {
+            end += 1;
+        }
+        // Exponent, only if digits follow: "2e3", "1.5E-7"
+        if end < bytes.len() && (bytes[end] | 0x20) == b'e' {
+            let mut exp = end + 1;
+            if exp < bytes.len() && (bytes[exp] == b'+' || bytes[exp] == b'-') {
+                exp += 1;
+            }
+            if exp < bytes.len() && bytes[exp].is_ascii_digit() {
+                while exp < bytes.len() && bytes[exp].is_ascii_digit() {
+                    exp += 1;
+                }
+                end = exp;
+            }
+        }
+        self.pos = end;
+        let text = &self.src[start..end];
+        match text.parse::<f64>() {
+            Ok(_) => Token::Number(text),
+            Err(_) => Token::Invalid,
+        }
+    }
+
+    /// `0x..`, `0o..`, `0b..`; `None` if there's no such prefix here
+    fn prefixed_integer(&mut self) -> Option<Token<'a>> {
+        let bytes = self.src.as_bytes();
+        let start = self.pos;
+        if bytes.get(start) != Some(&b'0') {
+            return None;
+        }
+        let radix = match bytes.get(start + 1).map(|b| b | 0x20) {
+            Some(b'x') => 16,
+            Some(b'o') => 8,
+            Some(b'b') => 2,
+            _ => return None,
+        };
+        // In hex "0B1" is the number B1, not a binary literal
+        if radix == 2 && self.radix == Some(16) {
+            return None;
+        }
+        let digits_len = self.src[start + 2..].bytes().take_while(u8::is_ascii_alphanumeric).count();
+        if digits_len == 0 {
+            return None;
+        }
+        self.pos = start + 2 + digits_len;
+        Some(parse_integer(&self.src[start + 2..self.pos], radix))
+    }
+
+    fn name(&mut self) -> Token<'a> {
+        let start = self.pos;
+        let rest = &self.src[start..];
+        let len = rest
+            .char_indices()
+            .find(|&(_, c)| !(c.is_alphanumeric() || c == '_'))
+            .map_or(rest.len(), |(i, _)| i);
+        self.pos += len;
+        Token::Name(&rest[..len])
+    }
+
+    /// Next token and where it starts; `None` at the end
+    pub fn next_token(&mut self) -> Option<(usize, Token<'a>)> {
+        let rest = &self.src[self.pos..];
+        let skipped = rest.len() - rest.trim_start().len();
+        self.pos += skipped;
+        let start = self.pos;
+        let c = self.src[start..].chars().next()?;
+        if let Some(token) = self.prefixed_integer() {
+            return Some((start, token));
+        }
+        if let Some(radix) = self.radix.filter(|_| c.is_ascii_alphanumeric()) {
+            let word = match self.name() {
+                Token::Name(word) => word,
+                _ => unreachable!(),
+            };
+            // In hex "FF" is a number but "and" is an operator
+            if word.bytes().all(|b| (b as char).is_digit(radix)) {
+                return Some((start, parse_integer(word, radix)));
+            }
+            return Some((start, Token::Name(word)));
+        }
+        let token = match c {
+            '0'..='9' | '.' => return Some((start, self.number())),
+            c if c.is_alphabetic() || c == '_' => return Some((start, self.name())),
+            '<' | '>' => {
+                let double = if c == '<' { "<<" } else { ">>" };
+                if self.src[start..].starts_with(double) {
+                    self.pos += 2;
+                    return Some((start, if c == '<' { Token::Shl } else { Token::Shr }));
+                }
+                Token::Invalid
+            }
+            '+' => Token::Plus,
+            '-' | '−' => Token::Minus,
+            '*' | '×' => Token::Star,
+            '/' | '÷' => Token::Slash,
+            '%' | '％' => Token::Percent,
+            '^' => Token::Caret,
+            '!' => Token::Bang,
+            '&' => Token::Amp,
+            '|' => Token::Pipe,
+            '~' => Token::Tilde,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            _ => Token::Invalid,
+        };
+        self.pos += c.len_utf8();
+        Some((start, token))
+    }
+}
+
+fn parse_integer(digits: &str, radix: u32) -> Token<'_> {
+    match u128::from_str_radix(digits, radix) {
+        Ok(n) => Token::Integer(n),
+        Err(_) => Token::Invalid,
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = (usize, Token<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_token()
+    }
+}
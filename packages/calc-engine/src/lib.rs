@@ -0,0 +1,79 @@
+//! UI-agnostic calculator core shared by the iced, raylib and crust
+//! calculators.
+//!
+//! `eval` evaluates a whole expression with the usual precedence, plus the
+//! scientific functions and constants; `eval_int` is its whole-number
+//! counterpart for programmer mode. The `Calculator` state machine turns
+//! button presses into such expressions and keeps memory and history. No
+//! allocation, so the crust programs can pull this file in with
+//! `#[path = ".../calc-engine/src/lib.rs"] mod calc;`.
+#![no_std]
+
+mod calculator;
+mod decimal;
+mod functions;
+mod history;
+mod lexer;
+mod locale;
+mod parser;
+mod programmer;
+mod text;
+
+pub use calculator::{Calculator, Key, Mode, Operator};
+pub use decimal::{Decimal, Rounding, DIVISION_DIGITS, MAX_DIGITS};
+pub use functions::{Angle, Constant, Function};
+pub use history::{Entry, History};
+pub use lexer::{Lexer, Token};
+pub use locale::NumberFormat;
+pub use parser::{eval, eval_decimal, eval_with, Context};
+pub use programmer::{eval_int, format_int, radix_label, IntContext, WordSize, RADIXES};
+pub use text::Text;
+
+use core::fmt;
+
+/// What frontends show instead of a number when evaluation fails
+pub const ERROR_TEXT: &str = "Error";
+
+/// Significant digits shown by `format_number`
+pub const DISPLAY_DIGITS: usize = 12;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CalcError {
+    Empty,
+    /// Unexpected token at this byte offset
+    Syntax(usize),
+    UnbalancedParens,
+    DivideByZero,
+    /// Result is out of range: past 1e9999, or an `f64` for `eval`
+    Overflow,
+    UnknownName(usize),
+    /// Outside a function's domain: `sqrt(-1)`, `ln 0`, `2.5!`
+    Domain,
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CalcError::Empty => f.write_str("empty expression"),
+            CalcError::Syntax(at) => write!(f, "syntax error at {}", at),
+            CalcError::UnbalancedParens => f.write_str("unbalanced parentheses"),
+            CalcError::DivideByZero => f.write_str("division by zero"),
+            CalcError::Overflow => f.write_str("overflow"),
+            CalcError::UnknownName(at) => write!(f, "unknown name at {}", at),
+            CalcError::Domain => f.write_str("domain error"),
+        }
+    }
+}
+
+pub type Result<T> = core::result::Result<T, CalcError>;
+
+/// Display form of an `f64` result: the shortest decimal that reads back
+/// as it, rounded to `DISPLAY_DIGITS` significant digits, so
+/// `0.1 + 0.2` reads `0.3`. Very large or tiny magnitudes switch to
+/// `1.5e20` notation.
+pub fn format_number(value: f64, out: &mut impl fmt::Write) -> fmt::Result {
+    match Decimal::from_f64(value) {
+        Ok(d) => d.format(&NumberFormat::PLAIN, out),
+        Err(_) => out.write_str(ERROR_TEXT),
+    }
+}
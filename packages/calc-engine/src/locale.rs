@@ -0,0 +1,84 @@
+use super::decimal::Rounding;
+use super::DISPLAY_DIGITS;
+use core::fmt;
+
+/// How numbers are written for people: decimal point, digit grouping and
+/// how many significant digits to show
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub decimal_point: char,
+    /// Between groups of `group_size` integer digits; `None` for 1234567
+    pub group_separator: Option<char>,
+    pub group_size: u8,
+    pub significant_digits: u32,
+    pub rounding: Rounding,
+}
+
+impl NumberFormat {
+    /// `1234567.89`
+    pub const PLAIN: NumberFormat = NumberFormat {
+        decimal_point: '.',
+        group_separator: None,
+        group_size: 3,
+        significant_digits: DISPLAY_DIGITS as u32,
+        rounding: Rounding::HalfEven,
+    };
+    /// `1,234,567.89`
+    pub const ENGLISH: NumberFormat = NumberFormat { group_separator: Some(','), ..NumberFormat::PLAIN };
+    /// `1.234.567,89`, also most of continental Europe and South America
+    pub const GERMAN: NumberFormat = NumberFormat { decimal_point: ',', group_separator: Some('.'), ..NumberFormat::PLAIN };
+    /// `1 234 567,89` with a narrow no-break space, also Nordic and
+    /// Slavic languages
+    pub const FRENCH: NumberFormat =
+        NumberFormat { decimal_point: ',', group_separator: Some('\u{202F}'), ..NumberFormat::PLAIN };
+    /// `1'234'567.89`
+    pub const SWISS: NumberFormat = NumberFormat { group_separator: Some('\''), ..NumberFormat::PLAIN };
+
+    /// Format for a POSIX locale name or BCP 47 tag: `de_DE.UTF-8`,
+    /// `fr-CA`, `C`. Unknown languages get `ENGLISH`.
+    pub fn for_locale(name: &str) -> NumberFormat {
+        let name = name.split(['.', '@']).next().unwrap_or("");
+        let mut parts = name.split(['_', '-']);
+        let language = parts.next().unwrap_or("");
+        let region = parts.next().unwrap_or("");
+        match (language, region) {
+            ("" | "C" | "POSIX", _) => NumberFormat::PLAIN,
+            ("de" | "it" | "fr" | "rm", "CH" | "LI") => NumberFormat::SWISS,
+            ("de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" | "ro" | "hr" | "sl" | "sr" | "vi", _) => {
+                NumberFormat::GERMAN
+            }
+            ("fr" | "ru" | "uk" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "nn" | "no" | "hu" | "bg" | "et"
+            | "lt" | "lv", _) => NumberFormat::FRENCH,
+            _ => NumberFormat::ENGLISH,
+        }
+    }
+
+    /// Rewrite a plain number like `-1234567.5` or `1.5e20` in this
+    /// format. Only the first run of digits is grouped, so exponents and
+    /// whatever follows the number are left alone.
+    pub fn localize(&self, plain: &str, out: &mut impl fmt::Write) -> fmt::Result {
+        let start = plain.find(|c: char| c.is_ascii_digit()).unwrap_or(plain.len());
+        let int_len = plain[start..].find(|c: char| !c.is_ascii_digit()).unwrap_or(plain.len() - start);
+        out.write_str(&plain[..start])?;
+        for (i, c) in plain[start..start + int_len].chars().enumerate() {
+            let left = int_len - i;
+            match self.group_separator {
+                Some(sep) if i > 0 && self.group_size > 0 && left % self.group_size as usize == 0 => {
+                    out.write_char(sep)?;
+                }
+                _ => {}
+            }
+            out.write_char(c)?;
+        }
+        for c in plain[start + int_len..].chars() {
+            out.write_char(if c == '.' { self.decimal_point } else { c })?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat::PLAIN
+    }
+}
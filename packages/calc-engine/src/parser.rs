@@ -0,0 +1,234 @@
+use super::decimal::{Decimal, Rounding, DIVISION_DIGITS};
+use super::functions::{self, Angle, Constant, Function};
+use super::lexer::{Lexer, Token};
+use super::{CalcError, Result};
+
+/// Values an expression can refer to by name, and how it's worked out
+#[derive(Copy, Clone, Debug)]
+pub struct Context {
+    /// Previous result, as `ans`
+    pub ans: Decimal,
+    /// Memory register, as `mr`
+    pub memory: Decimal,
+    /// Unit for trig functions
+    pub angle: Angle,
+    /// Significant digits kept by division
+    pub precision: u32,
+    /// How division rounds to `precision`
+    pub rounding: Rounding,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self {
+            ans: Decimal::ZERO,
+            memory: Decimal::ZERO,
+            angle: Angle::default(),
+            precision: DIVISION_DIGITS,
+            rounding: Rounding::default(),
+        }
+    }
+}
+
+/// Evaluate `expr` with `ans` and `mr` both zero
+pub fn eval(expr: &str) -> Result<f64> {
+    eval_with(expr, &Context::default())
+}
+
+/// `eval_decimal`, then to the nearest `f64`
+pub fn eval_with(expr: &str, ctx: &Context) -> Result<f64> {
+    let value = eval_decimal(expr, ctx)?.to_f64();
+    if value.is_finite() { Ok(value) } else { Err(CalcError::Overflow) }
+}
+
+/// Evaluate `expr`: `+ - * / mod ^` with the usual precedence (`^` is
+/// right-associative and binds tighter than unary minus, so `-2^2` is -4),
+/// parentheses, postfix `%` and `!`, the functions of [`Function`] and the
+/// constants of [`Constant`]. A number directly before a name or `(`
+/// multiplies it: `2pi`, `3(1 + 1)`, `2 sin 30`.
+///
+/// A percentage on the right of `+`/`-` is taken of the left side
+/// (`200 + 10%` is 220); elsewhere it's just /100.
+///
+/// Arithmetic is decimal, so `0.1 + 0.2 - 0.3` is exactly 0; only
+/// division, roots and the like round, to `ctx.precision` digits or what
+/// an `f64` holds.
+pub fn eval_decimal(expr: &str, ctx: &Context) -> Result<Decimal> {
+    let mut parser = Parser { lexer: Lexer::new(expr), peeked: None, ctx, depth: 0 };
+    if parser.peek().is_none() {
+        return Err(CalcError::Empty);
+    }
+    let value = parser.expr(0)?;
+    match parser.next() {
+        None => Ok(value.value),
+        Some((_, Token::RParen)) => Err(CalcError::UnbalancedParens),
+        Some((at, _)) => Err(CalcError::Syntax(at)),
+    }
+}
+
+/// Intermediate value; remembers whether it was written as a percentage
+#[derive(Copy, Clone)]
+struct Operand {
+    value: Decimal,
+    percent: bool,
+}
+
+impl Operand {
+    fn plain(value: Decimal) -> Self {
+        Self { value, percent: false }
+    }
+}
+
+// Binding powers: higher binds tighter
+const BP_SUM: u8 = 1;
+const BP_PRODUCT: u8 = 3;
+const BP_PREFIX: u8 = 5;
+const BP_POWER: u8 = 7;
+const BP_POSTFIX: u8 = 9;
+
+/// Deepest nesting of sub-expressions (brackets, prefix signs, `^`
+/// chains) before the parser gives up, well short of the stack's limit
+pub const MAX_DEPTH: u32 = 100;
+
+struct Parser<'a, 'c> {
+    lexer: Lexer<'a>,
+    peeked: Option<Option<(usize, Token<'a>)>>,
+    ctx: &'c Context,
+    // Calls to `expr` in progress
+    depth: u32,
+}
+
+impl<'a> Parser<'a, '_> {
+    fn peek(&mut self) -> Option<(usize, Token<'a>)> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.lexer.next_token());
+        }
+        self.peeked.flatten()
+    }
+
+    fn next(&mut self) -> Option<(usize, Token<'a>)> {
+        match self.peeked.take() {
+            Some(t) => t,
+            None => self.lexer.next_token(),
+        }
+    }
+
+    fn end(&self) -> usize {
+        self.lexer.position()
+    }
+
+    fn prefix(&mut self) -> Result<Operand> {
+        let Some((at, token)) = self.next() else {
+            return Err(CalcError::Syntax(self.end()));
+        };
+        match token {
+            Token::Number(text) => match Decimal::parse(text) {
+                Ok(value) => Ok(Operand::plain(value)),
+                Err(CalcError::Overflow) => Err(CalcError::Overflow),
+                Err(_) => Err(CalcError::Syntax(at)),
+            },
+            Token::Integer(n) => match i128::try_from(n) {
+                Ok(n) => Ok(Operand::plain(Decimal::from_i128(n))),
+                Err(_) => Err(CalcError::Overflow),
+            },
+            Token::Name(name) => {
+                if let Some(function) = Function::from_name(name) {
+                    let arg = self.argument()?;
+                    return Ok(Operand::plain(function.apply(arg, self.ctx.angle)?));
+                }
+                if let Some(constant) = Constant::from_name(name) {
+                    return Ok(Operand::plain(constant.decimal()));
+                }
+                match name {
+                    "ans" => Ok(Operand::plain(self.ctx.ans)),
+                    "mr" => Ok(Operand::plain(self.ctx.memory)),
+                    _ => Err(CalcError::UnknownName(at)),
+                }
+            }
+            Token::Minus => Ok(Operand::plain(-self.expr(BP_PREFIX)?.value)),
+            Token::Plus => Ok(Operand::plain(self.expr(BP_PREFIX)?.value)),
+            Token::LParen => Ok(Operand::plain(self.group()?)),
+            Token::RParen => Err(CalcError::UnbalancedParens),
+            _ => Err(CalcError::Syntax(at)),
+        }
+    }
+
+    /// Rest of a parenthesised expression, after the `(`
+    fn group(&mut self) -> Result<Decimal> {
+        let inner = self.expr(0)?;
+        match self.next() {
+            Some((_, Token::RParen)) => Ok(inner.value),
+            None => Err(CalcError::UnbalancedParens),
+            Some((at, _)) => Err(CalcError::Syntax(at)),
+        }
+    }
+
+    /// Function argument: `sin(30 + 60)` or, without parentheses, a
+    /// prefix-strength operand so `sin 30^2` is sin(900) but `sin 30 + 1`
+    /// adds after the sine.
+    fn argument(&mut self) -> Result<Decimal> {
+        if let Some((_, Token::LParen)) = self.peek() {
+            self.next();
+            return self.group();
+        }
+        Ok(self.expr(BP_PREFIX)?.value)
+    }
+
+    /// Pratt loop: parse operators binding tighter than `min_bp`
+    fn expr(&mut self, min_bp: u8) -> Result<Operand> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(CalcError::Syntax(self.end()));
+        }
+        let mut lhs = self.prefix()?;
+        while let Some((_, token)) = self.peek() {
+            if token == Token::Percent || token == Token::Bang {
+                if BP_POSTFIX < min_bp {
+                    break;
+                }
+                self.next();
+                lhs = match token {
+                    Token::Percent => Operand { value: lhs.value.scale(-2)?, percent: true },
+                    _ => Operand::plain(functions::factorial(lhs.value)?),
+                };
+                continue;
+            }
+            let (bp, implicit) = match token {
+                Token::Plus | Token::Minus => (BP_SUM, false),
+                Token::Star | Token::Slash | Token::Name("mod") => (BP_PRODUCT, false),
+                Token::Caret => (BP_POWER, false),
+                Token::Name(_) | Token::LParen => (BP_PRODUCT, true),
+                _ => break,
+            };
+            if bp < min_bp {
+                break;
+            }
+            if implicit {
+                let rhs = self.expr(bp + 1)?;
+                lhs = Operand::plain(apply(Token::Star, lhs.value, rhs, self.ctx)?);
+                continue;
+            }
+            self.next();
+            // Left-associative operators need the right side to bind
+            // strictly tighter; `^` is right-associative
+            let rhs = self.expr(if token == Token::Caret { bp } else { bp + 1 })?;
+            lhs = Operand::plain(apply(token, lhs.value, rhs, self.ctx)?);
+        }
+        self.depth -= 1;
+        Ok(lhs)
+    }
+}
+
+fn apply(op: Token, a: Decimal, b: Operand, ctx: &Context) -> Result<Decimal> {
+    match op {
+        Token::Plus if b.percent => a.checked_add(a.checked_mul(b.value)?),
+        Token::Minus if b.percent => a.checked_sub(a.checked_mul(b.value)?),
+        Token::Plus => a.checked_add(b.value),
+        Token::Minus => a.checked_sub(b.value),
+        Token::Star => a.checked_mul(b.value),
+        Token::Slash => a.div(b.value, ctx.precision, ctx.rounding),
+        Token::Caret => functions::power(a, b.value, ctx.precision, ctx.rounding),
+        Token::Name("mod") => a.checked_rem(b.value),
+        _ => unreachable!(),
+    }
+}
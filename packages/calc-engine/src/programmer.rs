@@ -0,0 +1,266 @@
+use super::lexer::{Lexer, Token};
+use super::parser::MAX_DEPTH;
+use super::{CalcError, Result};
+use core::fmt;
+
+/// Register width for programmer mode; every result wraps to it
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum WordSize {
+    Byte,
+    Word,
+    DWord,
+    #[default]
+    QWord,
+}
+
+impl WordSize {
+    pub const ALL: [WordSize; 4] = [WordSize::Byte, WordSize::Word, WordSize::DWord, WordSize::QWord];
+
+    pub fn bits(self) -> u32 {
+        match self {
+            WordSize::Byte => 8,
+            WordSize::Word => 16,
+            WordSize::DWord => 32,
+            WordSize::QWord => 64,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WordSize::Byte => "BYTE",
+            WordSize::Word => "WORD",
+            WordSize::DWord => "DWORD",
+            WordSize::QWord => "QWORD",
+        }
+    }
+
+    fn mask(self) -> u128 {
+        (1u128 << self.bits()) - 1
+    }
+}
+
+/// Number formats of programmer mode
+pub const RADIXES: [u32; 4] = [16, 10, 8, 2];
+
+pub fn radix_label(radix: u32) -> &'static str {
+    match radix {
+        16 => "HEX",
+        10 => "DEC",
+        8 => "OCT",
+        2 => "BIN",
+        _ => "?",
+    }
+}
+
+/// Literal prefix that makes a number readable in any radix
+pub fn radix_prefix(radix: u32) -> &'static str {
+    match radix {
+        16 => "0x",
+        8 => "0o",
+        2 => "0b",
+        _ => "",
+    }
+}
+
+/// How programmer mode reads and wraps numbers
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct IntContext {
+    /// Base of literals written without a `0x`/`0o`/`0b` prefix
+    pub radix: u32,
+    pub word: WordSize,
+    /// Two's complement if set, otherwise results are never negative
+    pub signed: bool,
+    /// Previous result, as `ans`
+    pub ans: i128,
+    /// Memory register, as `mr`
+    pub memory: i128,
+}
+
+impl Default for IntContext {
+    fn default() -> Self {
+        Self { radix: 10, word: WordSize::default(), signed: true, ans: 0, memory: 0 }
+    }
+}
+
+impl IntContext {
+    /// Truncate to the word size and reinterpret per signedness:
+    /// 0xFF is -1 as a signed byte and 255 unsigned
+    pub fn wrap(&self, value: i128) -> i128 {
+        let bits = (value as u128) & self.word.mask();
+        let top = 1u128 << (self.word.bits() - 1);
+        if self.signed && bits & top != 0 {
+            bits as i128 - (1i128 << self.word.bits())
+        } else {
+            bits as i128
+        }
+    }
+}
+
+/// Evaluate an integer expression the way C would on a `ctx.word`
+/// register: `| ^ & << >> + - * / %` from loosest to tightest, prefix
+/// `~` and `-`, and the word forms `or xor and mod not`. Division
+/// truncates toward zero. Every intermediate result wraps.
+pub fn eval_int(expr: &str, ctx: &IntContext) -> Result<i128> {
+    let mut parser = Parser { lexer: Lexer::with_radix(expr, ctx.radix), peeked: None, ctx, depth: 0 };
+    if parser.peek().is_none() {
+        return Err(CalcError::Empty);
+    }
+    let value = parser.expr(0)?;
+    match parser.next() {
+        None => Ok(value),
+        Some((_, Token::RParen)) => Err(CalcError::UnbalancedParens),
+        Some((at, _)) => Err(CalcError::Syntax(at)),
+    }
+}
+
+/// `value` as the display shows it: uppercase digits, and in anything but
+/// decimal the raw bit pattern, so -1 as a signed byte is `FF`.
+pub fn format_int(value: i128, radix: u32, word: WordSize, signed: bool, out: &mut impl fmt::Write) -> fmt::Result {
+    let ctx = IntContext { word, signed, ..IntContext::default() };
+    let value = ctx.wrap(value);
+    match radix {
+        16 => write!(out, "{:X}", value as u128 & word.mask()),
+        8 => write!(out, "{:o}", value as u128 & word.mask()),
+        2 => write!(out, "{:b}", value as u128 & word.mask()),
+        _ => write!(out, "{}", value),
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Op {
+    Or,
+    Xor,
+    And,
+    Shl,
+    Shr,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+impl Op {
+    fn from_token(token: Token) -> Option<Op> {
+        Some(match token {
+            Token::Pipe | Token::Name("or") => Op::Or,
+            Token::Caret | Token::Name("xor") => Op::Xor,
+            Token::Amp | Token::Name("and") => Op::And,
+            Token::Shl => Op::Shl,
+            Token::Shr => Op::Shr,
+            Token::Plus => Op::Add,
+            Token::Minus => Op::Sub,
+            Token::Star => Op::Mul,
+            Token::Slash => Op::Div,
+            Token::Percent | Token::Name("mod") => Op::Mod,
+            _ => return None,
+        })
+    }
+
+    /// Binding power, C order: higher binds tighter
+    fn bp(self) -> u8 {
+        match self {
+            Op::Or => 1,
+            Op::Xor => 2,
+            Op::And => 3,
+            Op::Shl | Op::Shr => 4,
+            Op::Add | Op::Sub => 5,
+            Op::Mul | Op::Div | Op::Mod => 6,
+        }
+    }
+}
+
+const BP_PREFIX: u8 = 7;
+
+struct Parser<'a, 'c> {
+    lexer: Lexer<'a>,
+    peeked: Option<Option<(usize, Token<'a>)>>,
+    ctx: &'c IntContext,
+    // Calls to `expr` in progress
+    depth: u32,
+}
+
+impl<'a> Parser<'a, '_> {
+    fn peek(&mut self) -> Option<(usize, Token<'a>)> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.lexer.next_token());
+        }
+        self.peeked.flatten()
+    }
+
+    fn next(&mut self) -> Option<(usize, Token<'a>)> {
+        match self.peeked.take() {
+            Some(t) => t,
+            None => self.lexer.next_token(),
+        }
+    }
+
+    fn prefix(&mut self) -> Result<i128> {
+        let Some((at, token)) = self.next() else {
+            return Err(CalcError::Syntax(self.lexer.position()));
+        };
+        match token {
+            Token::Integer(n) => Ok(self.ctx.wrap(n as i128)),
+            Token::Name("ans") => Ok(self.ctx.wrap(self.ctx.ans)),
+            Token::Name("mr") => Ok(self.ctx.wrap(self.ctx.memory)),
+            Token::Name("not") | Token::Tilde => Ok(self.ctx.wrap(!self.expr(BP_PREFIX)?)),
+            Token::Minus => Ok(self.ctx.wrap(self.expr(BP_PREFIX)?.wrapping_neg())),
+            Token::Plus => self.expr(BP_PREFIX),
+            Token::Name(_) => Err(CalcError::UnknownName(at)),
+            Token::LParen => {
+                let inner = self.expr(0)?;
+                match self.next() {
+                    Some((_, Token::RParen)) => Ok(inner),
+                    None => Err(CalcError::UnbalancedParens),
+                    Some((at, _)) => Err(CalcError::Syntax(at)),
+                }
+            }
+            Token::RParen => Err(CalcError::UnbalancedParens),
+            _ => Err(CalcError::Syntax(at)),
+        }
+    }
+
+    /// Pratt loop, all operators left-associative
+    fn expr(&mut self, min_bp: u8) -> Result<i128> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(CalcError::Syntax(self.lexer.position()));
+        }
+        let mut lhs = self.prefix()?;
+        while let Some((_, token)) = self.peek() {
+            let Some(op) = Op::from_token(token) else {
+                break;
+            };
+            if op.bp() < min_bp {
+                break;
+            }
+            self.next();
+            let rhs = self.expr(op.bp() + 1)?;
+            lhs = self.ctx.wrap(self.apply(op, lhs, rhs)?);
+        }
+        self.depth -= 1;
+        Ok(lhs)
+    }
+
+    fn apply(&self, op: Op, a: i128, b: i128) -> Result<i128> {
+        let bits = self.ctx.word.bits() as i128;
+        Ok(match op {
+            Op::Or => a | b,
+            Op::Xor => a ^ b,
+            Op::And => a & b,
+            Op::Shl | Op::Shr if b < 0 => return Err(CalcError::Domain),
+            Op::Shl if b >= bits => 0,
+            Op::Shl => a << b,
+            // Signed values are sign-extended, so this is arithmetic for
+            // signed words and logical for unsigned ones
+            Op::Shr if b >= bits => if a < 0 { -1 } else { 0 },
+            Op::Shr => a >> b,
+            Op::Add => a.wrapping_add(b),
+            Op::Sub => a.wrapping_sub(b),
+            Op::Mul => a.wrapping_mul(b),
+            Op::Div | Op::Mod if b == 0 => return Err(CalcError::DivideByZero),
+            Op::Div => a.wrapping_div(b),
+            Op::Mod => a.wrapping_rem(b),
+        })
+    }
+}
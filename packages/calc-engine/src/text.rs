@@ -0,0 +1,104 @@
+use core::fmt;
+
+/// Fixed-capacity UTF-8 buffer; writes that don't fit fail whole
+#[derive(Copy, Clone)]
+pub struct Text<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Text<N> {
+    pub const fn new() -> Self {
+        Self { buf: [0; N], len: 0 }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only ever filled from &str, one whole char at a time
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or("")
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    pub fn push_str(&mut self, s: &str) -> bool {
+        if self.len + s.len() > N {
+            return false;
+        }
+        self.buf[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+        self.len += s.len();
+        true
+    }
+
+    pub fn push(&mut self, c: char) -> bool {
+        self.push_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.as_str().chars().next_back()?;
+        self.len -= c.len_utf8();
+        Some(c)
+    }
+
+    pub fn set(&mut self, s: &str) -> bool {
+        self.clear();
+        self.push_str(s)
+    }
+
+    /// Insert at the front, e.g. a sign
+    pub fn prepend(&mut self, s: &str) -> bool {
+        if self.len + s.len() > N {
+            return false;
+        }
+        self.buf.copy_within(..self.len, s.len());
+        self.buf[..s.len()].copy_from_slice(s.as_bytes());
+        self.len += s.len();
+        true
+    }
+
+    pub fn remove_first(&mut self) -> Option<char> {
+        let c = self.as_str().chars().next()?;
+        let w = c.len_utf8();
+        self.buf.copy_within(w..self.len, 0);
+        self.len -= w;
+        Some(c)
+    }
+}
+
+impl<const N: usize> Default for Text<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Write for Text<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.push_str(s) { Ok(()) } else { Err(fmt::Error) }
+    }
+}
+
+impl<const N: usize> fmt::Display for Text<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl<const N: usize> fmt::Debug for Text<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> PartialEq<str> for Text<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
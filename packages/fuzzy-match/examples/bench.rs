@@ -0,0 +1,75 @@
+//! Typing a query into a 100k-item list, one char at a time:
+//! `cargo run --release --example bench`
+
+use std::time::{Duration, Instant};
+
+use fuzzy_match::{Filter, Matcher, Query};
+
+const ITEMS: usize = 100_000;
+
+const WORDS: [&str; 16] = [
+    "widget", "factory", "render", "tree", "signal", "layout", "button", "dropdown", "parser", "lexer", "history", "Matcher",
+    "café", "straße", "config", "event",
+];
+
+/// Paths like `src/render_tree/widgetFactory42.rs`, the same every run
+fn items() -> Vec<String> {
+    let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut next = move |n: usize| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (seed >> 33) as usize % n
+    };
+    (0..ITEMS)
+        .map(|i| {
+            format!(
+                "src/{}_{}/{}{}{}.rs",
+                WORDS[next(16)],
+                WORDS[next(16)],
+                WORDS[next(16)],
+                WORDS[next(16)].to_uppercase(),
+                i % 97
+            )
+        })
+        .collect()
+}
+
+fn time<T>(run: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = run();
+    (result, start.elapsed())
+}
+
+fn main() {
+    let items = items();
+    let pattern = "wfactrs";
+
+    let mut matcher = Matcher::new();
+    let query = Query::new(pattern);
+    let (matched, took) = time(|| items.iter().filter(|item| matcher.score(&query, item).is_some()).count());
+    println!("score {} items for {:?}: {} matched in {:?}", ITEMS, pattern, matched, took);
+
+    for threads in [1, 0] {
+        let label = if threads == 1 { "1 thread" } else { "all threads" };
+        let mut fresh = if threads == 1 { Filter::new().threads(1) } else { Filter::new() };
+        let mut incremental = if threads == 1 { Filter::new().threads(1) } else { Filter::new() };
+        let (mut total_fresh, mut total_incremental) = (Duration::ZERO, Duration::ZERO);
+        println!("\n{label}:");
+        for end in 1..=pattern.len() {
+            let typed = &pattern[..end];
+            fresh.reset();
+            let (full, took_fresh) = time(|| fresh.search(&items, typed));
+            let (narrowed, took_incremental) = time(|| incremental.search(&items, typed));
+            assert_eq!(full, narrowed);
+            total_fresh += took_fresh;
+            total_incremental += took_incremental;
+            println!(
+                "  {:<8} {:>6} matches  fresh {:>10.2?}  incremental {:>10.2?}",
+                typed,
+                full.len(),
+                took_fresh,
+                took_incremental
+            );
+        }
+        println!("  total             fresh {:>10.2?}  incremental {:>10.2?}", total_fresh, total_incremental);
+    }
+}
@@ -0,0 +1,97 @@
+use std::cmp::Reverse;
+use std::num::NonZeroUsize;
+use std::thread;
+
+use super::score::{Matcher, Query};
+
+/// Below this many candidates a search stays on the calling thread
+const PARALLEL_MIN: usize = 8 * 1024;
+
+/// One item that matched
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Match {
+    /// Position in the searched items
+    pub index: usize,
+    pub score: i32,
+}
+
+/// Ranks a list of items against a query as it's typed.
+///
+/// Each search remembers its matches. When the next query only narrows
+/// the last one, as typing another char does, just those are rescored
+/// rather than the whole list. Large candidate sets are split across
+/// threads.
+pub struct Filter {
+    last: Option<(Query, Vec<usize>)>,
+    threads: usize,
+    matcher: Matcher,
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Filter {
+    pub fn new() -> Self {
+        Self {
+            last: None,
+            threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            matcher: Matcher::new(),
+        }
+    }
+
+    /// Threads to search with at most; 1 keeps it on the calling thread
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Forget the last search; needed whenever the items change
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+
+    /// Matches of `pattern` in `items`, best first. Equal scores go to the
+    /// shorter item, then the earlier one. An empty pattern matches all
+    /// items in order.
+    ///
+    /// `items` must be the same list as in the last call unless `reset`
+    /// was called in between.
+    pub fn search<S: AsRef<str> + Sync>(&mut self, items: &[S], pattern: &str) -> Vec<Match> {
+        let query = Query::new(pattern);
+        if query.is_empty() {
+            self.last = None;
+            return (0..items.len()).map(|index| Match { index, score: 0 }).collect();
+        }
+
+        let candidates = match self.last.take() {
+            Some((last, matched)) if last.narrows_to(&query) => matched,
+            _ => (0..items.len()).collect(),
+        };
+        let mut matches = if self.threads > 1 && candidates.len() >= PARALLEL_MIN {
+            let chunk = candidates.len().div_ceil(self.threads);
+            thread::scope(|scope| {
+                let workers: Vec<_> = candidates
+                    .chunks(chunk)
+                    .map(|part| scope.spawn(|| score_all(&mut Matcher::new(), &query, items, part)))
+                    .collect();
+                workers.into_iter().flat_map(|worker| worker.join().unwrap_or_default()).collect()
+            })
+        } else {
+            score_all(&mut self.matcher, &query, items, &candidates)
+        };
+
+        self.last = Some((query, matches.iter().map(|m| m.index).collect()));
+        matches.sort_unstable_by_key(|m| (Reverse(m.score), items[m.index].as_ref().len(), m.index));
+        matches
+    }
+}
+
+fn score_all<S: AsRef<str>>(matcher: &mut Matcher, query: &Query, items: &[S], candidates: &[usize]) -> Vec<Match> {
+    candidates
+        .iter()
+        .filter_map(|&index| Some(Match { index, score: matcher.score(query, items[index].as_ref())? }))
+        .collect()
+}
@@ -0,0 +1,44 @@
+use super::normalize::is_combining;
+
+/// Runs of `text` alternating between unmatched and matched chars, from
+/// the char positions `Matcher::indices` gives
+pub fn highlight<'a>(text: &'a str, positions: &'a [usize]) -> Spans<'a> {
+    Spans { text, positions, offset: 0, char_index: 0 }
+}
+
+pub struct Spans<'a> {
+    text: &'a str,
+    positions: &'a [usize],
+    /// Byte offset of the next run
+    offset: usize,
+    char_index: usize,
+}
+
+impl<'a> Iterator for Spans<'a> {
+    /// The run, and whether it matched
+    type Item = (&'a str, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.text[self.offset..];
+        if rest.is_empty() {
+            return None;
+        }
+        let matched = self.positions.first() == Some(&self.char_index);
+        let mut len = 0;
+        for c in rest.chars() {
+            let here = self.positions.first() == Some(&self.char_index);
+            // Combining marks go with the char before them
+            if here != matched && !is_combining(c) {
+                break;
+            }
+            if here {
+                self.positions = &self.positions[1..];
+            }
+            len += c.len_utf8();
+            self.char_index += 1;
+        }
+        let run = &rest[..len];
+        self.offset += len;
+        Some((run, matched))
+    }
+}
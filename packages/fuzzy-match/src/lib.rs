@@ -0,0 +1,21 @@
+//! Fuzzy matching for pickers and search-as-you-type lists.
+//!
+//! `Matcher` scores how well a `Query` aligns with a target the way fzf
+//! does: every char of the query must appear in order, matches at word
+//! starts, camelCase humps and after `/` or `_` earn bonuses, runs of
+//! consecutive matches earn more, and gaps cost. The best alignment is
+//! found by dynamic programming rather than taken greedily, and its
+//! positions can be had for highlighting. `Filter` ranks a whole list,
+//! reusing its last result while the query only grows.
+//!
+//! Matching is smart-case and ignores diacritics: see `Query`.
+
+mod filter;
+mod highlight;
+mod normalize;
+mod score;
+
+pub use filter::{Filter, Match};
+pub use highlight::{highlight, Spans};
+pub use normalize::{fold, is_combining};
+pub use score::{Matcher, Query, GAP_EXTENSION, GAP_START, SCORE_MATCH};
@@ -0,0 +1,84 @@
+/// ASCII base letter of U+00C0..=U+017F, or `.` to keep the char. Letters
+/// that decompose to two (`æ`, `ß`, `œ`, `ĳ`) are handled in `fold`.
+const LATIN: &str = concat!(
+    "AAAAAA.CEEEEIIIIDNOOOOO.OUUUUY..",
+    "aaaaaa.ceeeeiiiidnooooo.ouuuuy.y",
+    "AaAaAaCcCcCcCcDdDdEeEeEeEeEeGgGg",
+    "GgGgHhHhIiIiIiIiIi..JjKk.LlLlLlL",
+    "lLlNnNnNn.NnOoOoOo..RrRrRrSsSsSs",
+    "SsTtTtTtUuUuUuUuUuUuWwYyYZzZzZzs",
+);
+
+/// Up to two chars a single char folds to
+#[derive(Copy, Clone, Debug)]
+pub struct Folded {
+    chars: [char; 2],
+    len: u8,
+    next: u8,
+}
+
+impl Folded {
+    const NONE: Folded = Folded { chars: ['\0'; 2], len: 0, next: 0 };
+
+    fn one(c: char) -> Self {
+        Folded { chars: [c, '\0'], len: 1, next: 0 }
+    }
+
+    fn two(pair: &str) -> Self {
+        let mut chars = pair.chars();
+        let first = chars.next().unwrap_or('\0');
+        let second = chars.next().unwrap_or('\0');
+        Folded { chars: [first, second], len: 2, next: 0 }
+    }
+}
+
+impl Iterator for Folded {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.next == self.len {
+            return None;
+        }
+        self.next += 1;
+        Some(self.chars[self.next as usize - 1])
+    }
+}
+
+/// Whether `c` is a combining diacritic, dropped when folding so a
+/// decomposed `e\u{301}` reads like `é`
+pub fn is_combining(c: char) -> bool {
+    matches!(c, '\u{0300}'..='\u{036F}' | '\u{1AB0}'..='\u{1AFF}' | '\u{1DC0}'..='\u{1DFF}' | '\u{20D0}'..='\u{20FF}')
+}
+
+/// `c` with diacritics and width stripped: `é` is `e`, `Ø` is `O`, `ß` is
+/// `ss`, fullwidth `Ａ` is `A`. Case is kept; lowercase first for a
+/// case-insensitive fold.
+pub fn fold(c: char) -> Folded {
+    match c {
+        '\0'..='\u{7F}' => Folded::one(c),
+        _ if is_combining(c) => Folded::NONE,
+        'Æ' => Folded::two("AE"),
+        'æ' => Folded::two("ae"),
+        'ß' => Folded::two("ss"),
+        'Ĳ' => Folded::two("IJ"),
+        'ĳ' => Folded::two("ij"),
+        'Œ' => Folded::two("OE"),
+        'œ' => Folded::two("oe"),
+        '\u{C0}'..='\u{17F}' => match LATIN.as_bytes()[c as usize - 0xC0] {
+            b'.' => Folded::one(c),
+            base => Folded::one(base as char),
+        },
+        // Fullwidth ASCII
+        '\u{FF01}'..='\u{FF5E}' => Folded::one(char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)),
+        _ => Folded::one(c),
+    }
+}
+
+/// Single-char lowercase; the rare multi-char ones keep their first char
+pub fn lower(c: char) -> char {
+    if c.is_ascii() {
+        c.to_ascii_lowercase()
+    } else {
+        c.to_lowercase().next().unwrap_or(c)
+    }
+}
@@ -0,0 +1,358 @@
+use super::normalize::{fold, is_combining, lower};
+
+// Scores after fzf: a match is worth 16, a gap costs 3 to open and 1 per
+// extra char, and matches where a word starts earn a bonus
+pub const SCORE_MATCH: i32 = 16;
+pub const GAP_START: i32 = -3;
+pub const GAP_EXTENSION: i32 = -1;
+const BONUS_BOUNDARY: i16 = (SCORE_MATCH / 2) as i16;
+const BONUS_BOUNDARY_WHITE: i16 = BONUS_BOUNDARY + 2;
+const BONUS_BOUNDARY_DELIMITER: i16 = BONUS_BOUNDARY + 1;
+const BONUS_NON_WORD: i16 = BONUS_BOUNDARY;
+const BONUS_CAMEL: i16 = BONUS_BOUNDARY + GAP_EXTENSION as i16;
+/// Keeps a run of matches together rather than splitting it to chase
+/// boundaries: it outweighs opening and extending a gap
+const BONUS_CONSECUTIVE: i16 = -(GAP_START + GAP_EXTENSION) as i16;
+/// The first query char's bonus counts double
+const FIRST_CHAR_MULTIPLIER: i32 = 2;
+
+/// Largest DP table, in cells, before falling back to a greedy alignment
+const MAX_CELLS: usize = 1 << 16;
+
+const NONE: i32 = i32::MIN / 2;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Class {
+    White,
+    Delimiter,
+    NonWord,
+    Lower,
+    Upper,
+    Letter,
+    Number,
+}
+
+fn classify(c: char) -> Class {
+    if c.is_ascii_lowercase() {
+        Class::Lower
+    } else if c.is_ascii_uppercase() {
+        Class::Upper
+    } else if c.is_ascii_digit() {
+        Class::Number
+    } else if c.is_whitespace() {
+        Class::White
+    } else if matches!(c, '/' | '\\' | ',' | ':' | ';' | '|' | '.' | '-' | '_') {
+        Class::Delimiter
+    } else if c.is_lowercase() {
+        Class::Lower
+    } else if c.is_uppercase() {
+        Class::Upper
+    } else if c.is_numeric() {
+        Class::Number
+    } else if c.is_alphabetic() {
+        Class::Letter
+    } else {
+        Class::NonWord
+    }
+}
+
+/// Bonus for matching a char of class `class` right after one of `prev`
+fn bonus(prev: Class, class: Class) -> i16 {
+    match class {
+        Class::White => BONUS_BOUNDARY_WHITE,
+        Class::Delimiter | Class::NonWord => BONUS_NON_WORD,
+        _ => match prev {
+            Class::White => BONUS_BOUNDARY_WHITE,
+            Class::Delimiter => BONUS_BOUNDARY_DELIMITER,
+            Class::NonWord => BONUS_BOUNDARY,
+            Class::Lower if class == Class::Upper => BONUS_CAMEL,
+            Class::Number => 0,
+            _ if class == Class::Number => BONUS_CAMEL,
+            _ => 0,
+        },
+    }
+}
+
+/// A search pattern, normalized once and matched against many targets.
+///
+/// Smart case: a pattern with no uppercase matches any case, one with
+/// uppercase only its own. Diacritics never matter, so `cafe` finds
+/// `Café` and `straße` finds `STRASSE`.
+#[derive(Clone, Debug)]
+pub struct Query {
+    pattern: String,
+    chars: Vec<char>,
+    /// `chars` as bytes when all ASCII, for a quick reject
+    ascii: Option<Vec<u8>>,
+    case_sensitive: bool,
+}
+
+impl Query {
+    pub fn new(pattern: &str) -> Self {
+        let case_sensitive = pattern.chars().any(char::is_uppercase);
+        let chars: Vec<char> = pattern
+            .chars()
+            .flat_map(|c| fold(if case_sensitive { c } else { lower(c) }))
+            .collect();
+        let ascii = chars.iter().all(char::is_ascii).then(|| chars.iter().map(|&c| c as u8).collect());
+        Self { pattern: pattern.to_owned(), chars, ascii, case_sensitive }
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Whether every target matching `self` also matches `narrower`, so
+    /// `narrower` need only look through `self`'s matches
+    pub fn narrows_to(&self, narrower: &Query) -> bool {
+        if self.case_sensitive && !narrower.case_sensitive {
+            return false;
+        }
+        let mut rest = narrower.chars.iter();
+        self.chars.iter().all(|c| rest.any(|n| n == c))
+    }
+}
+
+/// Scores targets against a `Query`, keeping its buffers between calls so
+/// a filter loop doesn't allocate. One per thread.
+#[derive(Default)]
+pub struct Matcher {
+    // The target, normalized like the query
+    chars: Vec<char>,
+    /// Char index in the original target of each of `chars`
+    origin: Vec<u32>,
+    bonus: Vec<i16>,
+    // DP table, one row per query char
+    score: Vec<i32>,
+    chunk: Vec<i16>,
+    from: Vec<u32>,
+    positions: Vec<usize>,
+}
+
+impl Matcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Best alignment score of `query` in `target`, or `None` when it isn't
+    /// a subsequence. An empty query matches everything with 0.
+    pub fn score(&mut self, query: &Query, target: &str) -> Option<i32> {
+        self.align(query, target)
+    }
+
+    /// `score`, plus the char index in `target` of each matched query char,
+    /// in order and without duplicates, for highlighting
+    pub fn indices(&mut self, query: &Query, target: &str) -> Option<(i32, Vec<usize>)> {
+        let score = self.align(query, target)?;
+        let mut positions: Vec<usize> = self.positions.iter().map(|&p| self.origin[p] as usize).collect();
+        // A folded pair like `ß` to `ss` maps both halves to one char
+        positions.dedup();
+        Some((score, positions))
+    }
+
+    fn align(&mut self, query: &Query, target: &str) -> Option<i32> {
+        self.positions.clear();
+        if query.is_empty() {
+            return Some(0);
+        }
+        if let Some(q) = &query.ascii
+            && target.is_ascii()
+            && !ascii_subsequence(q, target.as_bytes(), query.case_sensitive)
+        {
+            return None;
+        }
+        self.prepare(target, query.case_sensitive);
+
+        let q = &query.chars;
+        // Earliest start and latest end any alignment can have
+        let mut next = 0;
+        let mut start = None;
+        for (j, &c) in self.chars.iter().enumerate() {
+            if c == q[next] {
+                start.get_or_insert(j);
+                next += 1;
+                if next == q.len() {
+                    break;
+                }
+            }
+        }
+        if next < q.len() {
+            return None;
+        }
+        let start = start?;
+        let end = self.chars.iter().rposition(|&c| c == q[q.len() - 1])?;
+
+        if q.len() * (end - start + 1) > MAX_CELLS {
+            Some(self.greedy(q, start))
+        } else {
+            Some(self.optimal(q, start, end))
+        }
+    }
+
+    fn prepare(&mut self, target: &str, case_sensitive: bool) {
+        self.chars.clear();
+        self.origin.clear();
+        self.bonus.clear();
+        let mut prev = Class::White;
+        if target.is_ascii() {
+            for (i, &b) in target.as_bytes().iter().enumerate() {
+                let class = classify(b as char);
+                self.bonus.push(bonus(prev, class));
+                prev = class;
+                self.chars.push(if case_sensitive { b } else { b.to_ascii_lowercase() } as char);
+                self.origin.push(i as u32);
+            }
+            return;
+        }
+        for (i, c) in target.chars().enumerate() {
+            if is_combining(c) {
+                continue;
+            }
+            let class = classify(c);
+            let bonus = bonus(prev, class);
+            prev = class;
+            for (k, folded) in fold(if case_sensitive { c } else { lower(c) }).enumerate() {
+                self.chars.push(folded);
+                self.origin.push(i as u32);
+                self.bonus.push(if k == 0 { bonus } else { 0 });
+            }
+        }
+    }
+
+    /// Smith-Waterman style alignment over `start..=end` with affine gap
+    /// penalties, keeping for each cell the best score with query char `i`
+    /// matched right there
+    fn optimal(&mut self, q: &[char], start: usize, end: usize) -> i32 {
+        let width = end - start + 1;
+        let cells = q.len() * width;
+        self.score.clear();
+        self.score.resize(cells, NONE);
+        self.chunk.clear();
+        self.chunk.resize(cells, 0);
+        self.from.clear();
+        self.from.resize(cells, 0);
+
+        for (i, &qc) in q.iter().enumerate() {
+            let row = i * width;
+            let above = row.wrapping_sub(width);
+            // Best previous-row match at least two columns back, gap paid
+            let mut gap = NONE;
+            let mut gap_from = 0;
+            for j in 0..width {
+                if i > 0 && j >= 2 {
+                    let open = self.score[above + j - 2] + GAP_START;
+                    let extend = gap + GAP_EXTENSION;
+                    if open >= extend {
+                        gap = open;
+                        gap_from = j - 2;
+                    } else {
+                        gap = extend;
+                    }
+                }
+                if self.chars[start + j] != qc {
+                    continue;
+                }
+                let bonus = self.bonus[start + j];
+                if i == 0 {
+                    self.score[row + j] = SCORE_MATCH + bonus as i32 * FIRST_CHAR_MULTIPLIER;
+                    self.chunk[row + j] = bonus;
+                    continue;
+                }
+                let mut best = NONE;
+                if j >= 1 && self.score[above + j - 1] > NONE {
+                    let mut chunk = self.chunk[above + j - 1];
+                    if bonus >= BONUS_BOUNDARY && bonus > chunk {
+                        chunk = bonus;
+                    }
+                    best = self.score[above + j - 1] + SCORE_MATCH + bonus.max(chunk).max(BONUS_CONSECUTIVE) as i32;
+                    self.chunk[row + j] = chunk;
+                    self.from[row + j] = (j - 1) as u32;
+                }
+                if gap > NONE && gap + SCORE_MATCH + bonus as i32 > best {
+                    best = gap + SCORE_MATCH + bonus as i32;
+                    self.chunk[row + j] = bonus;
+                    self.from[row + j] = gap_from as u32;
+                }
+                self.score[row + j] = best;
+            }
+        }
+
+        let last = (q.len() - 1) * width;
+        let mut j = 0;
+        for col in 1..width {
+            if self.score[last + col] > self.score[last + j] {
+                j = col;
+            }
+        }
+        let best = self.score[last + j];
+        self.positions.resize(q.len(), 0);
+        for i in (0..q.len()).rev() {
+            self.positions[i] = start + j;
+            j = self.from[i * width + j] as usize;
+        }
+        best
+    }
+
+    /// Fallback for very long targets: the first end a forward scan
+    /// reaches, then the latest start that still fits before it
+    fn greedy(&mut self, q: &[char], start: usize) -> i32 {
+        let mut end = start;
+        let mut next = 0;
+        for (j, &c) in self.chars.iter().enumerate().skip(start) {
+            if c == q[next] {
+                next += 1;
+                if next == q.len() {
+                    end = j;
+                    break;
+                }
+            }
+        }
+        self.positions.resize(q.len(), 0);
+        let mut next = q.len();
+        for j in (start..=end).rev() {
+            if next > 0 && self.chars[j] == q[next - 1] {
+                next -= 1;
+                self.positions[next] = j;
+            }
+        }
+
+        let mut score = 0;
+        let mut chunk = 0;
+        for (i, &p) in self.positions.iter().enumerate() {
+            let bonus = self.bonus[p];
+            if i == 0 {
+                score += SCORE_MATCH + bonus as i32 * FIRST_CHAR_MULTIPLIER;
+                chunk = bonus;
+            } else if p == self.positions[i - 1] + 1 {
+                if bonus >= BONUS_BOUNDARY && bonus > chunk {
+                    chunk = bonus;
+                }
+                score += SCORE_MATCH + bonus.max(chunk).max(BONUS_CONSECUTIVE) as i32;
+            } else {
+                let gap = (p - self.positions[i - 1] - 1) as i32;
+                score += GAP_START + (gap - 1) * GAP_EXTENSION + SCORE_MATCH + bonus as i32;
+                chunk = bonus;
+            }
+        }
+        score
+    }
+}
+
+fn ascii_subsequence(query: &[u8], target: &[u8], case_sensitive: bool) -> bool {
+    let mut rest = target.iter();
+    query.iter().all(|&q| {
+        if case_sensitive {
+            rest.any(|&t| t == q)
+        } else {
+            rest.any(|&t| t.to_ascii_lowercase() == q)
+        }
+    })
+}
@@ -0,0 +1,117 @@
+//! Renderer-independent text layout: measuring, word wrap, alignment,
+//! ellipsis and styled spans, over whatever font a backend describes
+//! through `Metrics`.
+//!
+//! Everything works on `&str` one char at a time, so UTF-8 text lays out
+//! by codepoint, and results are slices of the input. No allocation, so
+//! the crust programs can pull this file in with
+//! `#[path = ".../text-layout/src/lib.rs"] mod text_layout;`.
+#![no_std]
+
+mod rich;
+mod wrap;
+
+pub use rich::{wrap_spans, RichLine, RichLines, Run, Runs, Span};
+pub use wrap::{wrap, Line, Wrap};
+
+/// What `ellipsize` puts after a cut; three dots, which every font has
+pub const ELLIPSIS: &str = "...";
+
+/// Glyph sizes of a font at some size
+pub trait Metrics {
+    /// Width of `c`'s glyph, spacing not included
+    fn advance(&self, c: char) -> f32;
+    /// Space between one glyph and the next
+    fn spacing(&self) -> f32;
+    /// Distance from the top of one line to the top of the next
+    fn line_height(&self) -> f32;
+}
+
+impl<M: Metrics + ?Sized> Metrics for &M {
+    fn advance(&self, c: char) -> f32 {
+        (**self).advance(c)
+    }
+
+    fn spacing(&self) -> f32 {
+        (**self).spacing()
+    }
+
+    fn line_height(&self) -> f32 {
+        (**self).line_height()
+    }
+}
+
+/// Placement within the room there is, on either axis
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Align {
+    #[default]
+    Start,
+    Center,
+    End,
+}
+
+impl Align {
+    /// Offset of something `size` long into `room`; negative when it
+    /// doesn't fit and isn't at the start
+    pub fn offset(self, room: f32, size: f32) -> f32 {
+        match self {
+            Align::Start => 0.0,
+            Align::Center => (room - size) / 2.0,
+            Align::End => room - size,
+        }
+    }
+}
+
+/// Width of `text` as one line, newlines included as glyphs
+pub fn measure(metrics: &impl Metrics, text: &str) -> f32 {
+    let mut width = 0.0;
+    for (i, c) in text.chars().enumerate() {
+        if i > 0 {
+            width += metrics.spacing();
+        }
+        width += metrics.advance(c);
+    }
+    width
+}
+
+/// Byte length of the longest prefix of `text` no wider than `width`
+pub fn fit(metrics: &impl Metrics, text: &str, width: f32) -> usize {
+    let mut x = 0.0;
+    for (i, (at, c)) in text.char_indices().enumerate() {
+        if i > 0 {
+            x += metrics.spacing();
+        }
+        x += metrics.advance(c);
+        if x > width {
+            return at;
+        }
+    }
+    text.len()
+}
+
+/// `text` if it fits in `width`, else the longest prefix that still fits
+/// with `ELLIPSIS` after it, and true to say the ellipsis is due.
+/// Whitespace before the cut goes, so it reads `long...` not `long ...`.
+pub fn ellipsize<'a>(metrics: &impl Metrics, text: &'a str, width: f32) -> (&'a str, bool) {
+    if measure(metrics, text) <= width {
+        return (text, false);
+    }
+    let room = width - measure(metrics, ELLIPSIS) - metrics.spacing();
+    let cut = if room < 0.0 { 0 } else { fit(metrics, text, room) };
+    (text[..cut].trim_end(), true)
+}
+
+/// Byte offset of the char boundary in `text` nearest `x`, for placing a
+/// caret where the mouse is
+pub fn boundary_at(metrics: &impl Metrics, text: &str, x: f32) -> usize {
+    let mut left = 0.0;
+    for (i, (at, c)) in text.char_indices().enumerate() {
+        let gap = if i > 0 { metrics.spacing() } else { 0.0 };
+        let right = left + gap + metrics.advance(c);
+        if x < (left + right) / 2.0 {
+            return at;
+        }
+        left = right;
+    }
+    text.len()
+}
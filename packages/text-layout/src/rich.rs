@@ -0,0 +1,167 @@
+use super::{measure, Metrics};
+
+/// Text in one style, e.g. a colour and whether it's bold; `Metrics` for
+/// it come from the closure given to `wrap_spans`
+#[derive(Copy, Clone, Debug)]
+pub struct Span<'a, S> {
+    pub text: &'a str,
+    pub style: S,
+}
+
+impl<'a, S> Span<'a, S> {
+    pub fn new(text: &'a str, style: S) -> Self {
+        Self { text, style }
+    }
+}
+
+/// Span index and byte offset into it
+type Pos = (usize, usize);
+
+/// Chars of `spans` from `from` on, with where each starts and ends
+fn chars<'a, S>(spans: &'a [Span<'a, S>], from: Pos) -> impl Iterator<Item = (Pos, Pos, char, &'a S)> + 'a {
+    spans.iter().enumerate().skip(from.0).flat_map(move |(i, span)| {
+        let skip = if i == from.0 { from.1 } else { 0 };
+        span.text[skip..].char_indices().map(move |(at, c)| ((i, skip + at), (i, skip + at + c.len_utf8()), c, &span.style))
+    })
+}
+
+/// One line of wrapped spans; `runs` gives the pieces to draw
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RichLine {
+    start: Pos,
+    end: Pos,
+    pub width: f32,
+}
+
+impl RichLine {
+    /// The line's text, one piece per span it crosses
+    pub fn runs<'a, 'f, S, M: Metrics, F: Fn(&S) -> M>(&self, spans: &'a [Span<'a, S>], metrics: &'f F) -> Runs<'a, 'f, S, F> {
+        Runs { spans, metrics, span: self.start.0, start: self.start, end: self.end, x: 0.0 }
+    }
+}
+
+/// Lines of `spans` no wider than `width`, wrapped as `wrap` wraps plain
+/// text. A word may cross spans, as in `**bold**ly`, and still stays
+/// whole.
+pub fn wrap_spans<'a, S, M: Metrics, F: Fn(&S) -> M>(spans: &'a [Span<'a, S>], metrics: F, width: f32) -> RichLines<'a, S, F> {
+    let done = spans.iter().all(|span| span.text.is_empty());
+    RichLines { spans, metrics, width, pos: (0, 0), done }
+}
+
+pub struct RichLines<'a, S, F> {
+    spans: &'a [Span<'a, S>],
+    metrics: F,
+    width: f32,
+    pos: Pos,
+    done: bool,
+}
+
+impl<'a, S, M: Metrics, F: Fn(&S) -> M> RichLines<'a, S, F> {
+    /// The style lookup the lines were measured with, for `RichLine::runs`
+    pub fn metrics(&self) -> &F {
+        &self.metrics
+    }
+}
+
+impl<'a, S, M: Metrics, F: Fn(&S) -> M> Iterator for RichLines<'a, S, F> {
+    type Item = RichLine;
+
+    fn next(&mut self) -> Option<RichLine> {
+        if self.done {
+            return None;
+        }
+        let start = self.pos;
+        let mut x = 0.0;
+        let mut spacing = 0.0;
+        let mut first = true;
+        // End of the last char that wasn't whitespace, and the width there
+        let mut ink = (start, 0.0);
+        // Where the line can break: `ink` as the last whitespace run
+        // began, and where the word after it starts
+        let mut space: Option<(Pos, f32)> = None;
+        let mut word_at = None;
+        let mut in_space = false;
+
+        for (at, after, c, style) in chars(self.spans, start) {
+            if c == '\n' {
+                self.pos = after;
+                return Some(RichLine { start, end: ink.0, width: ink.1 });
+            }
+            let metrics = (self.metrics)(style);
+            if c.is_whitespace() {
+                if !in_space && !first {
+                    space = Some(ink);
+                    word_at = None;
+                }
+                in_space = true;
+            } else {
+                if in_space && space.is_some() {
+                    word_at = Some(at);
+                }
+                in_space = false;
+            }
+
+            let right = x + if first { 0.0 } else { spacing } + metrics.advance(c);
+            if right > self.width && !first && !c.is_whitespace() {
+                return Some(match (space, word_at) {
+                    (Some((end, width)), Some(next)) => {
+                        self.pos = next;
+                        RichLine { start, end, width }
+                    }
+                    _ => {
+                        self.pos = at;
+                        RichLine { start, end: at, width: x }
+                    }
+                });
+            }
+            x = right;
+            spacing = metrics.spacing();
+            first = false;
+            if !c.is_whitespace() {
+                ink = (after, x);
+            }
+        }
+        self.done = true;
+        Some(RichLine { start, end: ink.0, width: ink.1 })
+    }
+}
+
+/// A piece of a line in one style, `x` from the line's start
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Run<'a, S> {
+    pub text: &'a str,
+    pub style: &'a S,
+    pub x: f32,
+    pub width: f32,
+}
+
+pub struct Runs<'a, 'f, S, F> {
+    spans: &'a [Span<'a, S>],
+    metrics: &'f F,
+    span: usize,
+    start: Pos,
+    end: Pos,
+    x: f32,
+}
+
+impl<'a, 'f, S, M: Metrics, F: Fn(&S) -> M> Iterator for Runs<'a, 'f, S, F> {
+    type Item = Run<'a, S>;
+
+    fn next(&mut self) -> Option<Run<'a, S>> {
+        while self.span <= self.end.0 && self.span < self.spans.len() {
+            let span = &self.spans[self.span];
+            let from = if self.span == self.start.0 { self.start.1 } else { 0 };
+            let to = if self.span == self.end.0 { self.end.1 } else { span.text.len() };
+            self.span += 1;
+            if from >= to {
+                continue;
+            }
+            let text = &span.text[from..to];
+            let metrics = (self.metrics)(&span.style);
+            let run = Run { text, style: &span.style, x: self.x, width: measure(&metrics, text) };
+            self.x += run.width + metrics.spacing();
+            return Some(run);
+        }
+        None
+    }
+}
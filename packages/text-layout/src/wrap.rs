@@ -0,0 +1,74 @@
+use super::{measure, Metrics};
+
+/// One line of wrapped text, without the whitespace it was broken at
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Line<'a> {
+    pub text: &'a str,
+    pub width: f32,
+}
+
+/// Lines of `text` no wider than `width`, broken at whitespace and at
+/// every `\n`. A word wider than a whole line is split between chars.
+/// Empty text has no lines.
+pub fn wrap<M: Metrics>(metrics: M, text: &str, width: f32) -> Wrap<'_, M> {
+    Wrap { metrics, rest: text, width, done: text.is_empty() }
+}
+
+pub struct Wrap<'a, M> {
+    metrics: M,
+    rest: &'a str,
+    width: f32,
+    done: bool,
+}
+
+impl<'a, M: Metrics> Wrap<'a, M> {
+    fn line(&self, text: &'a str) -> Line<'a> {
+        let text = text.trim_end();
+        Line { text, width: measure(&self.metrics, text) }
+    }
+}
+
+impl<'a, M: Metrics> Iterator for Wrap<'a, M> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Line<'a>> {
+        if self.done {
+            return None;
+        }
+        let text = self.rest;
+        let mut x = 0.0;
+        let mut first = true;
+        // Where the last whitespace run began, after some text
+        let mut space_at = None;
+        let mut in_space = false;
+        for (at, c) in text.char_indices() {
+            if c == '\n' {
+                self.rest = &text[at + 1..];
+                return Some(self.line(&text[..at]));
+            }
+            if c.is_whitespace() {
+                if !in_space && !first {
+                    space_at = Some(at);
+                }
+                in_space = true;
+            } else {
+                in_space = false;
+            }
+
+            let right = x + if first { 0.0 } else { self.metrics.spacing() } + self.metrics.advance(c);
+            // Whitespace may hang past the edge, it's trimmed anyway
+            if right > self.width && !first && !c.is_whitespace() {
+                let (end, next) = match space_at {
+                    Some(end) => (end, text[end..].trim_start_matches(|c: char| c.is_whitespace() && c != '\n')),
+                    None => (at, &text[at..]),
+                };
+                self.rest = next;
+                return Some(self.line(&text[..end]));
+            }
+            x = right;
+            first = false;
+        }
+        self.done = true;
+        Some(self.line(text))
+    }
+}
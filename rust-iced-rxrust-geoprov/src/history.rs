@@ -0,0 +1,65 @@
+//! History saved between runs as JSON, newest entry first
+
+use std::path::PathBuf;
+use std::{fs, io};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct Saved {
+    expression: String,
+    /// Result as the display showed it
+    display: String,
+    value: f64,
+    /// Exact programmer-mode result
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    integer: Option<i128>,
+}
+
+/// `$XDG_DATA_HOME/iced-calculator/history.json`, falling back to
+/// `~/.local/share`, or the working directory without a home
+fn path() -> PathBuf {
+    let data = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .unwrap_or_default();
+    data.join("iced-calculator").join("history.json")
+}
+
+/// Fill `engine`'s history from the last run. A missing file is an
+/// empty history.
+pub fn load(engine: &mut calc_engine::Calculator) -> io::Result<()> {
+    let json = match fs::read_to_string(path()) {
+        Ok(json) => json,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    let saved: Vec<Saved> = serde_json::from_str(&json)?;
+    for entry in saved.iter().rev() {
+        engine.restore_history(&entry.expression, Ok(entry.value), entry.integer, &entry.display);
+    }
+    Ok(())
+}
+
+/// Write `engine`'s history over the saved one. Errors aren't kept, they
+/// can't be recalled anyway.
+pub fn save(engine: &calc_engine::Calculator) -> io::Result<()> {
+    let saved: Vec<Saved> = engine
+        .history()
+        .iter()
+        .filter_map(|entry| {
+            Some(Saved {
+                expression: entry.expression().to_owned(),
+                display: entry.display().to_owned(),
+                value: entry.result().ok()?,
+                integer: entry.integer(),
+            })
+        })
+        .collect();
+    let path = path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&saved)?)
+}
@@ -1,136 +1,347 @@
+mod history;
+
 use iced::{
-    widget::{button, column, container, row, text, Column},
+    widget::{button, column, container, row, scrollable, text, Column},
     Alignment, Center, Element, Fill,
 };
-use iced::{application, Theme};
+use iced::{application, clipboard, keyboard, Subscription, Task, Theme};
+
+use calc_engine::{
+    format_int, radix_label, Angle, Constant, Function, Key, Mode, NumberFormat, Operator, WordSize, RADIXES,
+};
 
 #[derive(Debug, Clone)]
 enum Message {
-    Digit(u8),          // 0–9
+    Digit(u8),          // 0–9, A–F in hex
     Decimal,            // .
     Operator(Operator),
     Equals,
     Clear,
+    ClearEntry,
+    Negate,             // ±
+    Percent,
+    OpenParen,
+    CloseParen,
+    Backspace,
+    Memory(Memory),
+    SetMode(Mode),
+    // Keyboard and clipboard
+    Key(Key),
+    Copy,
+    Paste,
+    Pasted(Option<String>),
+    // History
+    Recall(usize),
+    ClearHistory,
+    // Scientific
+    ToggleAngle,
+    Function(Function),
+    Constant(Constant),
+    Factorial,
+    // Programmer
+    SetRadix(u32),
+    SetWordSize(WordSize),
+    ToggleSigned,
+    BitNot,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-enum Operator {
+enum Memory {
     Add,
     Subtract,
-    Multiply,
-    Divide,
+    Recall,
+    Clear,
 }
 
 #[derive(Default)]
 struct Calculator {
-    display: String,
-    previous: Option<f64>,
-    current: String,
-    operator: Option<Operator>,
-    waiting_for_operand: bool,
+    engine: calc_engine::Calculator,
 }
 
 impl Calculator {
-    fn update(&mut self, message: Message) {
-        match message {
-            Message::Digit(d) => {
-                if self.waiting_for_operand {
-                    self.current = d.to_string();
-                    self.waiting_for_operand = false;
-                } else {
-                    self.current.push(char::from(b'0' + d));
-                }
-                self.update_display();
-            }
+    /// Numbers are grouped and punctuated the way `LC_NUMERIC`/`LANG` say
+    fn new() -> Self {
+        let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .find_map(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+            .unwrap_or_default();
+        let mut calculator = Self::default();
+        calculator.engine.set_number_format(NumberFormat::for_locale(&locale));
+        if let Err(e) = history::load(&mut calculator.engine) {
+            tracing::warn!("couldn't load history: {e}");
+        }
+        calculator
+    }
 
-            Message::Decimal => {
-                if self.waiting_for_operand {
-                    self.current = "0.".to_string();
-                    self.waiting_for_operand = false;
-                } else if !self.current.contains('.') {
-                    self.current.push('.');
-                }
-                self.update_display();
+    fn update(&mut self, message: Message) -> Task<Message> {
+        let key = match message {
+            Message::Digit(d) => Key::Digit(d),
+            Message::Decimal => Key::Decimal,
+            Message::Operator(op) => Key::Operator(op),
+            Message::Equals => Key::Equals,
+            Message::Clear => Key::Clear,
+            Message::ClearEntry => Key::ClearEntry,
+            Message::Negate => Key::Negate,
+            Message::Percent => Key::Percent,
+            Message::OpenParen => Key::LeftParen,
+            Message::CloseParen => Key::RightParen,
+            Message::Backspace => Key::Backspace,
+            Message::Memory(Memory::Add) => Key::MemoryAdd,
+            Message::Memory(Memory::Subtract) => Key::MemorySubtract,
+            Message::Memory(Memory::Recall) => Key::MemoryRecall,
+            Message::Memory(Memory::Clear) => Key::MemoryClear,
+            Message::Function(f) => Key::Function(f),
+            Message::Constant(c) => Key::Constant(c),
+            Message::Factorial => Key::Factorial,
+            Message::BitNot => Key::BitNot,
+            Message::Key(key) => key,
+            Message::Copy => return clipboard::write(self.engine.display().to_owned()),
+            Message::Paste => return clipboard::read().map(Message::Pasted),
+            Message::Pasted(Some(pasted)) => {
+                self.paste(&pasted);
+                return Task::none();
             }
-
-            Message::Operator(op) => {
-                if let Some(prev) = self.current.parse::<f64>().ok() {
-                    if let Some(previous_val) = self.previous {
-                        let result = self.compute(previous_val, prev, self.operator.unwrap());
-                        self.previous = Some(result);
-                        self.current = result.to_string();
-                    } else {
-                        self.previous = Some(prev);
-                    }
-                }
-                self.operator = Some(op);
-                self.waiting_for_operand = true;
-                self.update_display();
+            Message::Pasted(None) => return Task::none(),
+            Message::Recall(index) => {
+                self.engine.recall(index);
+                return Task::none();
             }
-
-            Message::Equals => {
-                if let (Some(prev), Some(op)) = (self.previous, self.operator) {
-                    if let Ok(current_val) = self.current.parse::<f64>() {
-                        let result = self.compute(prev, current_val, op);
-                        self.display = result.to_string();
-                        self.previous = None;
-                        self.current = result.to_string();
-                        self.operator = None;
-                        self.waiting_for_operand = true;
-                    }
-                }
+            Message::ClearHistory => {
+                self.engine.clear_history();
+                self.save_history();
+                return Task::none();
             }
-
-            Message::Clear => {
-                self.display = "0".to_string();
-                self.current = "0".to_string();
-                self.previous = None;
-                self.operator = None;
-                self.waiting_for_operand = false;
+            Message::SetMode(mode) => {
+                self.engine.set_mode(mode);
+                return Task::none();
+            }
+            Message::ToggleAngle => {
+                let angle = match self.engine.angle() {
+                    Angle::Degrees => Angle::Radians,
+                    Angle::Radians => Angle::Degrees,
+                };
+                self.engine.set_angle(angle);
+                return Task::none();
+            }
+            Message::SetRadix(radix) => {
+                self.engine.set_radix(radix);
+                return Task::none();
             }
+            Message::SetWordSize(word) => {
+                self.engine.set_word_size(word);
+                return Task::none();
+            }
+            Message::ToggleSigned => {
+                self.engine.set_signed(!self.engine.is_signed());
+                return Task::none();
+            }
+        };
+        self.engine.press(key);
+        if key == Key::Equals {
+            self.save_history();
         }
+        Task::none()
     }
 
-    fn compute(&self, a: f64, b: f64, op: Operator) -> f64 {
-        match op {
-            Operator::Add => a + b,
-            Operator::Subtract => a - b,
-            Operator::Multiply => a * b,
-            Operator::Divide => {
-                if b != 0.0 { a / b } else { 0.0 } // simple error handling
-            }
+    /// Evaluate pasted text as one expression, so `sin(30)` keeps its
+    /// function name. The display's digit grouping (or whitespace) between
+    /// two digits is dropped and its decimal point read as `.`, so a copied
+    /// `1,234.5` reads back while `5 mod 3` keeps its spaces
+    fn paste(&mut self, pasted: &str) {
+        let format = self.engine.number_format();
+        let chars: Vec<char> = pasted.trim_end().trim_end_matches('=').chars().collect();
+        let expr: String = chars
+            .iter()
+            .enumerate()
+            .filter(|&(i, &c)| {
+                let separator = c.is_whitespace() || Some(c) == format.group_separator;
+                let between_digits = i > 0
+                    && chars[i - 1].is_ascii_digit()
+                    && chars.get(i + 1).is_some_and(char::is_ascii_digit);
+                !(separator && between_digits)
+            })
+            .map(|(_, &c)| if c == format.decimal_point { '.' } else { c })
+            .collect();
+        if expr.is_empty() {
+            return;
         }
+        self.engine.evaluate(&expr);
+        self.save_history();
     }
 
-    fn update_display(&mut self) {
-        let cleaned = self.current.trim_start_matches('-').trim_start_matches('0');
-        let display_val = if cleaned.is_empty() || cleaned == "." {
-            "0".to_string()
-        } else if self.current.starts_with("0.") || self.current.starts_with("-0.") {
-            self.current.clone()
-        } else if self.current.starts_with('0') && !self.current.contains('.') {
-            cleaned.to_string()
-        } else {
-            self.current.clone()
+    fn save_history(&self) {
+        if let Err(e) = history::save(&self.engine) {
+            tracing::warn!("couldn't save history: {e}");
+        }
+    }
+
+    /// Typing works like the keypad: digits, operators, Enter for =,
+    /// Escape for C, Backspace, Delete for CE, and Ctrl+C / Ctrl+V
+    fn subscription(&self) -> Subscription<Message> {
+        keyboard::on_key_press(|key, modifiers| match key.as_ref() {
+            keyboard::Key::Character("c") if modifiers.command() => Some(Message::Copy),
+            keyboard::Key::Character("v") if modifiers.command() => Some(Message::Paste),
+            _ if modifiers.command() || modifiers.alt() => None,
+            keyboard::Key::Named(keyboard::key::Named::Enter) => Some(Message::Equals),
+            keyboard::Key::Named(keyboard::key::Named::Escape) => Some(Message::Clear),
+            keyboard::Key::Named(keyboard::key::Named::Backspace) => Some(Message::Backspace),
+            keyboard::Key::Named(keyboard::key::Named::Delete) => Some(Message::ClearEntry),
+            keyboard::Key::Character(typed) => {
+                let mut chars = typed.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Key::from_char(c).map(Message::Key),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+    }
+
+    /// Past expressions, newest on top; clicking one recalls its result
+    fn history_panel(&self) -> Element<Message> {
+        let entries = self.engine.history().iter().enumerate().map(|(index, entry)| -> Element<Message> {
+            button(
+                column![
+                    text(entry.expression().to_owned()).size(14),
+                    text(entry.display().to_owned()).size(20),
+                ]
+                .align_x(Alignment::End)
+                .width(Fill),
+            )
+            .width(Fill)
+            .on_press_maybe(entry.result().ok().map(|_| Message::Recall(index)))
+            .style(button::text)
+            .into()
+        });
+
+        column![
+            row![
+                text("History").size(18).width(Fill),
+                button(text("Copy").size(14)).on_press(Message::Copy).style(button::secondary),
+                button(text("Paste").size(14)).on_press(Message::Paste).style(button::secondary),
+                button(text("Clear").size(14))
+                    .on_press_maybe(Some(Message::ClearHistory).filter(|_| !self.engine.history().is_empty()))
+                    .style(button::secondary),
+            ]
+            .spacing(5)
+            .align_y(Alignment::Center),
+            scrollable(Column::with_children(entries).spacing(2)).height(Fill),
+        ]
+        .spacing(10)
+        .width(260)
+        .into()
+    }
+
+    /// Functions and constants beside the keypad
+    fn scientific_panel(&self) -> Element<Message> {
+        let key = |label: &str, msg: Message| {
+            button(text(label.to_owned()).size(20).center().width(Fill).height(Fill))
+                .width(70)
+                .height(50)
+                .on_press(msg)
         };
+        let function = |f: Function| key(f.name(), Message::Function(f));
+
+        column![
+            row![
+                key(self.engine.angle().label(), Message::ToggleAngle).style(button::secondary),
+                key("xʸ", Message::Operator(Operator::Power)),
+                key("n!", Message::Factorial),
+                key("mod", Message::Operator(Operator::Modulo)),
+            ]
+            .spacing(10),
+            row![function(Function::Sin), function(Function::Cos), function(Function::Tan), function(Function::Sqrt)]
+                .spacing(10),
+            row![function(Function::Asin), function(Function::Acos), function(Function::Atan), function(Function::Cbrt)]
+                .spacing(10),
+            row![function(Function::Sinh), function(Function::Cosh), function(Function::Tanh), function(Function::Abs)]
+                .spacing(10),
+            row![function(Function::Ln), function(Function::Log10), function(Function::Log2), function(Function::Exp)]
+                .spacing(10),
+            row![
+                key("π", Message::Constant(Constant::Pi)),
+                key("e", Message::Constant(Constant::E)),
+                key("τ", Message::Constant(Constant::Tau)),
+                key("φ", Message::Constant(Constant::Phi)),
+            ]
+            .spacing(10),
+        ]
+        .spacing(10)
+        .into()
+    }
 
-        // Remove trailing .0 if integer
-        self.display = if display_val.ends_with(".0") {
-            display_val[..display_val.len() - 2].to_string()
-        } else {
-            display_val
+    /// The value in every radix, word size, signedness and bitwise keys
+    fn programmer_panel(&self) -> Element<Message> {
+        let key = |label: &str, msg: Option<Message>| {
+            button(text(label.to_owned()).size(20).center().width(Fill).height(Fill))
+                .width(70)
+                .height(50)
+                .on_press_maybe(msg)
         };
+        let radix = self.engine.radix();
+        let hex_digit = |d: u8| key(&format!("{:X}", d), Some(Message::Digit(d)).filter(|_| radix == 16));
+
+        let value = self.engine.integer();
+        let readouts = RADIXES.iter().map(|&r| -> Element<Message> {
+            let mut shown = String::new();
+            if let Some(n) = value {
+                let _ = format_int(n, r, self.engine.word_size(), self.engine.is_signed(), &mut shown);
+            }
+            button(row![text(radix_label(r)).size(16).width(50), text(shown).size(16)])
+                .width(Fill)
+                .on_press(Message::SetRadix(r))
+                .style(if r == radix { button::primary } else { button::text })
+                .into()
+        });
+
+        let words = WordSize::ALL.iter().map(|&w| -> Element<Message> {
+            key(w.label(), Some(Message::SetWordSize(w)))
+                .style(if w == self.engine.word_size() { button::primary } else { button::secondary })
+                .into()
+        });
+
+        column![
+            Column::with_children(readouts).spacing(2).width(310),
+            row(words).spacing(10),
+            row![
+                key(if self.engine.is_signed() { "signed" } else { "unsigned" }, Some(Message::ToggleSigned))
+                    .width(150)
+                    .style(button::secondary),
+                key("NOT", Some(Message::BitNot)),
+                key("mod", Some(Message::Operator(Operator::Modulo))),
+            ]
+            .spacing(10),
+            row![
+                key("AND", Some(Message::Operator(Operator::And))),
+                key("OR", Some(Message::Operator(Operator::Or))),
+                key("XOR", Some(Message::Operator(Operator::Xor))),
+                key("<<", Some(Message::Operator(Operator::ShiftLeft))),
+            ]
+            .spacing(10),
+            row![hex_digit(10), hex_digit(11), hex_digit(12), key(">>", Some(Message::Operator(Operator::ShiftRight)))]
+                .spacing(10),
+            row![hex_digit(13), hex_digit(14), hex_digit(15)].spacing(10),
+        ]
+        .spacing(10)
+        .into()
     }
 
     fn view(&self) -> Element<Message> {
-        let display = text(&self.display)
+        let expression = text(self.engine.expression())
+            .size(20)
+            .horizontal_alignment(iced::alignment::Horizontal::Right)
+            .width(Fill);
+
+        let display = text(self.engine.display())
             .size(64)
             .horizontal_alignment(iced::alignment::Horizontal::Right)
             .width(Fill);
 
-        let button = |label: &str, msg: Message| {
+        let memory = text(if self.engine.memory().is_some() { "M" } else { "" }).size(20);
+
+        let key = |label: &str, msg: Option<Message>| {
             button(
-                text(label)
+                text(label.to_owned())
                     .size(40)
                     .center()
                     .width(Fill)
@@ -138,65 +349,111 @@ impl Calculator {
             )
             .width(80)
             .height(80)
-            .on_press(msg)
+            .on_press_maybe(msg)
         };
+        let button = |label: &str, msg: Message| key(label, Some(msg));
+
+        // Digits the current radix doesn't have are greyed out
+        let programmer = self.engine.mode() == Mode::Programmer;
+        let radix = if programmer { self.engine.radix() } else { 10 };
+        let decimal_point = self.engine.number_format().decimal_point.to_string();
+        let digit = |d: u8| key(&d.to_string(), Some(Message::Digit(d)).filter(|_| u32::from(d) < radix));
+
+        let modes = Mode::ALL.iter().map(|&mode| -> Element<Message> {
+            iced::widget::button(text(mode.label()).size(18).center().width(Fill))
+                .width(Fill)
+                .on_press(Message::SetMode(mode))
+                .style(if mode == self.engine.mode() { button::primary } else { button::secondary })
+                .into()
+        });
 
         let calc_grid = column![
             // Display
-            container(display)
+            container(column![row![memory, expression], display])
                 .width(Fill)
                 .padding(20)
                 .style(container::rounded_box),
 
+            // Memory
+            row![
+                button("MC", Message::Memory(Memory::Clear)),
+                button("MR", Message::Memory(Memory::Recall)),
+                button("M+", Message::Memory(Memory::Add)),
+                button("M−", Message::Memory(Memory::Subtract)),
+            ]
+            .spacing(10),
+
+            // Parentheses and editing
+            row![
+                button("(", Message::OpenParen),
+                button(")", Message::CloseParen),
+                button("CE", Message::ClearEntry),
+                button("⌫", Message::Backspace),
+            ]
+            .spacing(10),
+
             // Row 1
             row![
                 button("C", Message::Clear).style(button::danger),
-                button("±", Message::Digit(0)), // placeholder, not implemented
-                button("％", Message::Digit(0)), // placeholder
+                button("±", Message::Negate),
+                key("％", Some(Message::Percent).filter(|_| !programmer)),
                 button("÷", Message::Operator(Operator::Divide)).style(button::primary),
             ]
             .spacing(10),
 
             // Row 2
             row![
-                button("7", Message::Digit(7)),
-                button("8", Message::Digit(8)),
-                button("9", Message::Digit(9)),
+                digit(7),
+                digit(8),
+                digit(9),
                 button("×", Message::Operator(Operator::Multiply)).style(button::primary),
             ]
             .spacing(10),
 
             // Row 3
             row![
-                button("4", Message::Digit(4)),
-                button("5", Message::Digit(5)),
-                button("6", Message::Digit(6)),
+                digit(4),
+                digit(5),
+                digit(6),
                 button("−", Message::Operator(Operator::Subtract)).style(button::primary),
             ]
             .spacing(10),
 
             // Row 4
             row![
-                button("1", Message::Digit(1)),
-                button("2", Message::Digit(2)),
-                button("3", Message::Digit(3)),
+                digit(1),
+                digit(2),
+                digit(3),
                 button("+", Message::Operator(Operator::Add)).style(button::primary),
             ]
             .spacing(10),
 
             // Row 5
             row![
-                button("0", Message::Digit(0)).width(170),
-                button(".", Message::Decimal),
+                digit(0).width(170),
+                key(&decimal_point, Some(Message::Decimal).filter(|_| !programmer)),
                 button("=", Message::Equals).style(button::success),
             ]
             .spacing(10),
         ]
         .spacing(10)
+        .align_x(Center);
+
+        let panel = match self.engine.mode() {
+            Mode::Standard => None,
+            Mode::Scientific => Some(self.scientific_panel()),
+            Mode::Programmer => Some(self.programmer_panel()),
+        };
+
+        let body = column![
+            row(modes).spacing(10),
+            row![calc_grid].push_maybe(panel).push(self.history_panel()).spacing(20),
+        ]
+        .spacing(10)
         .padding(20)
         .align_x(Center);
 
-        container(Column::with_children(vec![calc_grid.into()]))
+        container(Column::with_children(vec![body.into()]))
             .width(Fill)
             .height(Fill)
             .center_x(Fill)
@@ -208,6 +465,7 @@ impl Calculator {
 fn main() -> iced::Result {
     application("Iced Calculator 🧮", Calculator::update, Calculator::view)
         .theme(|_| Theme::Dark)
-        .window_size(iced::Size::new(400.0, 600.0))
-        .run_with(|| (Calculator::default(), iced::task::nothing()))
+        .subscription(Calculator::subscription)
+        .window_size(iced::Size::new(1040.0, 880.0))
+        .run_with(|| (Calculator::new(), iced::task::nothing()))
 }
@@ -1,119 +1,12 @@
 use raylib_ffi::*;
 use raylib_ffi::colors::*;
 
+use calc_engine::{Calculator, Key};
+
 // Mouse button constants (from raylib.h)
 const MOUSE_BUTTON_LEFT: i32 = 0;
 const MOUSE_BUTTON_RIGHT: i32 = 1;
 
-struct Calculator {
-    display: String,
-    current_value: f64,
-    previous_value: f64,
-    operator: char,
-    should_reset_display: bool,
-}
-
-impl Calculator {
-    fn new() -> Self {
-        Self {
-            display: String::from("0"),
-            current_value: 0.0,
-            previous_value: 0.0,
-            operator: ' ',
-            should_reset_display: false,
-        }
-    }
-
-    fn append_number(&mut self, num: char) {
-        if self.should_reset_display {
-            self.display.clear();
-            self.should_reset_display = false;
-        }
-        
-        if self.display == "0" {
-            self.display = num.to_string();
-        } else {
-            self.display.push(num);
-        }
-    }
-
-    fn append_decimal(&mut self) {
-        if self.should_reset_display {
-            self.display = String::from("0");
-            self.should_reset_display = false;
-        }
-        
-        if !self.display.contains('.') {
-            self.display.push('.');
-        }
-    }
-
-    fn set_operator(&mut self, op: char) {
-        if self.operator != ' ' {
-            self.calculate();
-        }
-        
-        self.current_value = self.display.parse().unwrap_or(0.0);
-        self.operator = op;
-        self.should_reset_display = true;
-    }
-
-    fn calculate(&mut self) {
-        let current = self.display.parse().unwrap_or(0.0);
-        
-        match self.operator {
-            '+' => self.current_value += current,
-            '-' => self.current_value -= current,
-            '*' => self.current_value *= current,
-            '/' => {
-                if current != 0.0 {
-                    self.current_value /= current;
-                } else {
-                    self.display = String::from("Error");
-                    self.operator = ' ';
-                    return;
-                }
-            }
-            _ => self.current_value = current,
-        }
-
-        // Format the result
-        if self.current_value.fract() == 0.0 {
-            self.display = format!("{}", self.current_value as i64);
-        } else {
-            self.display = format!("{:.6}", self.current_value);
-            // Trim trailing zeros
-            if self.display.contains('.') {
-                while self.display.ends_with('0') {
-                    self.display.pop();
-                }
-                if self.display.ends_with('.') {
-                    self.display.pop();
-                }
-            }
-        }
-
-        self.operator = ' ';
-        self.should_reset_display = true;
-    }
-
-    fn clear(&mut self) {
-        self.display = String::from("0");
-        self.current_value = 0.0;
-        self.previous_value = 0.0;
-        self.operator = ' ';
-        self.should_reset_display = false;
-    }
-
-    fn delete(&mut self) {
-        if self.display.len() > 1 {
-            self.display.pop();
-        } else {
-            self.display = String::from("0");
-        }
-    }
-}
-
 fn main() {
     unsafe {
         // Initialize window
@@ -166,14 +59,13 @@ fn main() {
                        mouse_pos.y >= y as f32 && 
                        mouse_pos.y <= (y + button_height) as f32 {
                         
-                        match symbol {
-                            '0'..='9' => calculator.append_number(symbol),
-                            '.' => calculator.append_decimal(),
-                            '+' | '-' | '*' | '/' => calculator.set_operator(symbol),
-                            '=' => calculator.calculate(),
-                            'C' => calculator.clear(),
-                            'D' => calculator.delete(),
-                            _ => {}
+                        let key = match symbol {
+                            'C' => Some(Key::Clear),
+                            'D' => Some(Key::Backspace),
+                            _ => Key::from_char(symbol),
+                        };
+                        if let Some(key) = key {
+                            calculator.press(key);
                         }
                     }
                 }
@@ -192,11 +84,11 @@ fn main() {
             DrawRectangleLines(20, 60, 340, 50, BLACK);
             
             // Draw display text (right-aligned)
-            let display_text = if calculator.display.len() > 15 {
+            let display = calculator.display();
+            let display_text = match display.char_indices().rev().nth(14) {
                 // Truncate very long numbers
-                format!("{}\0", &calculator.display[calculator.display.len()-15..])
-            } else {
-                format!("{}\0", calculator.display)
+                Some((start, _)) => format!("{}\0", &display[start..]),
+                None => format!("{}\0", display),
             };
             
             let text_width = MeasureText(display_text.as_ptr() as *const i8, 20);
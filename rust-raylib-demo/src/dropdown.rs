@@ -1,20 +1,41 @@
 use raylib_ffi::*;
 use raylib_ffi::colors::*;
+use fuzzy_match::{Filter, Matcher, Query};
+use std::cell::RefCell;
 use std::ffi::CString;
+use std::rc::Rc;
+
+#[path = "lib/reactivity.rs"]
+mod reactivity;
+#[path = "lib/text.rs"]
+mod text;
+#[path = "lib/tree.rs"]
+mod tree;
+
+use reactivity::{Computed, Signal};
+use tree::{Align, Node, Size, Tree};
 
-const MOUSE_BUTTON_LEFT: i32 = 0;
 const KEY_ESCAPE: i32 = 256;
 const KEY_BACKSPACE: i32 = 259;
 
+const MAX_VISIBLE_ITEMS: usize = 8;
 
-struct FuzzyDropdown {
-    items: Vec<String>,
-    filtered_items: Vec<(String, usize)>, // (item, original_index)
+#[derive(Clone)]
+struct DropdownState {
+    filtered_items: Rc<Vec<(String, usize)>>, // (item, original_index)
     search_text: String,
     is_open: bool,
     selected_index: Option<usize>,
-    hovered_index: Option<usize>,
-    item_offset: i32, // scroll offset in item units (number of items), not pixels
+    hovered_row: Option<usize>, // visible row under the mouse
+    item_offset: usize, // scroll offset in item units (number of items), not pixels
+}
+
+struct FuzzyDropdown {
+    items: Rc<Vec<String>>,
+    state: Signal<DropdownState>,
+    filter: RefCell<Filter>,
+    // Finds what to highlight in the visible rows
+    matcher: Rc<RefCell<Matcher>>,
 }
 
 impl FuzzyDropdown {
@@ -26,108 +47,159 @@ impl FuzzyDropdown {
             .collect();
 
         Self {
-            items,
-            filtered_items: filtered,
-            search_text: String::new(),
-            is_open: false,
-            selected_index: None,
-            hovered_index: None,
-            item_offset: 0,
+            items: Rc::new(items),
+            state: Signal::new(DropdownState {
+                filtered_items: Rc::new(filtered),
+                search_text: String::new(),
+                is_open: false,
+                selected_index: None,
+                hovered_row: None,
+                item_offset: 0,
+            }),
+            filter: RefCell::new(Filter::new()),
+            matcher: Rc::new(RefCell::new(Matcher::new())),
         }
     }
 
-    fn fuzzy_match(query: &str, target: &str) -> Option<i32> {
-        if query.is_empty() {
-            return Some(0);
-        }
-
-        let query_lower = query.to_lowercase();
-        let target_lower = target.to_lowercase();
-
-        let mut query_chars = query_lower.chars().peekable();
-        let mut score = 0;
-        let mut last_match_index = 0;
-        let mut consecutive_matches = 0;
-
-        for (i, target_char) in target_lower.chars().enumerate() {
-            if let Some(&query_char) = query_chars.peek() {
-                if query_char == target_char {
-                    query_chars.next();
-
-                    // Bonus for consecutive matches
-                    if i == last_match_index + 1 {
-                        consecutive_matches += 1;
-                        score += 5 + consecutive_matches;
-                    } else {
-                        consecutive_matches = 0;
-                        score += 1;
-                    }
-
-                    // Bonus for matching at word start
-                    if i == 0 || target_lower.chars().nth(i - 1).unwrap().is_whitespace() {
-                        score += 10;
-                    }
-
-                    last_match_index = i;
-                }
-            } else {
-                break;
-            }
-        }
+    /// Matching items, best first
+    fn filter(&self, search_text: &str) -> Vec<(String, usize)> {
+        let matches = self.filter.borrow_mut().search(&self.items, search_text);
+        matches.into_iter().map(|m| (self.items[m.index].clone(), m.index)).collect()
+    }
 
-        if query_chars.peek().is_none() {
-            Some(score)
-        } else {
-            None
-        }
+    fn edit_search(&self, edit: impl FnOnce(&mut String)) {
+        self.state.update(|state| {
+            edit(&mut state.search_text);
+            state.filtered_items = Rc::new(self.filter(&state.search_text));
+            state.item_offset = 0;
+            state.hovered_row = None;
+        });
     }
 
-    fn update_filter(&mut self) {
-        // Build a scored list, sort, then strip scores
-        let mut scored: Vec<(String, usize, i32)> = self
-            .items
-            .iter()
-            .enumerate()
-            .filter_map(|(i, item)| Self::fuzzy_match(&self.search_text, item).map(|score| (item.clone(), i, score)))
-            .collect();
+    fn add_char(&self, c: char) {
+        self.edit_search(|search| search.push(c));
+    }
 
-        scored.sort_by(|a, b| b.2.cmp(&a.2)); // descending by score
+    fn backspace(&self) {
+        self.edit_search(|search| {
+            search.pop();
+        });
+    }
 
-        self.filtered_items = scored.into_iter().map(|(item, idx, _)| (item, idx)).collect();
+    fn is_open(&self) -> bool {
+        self.state.get().is_open
+    }
 
-        self.item_offset = 0;
-        self.hovered_index = None;
+    fn toggle(&self) {
+        let open = !self.is_open();
+        self.edit_search(|search| search.clear());
+        self.state.update(|state| state.is_open = open);
     }
 
-    fn add_char(&mut self, c: char) {
-        self.search_text.push(c);
-        self.update_filter();
+    fn close(&self) {
+        if self.is_open() {
+            self.toggle();
+        }
     }
 
-    fn backspace(&mut self) {
-        self.search_text.pop();
-        self.update_filter();
+    fn select_row(&self, row: usize) {
+        let state = self.state.get();
+        if let Some(&(_, index)) = state.filtered_items.get(state.item_offset + row) {
+            self.state.update(|state| state.selected_index = Some(index));
+            self.close();
+        }
     }
 
-    fn clear_search(&mut self) {
-        self.search_text.clear();
-        self.update_filter();
+    fn hover_row(&self, row: usize, entered: bool) {
+        self.state.update(|state| {
+            if entered {
+                state.hovered_row = Some(row);
+            } else if state.hovered_row == Some(row) {
+                state.hovered_row = None;
+            }
+        });
     }
 
-    fn select_item(&mut self, filtered_index: usize) {
-        if filtered_index < self.filtered_items.len() {
-            self.selected_index = Some(self.filtered_items[filtered_index].1);
-            self.is_open = false;
-            self.clear_search();
-        }
+    fn scroll(&self, wheel: f32) {
+        self.state.update(|state| {
+            let max_offset = state.filtered_items.len().saturating_sub(MAX_VISIBLE_ITEMS) as i32;
+            // Scroll by whole items per wheel notch
+            let offset = state.item_offset as i32 - wheel.round() as i32;
+            state.item_offset = offset.clamp(0, max_offset) as usize;
+        });
     }
 
-    fn get_selected_text(&self) -> String {
-        self.selected_index
-            .and_then(|i| self.items.get(i))
+    fn get_selected_text(items: &[String], selected_index: Option<usize>) -> String {
+        selected_index
+            .and_then(|i| items.get(i))
             .cloned()
             .unwrap_or_else(|| "Select an item...".to_string())
     }
+
+    /// Header showing the selection or the search, and the list below it
+    fn view(self: &Rc<Self>, width: f32, height: f32, item_height: f32) -> Node {
+        let items = self.items.clone();
+        let header_text = Computed::new(&self.state, move |state| {
+            if state.is_open && !state.search_text.is_empty() {
+                state.search_text
+            } else {
+                Self::get_selected_text(&items, state.selected_index)
+            }
+        });
+        let toggle = self.clone();
+        let header = Node::row()
+            .width(Size::Px(width))
+            .height(Size::Px(height))
+            .padding(10.0)
+            .background(Computed::new(&self.state, |state| state.is_open).map(|open| if open { SKYBLUE } else { LIGHTGRAY }))
+            .border(DARKGRAY)
+            .on_click(move || toggle.toggle())
+            .child(Node::text(header_text).width(Size::Grow(1.0)).ellipsis())
+            // ASCII-safe arrow
+            .child(Node::text(Computed::new(&self.state, |state| if state.is_open { "^" } else { "v" }.to_string())).color(DARKGRAY));
+
+        let rows = (0..MAX_VISIBLE_ITEMS).map(|row| {
+            let (hover, select) = (self.clone(), self.clone());
+            let matcher = self.matcher.clone();
+            let matched = Computed::new(&self.state, move |state| {
+                let Some((item, _)) = state.filtered_items.get(state.item_offset + row) else { return Vec::new() };
+                let query = Query::new(&state.search_text);
+                matcher.borrow_mut().indices(&query, item).map(|(_, chars)| chars).unwrap_or_default()
+            });
+            Node::text(Computed::new(&self.state, move |state| {
+                state.filtered_items.get(state.item_offset + row).map(|item| item.0.clone()).unwrap_or_default()
+            }))
+            .highlight(DARKBLUE, matched)
+            .ellipsis()
+            .height(Size::Px(item_height))
+            .padding_xy(10.0, 0.0)
+            .align(Align::Center)
+            .font_size(18)
+            .background(Computed::new(&self.state, move |state| state.hovered_row == Some(row)).map(|hovered| if hovered { SKYBLUE } else { WHITE }))
+            .visible(Computed::new(&self.state, move |state| state.item_offset + row < state.filtered_items.len()))
+            .on_hover(move |entered| hover.hover_row(row, entered))
+            .on_click(move || select.select_row(row))
+        });
+
+        // Search hint (ASCII only)
+        let hint = Computed::new(&self.state, |state| {
+            if state.search_text.is_empty() {
+                format!("Type to search... ({} items)", state.filtered_items.len())
+            } else {
+                format!("Found {} items", state.filtered_items.len())
+            }
+        });
+        let hint_color = Computed::new(&self.state, |state| state.search_text.is_empty()).map(|empty| if empty { GRAY } else { DARKGREEN });
+
+        let list = Node::column()
+            .absolute(0.0, height + 5.0)
+            .width(Size::Px(width))
+            .visible(Computed::new(&self.state, |state| state.is_open))
+            .child(Node::column().background(WHITE).border(DARKGRAY).children(rows))
+            .child(Node::text(hint).font_size(14).color(hint_color).padding_xy(10.0, 10.0));
+
+        Node::column().child(header).child(list)
+    }
 }
 
 fn main() {
@@ -159,203 +231,80 @@ fn main() {
             "Watermelon".to_string(),
             "Blueberry".to_string(),
             "Blackberry".to_string(),
+            "Açaí".to_string(),
+            "Dragon Fruit, also called Pitaya or Strawberry Pear".to_string(),
         ];
 
-        let mut dropdown = FuzzyDropdown::new(items);
-
-        let dropdown_x = 50;
-        let dropdown_y = 100;
-        let dropdown_width = 400;
-        let dropdown_height = 40;
-        let max_visible_items = 8;
-        let item_height = 35;
-
-        // Pre-create static texts
-        let title_text = CString::new("Fuzzy Search Dropdown Demo").unwrap();
-        let subtitle_text = CString::new("Click to open, type to filter").unwrap();
-
-        while !WindowShouldClose() {
-            // Handle mouse input
-            if IsMouseButtonPressed(MOUSE_BUTTON_LEFT) {
-                let mouse_pos = GetMousePosition();
-                let mx = mouse_pos.x as i32;
-                let my = mouse_pos.y as i32;
-
-                // Header toggle
-                if mx >= dropdown_x
-                    && mx <= dropdown_x + dropdown_width
-                    && my >= dropdown_y
-                    && my <= dropdown_y + dropdown_height
-                {
-                    dropdown.is_open = !dropdown.is_open;
-                    if !dropdown.is_open {
-                        dropdown.clear_search();
-                    }
-                } else if dropdown.is_open {
-                    // Click in list
-                    let list_y = dropdown_y + dropdown_height + 5;
-                    let total_items = dropdown.filtered_items.len() as i32;
-                    let visible_items = total_items.min(max_visible_items as i32);
-                    let list_height = visible_items * item_height;
-
-                    // Determine clicked visible index
-                    if mx >= dropdown_x && mx <= dropdown_x + dropdown_width && my >= list_y && my <= list_y + list_height {
-                        let relative_y = my - list_y;
-                        let vis_index = relative_y / item_height;
-                        let actual_index = vis_index + dropdown.item_offset;
-                        if actual_index >= 0 && (actual_index as usize) < dropdown.filtered_items.len() {
-                            dropdown.select_item(actual_index as usize);
-                        }
-                    } else {
-                        // Click outside: close
-                        dropdown.is_open = false;
-                        dropdown.clear_search();
-                    }
-                }
-            }
-
-            // Handle mouse hover
-            if dropdown.is_open {
-                let mouse_pos = GetMousePosition();
-                let mx = mouse_pos.x as i32;
-                let my = mouse_pos.y as i32;
-                let list_y = dropdown_y + dropdown_height + 5;
-
-                dropdown.hovered_index = None;
-
-                let total_items = dropdown.filtered_items.len() as i32;
-                let visible_items = total_items.min(max_visible_items as i32);
-                let list_height = visible_items * item_height;
-
-                if mx >= dropdown_x && mx <= dropdown_x + dropdown_width && my >= list_y && my <= list_y + list_height {
-                    let relative_y = my - list_y;
-                    let vis_index = relative_y / item_height;
-                    let actual_index = vis_index + dropdown.item_offset;
-                    if actual_index >= 0 && (actual_index as usize) < dropdown.filtered_items.len() {
-                        dropdown.hovered_index = Some(actual_index as usize);
-                    }
+        let dropdown = Rc::new(FuzzyDropdown::new(items));
+
+        // A TTF to use instead of the built-in font: `cargo run --bin dropdown -- path/to/font.ttf`
+        let font = std::env::args().nth(1).and_then(|path| text::load_font(&path, 48));
+
+        let dropdown_width = 400.0;
+        let dropdown_height = 40.0;
+        let item_height = 35.0;
+
+        // Selected item info
+        let items = dropdown.items.clone();
+        let info = Computed::new(&dropdown.state, move |state| match state.selected_index {
+            Some(idx) => format!("Selected: {} (index: {})", items[idx], idx),
+            None => String::new(),
+        });
+
+        // Clicks that reach the background close the list, the wheel scrolls it
+        let (close, scroll) = (dropdown.clone(), dropdown.clone());
+        let root = Node::column()
+            .font(font.unwrap_or_else(text::default_font))
+            .padding_xy(50.0, 30.0)
+            .gap(7.0)
+            .on_click(move || close.close())
+            .on_scroll(move |wheel| {
+                if scroll.is_open() {
+                    scroll.scroll(wheel);
                 }
-            }
+            })
+            .child(Node::text("Fuzzy Search Dropdown Demo").font_size(28).color(DARKGRAY))
+            .child(Node::text("Click to open, type to filter").font_size(16).color(GRAY))
+            .child(Node::column().height(Size::Px(5.0)))
+            .child(dropdown.view(dropdown_width, dropdown_height, item_height))
+            .child(Node::text(info).font_size(18).color(DARKPURPLE).absolute(0.0, 470.0));
+        let mut ui = Tree::new(root, 500, 600, RAYWHITE);
 
+        while !WindowShouldClose() {
             // Handle keyboard input
-            if dropdown.is_open {
-                let key = GetCharPressed();
-                if key > 0 {
-                    if let Some(c) = char::from_u32(key as u32) {
-                        if c.is_alphanumeric() || c.is_whitespace() {
-                            dropdown.add_char(c);
-                        }
+            if dropdown.is_open() {
+                loop {
+                    let key = GetCharPressed();
+                    if key <= 0 {
+                        break;
+                    }
+                    let c = char::from_u32(key as u32).filter(|c| c.is_alphanumeric() || c.is_whitespace());
+                    if let Some(c) = c {
+                        dropdown.add_char(c);
                     }
                 }
 
-                if IsKeyPressed(KEY_BACKSPACE as i32) {
+                if IsKeyPressed(KEY_BACKSPACE) {
                     dropdown.backspace();
                 }
 
-                if IsKeyPressed(KEY_ESCAPE as i32) {
-                    dropdown.is_open = false;
-                    dropdown.clear_search();
-                }
-
-                // Handle scroll (item-based)
-                let wheel = GetMouseWheelMove();
-                if wheel != 0.0 {
-                    let total_items = dropdown.filtered_items.len() as i32;
-                    let max_offset = (total_items - max_visible_items as i32).max(0);
-                    // Scroll by whole items per wheel notch
-                    dropdown.item_offset -= wheel.round() as i32;
-                    if dropdown.item_offset < 0 {
-                        dropdown.item_offset = 0;
-                    } else if dropdown.item_offset > max_offset {
-                        dropdown.item_offset = max_offset;
-                    }
+                if IsKeyPressed(KEY_ESCAPE) {
+                    dropdown.close();
                 }
             }
 
+            ui.update();
+
             BeginDrawing();
             ClearBackground(RAYWHITE);
-
-            // Draw title
-            DrawText(title_text.as_ptr(), 50, 30, 28, DARKGRAY);
-            DrawText(subtitle_text.as_ptr(), 50, 65, 16, GRAY);
-
-            // Draw dropdown header
-            let header_color = if dropdown.is_open { SKYBLUE } else { LIGHTGRAY };
-            DrawRectangle(dropdown_x, dropdown_y, dropdown_width, dropdown_height, header_color);
-            DrawRectangleLines(dropdown_x, dropdown_y, dropdown_width, dropdown_height, DARKGRAY);
-
-            // Draw selected text or search text (CString each frame; lifetime covers the call)
-            let display_text_str = if dropdown.is_open && !dropdown.search_text.is_empty() {
-                dropdown.search_text.clone()
-            } else {
-                dropdown.get_selected_text()
-            };
-            let display_text = CString::new(display_text_str).unwrap();
-            DrawText(display_text.as_ptr(), dropdown_x + 10, dropdown_y + 10, 20, BLACK);
-
-            // Draw dropdown arrow (ASCII-safe)
-            let arrow_char = if dropdown.is_open { "^" } else { "v" };
-            let arrow = CString::new(arrow_char).unwrap();
-            DrawText(
-                arrow.as_ptr(),
-                dropdown_x + dropdown_width - 30,
-                dropdown_y + 10,
-                20,
-                DARKGRAY,
-            );
-
-            // Draw dropdown list
-            if dropdown.is_open {
-                let list_y = dropdown_y + dropdown_height + 5;
-                let total_items = dropdown.filtered_items.len() as i32;
-                let visible_items = total_items.min(max_visible_items as i32);
-                let list_height = visible_items * item_height;
-
-                DrawRectangle(dropdown_x, list_y, dropdown_width, list_height, WHITE);
-                DrawRectangleLines(dropdown_x, list_y, dropdown_width, list_height, DARKGRAY);
-
-                // Draw visible items with correct offset
-                for vis_i in 0..visible_items {
-                    let actual_index = (vis_i + dropdown.item_offset) as usize;
-                    if actual_index >= dropdown.filtered_items.len() {
-                        break;
-                    }
-
-                    let item_y = list_y + (vis_i * item_height);
-
-                    let is_hovered = dropdown.hovered_index == Some(actual_index);
-                    if is_hovered {
-                        DrawRectangle(dropdown_x, item_y, dropdown_width, item_height, SKYBLUE);
-                    }
-
-                    let item_text = CString::new(dropdown.filtered_items[actual_index].0.clone()).unwrap();
-                    DrawText(item_text.as_ptr(), dropdown_x + 10, item_y + 8, 18, BLACK);
-                }
-
-                // Draw search hint (ensure ASCII)
-                if dropdown.search_text.is_empty() {
-                    let hint = CString::new(format!(
-                        "Type to search... ({} items)",
-                        dropdown.filtered_items.len()
-                    ))
-                    .unwrap();
-                    DrawText(hint.as_ptr(), dropdown_x + 10, list_y + list_height + 10, 14, GRAY);
-                } else {
-                    let hint = CString::new(format!("Found {} items", dropdown.filtered_items.len())).unwrap();
-                    DrawText(hint.as_ptr(), dropdown_x + 10, list_y + list_height + 10, 14, DARKGREEN);
-                }
-            }
-
-            // Draw selected item info
-            if let Some(idx) = dropdown.selected_index {
-                let info = CString::new(format!("Selected: {} (index: {})", dropdown.items[idx], idx)).unwrap();
-                DrawText(info.as_ptr(), 50, 500, 18, DARKPURPLE);
-            }
-
+            ui.draw(0.0, 0.0);
             EndDrawing();
         }
 
+        drop(ui);
+        if let Some(font) = font {
+            UnloadFont(font);
+        }
         CloseWindow();
     }
 }
-
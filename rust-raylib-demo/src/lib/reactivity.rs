@@ -1,14 +1,26 @@
+// Shared by several binaries, each using part of it
+#![allow(dead_code)]
+
 use std::cell::RefCell;
 use std::rc::{Rc, Weak};
 
 // EffectFn is a boxed callable
-type EffectFn = Rc<dyn Fn()>;
+pub(crate) type EffectFn = Rc<dyn Fn()>;
 
 pub struct Signal<T> {
     value: Rc<RefCell<T>>,
     subscribers: Rc<RefCell<Vec<Weak<RefCell<EffectFn>>>>>,
 }
 
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            subscribers: self.subscribers.clone(),
+        }
+    }
+}
+
 impl<T: Clone> Signal<T> {
     pub fn new(value: T) -> Self {
         Self {
@@ -21,53 +33,106 @@ impl<T: Clone> Signal<T> {
         self.value.borrow().clone()
     }
 
-    pub fn set(&self, value: T) {
+    // Store `value` and notify, whether or not it changed
+    fn replace(&self, value: T) {
         *self.value.borrow_mut() = value;
-        let mut subs = self.subscribers.borrow_mut();
-        subs.retain(|weak_cb| {
-            if let Some(cb) = weak_cb.upgrade() {
-                cb.borrow()();
-                true
-            } else {
-                false
-            }
-        });
+        self.notify();
+    }
+
+    /// `f` of this value, recomputed after every change
+    pub fn map<V: Clone + 'static>(&self, f: impl Fn(T) -> V + 'static) -> Computed<V>
+    where
+        T: 'static,
+    {
+        Computed::follow(self, f, Signal::replace)
     }
 
+    /// Change the value in place, then notify once
+    pub fn update(&self, change: impl FnOnce(&mut T)) {
+        change(&mut self.value.borrow_mut());
+        self.notify();
+    }
+
+    fn notify(&self) {
+        // Snapshot first, so an effect may subscribe or set other signals
+        let subs: Vec<_> = self.subscribers.borrow().iter().filter_map(Weak::upgrade).collect();
+        self.subscribers.borrow_mut().retain(|weak_cb| weak_cb.strong_count() > 0);
+        for cb in subs {
+            let cb = cb.borrow().clone();
+            cb();
+        }
+    }
+
+    /// Run `effect` after every change for as long as the caller keeps
+    /// it alive
     pub(crate) fn subscribe(&self, effect: &Rc<RefCell<EffectFn>>) {
         self.subscribers.borrow_mut().push(Rc::downgrade(effect));
     }
 }
 
+impl<T: Clone + PartialEq> Signal<T> {
+    /// Store `value`; subscribers hear of it only if it differs
+    pub fn set(&self, value: T) {
+        if *self.value.borrow() != value {
+            self.replace(value);
+        }
+    }
+}
+
 pub struct Computed<T> {
-    value: Rc<RefCell<T>>,
+    value: Signal<T>,
+    // The source only holds it weakly
+    effect: Rc<RefCell<EffectFn>>,
+}
+
+impl<T> Clone for Computed<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            effect: self.effect.clone(),
+        }
+    }
 }
 
 impl<T: Clone + 'static> Computed<T> {
-    pub fn new<U: Clone + 'static, F>(source: &Signal<U>, compute: F) -> Self
-    where
-        F: Fn(U) -> T + 'static,
-    {
-        let initial = compute(source.get());
-        let value = Rc::new(RefCell::new(initial));
+    // Recompute from `source` after each of its changes and hand the
+    // result to `store`
+    fn follow<U: Clone + 'static>(source: &Signal<U>, compute: impl Fn(U) -> T + 'static, store: fn(&Signal<T>, T)) -> Self {
+        let value = Signal::new(compute(source.get()));
 
         let value_clone = value.clone();
-        let compute_clone = Rc::new(compute);
-        // 🟢 Wrap closure in Rc<dyn Fn()>
-        let closure: Rc<dyn Fn()> = Rc::new(move || {
-            let new_val = compute_clone(source.get());
-            *value_clone.borrow_mut() = new_val;
-        });
+        let source_clone = source.clone();
+        let closure: EffectFn = Rc::new(move || store(&value_clone, compute(source_clone.get())));
+        let effect = Rc::new(RefCell::new(closure));
 
-        // 🟢 Now RefCell holds Rc<dyn Fn()>
-        let effect_fn = Rc::new(RefCell::new(closure));
+        source.subscribe(&effect);
 
-        source.subscribe(&effect_fn);
+        Self { value, effect }
+    }
 
-        Self { value }
+    /// `f` of this value, recomputed only when this value changes, so the
+    /// result needn't be comparable (raylib's `Color` isn't)
+    pub fn map<V: Clone + 'static>(&self, f: impl Fn(T) -> V + 'static) -> Computed<V> {
+        Computed::follow(&self.value, f, Signal::replace)
     }
 
     pub fn get(&self) -> T {
-        self.value.borrow().clone()
+        self.value.get()
+    }
+
+    /// Run `effect` after every change of the computed value
+    pub(crate) fn subscribe(&self, effect: &Rc<RefCell<EffectFn>>) {
+        self.value.subscribe(effect);
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Computed<T> {
+    /// `compute` of the source, rerun on each of its changes; subscribers
+    /// hear only of results that differ from the last
+    pub fn new<U: Clone + 'static, F>(source: &Signal<U>, compute: F) -> Self
+    where
+        F: Fn(U) -> T + 'static,
+    {
+        Self::follow(source, compute, Signal::set)
     }
 }
@@ -0,0 +1,82 @@
+//! Fonts for the tree: raylib's built-in one or a loaded TTF, measured
+//! glyph by glyph for the `text-layout` crate, so wrapped, centered and
+//! cut-off text lines up with what `DrawTextEx` draws.
+
+// Shared by several binaries, each using part of it
+#![allow(dead_code)]
+
+use raylib_ffi::*;
+use std::ffi::CString;
+use text_layout::Metrics;
+
+const TEXTURE_FILTER_BILINEAR: i32 = 1;
+
+/// Printable ASCII, Latin-1, Latin Extended-A, and the dashes, quotes
+/// and ellipsis of General Punctuation
+fn codepoints() -> Vec<i32> {
+    (32..=126).chain(160..=383).chain(0x2010..=0x2027).collect()
+}
+
+/// The TTF or OTF at `path` rasterized at `size` pixels, drawn smoothly
+/// at other sizes; `None` if it can't be loaded
+pub fn load_font(path: &str, size: i32) -> Option<Font> {
+    let path = CString::new(path).ok()?;
+    let mut codepoints = codepoints();
+    unsafe {
+        let font = LoadFontEx(path.as_ptr(), size, codepoints.as_mut_ptr(), codepoints.len() as i32);
+        if !IsFontValid(font) {
+            return None;
+        }
+        SetTextureFilter(font.texture, TEXTURE_FILTER_BILINEAR);
+        Some(font)
+    }
+}
+
+pub fn default_font() -> Font {
+    unsafe { GetFontDefault() }
+}
+
+/// A font at a size, spaced the way `DrawText` spaces the built-in font
+#[derive(Copy, Clone)]
+pub struct FontMetrics {
+    pub font: Font,
+    pub size: f32,
+    pub spacing: f32,
+}
+
+impl FontMetrics {
+    pub fn new(font: Font, size: i32) -> Self {
+        Self { font, size: size as f32, spacing: (size / 10) as f32 }
+    }
+}
+
+impl Metrics for FontMetrics {
+    fn advance(&self, c: char) -> f32 {
+        let font = &self.font;
+        if font.glyphs.is_null() || font.baseSize == 0 {
+            return 0.0;
+        }
+        unsafe {
+            // Glyphs the font lacks come back as '?'
+            let index = GetGlyphIndex(*font, c as i32) as usize;
+            let glyph = *font.glyphs.add(index);
+            let width = if glyph.advanceX != 0 { glyph.advanceX as f32 } else { (*font.recs.add(index)).width };
+            width * self.size / font.baseSize as f32
+        }
+    }
+
+    fn spacing(&self) -> f32 {
+        self.spacing
+    }
+
+    fn line_height(&self) -> f32 {
+        self.size * 1.25
+    }
+}
+
+/// One line of `text` with its top left at `x`, `y`
+pub fn draw(metrics: &FontMetrics, text: &str, x: f32, y: f32, color: Color) {
+    let text = CString::new(text).unwrap_or_default();
+    let position = Vector2 { x, y };
+    unsafe { DrawTextEx(metrics.font, text.as_ptr(), position, metrics.size, metrics.spacing, color) };
+}
@@ -0,0 +1,860 @@
+//! Retained-mode UI: a tree of nodes built once, whose properties can be
+//! bound to a `Signal` or `Computed` instead of a fixed value.
+//!
+//! When a bound value changes, only that node is invalidated: a colour
+//! change repaints its rectangle, a text or visibility change re-measures
+//! it and its ancestors and re-lays out the subtrees whose rectangles
+//! moved. The tree paints into a render texture, and only the damaged part
+//! of it, so a frame where nothing changed costs one texture blit.
+//!
+//! Layout is a small flexbox: a node lays its children out in a row or a
+//! column with a gap, sized `Auto` (fit the content), `Px` or `Grow`
+//! (share the free space), aligned on the cross axis and justified on the
+//! main one. `absolute` nodes are taken out of the flow and drawn on top.
+
+// Shared by several binaries, each using part of it
+#![allow(dead_code)]
+
+use super::reactivity::{Computed, EffectFn, Signal};
+use super::text::{self, FontMetrics};
+use raylib_ffi::colors::*;
+use raylib_ffi::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use text_layout::{Align as TextAlign, Metrics, ELLIPSIS};
+
+// ============== PROPERTIES ==============
+
+/// A node property: a fixed value, or one that follows a signal
+pub enum Prop<T> {
+    Fixed(T),
+    Signal(Signal<T>),
+    Computed(Computed<T>),
+}
+
+impl<T: Clone + 'static> Prop<T> {
+    pub fn get(&self) -> T {
+        match self {
+            Prop::Fixed(value) => value.clone(),
+            Prop::Signal(signal) => signal.get(),
+            Prop::Computed(computed) => computed.get(),
+        }
+    }
+
+    fn subscribe(&self, effect: &Rc<RefCell<EffectFn>>) {
+        match self {
+            Prop::Fixed(_) => {}
+            Prop::Signal(signal) => signal.subscribe(effect),
+            Prop::Computed(computed) => computed.subscribe(effect),
+        }
+    }
+}
+
+impl<T> From<T> for Prop<T> {
+    fn from(value: T) -> Self {
+        Prop::Fixed(value)
+    }
+}
+
+impl From<&str> for Prop<String> {
+    fn from(value: &str) -> Self {
+        Prop::Fixed(value.to_string())
+    }
+}
+
+impl<T> From<Signal<T>> for Prop<T> {
+    fn from(signal: Signal<T>) -> Self {
+        Prop::Signal(signal)
+    }
+}
+
+impl<T> From<&Signal<T>> for Prop<T> {
+    fn from(signal: &Signal<T>) -> Self {
+        Prop::Signal(signal.clone())
+    }
+}
+
+impl<T> From<Computed<T>> for Prop<T> {
+    fn from(computed: Computed<T>) -> Self {
+        Prop::Computed(computed)
+    }
+}
+
+// ============== STYLE ==============
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Size {
+    /// Fit the content
+    Auto,
+    Px(f32),
+    /// Content size plus this share of the free space on the main axis;
+    /// on the cross axis, fill it
+    Grow(f32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Row,
+    Column,
+}
+
+/// Where children sit across the main axis; also where a text node
+/// places its text down
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+    Stretch,
+}
+
+/// Where children sit along the main axis when there's room left over;
+/// also how a text node places its text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Justify {
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+}
+
+// ============== NODES ==============
+
+pub type Handler = Rc<dyn Fn()>;
+
+/// Description of a node and its children, turned into a live tree by
+/// `Tree::new`
+pub struct Node {
+    direction: Direction,
+    width: Size,
+    height: Size,
+    /// Horizontal and vertical
+    padding: (f32, f32),
+    gap: f32,
+    align: Align,
+    justify: Justify,
+    /// Offset from the parent's content box, outside the flow
+    absolute: Option<(f32, f32)>,
+    text: Option<Prop<String>>,
+    /// Inherited by descendants; the built-in font when nothing sets it
+    font: Option<Font>,
+    font_size: i32,
+    /// Wrap width, padding included
+    wrap: Option<f32>,
+    ellipsis: bool,
+    color: Prop<Color>,
+    /// Char indices of the text drawn in another colour
+    highlight: Option<(Prop<Color>, Prop<Vec<usize>>)>,
+    background: Option<Prop<Color>>,
+    border: Option<Prop<Color>>,
+    visible: Prop<bool>,
+    on_click: Option<Handler>,
+    on_hover: Option<Rc<dyn Fn(bool)>>,
+    on_scroll: Option<Rc<dyn Fn(f32)>>,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn new(direction: Direction) -> Self {
+        Self {
+            direction,
+            width: Size::Auto,
+            height: Size::Auto,
+            padding: (0.0, 0.0),
+            gap: 0.0,
+            align: Align::Stretch,
+            justify: Justify::Start,
+            absolute: None,
+            text: None,
+            font: None,
+            font_size: 20,
+            wrap: None,
+            ellipsis: false,
+            color: BLACK.into(),
+            highlight: None,
+            background: None,
+            border: None,
+            visible: true.into(),
+            on_click: None,
+            on_hover: None,
+            on_scroll: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Children left to right
+    pub fn row() -> Self {
+        Self::new(Direction::Row)
+    }
+
+    /// Children top to bottom
+    pub fn column() -> Self {
+        Self::new(Direction::Column)
+    }
+
+    /// Text on one line unless `wrap`ped; `justify` places it within the
+    /// node across, `align` down
+    pub fn text(text: impl Into<Prop<String>>) -> Self {
+        Self { text: Some(text.into()), align: Align::Start, ..Self::new(Direction::Row) }
+    }
+
+    pub fn width(mut self, width: Size) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Size) -> Self {
+        self.height = height;
+        self
+    }
+
+    pub fn padding(self, padding: f32) -> Self {
+        self.padding_xy(padding, padding)
+    }
+
+    pub fn padding_xy(mut self, x: f32, y: f32) -> Self {
+        self.padding = (x, y);
+        self
+    }
+
+    pub fn gap(mut self, gap: f32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn justify(mut self, justify: Justify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    /// Take the node out of the flow and put it at `x`, `y` in the
+    /// parent's content box, above its siblings
+    pub fn absolute(mut self, x: f32, y: f32) -> Self {
+        self.absolute = Some((x, y));
+        self
+    }
+
+    /// Font for this node's text and its descendants'
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Break the text into lines at whitespace so it measures no wider
+    /// than `max_width`; it's drawn wrapped to the width it's given
+    pub fn wrap(mut self, max_width: f32) -> Self {
+        self.wrap = Some(max_width);
+        self
+    }
+
+    /// Cut text too wide for the node short with `...`
+    pub fn ellipsis(mut self) -> Self {
+        self.ellipsis = true;
+        self
+    }
+
+    pub fn font_size(mut self, size: i32) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    pub fn color(mut self, color: impl Into<Prop<Color>>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Draw the chars at `chars`, as char indices into the text, in
+    /// `color`, e.g. to show what a search matched
+    pub fn highlight(mut self, color: impl Into<Prop<Color>>, chars: impl Into<Prop<Vec<usize>>>) -> Self {
+        self.highlight = Some((color.into(), chars.into()));
+        self
+    }
+
+    pub fn background(mut self, color: impl Into<Prop<Color>>) -> Self {
+        self.background = Some(color.into());
+        self
+    }
+
+    pub fn border(mut self, color: impl Into<Prop<Color>>) -> Self {
+        self.border = Some(color.into());
+        self
+    }
+
+    /// Hidden nodes take no space and get no events
+    pub fn visible(mut self, visible: impl Into<Prop<bool>>) -> Self {
+        self.visible = visible.into();
+        self
+    }
+
+    /// Clicks on this node or a child without its own handler
+    pub fn on_click(mut self, handler: impl Fn() + 'static) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+
+    /// Called with true when the mouse comes in, false when it leaves
+    pub fn on_hover(mut self, handler: impl Fn(bool) + 'static) -> Self {
+        self.on_hover = Some(Rc::new(handler));
+        self
+    }
+
+    /// Wheel notches while the mouse is over the node
+    pub fn on_scroll(mut self, handler: impl Fn(f32) + 'static) -> Self {
+        self.on_scroll = Some(Rc::new(handler));
+        self
+    }
+
+    pub fn child(mut self, child: Node) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn children(mut self, children: impl IntoIterator<Item = Node>) -> Self {
+        self.children.extend(children);
+        self
+    }
+}
+
+// ============== TREE ==============
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Dirt {
+    Paint,
+    Layout,
+}
+
+struct Slot {
+    node: Node,
+    parent: Option<usize>,
+    /// Flow children first, then absolute ones, which is paint order
+    children: Vec<usize>,
+    measured: Option<(f32, f32)>,
+    rect: Rectangle,
+    layout_dirty: bool,
+}
+
+/// A mounted node tree with its render texture
+pub struct Tree {
+    slots: Vec<Slot>,
+    width: i32,
+    height: i32,
+    background: Color,
+    target: RenderTexture2D,
+    /// Nodes whose bound properties changed since the last update
+    invalid: Rc<RefCell<Vec<(usize, Dirt)>>>,
+    // Signals only hold their subscribers weakly
+    effects: Vec<Rc<RefCell<EffectFn>>>,
+    damage: Option<Rectangle>,
+    hovered: Option<usize>,
+}
+
+const ORIGIN: Rectangle = Rectangle { x: 0.0, y: 0.0, width: 0.0, height: 0.0 };
+
+impl Tree {
+    /// Mount `root` filling a `width` x `height` area. Needs an open
+    /// window for the render texture.
+    pub fn new(root: Node, width: i32, height: i32, background: Color) -> Self {
+        let mut tree = Self {
+            slots: Vec::new(),
+            width,
+            height,
+            background,
+            target: unsafe { LoadRenderTexture(width, height) },
+            invalid: Rc::new(RefCell::new(Vec::new())),
+            effects: Vec::new(),
+            damage: None,
+            hovered: None,
+        };
+        tree.mount(root, None);
+        tree.layout();
+        tree.damage = Some(tree.bounds());
+        tree
+    }
+
+    fn mount(&mut self, mut node: Node, parent: Option<usize>) -> usize {
+        let id = self.slots.len();
+        let children = std::mem::take(&mut node.children);
+
+        let invalidate = |dirt: Dirt| -> Rc<RefCell<EffectFn>> {
+            let invalid = self.invalid.clone();
+            let effect: EffectFn = Rc::new(move || invalid.borrow_mut().push((id, dirt)));
+            Rc::new(RefCell::new(effect))
+        };
+        let layout = invalidate(Dirt::Layout);
+        let paint = invalidate(Dirt::Paint);
+        node.visible.subscribe(&layout);
+        if let Some(text) = &node.text {
+            text.subscribe(&layout);
+        }
+        node.color.subscribe(&paint);
+        if let Some((color, chars)) = &node.highlight {
+            color.subscribe(&paint);
+            chars.subscribe(&paint);
+        }
+        for prop in node.background.iter().chain(node.border.iter()) {
+            prop.subscribe(&paint);
+        }
+        self.effects.push(layout);
+        self.effects.push(paint);
+
+        self.slots.push(Slot { node, parent, children: Vec::new(), measured: None, rect: ORIGIN, layout_dirty: true });
+        let (absolute, flow): (Vec<Node>, Vec<Node>) = children.into_iter().partition(|c| c.absolute.is_some());
+        for child in flow.into_iter().chain(absolute) {
+            let child = self.mount(child, Some(id));
+            self.slots[id].children.push(child);
+        }
+        id
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle { x: 0.0, y: 0.0, width: self.width as f32, height: self.height as f32 }
+    }
+
+    /// Handle this frame's mouse, apply whatever changed and repaint the
+    /// damaged area. Call before `BeginDrawing`.
+    pub fn update(&mut self) {
+        self.dispatch_input();
+        let invalid: Vec<_> = self.invalid.borrow_mut().drain(..).collect();
+        for (id, dirt) in invalid {
+            self.add_damage(self.slots[id].rect);
+            if dirt == Dirt::Layout {
+                let mut at = Some(id);
+                while let Some(i) = at {
+                    self.slots[i].measured = None;
+                    self.slots[i].layout_dirty = true;
+                    at = self.slots[i].parent;
+                }
+            }
+        }
+        self.layout();
+        self.repaint();
+    }
+
+    /// Show the tree with its top-left corner at `x`, `y`
+    pub fn draw(&self, x: f32, y: f32) {
+        // Render textures are stored upside down
+        let source = Rectangle { x: 0.0, y: 0.0, width: self.width as f32, height: -self.height as f32 };
+        unsafe { DrawTextureRec(self.target.texture, source, Vector2 { x, y }, WHITE) };
+    }
+
+    fn add_damage(&mut self, rect: Rectangle) {
+        if rect.width <= 0.0 || rect.height <= 0.0 {
+            return;
+        }
+        self.damage = Some(match self.damage {
+            Some(d) => union(d, rect),
+            None => rect,
+        });
+    }
+
+    // ---------- input ----------
+
+    fn dispatch_input(&mut self) {
+        let (mouse, clicked, wheel) =
+            unsafe { (GetMousePosition(), IsMouseButtonPressed(0), GetMouseWheelMove()) };
+        let target = self.hit(0, mouse);
+
+        let hovered = self.handler_for(target, |n| n.on_hover.is_some());
+        if hovered != self.hovered {
+            let leave = self.hovered.and_then(|id| self.slots[id].node.on_hover.clone());
+            let enter = hovered.and_then(|id| self.slots[id].node.on_hover.clone());
+            self.hovered = hovered;
+            if let Some(leave) = leave {
+                leave(false);
+            }
+            if let Some(enter) = enter {
+                enter(true);
+            }
+        }
+        if clicked {
+            let handler = self.handler_for(target, |n| n.on_click.is_some());
+            if let Some(handler) = handler.and_then(|id| self.slots[id].node.on_click.clone()) {
+                handler();
+            }
+        }
+        if wheel != 0.0 {
+            let handler = self.handler_for(target, |n| n.on_scroll.is_some());
+            if let Some(handler) = handler.and_then(|id| self.slots[id].node.on_scroll.clone()) {
+                handler(wheel);
+            }
+        }
+    }
+
+    /// Topmost visible node under `point`
+    fn hit(&self, id: usize, point: Vector2) -> Option<usize> {
+        let slot = &self.slots[id];
+        if !slot.node.visible.get() {
+            return None;
+        }
+        // Later children are painted on top; absolute ones may stick out
+        slot.children
+            .iter()
+            .rev()
+            .find_map(|&child| self.hit(child, point))
+            .or_else(|| contains(slot.rect, point).then_some(id))
+    }
+
+    /// `id` or its nearest ancestor that `has` a handler
+    fn handler_for(&self, mut id: Option<usize>, has: impl Fn(&Node) -> bool) -> Option<usize> {
+        while let Some(i) = id {
+            if has(&self.slots[i].node) {
+                return Some(i);
+            }
+            id = self.slots[i].parent;
+        }
+        None
+    }
+
+    // ---------- layout ----------
+
+    fn layout(&mut self) {
+        if self.slots[0].layout_dirty {
+            self.arrange(0, self.bounds());
+        }
+    }
+
+    /// Natural size, cached until the node or a descendant changes
+    fn measure(&mut self, id: usize) -> (f32, f32) {
+        if let Some(size) = self.slots[id].measured {
+            return size;
+        }
+        let node = &self.slots[id].node;
+        let size = if !node.visible.get() {
+            (0.0, 0.0)
+        } else {
+            let (pad_x, pad_y) = node.padding;
+            let (width, height, direction, gap) = (node.width, node.height, node.direction, node.gap);
+            let content = match &node.text {
+                Some(text) => {
+                    let text = text.get();
+                    let metrics = self.metrics(id);
+                    match node.wrap {
+                        Some(max_width) => {
+                            let lines = text_layout::wrap(&metrics, &text, max_width - 2.0 * pad_x);
+                            let (width, count) = lines.fold((0.0f32, 0), |(width, count), line| (width.max(line.width), count + 1));
+                            (width, text_height(&metrics, count))
+                        }
+                        None => (text_layout::measure(&metrics, &text), metrics.size),
+                    }
+                }
+                None => {
+                    let flow: Vec<usize> = self.flow_children(id).collect();
+                    let mut main = 0.0f32;
+                    let mut cross = 0.0f32;
+                    let mut shown = 0;
+                    for child in flow {
+                        let (w, h) = self.measure(child);
+                        if !self.slots[child].node.visible.get() {
+                            continue;
+                        }
+                        let (m, c) = along(direction, w, h);
+                        main += m;
+                        cross = cross.max(c);
+                        shown += 1;
+                    }
+                    main += gap * (shown.max(1) - 1) as f32;
+                    let (w, h) = along(direction, main, cross);
+                    (w, h)
+                }
+            };
+            let fixed = |size: Size, content: f32, pad: f32| match size {
+                Size::Px(px) => px,
+                _ => content + 2.0 * pad,
+            };
+            (fixed(width, content.0, pad_x), fixed(height, content.1, pad_y))
+        };
+        self.slots[id].measured = Some(size);
+        size
+    }
+
+    fn flow_children(&self, id: usize) -> impl Iterator<Item = usize> + '_ {
+        self.slots[id].children.iter().copied().filter(move |&c| self.slots[c].node.absolute.is_none())
+    }
+
+    /// Place `id` at `rect` and lay out its children, skipping subtrees
+    /// that are clean and didn't move
+    fn arrange(&mut self, id: usize, rect: Rectangle) {
+        let slot = &self.slots[id];
+        if !slot.layout_dirty && same(slot.rect, rect) {
+            return;
+        }
+        if !same(slot.rect, rect) {
+            self.add_damage(slot.rect);
+            self.add_damage(rect);
+        }
+        let slot = &mut self.slots[id];
+        slot.rect = rect;
+        slot.layout_dirty = false;
+        let node = &slot.node;
+        if !node.visible.get() || node.text.is_some() {
+            return;
+        }
+
+        let (pad_x, pad_y) = node.padding;
+        let content = Rectangle {
+            x: rect.x + pad_x,
+            y: rect.y + pad_y,
+            width: (rect.width - 2.0 * pad_x).max(0.0),
+            height: (rect.height - 2.0 * pad_y).max(0.0),
+        };
+        let (direction, gap, align, justify) = (node.direction, node.gap, node.align, node.justify);
+        let (room_main, room_cross) = along(direction, content.width, content.height);
+
+        // Main-axis sizes: fixed, natural, then the free space shared out
+        let flow: Vec<usize> = self.flow_children(id).filter(|&c| self.slots[c].node.visible.get()).collect();
+        let mut sizes = Vec::with_capacity(flow.len());
+        let mut grow_total = 0.0;
+        for &child in &flow {
+            let natural = self.measure(child);
+            let node = &self.slots[child].node;
+            let (main_size, cross_size) = along(direction, node.width, node.height);
+            let (natural_main, natural_cross) = along(direction, natural.0, natural.1);
+            let main = match main_size {
+                Size::Px(px) => px,
+                Size::Grow(g) => {
+                    grow_total += g;
+                    natural_main
+                }
+                Size::Auto => natural_main,
+            };
+            let cross = match (cross_size, align) {
+                (Size::Px(px), _) => px,
+                (Size::Grow(_), _) | (Size::Auto, Align::Stretch) => room_cross,
+                (Size::Auto, _) => natural_cross,
+            };
+            sizes.push((main, cross, main_size));
+        }
+        let used: f32 = sizes.iter().map(|s| s.0).sum::<f32>() + gap * (flow.len().max(1) - 1) as f32;
+        let mut free = (room_main - used).max(0.0);
+        if grow_total > 0.0 {
+            for size in &mut sizes {
+                if let Size::Grow(g) = size.2 {
+                    size.0 += free * g / grow_total;
+                }
+            }
+            free = 0.0;
+        }
+        let (mut at, between) = match justify {
+            Justify::Start => (0.0, gap),
+            Justify::Center => (free / 2.0, gap),
+            Justify::End => (free, gap),
+            Justify::SpaceBetween if flow.len() > 1 => (0.0, gap + free / (flow.len() - 1) as f32),
+            Justify::SpaceBetween => (0.0, gap),
+        };
+
+        let (origin_main, origin_cross) = along(direction, content.x, content.y);
+        for (&child, &(main, cross, _)) in flow.iter().zip(&sizes) {
+            let offset = match align {
+                Align::Start | Align::Stretch => 0.0,
+                Align::Center => (room_cross - cross) / 2.0,
+                Align::End => room_cross - cross,
+            };
+            let (x, y) = along(direction, origin_main + at, origin_cross + offset);
+            let (width, height) = along(direction, main, cross);
+            self.arrange(child, Rectangle { x, y, width, height });
+            at += main + between;
+        }
+
+        let absolute: Vec<usize> =
+            self.slots[id].children.iter().copied().filter(|&c| self.slots[c].node.absolute.is_some()).collect();
+        for child in absolute {
+            let (w, h) = self.measure(child);
+            let (dx, dy) = self.slots[child].node.absolute.unwrap_or_default();
+            self.arrange(child, Rectangle { x: content.x + dx, y: content.y + dy, width: w, height: h });
+        }
+    }
+
+    // ---------- painting ----------
+
+    fn repaint(&mut self) {
+        let Some(damage) = self.damage.take() else {
+            return;
+        };
+        let damage = intersect(damage, self.bounds());
+        unsafe {
+            BeginTextureMode(self.target);
+            BeginScissorMode(
+                damage.x.floor() as i32,
+                damage.y.floor() as i32,
+                damage.width.ceil() as i32 + 1,
+                damage.height.ceil() as i32 + 1,
+            );
+            DrawRectangleRec(damage, self.background);
+            self.paint(0, damage);
+            EndScissorMode();
+            EndTextureMode();
+        }
+    }
+
+    fn paint(&self, id: usize, damage: Rectangle) {
+        let slot = &self.slots[id];
+        let node = &slot.node;
+        if !node.visible.get() {
+            return;
+        }
+        let rect = slot.rect;
+        if overlaps(rect, damage) {
+            unsafe {
+                if let Some(background) = &node.background {
+                    DrawRectangleRec(rect, background.get());
+                }
+                if let Some(border) = &node.border {
+                    DrawRectangleLinesEx(rect, 1.0, border.get());
+                }
+            }
+            if let Some(text) = &node.text {
+                self.paint_text(id, &text.get());
+            }
+        }
+        for &child in &slot.children {
+            self.paint(child, damage);
+        }
+    }
+}
+
+impl Tree {
+    fn metrics(&self, id: usize) -> FontMetrics {
+        let mut font = None;
+        let mut at = Some(id);
+        while let (None, Some(ancestor)) = (font, at) {
+            font = self.slots[ancestor].node.font;
+            at = self.slots[ancestor].parent;
+        }
+        FontMetrics::new(font.unwrap_or_else(text::default_font), self.slots[id].node.font_size)
+    }
+
+    fn paint_text(&self, id: usize, text: &str) {
+        let slot = &self.slots[id];
+        let node = &slot.node;
+        let rect = slot.rect;
+        let metrics = self.metrics(id);
+        let (pad_x, pad_y) = node.padding;
+        let room = rect.width - 2.0 * pad_x;
+
+        // Lines as slices of `text`, with their widths
+        let mut cut = false;
+        let lines: Vec<(&str, f32)> = if node.wrap.is_some() {
+            text_layout::wrap(&metrics, text, room).map(|line| (line.text, line.width)).collect()
+        } else if node.ellipsis {
+            let (shown, ellipsized) = text_layout::ellipsize(&metrics, text, room);
+            cut = ellipsized;
+            let mut width = text_layout::measure(&metrics, shown);
+            if cut {
+                width += metrics.spacing() + text_layout::measure(&metrics, ELLIPSIS);
+            }
+            vec![(shown, width)]
+        } else {
+            vec![(text, text_layout::measure(&metrics, text))]
+        };
+
+        let across = match node.justify {
+            Justify::Center => TextAlign::Center,
+            Justify::End => TextAlign::End,
+            _ => TextAlign::Start,
+        };
+        let down = match node.align {
+            Align::Center => TextAlign::Center,
+            Align::End => TextAlign::End,
+            _ => TextAlign::Start,
+        };
+        let color = node.color.get();
+        let highlight = node.highlight.as_ref().map(|(color, chars)| (color.get(), char_positions(text, chars.get())));
+        let mut y = rect.y + pad_y + down.offset(rect.height - 2.0 * pad_y, text_height(&metrics, lines.len()));
+        for (line, width) in lines {
+            let x = rect.x + pad_x + across.offset(room, width);
+            text::draw(&metrics, line, x, y, color);
+            if cut {
+                text::draw(&metrics, ELLIPSIS, x + width - text_layout::measure(&metrics, ELLIPSIS), y, color);
+            }
+            if let Some((color, marks)) = &highlight {
+                // Overdraw the highlighted chars where the line put them
+                let start = line.as_ptr() as usize - text.as_ptr() as usize;
+                let first = marks.partition_point(|&(at, _)| at < start);
+                for &(at, c) in marks[first..].iter().take_while(|&&(at, _)| at < start + line.len()) {
+                    let x = x + text_offset(&metrics, &line[..at - start]);
+                    text::draw(&metrics, c.encode_utf8(&mut [0; 4]), x, y, *color);
+                }
+            }
+            y += metrics.line_height();
+        }
+    }
+}
+
+impl Drop for Tree {
+    fn drop(&mut self) {
+        unsafe { UnloadRenderTexture(self.target) };
+    }
+}
+
+// ============== GEOMETRY ==============
+
+/// Swap to (main, cross) for `direction`, or back again
+fn along<T>(direction: Direction, x: T, y: T) -> (T, T) {
+    match direction {
+        Direction::Row => (x, y),
+        Direction::Column => (y, x),
+    }
+}
+
+/// The first line is as tall as the font, the others a line height
+/// further down each
+fn text_height(metrics: &FontMetrics, lines: usize) -> f32 {
+    match lines {
+        0 => 0.0,
+        n => metrics.size + (n - 1) as f32 * metrics.line_height(),
+    }
+}
+
+/// Where the char after `prefix` starts
+fn text_offset(metrics: &FontMetrics, prefix: &str) -> f32 {
+    if prefix.is_empty() {
+        0.0
+    } else {
+        text_layout::measure(metrics, prefix) + metrics.spacing()
+    }
+}
+
+/// Byte offset and char for each char index in `indices`, found in one
+/// walk over `text`; indices past its end are dropped
+fn char_positions(text: &str, mut indices: Vec<usize>) -> Vec<(usize, char)> {
+    indices.sort_unstable();
+    indices.dedup();
+    let mut wanted = indices.into_iter().peekable();
+    text.char_indices()
+        .enumerate()
+        .filter_map(|(i, at_c)| wanted.next_if_eq(&i).map(|_| at_c))
+        .collect()
+}
+
+fn same(a: Rectangle, b: Rectangle) -> bool {
+    a.x == b.x && a.y == b.y && a.width == b.width && a.height == b.height
+}
+
+fn contains(rect: Rectangle, point: Vector2) -> bool {
+    point.x >= rect.x && point.x < rect.x + rect.width && point.y >= rect.y && point.y < rect.y + rect.height
+}
+
+fn overlaps(a: Rectangle, b: Rectangle) -> bool {
+    a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+}
+
+fn union(a: Rectangle, b: Rectangle) -> Rectangle {
+    let x = a.x.min(b.x);
+    let y = a.y.min(b.y);
+    let right = (a.x + a.width).max(b.x + b.width);
+    let bottom = (a.y + a.height).max(b.y + b.height);
+    Rectangle { x, y, width: right - x, height: bottom - y }
+}
+
+fn intersect(a: Rectangle, b: Rectangle) -> Rectangle {
+    let x = a.x.max(b.x);
+    let y = a.y.max(b.y);
+    let right = (a.x + a.width).min(b.x + b.width);
+    let bottom = (a.y + a.height).min(b.y + b.height);
+    Rectangle { x, y, width: (right - x).max(0.0), height: (bottom - y).max(0.0) }
+}
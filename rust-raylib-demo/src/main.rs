@@ -2,13 +2,22 @@ use raylib_ffi::*;
 use raylib_ffi::colors::*;
 use std::ffi::CString;
 
-const MOUSE_BUTTON_LEFT: i32 = 0;
+#[path = "lib/reactivity.rs"]
+mod reactivity;
+#[path = "lib/text.rs"]
+mod text;
+#[path = "lib/tree.rs"]
+mod tree;
+
+use reactivity::Signal;
+use tree::{Align, Node, Size, Tree};
+
 const KEY_ESCAPE: i32 = 256;
 
 struct AccordionSection {
     title: String,
     content: String,
-    is_open: bool,
+    is_open: Signal<bool>,
 }
 
 impl AccordionSection {
@@ -16,9 +25,33 @@ impl AccordionSection {
         Self {
             title: title.to_string(),
             content: content.to_string(),
-            is_open: false,
+            is_open: Signal::new(false),
         }
     }
+
+    /// Header that toggles the section, and the content below it,
+    /// wrapped to `width`
+    fn view(&self, width: f32, header_height: f32) -> [Node; 2] {
+        let is_open = self.is_open.clone();
+        let header_color = self.is_open.map(|open| if open { SKYBLUE } else { LIGHTGRAY });
+        [
+            Node::text(self.title.as_str())
+                .height(Size::Px(header_height))
+                .padding_xy(10.0, 0.0)
+                .align(Align::Center)
+                .background(header_color)
+                .border(DARKGRAY)
+                .on_click(move || is_open.set(!is_open.get())),
+            Node::text(self.content.as_str())
+                .wrap(width)
+                .padding(10.0)
+                .font_size(18)
+                .color(DARKGREEN)
+                .background(WHITE)
+                .border(GRAY)
+                .visible(&self.is_open),
+        ]
+    }
 }
 
 fn main() {
@@ -27,82 +60,53 @@ fn main() {
         InitWindow(500, 600, title.as_ptr());
         SetTargetFPS(60);
 
-        let mut sections = vec![
+        let sections = [
             AccordionSection::new("Section 1", "This is the content of section 1."),
-            AccordionSection::new("Section 2", "Here lies section 2’s content."),
-            AccordionSection::new("Section 3", "Section 3 has some text too."),
+            AccordionSection::new(
+                "Section 2",
+                "Here lies section 2’s content. It runs on long enough to need a second line, \
+                 and a third, to show that content wraps to the width of the accordion.",
+            ),
+            AccordionSection::new("Section 3", "Section 3 has some text too.\nOn two lines."),
         ];
 
-        let section_x = 50;
-        let section_y = 80;
-        let section_width = 400;
-        let header_height = 40;
-        let content_height = 80;
+        // A TTF to use instead of the built-in font: `cargo run -- path/to/font.ttf`
+        let font = std::env::args().nth(1).and_then(|path| text::load_font(&path, 48));
 
-        while !WindowShouldClose() {
-            // Handle mouse clicks
-            if IsMouseButtonPressed(MOUSE_BUTTON_LEFT) {
-                let mouse_pos = GetMousePosition();
-                let mx = mouse_pos.x as i32;
-                let my = mouse_pos.y as i32;
+        let section_width = 400.0;
+        let header_height = 40.0;
 
-                let mut current_y = section_y;
-                for section in sections.iter_mut() {
-                    // Header area
-                    if mx >= section_x && mx <= section_x + section_width &&
-                       my >= current_y && my <= current_y + header_height {
-                        // Toggle open/close
-                        section.is_open = !section.is_open;
-                    }
-                    current_y += header_height;
-                    if section.is_open {
-                        current_y += content_height;
-                    }
-                }
-            }
+        let accordion = Node::column()
+            .width(Size::Px(section_width))
+            .children(sections.iter().flat_map(|s| s.view(section_width, header_height)));
+        let root = Node::column()
+            .font(font.unwrap_or_else(text::default_font))
+            .padding_xy(50.0, 30.0)
+            .gap(26.0)
+            .child(Node::text("Accordion Demo (click headers)").font_size(24).color(DARKGRAY))
+            .child(accordion);
+        let mut ui = Tree::new(root, 500, 600, RAYWHITE);
 
+        while !WindowShouldClose() {
             // Escape closes all
             if IsKeyPressed(KEY_ESCAPE) {
-                for section in sections.iter_mut() {
-                    section.is_open = false;
+                for section in sections.iter() {
+                    section.is_open.set(false);
                 }
             }
 
+            ui.update();
+
             BeginDrawing();
             ClearBackground(RAYWHITE);
-
-            let demo_title = CString::new("Accordion Demo (click headers)").unwrap();
-            DrawText(demo_title.as_ptr(), 50, 30, 24, DARKGRAY);
-
-            // Draw sections
-            let mut current_y = section_y;
-            for section in &sections {
-                // Header
-                let header_color = if section.is_open { SKYBLUE } else { LIGHTGRAY };
-                DrawRectangle(section_x, current_y, section_width, header_height, header_color);
-                DrawRectangleLines(section_x, current_y, section_width, header_height, DARKGRAY);
-
-                let title_text = CString::new(section.title.clone()).unwrap();
-                DrawText(title_text.as_ptr(), section_x + 10, current_y + 10, 20, BLACK);
-
-                current_y += header_height;
-
-                // Content if open
-                if section.is_open {
-                    DrawRectangle(section_x, current_y, section_width, content_height, WHITE);
-                    DrawRectangleLines(section_x, current_y, section_width, content_height, GRAY);
-
-                    let content_text = CString::new(section.content.clone()).unwrap();
-                    DrawText(content_text.as_ptr(), section_x + 10, current_y + 10, 18, DARKGREEN);
-
-                    current_y += content_height;
-                }
-            }
-
+            ui.draw(0.0, 0.0);
             EndDrawing();
         }
 
+        drop(ui);
+        if let Some(font) = font {
+            UnloadFont(font);
+        }
         CloseWindow();
     }
 }
-